@@ -0,0 +1,66 @@
+#![forbid(unsafe_code)]
+
+mod metrics;
+mod service;
+mod watchdog;
+
+use metrics::Metrics;
+use watchdog::{NodeCommitments, Watchdog};
+
+use anyhow::Context;
+use clap::Parser;
+use mcr_settlement_client::McrSettlementClient;
+use movement_full_node::common_args::MovementArgs;
+
+use std::sync::Arc;
+use tracing::info;
+
+/// Watches the settlement of the network, checking the commitments accepted by
+/// the MCR contract and posted by each attester against the local ledger.
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+struct McrWatchdog {
+	#[clap(flatten)]
+	movement_args: MovementArgs,
+
+	/// Address of the health and metrics endpoints.
+	#[clap(long, default_value = "0.0.0.0:30735")]
+	listen_address: String,
+
+	/// Base URL of the REST API of the node whose ledger is watched.
+	/// Defaults to the node REST connection of the configuration.
+	#[clap(long)]
+	node_url: Option<String>,
+
+	/// Exit with an error on the first divergence.
+	#[clap(long)]
+	ci: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+	let _guard = movement_tracing::init_tracing_subscriber(movement_tracing::Config::default());
+
+	let args = McrWatchdog::parse();
+	let config = args.movement_args.config().await?;
+	let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
+		.await
+		.context("Failed to build MCR settlement client with config")?;
+	let node_url = args.node_url.unwrap_or_else(|| {
+		let client = &config.execution_config.maptos_config.client;
+		format!(
+			"http://{}:{}",
+			client.maptos_rest_connection_hostname, client.maptos_rest_connection_port
+		)
+	});
+	info!("Checking commitments against the node at {}", node_url);
+
+	let metrics = Arc::new(Metrics::default());
+	let watchdog =
+		Watchdog::new(settlement_client, NodeCommitments::new(node_url), metrics.clone(), args.ci);
+
+	tokio::select! {
+		res = watchdog.run() => res,
+		res = service::serve(args.listen_address, metrics) => res,
+	}
+}
@@ -0,0 +1,86 @@
+use crate::watchdog::Divergence;
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// The number of divergences kept for the health endpoint.
+const MAX_RECENT_DIVERGENCES: usize = 64;
+
+/// Metrics of the watchdog, exposed in the Prometheus text format.
+#[derive(Debug, Default)]
+pub struct Metrics {
+	checked_heights: AtomicU64,
+	last_checked_height: AtomicU64,
+	unverified_heights: AtomicU64,
+	divergences: AtomicU64,
+	recent_divergences: Mutex<Vec<Divergence>>,
+}
+
+impl Metrics {
+	/// Records the check of the accepted commitment at a height.
+	pub fn record_check(&self, height: u64, divergences: &[Divergence]) {
+		self.checked_heights.fetch_add(1, Ordering::Relaxed);
+		self.last_checked_height.fetch_max(height, Ordering::Relaxed);
+		if divergences.is_empty() {
+			return;
+		}
+		self.divergences.fetch_add(divergences.len() as u64, Ordering::Relaxed);
+		let mut recent = self.recent_divergences.lock().unwrap();
+		recent.extend_from_slice(divergences);
+		let excess = recent.len().saturating_sub(MAX_RECENT_DIVERGENCES);
+		recent.drain(..excess);
+	}
+
+	/// Sets the number of accepted commitments the local ledger has not reached yet.
+	pub fn set_unverified_heights(&self, count: usize) {
+		self.unverified_heights.store(count as u64, Ordering::Relaxed);
+	}
+
+	pub fn divergences(&self) -> u64 {
+		self.divergences.load(Ordering::Relaxed)
+	}
+
+	/// The most recent divergences, oldest first.
+	pub fn recent_divergences(&self) -> Vec<Divergence> {
+		self.recent_divergences.lock().unwrap().clone()
+	}
+
+	/// Renders the metrics in the Prometheus text format.
+	pub fn render(&self) -> String {
+		let mut output = String::new();
+		let metrics = [
+			(
+				"mcr_watchdog_checked_heights_total",
+				"counter",
+				"Accepted commitments checked against the local ledger",
+				&self.checked_heights,
+			),
+			(
+				"mcr_watchdog_last_checked_height",
+				"gauge",
+				"Height of the last accepted commitment checked",
+				&self.last_checked_height,
+			),
+			(
+				"mcr_watchdog_unverified_heights",
+				"gauge",
+				"Accepted commitments above the height of the local ledger",
+				&self.unverified_heights,
+			),
+			(
+				"mcr_watchdog_divergences_total",
+				"counter",
+				"Commitments diverging from the local ledger",
+				&self.divergences,
+			),
+		];
+		for (name, kind, help, value) in metrics {
+			// writing to a String does not fail
+			let _ = writeln!(output, "# HELP {} {}", name, help);
+			let _ = writeln!(output, "# TYPE {} {}", name, kind);
+			let _ = writeln!(output, "{} {}", name, value.load(Ordering::Relaxed));
+		}
+		output
+	}
+}
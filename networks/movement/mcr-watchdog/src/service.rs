@@ -0,0 +1,39 @@
+use crate::metrics::Metrics;
+
+use poem::http::StatusCode;
+use poem::listener::TcpListener;
+use poem::{get, handler, web::Data, EndpointExt, IntoResponse, Response, Route, Server};
+use tracing::info;
+
+use std::sync::Arc;
+
+/// Serves the health and metrics endpoints of the watchdog.
+pub async fn serve(listen_address: String, metrics: Arc<Metrics>) -> Result<(), anyhow::Error> {
+	info!("Watchdog health and metrics listening on {}", listen_address);
+	let routes = Route::new()
+		.at("/health", get(health))
+		.at("/metrics", get(prometheus_metrics))
+		.data(metrics);
+	Server::new(TcpListener::bind(listen_address)).run(routes).await?;
+	Ok(())
+}
+
+/// Unhealthy once a divergence has been found, listing the recent divergences.
+#[handler]
+pub async fn health(metrics: Data<&Arc<Metrics>>) -> Response {
+	let divergences = metrics.recent_divergences();
+	if divergences.is_empty() {
+		return "OK".into_response();
+	}
+	let body: Vec<_> = divergences.iter().map(ToString::to_string).collect();
+	Response::builder()
+		.status(StatusCode::SERVICE_UNAVAILABLE)
+		.body(body.join("\n"))
+}
+
+#[handler]
+pub async fn prometheus_metrics(metrics: Data<&Arc<Metrics>>) -> Response {
+	Response::builder()
+		.content_type("text/plain; version=0.0.4")
+		.body(metrics.render())
+}
@@ -0,0 +1,293 @@
+use crate::metrics::Metrics;
+use mcr_settlement_client::McrSettlementClientOperations;
+use movement_types::block::BlockCommitment;
+
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use tokio::time::{interval, MissedTickBehavior};
+use tokio_stream::StreamExt;
+use tracing::{error, info};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the accepted commitments above the local ledger are checked again.
+const RECHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The source of the commitments of the local ledger.
+#[async_trait]
+pub trait LocalCommitments {
+	/// Gets the local commitment at a height, or `None` if the ledger has not reached it yet.
+	async fn commitment_at_height(
+		&self,
+		height: u64,
+	) -> Result<Option<BlockCommitment>, anyhow::Error>;
+}
+
+/// Commitments served by the REST API of a running node.
+pub struct NodeCommitments {
+	client: reqwest::Client,
+	url: String,
+}
+
+impl NodeCommitments {
+	/// Queries the node's REST API at the given base URL.
+	pub fn new(url: String) -> Self {
+		Self { client: reqwest::Client::new(), url }
+	}
+}
+
+#[async_trait]
+impl LocalCommitments for NodeCommitments {
+	async fn commitment_at_height(
+		&self,
+		height: u64,
+	) -> Result<Option<BlockCommitment>, anyhow::Error> {
+		let response = self
+			.client
+			.get(format!("{}/movement/v1/block-commitment/{}", self.url, height))
+			.send()
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to query the node commitment: {:?}", e))?;
+		if response.status() == StatusCode::NOT_FOUND {
+			return Ok(None);
+		}
+		let commitment = response
+			.error_for_status()
+			.map_err(|e| anyhow::anyhow!("Failed to get the node commitment: {:?}", e))?
+			.json()
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to decode the node commitment: {:?}", e))?;
+		Ok(Some(commitment))
+	}
+}
+
+/// A commitment diverging from the local ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+	/// The commitment accepted by the settlement contract.
+	Accepted { local: BlockCommitment, accepted: BlockCommitment },
+	/// The commitment posted by an attester.
+	Attester { attester: Address, local: BlockCommitment, posted: BlockCommitment },
+}
+
+impl Divergence {
+	pub fn height(&self) -> u64 {
+		match self {
+			Divergence::Accepted { local, .. } | Divergence::Attester { local, .. } => {
+				local.height()
+			}
+		}
+	}
+}
+
+impl fmt::Display for Divergence {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Divergence::Accepted { local, accepted } => write!(
+				f,
+				"accepted commitment {} diverges from local commitment {} at height {}",
+				accepted,
+				local,
+				self.height()
+			),
+			Divergence::Attester { attester, local, posted } => write!(
+				f,
+				"attester {} posted commitment {} diverging from local commitment {} at height {}",
+				attester,
+				posted,
+				local,
+				self.height()
+			),
+		}
+	}
+}
+
+/// Follows the commitments accepted by the settlement contract and checks them,
+/// and those posted by each attester, against the local ledger.
+pub struct Watchdog<C, L> {
+	client: C,
+	local: L,
+	metrics: Arc<Metrics>,
+	/// Whether to fail on the first divergence
+	ci: bool,
+}
+
+impl<C, L> Watchdog<C, L>
+where
+	C: McrSettlementClientOperations,
+	L: LocalCommitments,
+{
+	pub fn new(client: C, local: L, metrics: Arc<Metrics>, ci: bool) -> Self {
+		Self { client, local, metrics, ci }
+	}
+
+	/// Runs the watchdog until the accepted commitments stream ends,
+	/// or until a divergence is found in CI mode.
+	pub async fn run(&self) -> Result<(), anyhow::Error> {
+		let mut accepted_stream = self.client.stream_block_commitments().await?;
+		let mut unverified = BTreeMap::new();
+		let mut recheck = interval(RECHECK_INTERVAL);
+		recheck.set_missed_tick_behavior(MissedTickBehavior::Delay);
+		info!("Watching the settlement of commitments");
+
+		loop {
+			tokio::select! {
+				accepted = accepted_stream.next() => {
+					let accepted = accepted
+						.ok_or(anyhow::anyhow!("Accepted commitments stream closed"))??;
+					unverified.insert(accepted.height(), accepted);
+				}
+				_ = recheck.tick() => {}
+			}
+			self.check_unverified(&mut unverified).await?;
+		}
+	}
+
+	/// Checks the accepted commitments the local ledger has reached, in height order.
+	pub async fn check_unverified(
+		&self,
+		unverified: &mut BTreeMap<u64, BlockCommitment>,
+	) -> Result<(), anyhow::Error> {
+		while let Some(entry) = unverified.first_entry() {
+			let height = *entry.key();
+			let Some(local) = self.local.commitment_at_height(height).await? else {
+				// the heights above are not reached either
+				break;
+			};
+			let accepted = entry.remove();
+
+			let divergences = self.check(&local, &accepted).await?;
+			for divergence in divergences.iter() {
+				error!("Settlement divergence: {}", divergence);
+			}
+			self.metrics.record_check(height, &divergences);
+			if self.ci && !divergences.is_empty() {
+				anyhow::bail!("Settlement diverged at height {}", height);
+			}
+		}
+		self.metrics.set_unverified_heights(unverified.len());
+		Ok(())
+	}
+
+	/// Checks an accepted commitment and the commitments posted by the attesters
+	/// against the local commitment at the same height.
+	pub async fn check(
+		&self,
+		local: &BlockCommitment,
+		accepted: &BlockCommitment,
+	) -> Result<Vec<Divergence>, anyhow::Error> {
+		let mut divergences = Vec::new();
+		if accepted != local {
+			divergences
+				.push(Divergence::Accepted { local: local.clone(), accepted: accepted.clone() });
+		}
+		for attester in self.client.get_attesters().await? {
+			let posted =
+				self.client.get_attester_commitment_at_height(local.height(), attester).await?;
+			match posted {
+				Some(posted) if &posted != local => {
+					divergences.push(Divergence::Attester {
+						attester,
+						local: local.clone(),
+						posted,
+					});
+				}
+				_ => {}
+			}
+		}
+		Ok(divergences)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use mcr_settlement_client::mock::McrSettlementClient;
+	use movement_types::block::{Commitment, Id};
+
+	struct LedgerCommitments(BTreeMap<u64, BlockCommitment>);
+
+	#[async_trait]
+	impl LocalCommitments for LedgerCommitments {
+		async fn commitment_at_height(
+			&self,
+			height: u64,
+		) -> Result<Option<BlockCommitment>, anyhow::Error> {
+			Ok(self.0.get(&height).cloned())
+		}
+	}
+
+	fn commitment(height: u64, value: u8) -> BlockCommitment {
+		BlockCommitment::new(height, Id::new([height as u8; 32]), Commitment::new([value; 32]))
+	}
+
+	#[tokio::test]
+	async fn test_reports_divergent_attesters() -> Result<(), anyhow::Error> {
+		let alice = Address::repeat_byte(1);
+		let bob = Address::repeat_byte(2);
+		let client = McrSettlementClient::new();
+		client.post_attester_commitment(alice, commitment(1, 1)).await;
+		client.post_attester_commitment(bob, commitment(1, 2)).await;
+		let ledger = LedgerCommitments(BTreeMap::from([(1, commitment(1, 1))]));
+		let watchdog = Watchdog::new(client, ledger, Arc::new(Metrics::default()), false);
+
+		assert_eq!(
+			watchdog.check(&commitment(1, 1), &commitment(1, 1)).await?,
+			vec![Divergence::Attester {
+				attester: bob,
+				local: commitment(1, 1),
+				posted: commitment(1, 2)
+			}]
+		);
+		assert_eq!(
+			watchdog.check(&commitment(1, 1), &commitment(1, 2)).await?,
+			vec![
+				Divergence::Accepted { local: commitment(1, 1), accepted: commitment(1, 2) },
+				Divergence::Attester {
+					attester: bob,
+					local: commitment(1, 1),
+					posted: commitment(1, 2)
+				},
+			]
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_waits_for_the_local_ledger() -> Result<(), anyhow::Error> {
+		let client = McrSettlementClient::new();
+		let ledger = LedgerCommitments(BTreeMap::from([(1, commitment(1, 1))]));
+		let metrics = Arc::new(Metrics::default());
+		let watchdog = Watchdog::new(client, ledger, metrics.clone(), true);
+
+		let mut unverified = BTreeMap::from([(1, commitment(1, 1)), (2, commitment(2, 2))]);
+		watchdog.check_unverified(&mut unverified).await?;
+		assert_eq!(unverified, BTreeMap::from([(2, commitment(2, 2))]));
+		assert_eq!(metrics.divergences(), 0);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_fails_on_divergence_in_ci_mode() -> Result<(), anyhow::Error> {
+		let client = McrSettlementClient::new();
+		let ledger = LedgerCommitments(BTreeMap::from([(1, commitment(1, 1))]));
+		let metrics = Arc::new(Metrics::default());
+		let watchdog = Watchdog::new(client, ledger, metrics.clone(), true);
+
+		let mut unverified = BTreeMap::from([(1, commitment(1, 2))]);
+		assert!(watchdog.check_unverified(&mut unverified).await.is_err());
+		assert_eq!(metrics.divergences(), 1);
+		assert_eq!(
+			metrics.recent_divergences(),
+			vec![Divergence::Accepted { local: commitment(1, 1), accepted: commitment(1, 2) }]
+		);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,23 @@
+use godfig::env_default;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+	/// The directory the diagnostics of rejected commitments are written to, relative to the
+	/// dot movement directory unless absolute
+	#[serde(default = "default_diagnostics_path")]
+	pub diagnostics_path: String,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self { diagnostics_path: default_diagnostics_path() }
+	}
+}
+
+env_default!(
+	default_diagnostics_path,
+	"MOVEMENT_DIAGNOSTICS_PATH",
+	String,
+	"diagnostics".to_string()
+);
@@ -1,4 +1,5 @@
 pub mod da_db;
+pub mod diagnostics;
 pub mod execution_extension;
 pub mod syncing;
 
@@ -24,6 +25,9 @@ pub struct Config {
 	#[serde(default)]
 	pub da_db: da_db::Config,
 
+	#[serde(default)]
+	pub diagnostics: diagnostics::Config,
+
 	#[serde(default)]
 	pub execution_extension: execution_extension::Config,
 
@@ -38,6 +42,7 @@ impl Default for Config {
 			celestia_da_light_node: CelestiaDaLightNodeConfig::default(),
 			mcr: McrConfig::default(),
 			da_db: da_db::Config::default(),
+			diagnostics: diagnostics::Config::default(),
 			execution_extension: execution_extension::Config::default(),
 			syncing: syncing::Config::default(),
 		}
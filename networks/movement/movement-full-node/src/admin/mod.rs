@@ -1,16 +1,23 @@
 pub mod force_commitment;
+pub mod simulate_block;
+pub mod staking;
 use clap::Subcommand;
 
 #[derive(Subcommand, Debug)]
 #[clap(rename_all = "kebab-case", about = "Commands for syncing")]
 pub enum Admin {
 	ForceCommitment(force_commitment::ForceCommitment),
+	SimulateBlock(simulate_block::SimulateBlock),
+	#[clap(subcommand)]
+	Staking(staking::Staking),
 }
 
 impl Admin {
 	pub async fn execute(&self) -> Result<(), anyhow::Error> {
 		match self {
 			Admin::ForceCommitment(force_commitment) => force_commitment.execute().await,
+			Admin::SimulateBlock(simulate_block) => simulate_block.execute().await,
+			Admin::Staking(staking) => staking.execute().await,
 		}
 	}
 }
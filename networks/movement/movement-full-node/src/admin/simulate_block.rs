@@ -0,0 +1,74 @@
+use crate::common_args::MovementArgs;
+use crate::node::partial::MovementPartialNode;
+use anyhow::Context;
+use aptos_crypto::hash::CryptoHash;
+use clap::Parser;
+use maptos_dof_execution::{
+	DynOptFinExecutor, ExecutableBlock, ExecutableTransactions, HashValue,
+	SignatureVerifiedTransaction, SignedTransaction, Transaction,
+};
+use std::str::FromStr;
+use tracing::info;
+
+#[derive(Debug, Parser, Clone)]
+#[clap(
+	rename_all = "kebab-case",
+	about = "Re-executes the block at a height against the state it was executed on, without committing. Transactions can be added to or removed from the block. The commitment is only computed on top of the latest version."
+)]
+pub struct SimulateBlock {
+	#[clap(flatten)]
+	pub movement_args: MovementArgs,
+	/// The height of the block to simulate
+	#[clap(long)]
+	pub height: u64,
+	/// A hex-encoded BCS signed transaction to append to the block
+	#[clap(long)]
+	pub add: Vec<String>,
+	/// The hash of a transaction to remove from the block
+	#[clap(long)]
+	pub remove: Vec<String>,
+}
+
+impl SimulateBlock {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		let config = self.movement_args.config().await?;
+		info!("Loaded config {:?}", config);
+		let executor = MovementPartialNode::try_executor_from_config(config)
+			.await
+			.context("Failed to create the executor")?;
+
+		let removed = self
+			.remove
+			.iter()
+			.map(|hash| {
+				HashValue::from_str(hash.trim_start_matches("0x")).map_err(|e| {
+					anyhow::anyhow!("Failed to parse transaction hash {}: {:?}", hash, e)
+				})
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let (block, version) = executor.get_executable_block_at_height(self.height)?;
+		let mut transactions: Vec<SignatureVerifiedTransaction> = block
+			.transactions
+			.into_txns()
+			.into_iter()
+			.filter(|transaction| !removed.contains(&transaction.clone().into_inner().hash()))
+			.collect();
+		for bytes in &self.add {
+			let bytes = hex::decode(bytes.trim_start_matches("0x"))
+				.context("Failed to decode added transaction")?;
+			let transaction: SignedTransaction =
+				bcs::from_bytes(&bytes).context("Failed to deserialize added transaction")?;
+			transactions.push(Transaction::UserTransaction(transaction).into());
+		}
+
+		info!("Simulating block at height {} on version {}", self.height, version);
+		let block =
+			ExecutableBlock::new(block.block_id, ExecutableTransactions::Unsharded(transactions));
+		let simulation = executor.simulate_block_opt(block, version).await?;
+		// Use println as this is standard (non-logging output)
+		println!("{}", serde_json::to_string_pretty(&simulation)?);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,24 @@
+use crate::common_args::MovementArgs;
+use anyhow::Context;
+use clap::Parser;
+use mcr_settlement_client::{McrSettlementClient, McrStakingClientOperations};
+
+#[derive(Debug, Parser, Clone)]
+#[clap(rename_all = "kebab-case", about = "Gets the current epoch of the MCR settlement contract.")]
+pub struct Epoch {
+	#[clap(flatten)]
+	pub movement_args: MovementArgs,
+}
+
+impl Epoch {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		let config = self.movement_args.config().await?;
+		let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
+			.await
+			.context("Failed to build MCR settlement client with config")?;
+
+		println!("{}", settlement_client.get_current_epoch().await?);
+
+		Ok(())
+	}
+}
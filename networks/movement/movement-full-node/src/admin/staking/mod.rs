@@ -0,0 +1,87 @@
+pub mod epoch;
+pub mod rewards;
+pub mod slashing;
+pub mod stake;
+pub mod status;
+pub mod unstake;
+pub mod validators;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+#[clap(rename_all = "kebab-case", about = "Commands for staking with the MCR settlement contract")]
+pub enum Staking {
+	Stake(stake::Stake),
+	Unstake(unstake::Unstake),
+	Epoch(epoch::Epoch),
+	Validators(validators::Validators),
+	Status(status::Status),
+	Rewards(rewards::Rewards),
+	Slashing(slashing::Slashing),
+}
+
+impl Staking {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		match self {
+			Staking::Stake(stake) => stake.execute().await,
+			Staking::Unstake(unstake) => unstake.execute().await,
+			Staking::Epoch(epoch) => epoch.execute().await,
+			Staking::Validators(validators) => validators.execute().await,
+			Staking::Status(status) => status.execute().await,
+			Staking::Rewards(rewards) => rewards.execute().await,
+			Staking::Slashing(slashing) => slashing.execute().await,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{Address, U256};
+	use clap::Parser;
+
+	#[derive(Parser, Debug)]
+	struct Cli {
+		#[clap(subcommand)]
+		staking: Staking,
+	}
+
+	fn parse(args: &[&str]) -> Result<Staking, clap::Error> {
+		Cli::try_parse_from(std::iter::once("staking").chain(args.iter().copied()))
+			.map(|cli| cli.staking)
+	}
+
+	#[test]
+	fn test_parse_staking_commands() -> Result<(), anyhow::Error> {
+		let attester = Address::repeat_byte(1);
+		let attester_arg = attester.to_string();
+
+		match parse(&["stake", "100"])? {
+			Staking::Stake(stake) => assert_eq!(stake.amount, U256::from(100)),
+			command => panic!("unexpected command {:?}", command),
+		}
+		match parse(&["unstake", "40"])? {
+			Staking::Unstake(unstake) => assert_eq!(unstake.amount, U256::from(40)),
+			command => panic!("unexpected command {:?}", command),
+		}
+		match parse(&["status", &attester_arg])? {
+			Staking::Status(status) => assert_eq!(status.attester, Some(attester)),
+			command => panic!("unexpected command {:?}", command),
+		}
+		match parse(&["rewards"])? {
+			Staking::Rewards(rewards) => assert_eq!(rewards.attester, None),
+			command => panic!("unexpected command {:?}", command),
+		}
+		match parse(&["slashing", &attester_arg])? {
+			Staking::Slashing(slashing) => assert_eq!(slashing.attester, Some(attester)),
+			command => panic!("unexpected command {:?}", command),
+		}
+		assert!(matches!(parse(&["epoch"])?, Staking::Epoch(_)));
+		assert!(matches!(parse(&["validators"])?, Staking::Validators(_)));
+
+		// amounts are required and must be numbers
+		assert!(parse(&["stake"]).is_err());
+		assert!(parse(&["unstake", "forty"]).is_err());
+
+		Ok(())
+	}
+}
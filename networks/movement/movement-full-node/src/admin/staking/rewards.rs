@@ -0,0 +1,32 @@
+use crate::common_args::MovementArgs;
+use alloy_primitives::Address;
+use anyhow::Context;
+use clap::Parser;
+use mcr_settlement_client::{McrSettlementClient, McrStakingClientOperations};
+
+#[derive(Debug, Parser, Clone)]
+#[clap(
+	rename_all = "kebab-case",
+	about = "Gets the rewards paid to a validator. If no attester is provided, uses the signer of this node."
+)]
+pub struct Rewards {
+	#[clap(flatten)]
+	pub movement_args: MovementArgs,
+	pub attester: Option<Address>,
+}
+
+impl Rewards {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		let config = self.movement_args.config().await?;
+		let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
+			.await
+			.context("Failed to build MCR settlement client with config")?;
+
+		let attester = self.attester.unwrap_or(settlement_client.signer_address);
+		let rewards = settlement_client.get_rewards(attester).await?;
+		println!("attester: {}", attester);
+		println!("rewards paid: {}", rewards);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,40 @@
+use crate::common_args::MovementArgs;
+use alloy_primitives::Address;
+use anyhow::Context;
+use clap::Parser;
+use mcr_settlement_client::{McrSettlementClient, McrStakingClientOperations};
+
+#[derive(Debug, Parser, Clone)]
+#[clap(
+	rename_all = "kebab-case",
+	about = "Lists the stake and the slashing of a validator for each epoch rolled over. If no attester is provided, uses the signer of this node."
+)]
+pub struct Slashing {
+	#[clap(flatten)]
+	pub movement_args: MovementArgs,
+	pub attester: Option<Address>,
+}
+
+impl Slashing {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		let config = self.movement_args.config().await?;
+		let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
+			.await
+			.context("Failed to build MCR settlement client with config")?;
+
+		let attester = self.attester.unwrap_or(settlement_client.signer_address);
+		println!("attester: {}", attester);
+		for epoch_stake in settlement_client.get_epoch_stakes(attester).await? {
+			println!(
+				"epoch {}: starting stake {}, stake {}, slashed {}, unstaked {}",
+				epoch_stake.epoch,
+				epoch_stake.starting_stake,
+				epoch_stake.stake,
+				epoch_stake.slashed(),
+				epoch_stake.unstake
+			);
+		}
+
+		Ok(())
+	}
+}
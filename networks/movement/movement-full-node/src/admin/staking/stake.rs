@@ -0,0 +1,32 @@
+use crate::common_args::MovementArgs;
+use alloy_primitives::U256;
+use anyhow::Context;
+use clap::Parser;
+use mcr_settlement_client::{McrSettlementClient, McrStakingClientOperations};
+use tracing::info;
+
+#[derive(Debug, Parser, Clone)]
+#[clap(
+	rename_all = "kebab-case",
+	about = "Stakes an amount of MOVE for the next epoch with the signer of this node."
+)]
+pub struct Stake {
+	#[clap(flatten)]
+	pub movement_args: MovementArgs,
+	pub amount: U256,
+}
+
+impl Stake {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		let config = self.movement_args.config().await?;
+		let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
+			.await
+			.context("Failed to build MCR settlement client with config")?;
+
+		info!("Staking {} for {}", self.amount, settlement_client.signer_address);
+		settlement_client.stake(self.amount).await?;
+		println!("Staked {} for the next epoch", self.amount);
+
+		Ok(())
+	}
+}
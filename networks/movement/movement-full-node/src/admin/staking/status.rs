@@ -0,0 +1,36 @@
+use crate::common_args::MovementArgs;
+use alloy_primitives::Address;
+use anyhow::Context;
+use clap::Parser;
+use mcr_settlement_client::{McrSettlementClient, McrStakingClientOperations};
+
+#[derive(Debug, Parser, Clone)]
+#[clap(
+	rename_all = "kebab-case",
+	about = "Gets the staking status of a validator. If no attester is provided, uses the signer of this node."
+)]
+pub struct Status {
+	#[clap(flatten)]
+	pub movement_args: MovementArgs,
+	pub attester: Option<Address>,
+}
+
+impl Status {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		let config = self.movement_args.config().await?;
+		let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
+			.await
+			.context("Failed to build MCR settlement client with config")?;
+
+		let attester = self.attester.unwrap_or(settlement_client.signer_address);
+		let status = settlement_client.get_validator_status(attester).await?;
+		println!("attester: {}", status.attester);
+		println!("whitelisted: {}", status.whitelisted);
+		println!("current epoch: {}", status.current_epoch);
+		println!("current stake: {}", status.current_stake);
+		println!("next epoch stake: {}", status.next_epoch_stake);
+		println!("pending unstake: {}", status.pending_unstake);
+
+		Ok(())
+	}
+}
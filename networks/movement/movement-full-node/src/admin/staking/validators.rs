@@ -0,0 +1,29 @@
+use crate::common_args::MovementArgs;
+use anyhow::Context;
+use clap::Parser;
+use mcr_settlement_client::{McrSettlementClient, McrStakingClientOperations};
+
+#[derive(Debug, Parser, Clone)]
+#[clap(
+	rename_all = "kebab-case",
+	about = "Lists the attesters of the MCR settlement contract with their stake in the current epoch."
+)]
+pub struct Validators {
+	#[clap(flatten)]
+	pub movement_args: MovementArgs,
+}
+
+impl Validators {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		let config = self.movement_args.config().await?;
+		let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
+			.await
+			.context("Failed to build MCR settlement client with config")?;
+
+		for (attester, stake) in settlement_client.get_validator_set().await? {
+			println!("{} {}", attester, stake);
+		}
+
+		Ok(())
+	}
+}
@@ -1,14 +1,61 @@
-use rocksdb::{ColumnFamilyDescriptor, Options, DB};
+use async_trait::async_trait;
+use mcr_settlement_manager::{PendingCommitment, SettlementStore};
+use movement_types::block::Id;
+use rocksdb::{ColumnFamilyDescriptor, Options, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
 
 use std::path::Path;
 use std::sync::Arc;
 
 mod column_families {
 	pub const EXECUTED_BLOCKS: &str = "executed_blocks";
+	pub const EXECUTED_BLOCK_HEIGHTS: &str = "executed_block_heights";
 	pub const SYNCED_HEIGHT: &str = "synced_height";
+	pub const REVERT_CHECKPOINT: &str = "revert_checkpoint";
+	pub const PENDING_COMMITMENTS: &str = "pending_commitments";
 }
 use column_families::*;
 
+/// A block executed from the DA, indexed by its height in the ledger.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutedBlock {
+	/// The id of the block on the DA
+	pub id: Vec<u8>,
+	/// The height of the block in the ledger
+	pub height: u64,
+	/// The DA height the block was read at
+	pub da_height: u64,
+	/// The id of the block in the ledger, not recorded for the blocks indexed before it was
+	#[serde(default)]
+	pub ledger_block_id: Option<Id>,
+}
+
+/// Checkpoint of the DA state taken before reverting the ledger.
+///
+/// The checkpoint is persisted until the revert is fully applied,
+/// so that an interrupted revert can be resumed on startup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevertCheckpoint {
+	/// The ledger height to revert to
+	pub target_height: u64,
+	/// The synced DA height before the revert
+	pub synced_height: u64,
+	/// The executed blocks dropped by the revert
+	pub dropped_blocks: Vec<ExecutedBlock>,
+}
+
+impl RevertCheckpoint {
+	/// The synced DA height to replay the dropped blocks from.
+	pub fn replay_height(&self) -> u64 {
+		self.dropped_blocks
+			.iter()
+			// the synced height lags one behind the DA height of the executed block
+			.map(|block| block.da_height.saturating_sub(1))
+			.min()
+			.map_or(self.synced_height, |height| height.min(self.synced_height))
+	}
+}
+
 /// Simple data store for locally recorded DA events.
 ///
 /// An async access API is provided to avoid blocking async tasks.
@@ -26,20 +73,43 @@ impl DaDB {
 
 		let synced_height = ColumnFamilyDescriptor::new(SYNCED_HEIGHT, Options::default());
 		let executed_blocks = ColumnFamilyDescriptor::new(EXECUTED_BLOCKS, Options::default());
+		let executed_block_heights =
+			ColumnFamilyDescriptor::new(EXECUTED_BLOCK_HEIGHTS, Options::default());
+		let revert_checkpoint = ColumnFamilyDescriptor::new(REVERT_CHECKPOINT, Options::default());
+		let pending_commitments =
+			ColumnFamilyDescriptor::new(PENDING_COMMITMENTS, Options::default());
 
-		let db = DB::open_cf_descriptors(&options, path, vec![synced_height, executed_blocks])
-			.map_err(|e| anyhow::anyhow!("Failed to open DA DB: {:?}", e))?;
+		let db = DB::open_cf_descriptors(
+			&options,
+			path,
+			vec![
+				synced_height,
+				executed_blocks,
+				executed_block_heights,
+				revert_checkpoint,
+				pending_commitments,
+			],
+		)
+		.map_err(|e| anyhow::anyhow!("Failed to open DA DB: {:?}", e))?;
 		Ok(Self { inner: Arc::new(db) })
 	}
 
-	pub async fn add_executed_block(&self, id: Vec<u8>) -> Result<(), anyhow::Error> {
+	pub async fn add_executed_block(&self, block: ExecutedBlock) -> Result<(), anyhow::Error> {
 		let da_db = self.inner.clone();
 		tokio::task::spawn_blocking(move || {
 			let cf = da_db
 				.cf_handle(EXECUTED_BLOCKS)
 				.ok_or(anyhow::anyhow!("No executed_blocks column family"))?;
+			let heights_cf = da_db
+				.cf_handle(EXECUTED_BLOCK_HEIGHTS)
+				.ok_or(anyhow::anyhow!("No executed_block_heights column family"))?;
+			let value = serde_json::to_vec(&block)
+				.map_err(|e| anyhow::anyhow!("Failed to serialize executed block: {:?}", e))?;
+			let mut batch = WriteBatch::default();
+			batch.put_cf(&cf, block.id.clone(), block.id.clone());
+			batch.put_cf(&heights_cf, block.height.to_be_bytes(), value);
 			da_db
-				.put_cf(&cf, id.clone(), id)
+				.write(batch)
 				.map_err(|e| anyhow::anyhow!("Failed to add executed block: {:?}", e))
 		})
 		.await??;
@@ -60,6 +130,31 @@ impl DaDB {
 		Ok(id.is_some())
 	}
 
+	/// Gets the block executed from the DA at the given ledger height.
+	pub async fn get_executed_block_at_height(
+		&self,
+		height: u64,
+	) -> Result<Option<ExecutedBlock>, anyhow::Error> {
+		let da_db = self.inner.clone();
+		let block = tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(EXECUTED_BLOCK_HEIGHTS)
+				.ok_or(anyhow::anyhow!("No executed_block_heights column family"))?;
+			let value = da_db
+				.get_cf(&cf, height.to_be_bytes())
+				.map_err(|e| anyhow::anyhow!("Failed to get executed block: {:?}", e))?;
+			value
+				.map(|value| {
+					serde_json::from_slice(&value).map_err(|e| {
+						anyhow::anyhow!("Failed to deserialize executed block: {:?}", e)
+					})
+				})
+				.transpose()
+		})
+		.await??;
+		Ok(block)
+	}
+
 	pub async fn set_synced_height(&self, height: u64) -> Result<(), anyhow::Error> {
 		// This is heavy for this purpose, but progressively the contents of the DA DB will be used for more things
 		let da_db = self.inner.clone();
@@ -97,4 +192,320 @@ impl DaDB {
 		.await??;
 		Ok(height)
 	}
+
+	/// Gets the executed blocks above the given ledger height, in height order.
+	pub async fn get_executed_blocks_above(
+		&self,
+		height: u64,
+	) -> Result<Vec<ExecutedBlock>, anyhow::Error> {
+		let da_db = self.inner.clone();
+		let blocks = tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(EXECUTED_BLOCK_HEIGHTS)
+				.ok_or(anyhow::anyhow!("No executed_block_heights column family"))?;
+			let start = (height + 1).to_be_bytes();
+			let mut blocks = Vec::new();
+			for item in da_db
+				.iterator_cf(&cf, rocksdb::IteratorMode::From(&start, rocksdb::Direction::Forward))
+			{
+				let (_key, value) =
+					item.map_err(|e| anyhow::anyhow!("Failed to read executed block: {:?}", e))?;
+				let block: ExecutedBlock = serde_json::from_slice(&value).map_err(|e| {
+					anyhow::anyhow!("Failed to deserialize executed block: {:?}", e)
+				})?;
+				blocks.push(block);
+			}
+			Ok::<Vec<ExecutedBlock>, anyhow::Error>(blocks)
+		})
+		.await??;
+		Ok(blocks)
+	}
+
+	/// Records a checkpoint of the DA state before reverting the ledger from `head_height`
+	/// to `target_height`.
+	///
+	/// Blocks executed before the height index was introduced have no entry in it,
+	/// so their DA state can't be rolled back. The revert is refused if it drops such blocks.
+	pub async fn create_revert_checkpoint(
+		&self,
+		target_height: u64,
+		head_height: u64,
+	) -> Result<RevertCheckpoint, anyhow::Error> {
+		let dropped_blocks = self.get_executed_blocks_above(target_height).await?;
+		let unindexed_heights: Vec<u64> = (target_height + 1..=head_height)
+			.filter(|height| !dropped_blocks.iter().any(|block| block.height == *height))
+			.collect();
+		if !unindexed_heights.is_empty() {
+			anyhow::bail!(
+				"Can't revert to height {}: heights {:?} are not indexed, resync the node from the DA",
+				target_height,
+				unindexed_heights
+			);
+		}
+		let checkpoint = RevertCheckpoint {
+			target_height,
+			synced_height: self.get_synced_height().await?,
+			dropped_blocks,
+		};
+		let value = serde_json::to_vec(&checkpoint)
+			.map_err(|e| anyhow::anyhow!("Failed to serialize revert checkpoint: {:?}", e))?;
+		let da_db = self.inner.clone();
+		tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(REVERT_CHECKPOINT)
+				.ok_or(anyhow::anyhow!("No revert_checkpoint column family"))?;
+			da_db
+				.put_cf(&cf, "revert_checkpoint", value)
+				.map_err(|e| anyhow::anyhow!("Failed to set revert checkpoint: {:?}", e))
+		})
+		.await??;
+		Ok(checkpoint)
+	}
+
+	/// Gets the checkpoint of a revert which has not been completed.
+	pub async fn get_revert_checkpoint(&self) -> Result<Option<RevertCheckpoint>, anyhow::Error> {
+		let da_db = self.inner.clone();
+		let checkpoint = tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(REVERT_CHECKPOINT)
+				.ok_or(anyhow::anyhow!("No revert_checkpoint column family"))?;
+			let checkpoint = da_db
+				.get_cf(&cf, "revert_checkpoint")
+				.map_err(|e| anyhow::anyhow!("Failed to get revert checkpoint: {:?}", e))?;
+			checkpoint
+				.map(|checkpoint| serde_json::from_slice(&checkpoint))
+				.transpose()
+				.map_err(|e| anyhow::anyhow!("Failed to deserialize revert checkpoint: {:?}", e))
+		})
+		.await??;
+		Ok(checkpoint)
+	}
+
+	/// Rolls the DA state back to the checkpoint and clears it.
+	///
+	/// The dropped blocks are forgotten and the synced height is rewound,
+	/// so that the dropped blocks are read from the DA and executed again.
+	/// Returns the synced height to replay from.
+	pub async fn apply_revert_checkpoint(
+		&self,
+		checkpoint: &RevertCheckpoint,
+	) -> Result<u64, anyhow::Error> {
+		let replay_height = checkpoint.replay_height();
+		let synced_height = serde_json::to_string(&replay_height)
+			.map_err(|e| anyhow::anyhow!("Failed to serialize synced height: {:?}", e))?;
+		let dropped_blocks = checkpoint.dropped_blocks.clone();
+		let da_db = self.inner.clone();
+		tokio::task::spawn_blocking(move || {
+			let executed_blocks_cf = da_db
+				.cf_handle(EXECUTED_BLOCKS)
+				.ok_or(anyhow::anyhow!("No executed_blocks column family"))?;
+			let heights_cf = da_db
+				.cf_handle(EXECUTED_BLOCK_HEIGHTS)
+				.ok_or(anyhow::anyhow!("No executed_block_heights column family"))?;
+			let synced_height_cf = da_db
+				.cf_handle(SYNCED_HEIGHT)
+				.ok_or(anyhow::anyhow!("No synced_height column family"))?;
+			let checkpoint_cf = da_db
+				.cf_handle(REVERT_CHECKPOINT)
+				.ok_or(anyhow::anyhow!("No revert_checkpoint column family"))?;
+
+			// apply the rollback and clear the checkpoint atomically
+			let mut batch = WriteBatch::default();
+			for block in dropped_blocks {
+				batch.delete_cf(&executed_blocks_cf, block.id);
+				batch.delete_cf(&heights_cf, block.height.to_be_bytes());
+			}
+			batch.put_cf(&synced_height_cf, "synced_height", synced_height);
+			batch.delete_cf(&checkpoint_cf, "revert_checkpoint");
+			da_db
+				.write(batch)
+				.map_err(|e| anyhow::anyhow!("Failed to apply revert checkpoint: {:?}", e))
+		})
+		.await??;
+		Ok(replay_height)
+	}
+}
+
+/// Keeps the pending commitments of the settlement manager across restarts.
+#[async_trait]
+impl SettlementStore for DaDB {
+	async fn load_pending_commitments(&self) -> Result<Vec<PendingCommitment>, anyhow::Error> {
+		let da_db = self.inner.clone();
+		let commitments = tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(PENDING_COMMITMENTS)
+				.ok_or(anyhow::anyhow!("No pending_commitments column family"))?;
+			let mut commitments = Vec::new();
+			// keys are big-endian heights, so iteration is in height order
+			for item in da_db.iterator_cf(&cf, rocksdb::IteratorMode::Start) {
+				let (_key, value) = item
+					.map_err(|e| anyhow::anyhow!("Failed to read pending commitment: {:?}", e))?;
+				let commitment: PendingCommitment =
+					serde_json::from_slice(&value).map_err(|e| {
+						anyhow::anyhow!("Failed to deserialize pending commitment: {:?}", e)
+					})?;
+				commitments.push(commitment);
+			}
+			Ok::<Vec<PendingCommitment>, anyhow::Error>(commitments)
+		})
+		.await??;
+		Ok(commitments)
+	}
+
+	async fn put_pending_commitments(
+		&self,
+		commitments: Vec<PendingCommitment>,
+	) -> Result<(), anyhow::Error> {
+		let da_db = self.inner.clone();
+		tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(PENDING_COMMITMENTS)
+				.ok_or(anyhow::anyhow!("No pending_commitments column family"))?;
+			let mut batch = WriteBatch::default();
+			for commitment in commitments {
+				let value = serde_json::to_vec(&commitment).map_err(|e| {
+					anyhow::anyhow!("Failed to serialize pending commitment: {:?}", e)
+				})?;
+				batch.put_cf(&cf, commitment.commitment.height().to_be_bytes(), value);
+			}
+			da_db
+				.write(batch)
+				.map_err(|e| anyhow::anyhow!("Failed to put pending commitments: {:?}", e))
+		})
+		.await??;
+		Ok(())
+	}
+
+	async fn remove_pending_commitments(&self, heights: Vec<u64>) -> Result<(), anyhow::Error> {
+		let da_db = self.inner.clone();
+		tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(PENDING_COMMITMENTS)
+				.ok_or(anyhow::anyhow!("No pending_commitments column family"))?;
+			let mut batch = WriteBatch::default();
+			for height in heights {
+				batch.delete_cf(&cf, height.to_be_bytes());
+			}
+			da_db
+				.write(batch)
+				.map_err(|e| anyhow::anyhow!("Failed to remove pending commitments: {:?}", e))
+		})
+		.await??;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use movement_types::block::{BlockCommitment, Commitment};
+
+	fn executed_block(height: u64, da_height: u64) -> ExecutedBlock {
+		ExecutedBlock {
+			id: format!("block-{}", height).into_bytes(),
+			height,
+			da_height,
+			ledger_block_id: Some(Id::new([height as u8; 32])),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_multi_block_revert_checkpoint() -> Result<(), anyhow::Error> {
+		let tempdir = tempfile::tempdir()?;
+		let da_db = DaDB::open(tempdir.path())?;
+
+		// blocks 1..=6, two blocks per DA height
+		for height in 1..=6 {
+			da_db.add_executed_block(executed_block(height, 2 + height / 2)).await?;
+		}
+		da_db.set_synced_height(4).await?;
+
+		let checkpoint = da_db.create_revert_checkpoint(2, 6).await?;
+		assert_eq!(checkpoint.dropped_blocks.len(), 4);
+		assert_eq!(da_db.get_revert_checkpoint().await?, Some(checkpoint.clone()));
+
+		let replay_height = da_db.apply_revert_checkpoint(&checkpoint).await?;
+		// block 3 was read at DA height 3
+		assert_eq!(replay_height, 2);
+		assert_eq!(da_db.get_synced_height().await?, 2);
+		assert_eq!(da_db.get_revert_checkpoint().await?, None);
+		for height in 1..=6 {
+			let executed = da_db.has_executed_block(executed_block(height, 0).id).await?;
+			assert_eq!(executed, height <= 2);
+		}
+		assert!(da_db.get_executed_blocks_above(2).await?.is_empty());
+		assert_eq!(da_db.get_executed_block_at_height(2).await?, Some(executed_block(2, 3)));
+		assert_eq!(da_db.get_executed_block_at_height(3).await?, None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_revert_checkpoint_without_dropped_blocks() -> Result<(), anyhow::Error> {
+		let tempdir = tempfile::tempdir()?;
+		let da_db = DaDB::open(tempdir.path())?;
+
+		da_db.add_executed_block(executed_block(1, 2)).await?;
+		da_db.set_synced_height(7).await?;
+
+		let checkpoint = da_db.create_revert_checkpoint(1, 1).await?;
+		assert!(checkpoint.dropped_blocks.is_empty());
+		assert_eq!(da_db.apply_revert_checkpoint(&checkpoint).await?, 7);
+		assert_eq!(da_db.get_synced_height().await?, 7);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_revert_checkpoint_refuses_unindexed_blocks() -> Result<(), anyhow::Error> {
+		let tempdir = tempfile::tempdir()?;
+		let da_db = DaDB::open(tempdir.path())?;
+
+		// blocks 1..=3 were executed before the height index was introduced
+		for height in 4..=5 {
+			da_db.add_executed_block(executed_block(height, height)).await?;
+		}
+		da_db.set_synced_height(4).await?;
+
+		assert!(da_db.create_revert_checkpoint(2, 5).await.is_err());
+		assert_eq!(da_db.get_revert_checkpoint().await?, None);
+		let checkpoint = da_db.create_revert_checkpoint(3, 5).await?;
+		assert_eq!(checkpoint.dropped_blocks.len(), 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_pending_commitments_survive_reopen() -> Result<(), anyhow::Error> {
+		let tempdir = tempfile::tempdir()?;
+		let pending = |height: u64, posted: bool| PendingCommitment {
+			commitment: BlockCommitment::new(
+				height,
+				Default::default(),
+				Commitment::new([height as u8; 32]),
+			),
+			posted,
+		};
+
+		{
+			let da_db = DaDB::open(tempdir.path())?;
+			da_db
+				.put_pending_commitments(vec![
+					pending(300, false),
+					pending(2, false),
+					pending(1, true),
+				])
+				.await?;
+			da_db.put_pending_commitments(vec![pending(2, true)]).await?;
+			da_db.remove_pending_commitments(vec![1]).await?;
+		}
+
+		let da_db = DaDB::open(tempdir.path())?;
+		assert_eq!(
+			da_db.load_pending_commitments().await?,
+			vec![pending(2, true), pending(300, false)]
+		);
+
+		Ok(())
+	}
 }
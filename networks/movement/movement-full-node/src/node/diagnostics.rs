@@ -0,0 +1,231 @@
+//! Diagnostics of the commitments rejected by the settlement contract.
+
+use crate::node::da_db::ExecutedBlock;
+
+use dot_movement::DotMovement;
+use maptos_dof_execution::BlockDiagnostics;
+use movement_config::diagnostics::Config as DiagnosticsConfig;
+use movement_types::block::{BlockCommitment, BlockCommitmentRejectionReason};
+
+use serde::{Deserialize, Serialize};
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The likely cause of a local commitment diverging from the accepted one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RootCause {
+	/// The same block was executed to a different state.
+	NondeterministicExecution,
+	/// A different block was read from the DA at this height.
+	BadDaRead,
+	/// The local ledger at this height was not built from the blocks read by this node,
+	/// e.g. it was restored from an outdated snapshot.
+	StaleNode,
+	/// No commitment was accepted at this height to compare with.
+	Unknown,
+}
+
+impl RootCause {
+	pub fn classify(
+		local: &BlockCommitment,
+		accepted: Option<&BlockCommitment>,
+		executed_block: Option<&ExecutedBlock>,
+	) -> Self {
+		let Some(accepted) = accepted else {
+			return RootCause::Unknown;
+		};
+		// without an index entry, the block at this height was not executed from the DA by this node
+		let Some(executed_block) = executed_block else {
+			return RootCause::StaleNode;
+		};
+		// the blocks indexed before the ledger block id was recorded fall back to the local commitment
+		let executed_block_id = executed_block.ledger_block_id.as_ref().unwrap_or(local.block_id());
+		if executed_block_id == accepted.block_id() {
+			RootCause::NondeterministicExecution
+		} else {
+			RootCause::BadDaRead
+		}
+	}
+}
+
+/// The diagnostic bundle of a rejected commitment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectionDiagnostics {
+	pub height: u64,
+	pub reason: BlockCommitmentRejectionReason,
+	/// Seconds since the Unix epoch when the rejection was processed
+	pub rejected_at: u64,
+	pub root_cause: RootCause,
+	/// The commitment accepted by the settlement contract at the height
+	pub accepted: Option<BlockCommitment>,
+	/// The local block at the height, with its state and write sets
+	pub local: BlockDiagnostics,
+	/// The DA block executed at the height, if it was read by this node
+	pub executed_block: Option<ExecutedBlock>,
+	/// The synced DA height of this node
+	pub synced_height: u64,
+}
+
+impl RejectionDiagnostics {
+	pub fn new(
+		reason: BlockCommitmentRejectionReason,
+		accepted: Option<BlockCommitment>,
+		local: BlockDiagnostics,
+		executed_block: Option<ExecutedBlock>,
+		synced_height: u64,
+	) -> Result<Self, anyhow::Error> {
+		let rejected_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+		let root_cause =
+			RootCause::classify(&local.commitment, accepted.as_ref(), executed_block.as_ref());
+		Ok(Self {
+			height: local.commitment.height(),
+			reason,
+			rejected_at,
+			root_cause,
+			accepted,
+			local,
+			executed_block,
+			synced_height,
+		})
+	}
+}
+
+/// Writes the diagnostic bundles to a directory, one JSON file per rejected commitment.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsStore {
+	path: PathBuf,
+}
+
+impl DiagnosticsStore {
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self { path: path.into() }
+	}
+
+	/// Writes to the directory of the config, a relative directory is in the dot movement.
+	pub fn from_config(config: &DiagnosticsConfig, dot_movement: &DotMovement) -> Self {
+		Self::new(dot_movement.get_path().join(&config.diagnostics_path))
+	}
+
+	/// Writes a bundle, returning the path of its file.
+	pub async fn write(
+		&self,
+		diagnostics: &RejectionDiagnostics,
+	) -> Result<PathBuf, anyhow::Error> {
+		tokio::fs::create_dir_all(&self.path).await.map_err(|e| {
+			anyhow::anyhow!("Failed to create diagnostics directory {:?}: {:?}", self.path, e)
+		})?;
+		let file = self.path.join(format!(
+			"rejected-commitment-{}-{}.json",
+			diagnostics.height, diagnostics.rejected_at
+		));
+		let contents = serde_json::to_vec_pretty(diagnostics)
+			.map_err(|e| anyhow::anyhow!("Failed to serialize diagnostics: {:?}", e))?;
+		tokio::fs::write(&file, contents)
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to write diagnostics {:?}: {:?}", file, e))?;
+		Ok(file)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use maptos_dof_execution::HashValue;
+	use movement_types::block::{Commitment, Id};
+
+	fn commitment(block: u8, value: u8) -> BlockCommitment {
+		BlockCommitment::new(1, Id::new([block; 32]), Commitment::new([value; 32]))
+	}
+
+	fn executed_block(ledger_block: Option<u8>) -> ExecutedBlock {
+		ExecutedBlock {
+			id: vec![1; 32],
+			height: 1,
+			da_height: 2,
+			ledger_block_id: ledger_block.map(|block| Id::new([block; 32])),
+		}
+	}
+
+	#[test]
+	fn test_classify_root_cause() {
+		let local = commitment(1, 1);
+		assert_eq!(
+			RootCause::classify(&local, Some(&commitment(1, 2)), Some(&executed_block(Some(1)))),
+			RootCause::NondeterministicExecution
+		);
+		assert_eq!(
+			RootCause::classify(&local, Some(&commitment(2, 2)), Some(&executed_block(Some(1)))),
+			RootCause::BadDaRead
+		);
+		assert_eq!(
+			RootCause::classify(&local, Some(&commitment(2, 2)), None),
+			RootCause::StaleNode
+		);
+		assert_eq!(RootCause::classify(&local, None, None), RootCause::Unknown);
+	}
+
+	#[test]
+	fn test_classify_root_cause_against_the_executed_block() {
+		// the ledger was not built from the block read from the DA at this height
+		let local = commitment(2, 1);
+		assert_eq!(
+			RootCause::classify(&local, Some(&commitment(2, 2)), Some(&executed_block(Some(1)))),
+			RootCause::BadDaRead
+		);
+		// a missing index entry is a stale node, even if the block ids agree
+		assert_eq!(
+			RootCause::classify(&local, Some(&commitment(2, 2)), None),
+			RootCause::StaleNode
+		);
+		// an entry indexed without the ledger block id is compared to the local block
+		assert_eq!(
+			RootCause::classify(&local, Some(&commitment(2, 2)), Some(&executed_block(None))),
+			RootCause::NondeterministicExecution
+		);
+		assert_eq!(
+			RootCause::classify(&local, Some(&commitment(1, 2)), Some(&executed_block(None))),
+			RootCause::BadDaRead
+		);
+	}
+
+	#[test]
+	fn test_store_in_dot_movement() {
+		let dot_movement = DotMovement::new("/tmp/.movement");
+		let config = DiagnosticsConfig { diagnostics_path: "diagnostics".to_string() };
+		let store = DiagnosticsStore::from_config(&config, &dot_movement);
+		assert_eq!(store.path, PathBuf::from("/tmp/.movement/diagnostics"));
+
+		let config = DiagnosticsConfig { diagnostics_path: "/var/diagnostics".to_string() };
+		let store = DiagnosticsStore::from_config(&config, &dot_movement);
+		assert_eq!(store.path, PathBuf::from("/var/diagnostics"));
+	}
+
+	#[tokio::test]
+	async fn test_write_diagnostics() -> Result<(), anyhow::Error> {
+		let tempdir = tempfile::tempdir()?;
+		let store = DiagnosticsStore::new(tempdir.path().join("diagnostics"));
+		let local = BlockDiagnostics {
+			commitment: commitment(1, 1),
+			transaction_accumulator_hash: HashValue::zero(),
+			state_root: HashValue::zero(),
+			first_version: 1,
+			last_version: 2,
+			transactions: vec![],
+		};
+		let diagnostics = RejectionDiagnostics::new(
+			BlockCommitmentRejectionReason::InvalidCommitment,
+			Some(commitment(1, 2)),
+			local,
+			Some(executed_block(Some(1))),
+			2,
+		)?;
+
+		let file = store.write(&diagnostics).await?;
+		let written: RejectionDiagnostics = serde_json::from_slice(&tokio::fs::read(file).await?)?;
+		assert_eq!(written, diagnostics);
+		assert_eq!(written.root_cause, RootCause::NondeterministicExecution);
+
+		Ok(())
+	}
+}
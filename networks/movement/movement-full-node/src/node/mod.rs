@@ -1,4 +1,5 @@
 mod da_db;
+mod diagnostics;
 pub mod manager;
 pub mod partial;
 mod tasks;
@@ -1,7 +1,12 @@
-use crate::node::{da_db::DaDB, tasks};
+use crate::node::tasks::settlement_slots::{
+	L1BlockSlots, SettlementSlots, SlotClock, WallClockSlots,
+};
+use crate::node::{da_db::DaDB, diagnostics::DiagnosticsStore, tasks};
 use maptos_dof_execution::MakeOptFinServices;
 use maptos_dof_execution::{v1::Executor, DynOptFinExecutor};
+use mcr_settlement_aggregator::{Aggregation, AttestationService};
 use mcr_settlement_client::McrSettlementClient;
+use mcr_settlement_config::common::settlement::SettlementSlots as SettlementSlotsConfig;
 use mcr_settlement_manager::CommitmentEventStream;
 use mcr_settlement_manager::McrSettlementManager;
 use movement_config::Config;
@@ -9,6 +14,8 @@ use movement_da_light_node_client::MovementDaLightNodeClient;
 use movement_rest::MovementRest;
 
 use anyhow::Context;
+use dot_movement::DotMovement;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::try_join;
 use tracing::debug;
@@ -18,6 +25,8 @@ pub struct MovementPartialNode<T> {
 	light_node_client: MovementDaLightNodeClient,
 	settlement_manager: Option<McrSettlementManager>,
 	commitment_events: Option<CommitmentEventStream>,
+	settlement_slots: Option<SettlementSlots>,
+	attestation_service: Option<AttestationService>,
 	movement_rest: MovementRest,
 	config: Config,
 	da_db: DaDB,
@@ -53,6 +62,8 @@ where
 			self.commitment_events,
 			self.config.execution_extension.clone(),
 			self.config.mcr.clone(),
+			self.settlement_slots,
+			DiagnosticsStore::from_config(&self.config.diagnostics, &DotMovement::try_from_env()?),
 		);
 		let transaction_ingress_task = tasks::transaction_ingress::Task::new(
 			transaction_receiver,
@@ -61,22 +72,32 @@ where
 			self.config.celestia_da_light_node.celestia_da_light_node_config,
 		);
 
+		let attestation_service = self.attestation_service;
+
 		let (
 			execution_and_settlement_result,
 			transaction_ingress_result,
 			background_task_result,
 			services_result,
+			attestation_service_result,
 		) = try_join!(
 			tokio::spawn(async move { exec_settle_task.run().await }),
 			tokio::spawn(async move { transaction_ingress_task.run().await }),
 			tokio::spawn(exec_background),
 			tokio::spawn(services.run()),
+			tokio::spawn(async move {
+				match attestation_service {
+					Some(service) => service.run().await,
+					None => Ok(()),
+				}
+			}),
 			// tokio::spawn(async move { movement_rest.run_service().await }),
 		)?;
 		execution_and_settlement_result
 			.and(transaction_ingress_result)
 			.and(background_task_result)
 			.and(services_result)
+			.and(attestation_service_result)
 	}
 }
 
@@ -142,34 +163,74 @@ impl MovementPartialNode<Executor> {
 		let executor = Executor::try_from_config(config.execution_config.maptos_config.clone())
 			.context("Failed to create the inner executor")?;
 
-		let (settlement_manager, commitment_events) = if config.mcr.should_settle() {
+		debug!("Creating the DA DB");
+		let da_db =
+			DaDB::open(&config.da_db.da_db_path).context("Failed to create or get DA DB")?;
+
+		let mut attestation_service = None;
+		let (settlement_manager, commitment_events, settlement_slots) = if config
+			.mcr
+			.should_settle()
+		{
 			debug!("Creating the settlement client");
 			let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
 				.await
 				.context("Failed to build MCR settlement client with config")?;
-			let (settlement_manager, commitment_events) =
-				McrSettlementManager::new(settlement_client, &config.mcr);
-			(Some(settlement_manager), Some(commitment_events))
+			// pending commitments are kept in the DA DB to be restored on restart
+			let (settlement_manager, commitment_events) = if config.mcr.aggregation.is_some() {
+				debug!("Creating the MCR attestation aggregation");
+				let (aggregation, service) = Aggregation::try_from_config(&config.mcr)
+					.context("Failed to create the MCR attestation aggregation")?;
+				attestation_service = Some(service);
+				McrSettlementManager::new_with_aggregation(
+					settlement_client,
+					&config.mcr,
+					da_db.clone(),
+					aggregation,
+				)
+			} else {
+				McrSettlementManager::new_with_store(settlement_client, &config.mcr, da_db.clone())
+			};
+			let settlement_slots = Self::settlement_slots(&config.mcr).await?;
+			(Some(settlement_manager), Some(commitment_events), settlement_slots)
 		} else {
-			(None, None)
+			(None, None, None)
 		};
 
 		debug!("Creating the movement rest service");
 		let movement_rest =
 			MovementRest::try_from_env().context("Failed to create MovementRest")?;
 
-		debug!("Creating the DA DB");
-		let da_db =
-			DaDB::open(&config.da_db.da_db_path).context("Failed to create or get DA DB")?;
-
 		Ok(Self {
 			executor,
 			light_node_client,
 			settlement_manager,
 			commitment_events,
+			settlement_slots,
+			attestation_service,
 			movement_rest,
 			config,
 			da_db,
 		})
 	}
+
+	async fn settlement_slots(
+		config: &mcr_settlement_config::Config,
+	) -> Result<Option<SettlementSlots>, anyhow::Error> {
+		let slot_length = config.settle.settlement_slot_length;
+		let clock: Box<dyn SlotClock> = match config.settle.settlement_slots {
+			SettlementSlotsConfig::DaHeight => return Ok(None),
+			SettlementSlotsConfig::WallClock => {
+				Box::new(WallClockSlots::new(Duration::from_secs(slot_length)))
+			}
+			SettlementSlotsConfig::L1Block => {
+				// a client of its own, the settlement manager owns the one posting commitments
+				let client = McrSettlementClient::build_with_config(config)
+					.await
+					.context("Failed to build MCR settlement client for the L1 block slots")?;
+				Box::new(L1BlockSlots::new(client, slot_length))
+			}
+		};
+		Ok(Some(SettlementSlots::new(clock)))
+	}
 }
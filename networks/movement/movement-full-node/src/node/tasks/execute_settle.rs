@@ -1,6 +1,8 @@
 //! Task module to execute blocks from the DA and process settlement.
 
-use crate::node::da_db::DaDB;
+use crate::node::da_db::{DaDB, ExecutedBlock, RevertCheckpoint};
+use crate::node::diagnostics::{DiagnosticsStore, RejectionDiagnostics};
+use crate::node::tasks::settlement_slots::SettlementSlots;
 
 use maptos_dof_execution::{
 	DynOptFinExecutor, ExecutableBlock, ExecutableTransactions, HashValue,
@@ -11,14 +13,18 @@ use movement_da_light_node_client::MovementDaLightNodeClient;
 use movement_da_light_node_proto::{
 	blob_response, StreamReadFromHeightRequest, StreamReadFromHeightResponse,
 };
-use movement_types::block::{Block, BlockCommitment, BlockCommitmentEvent};
+use movement_types::block::{
+	Block, BlockCommitment, BlockCommitmentEvent, BlockCommitmentRejectionReason,
+};
 
 use anyhow::Context;
 use futures::{future::Either, stream};
 use movement_config::execution_extension;
 use tokio::select;
 use tokio_stream::{Stream, StreamExt};
-use tracing::{debug, error, info, info_span, Instrument};
+use tracing::{debug, error, info, info_span, warn, Instrument};
+
+use std::path::PathBuf;
 
 pub struct Task<E, S> {
 	executor: E,
@@ -30,6 +36,10 @@ pub struct Task<E, S> {
 		Either<CommitmentEventStream, stream::Pending<<CommitmentEventStream as Stream>::Item>>,
 	execution_extension: execution_extension::Config,
 	settlement_config: mcr_settlement_config::Config,
+	// Slots at which blocks are settled, blocks are settled by DA height when not set
+	settlement_slots: Option<SettlementSlots>,
+	// Where the diagnostics of rejected commitments are written
+	diagnostics: DiagnosticsStore,
 }
 
 impl<E, S> Task<E, S> {
@@ -41,6 +51,8 @@ impl<E, S> Task<E, S> {
 		commitment_events: Option<CommitmentEventStream>,
 		execution_extension: execution_extension::Config,
 		settlement_config: mcr_settlement_config::Config,
+		settlement_slots: Option<SettlementSlots>,
+		diagnostics: DiagnosticsStore,
 	) -> Self {
 		let commitment_events = match commitment_events {
 			Some(stream) => Either::Left(stream),
@@ -54,12 +66,22 @@ impl<E, S> Task<E, S> {
 			commitment_events,
 			execution_extension,
 			settlement_config,
+			settlement_slots,
+			diagnostics,
 		}
 	}
 
 	fn settlement_enabled(&self) -> bool {
 		matches!(&self.commitment_events, Either::Left(_))
 	}
+
+	/// Waits for the next check of the settlement slots, forever if there are none.
+	async fn next_slot_tick(settlement_slots: &mut Option<SettlementSlots>) {
+		match settlement_slots {
+			Some(settlement_slots) => settlement_slots.tick().await,
+			None => std::future::pending().await,
+		}
+	}
 }
 
 impl<E, S> Task<E, S>
@@ -68,6 +90,12 @@ where
 	S: McrSettlementManagerOperations,
 {
 	pub async fn run(mut self) -> anyhow::Result<()> {
+		// finish a revert that was interrupted before it was fully applied
+		if let Some(checkpoint) = self.da_db.get_revert_checkpoint().await? {
+			info!("Resuming interrupted revert: {:?}", checkpoint);
+			self.apply_revert(checkpoint).await?;
+		}
+
 		let synced_height = self.da_db.get_synced_height().await?;
 		info!("Synced height: {:?}", synced_height);
 		let mut blocks_from_da = self
@@ -83,7 +111,19 @@ where
 				}
 				Some(res) = self.commitment_events.next() => {
 					let event = res.context("failed to get commitment event")?;
-					self.process_commitment_event(event).await?;
+					if let Some(replay_height) = self.process_commitment_event(event).await? {
+						// replay the reverted blocks from the DA
+						info!("Replaying blocks from DA height {}", replay_height);
+						blocks_from_da = self
+							.da_light_node_client
+							.stream_read_from_height(StreamReadFromHeightRequest {
+								height: replay_height,
+							})
+							.await?;
+					}
+				}
+				_ = Self::next_slot_tick(&mut self.settlement_slots) => {
+					self.process_slot_tick().await;
 				}
 				else => break,
 			}
@@ -144,33 +184,62 @@ where
 		self.da_db.set_synced_height(da_height - 1).await?;
 
 		// set the block as executed
-		self.da_db.add_executed_block(block_id.clone()).await?;
+		self.da_db
+			.add_executed_block(ExecutedBlock {
+				id: block_id.clone(),
+				height: commitment.height(),
+				da_height,
+				ledger_block_id: Some(*commitment.block_id()),
+			})
+			.await?;
 
-		if self.settlement_enabled()
-			// only settle every super_block_size_heights 
-			// todo: replace with timeslot tolerance
+		if let Some(settlement_slots) = &mut self.settlement_slots {
+			// the executed blocks are settled when the slot ends
+			settlement_slots.block_executed(commitment);
+		} else if self.settlement_enabled()
+			// only settle every super_block_size_heights
 			&& da_height % self.settlement_config.settle.settlement_super_block_size == 0
 		{
-			info!("Posting block commitment via settlement manager");
-			match &self.settlement_manager {
-				Some(settlement_manager) => {
-					match settlement_manager.post_block_commitment(commitment).await {
-						Ok(_) => {}
-						Err(e) => {
-							error!("Failed to post block commitment: {:?}", e);
-						}
-					}
-				}
-				None => {
-					error!("Settlement manager not initialized");
-				}
-			}
+			self.settle(commitment).await;
 		} else {
 			info!(block_id = ?block_id, "Skipping settlement");
 		}
 
 		Ok(())
 	}
+
+	/// Settles the latest executed block if a settlement slot has ended.
+	async fn process_slot_tick(&mut self) {
+		let Some(settlement_slots) = &mut self.settlement_slots else {
+			return;
+		};
+		match settlement_slots.poll().await {
+			Ok(commitments) => {
+				for commitment in commitments {
+					self.settle(commitment).await;
+				}
+			}
+			// the clock is checked again at the next tick
+			Err(e) => error!("Failed to check the settlement slot: {:?}", e),
+		}
+	}
+
+	async fn settle(&self, commitment: BlockCommitment) {
+		info!("Posting block commitment via settlement manager");
+		match &self.settlement_manager {
+			Some(settlement_manager) => {
+				match settlement_manager.post_block_commitment(commitment).await {
+					Ok(_) => {}
+					Err(e) => {
+						error!("Failed to post block commitment: {:?}", e);
+					}
+				}
+			}
+			None => {
+				error!("Settlement manager not initialized");
+			}
+		}
+	}
 }
 
 impl<E, S> Task<E, S>
@@ -247,35 +316,160 @@ where
 		Ok(commitment)
 	}
 
+	/// Processes a commitment event from the settlement manager.
+	/// If the ledger is reverted, returns the DA height to replay the reverted blocks from.
 	async fn process_commitment_event(
 		&mut self,
 		event: BlockCommitmentEvent,
-	) -> anyhow::Result<()> {
+	) -> anyhow::Result<Option<u64>> {
 		match event {
 			BlockCommitmentEvent::Accepted(commitment) => {
 				debug!("Commitment accepted: {:?}", commitment);
 				self.executor
 					.set_finalized_block_height(commitment.height())
-					.context("failed to set finalized block height")
+					.context("failed to set finalized block height")?;
+				Ok(None)
 			}
-			BlockCommitmentEvent::Rejected { height, reason } => {
+			BlockCommitmentEvent::Rejected { height, reason, accepted } => {
 				debug!("Commitment rejected: {:?} {:?}", height, reason);
 				let current_head_height = self.executor.get_block_head_height()?;
 				if height > current_head_height {
 					// Nothing to revert
-					Ok(())
-				} else if self.settlement_config.settle.settlement_admin_mode {
+					return Ok(None);
+				}
+				if reason == BlockCommitmentRejectionReason::InvalidCommitment {
+					// the diagnostics must be taken before the diverging block is reverted
+					match self.write_rejection_diagnostics(height, reason, accepted).await {
+						Ok(file) => warn!(
+							"Commitment at height {} diverged, diagnostics written to {:?}",
+							height, file
+						),
+						Err(e) => error!("Failed to write rejection diagnostics: {:?}", e),
+					}
+				}
+				if self.settlement_config.settle.settlement_admin_mode {
 					// Settlement admin assumes it's right.
 					// It does not try to correct settled value on the L1.
 					// Nor does it try to recompute its ledger.
-					Ok(())
+					Ok(None)
 				} else {
-					self.executor
-						.revert_block_head_to(height - 1)
+					let replay_height = self
+						.revert_to(height - 1)
 						.await
-						.context(format!("failed to revert to block height {}", height - 1))
+						.context(format!("failed to revert to block height {}", height - 1))?;
+					Ok(Some(replay_height))
 				}
 			}
 		}
 	}
+
+	/// Writes the diagnostic bundle of the local block whose commitment was rejected.
+	async fn write_rejection_diagnostics(
+		&self,
+		height: u64,
+		reason: BlockCommitmentRejectionReason,
+		accepted: Option<BlockCommitment>,
+	) -> anyhow::Result<PathBuf> {
+		let local = self.executor.get_block_diagnostics_at_height(height).await?;
+		let executed_block = self.da_db.get_executed_block_at_height(height).await?;
+		let synced_height = self.da_db.get_synced_height().await?;
+		let diagnostics =
+			RejectionDiagnostics::new(reason, accepted, local, executed_block, synced_height)?;
+		info!(
+			height,
+			root_cause = ?diagnostics.root_cause,
+			"Collected diagnostics of the rejected commitment"
+		);
+		self.diagnostics.write(&diagnostics).await
+	}
+
+	/// Reverts the ledger and the DA state together to the given block height.
+	///
+	/// A checkpoint of the DA state is persisted before the ledger is reverted,
+	/// so that a revert interrupted by a crash is completed on restart.
+	/// Returns the DA height to replay the reverted blocks from.
+	async fn revert_to(&mut self, height: u64) -> anyhow::Result<u64> {
+		let head_height = self.executor.get_block_head_height()?;
+		let checkpoint = self.da_db.create_revert_checkpoint(height, head_height).await?;
+		info!(
+			target_height = height,
+			dropped_blocks = checkpoint.dropped_blocks.len(),
+			"Reverting ledger"
+		);
+		self.apply_revert(checkpoint).await
+	}
+
+	async fn apply_revert(&mut self, checkpoint: RevertCheckpoint) -> anyhow::Result<u64> {
+		if let Some(settlement_slots) = &mut self.settlement_slots {
+			settlement_slots.reverted_to(checkpoint.target_height);
+		}
+		// the ledger may already be reverted if the revert was interrupted
+		if self.executor.get_block_head_height()? > checkpoint.target_height {
+			self.executor.revert_block_head_to(checkpoint.target_height).await?;
+		}
+		self.da_db.apply_revert_checkpoint(&checkpoint).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use maptos_dof_execution::v1::Executor;
+	use maptos_execution_util::config::Config;
+	use mcr_settlement_manager::McrSettlementManager;
+	use movement_types::block::{BlockMetadata, Id};
+	use std::collections::BTreeSet;
+
+	#[tokio::test]
+	async fn test_rejected_commitment_reverts_ledger_and_da_db() -> Result<(), anyhow::Error> {
+		let tempdir = tempfile::tempdir()?;
+		let mut maptos_config = Config::default();
+		maptos_config.chain.maptos_db_path.replace(tempdir.path().join("maptos"));
+		let executor = Executor::try_from_config(maptos_config)?;
+		let mut task: Task<Executor, McrSettlementManager> = Task::new(
+			executor,
+			None,
+			DaDB::open(tempdir.path().join("da-db"))?,
+			MovementDaLightNodeClient::try_http1("http://localhost:30730")?,
+			None,
+			execution_extension::Config::default(),
+			mcr_settlement_config::Config::default(),
+			None,
+			DiagnosticsStore::new(tempdir.path().join("diagnostics")),
+		);
+
+		// blocks 1..=4 are read from DA heights 2..=5
+		for height in 1..=4u64 {
+			let parent = Id::new([height as u8; 32]);
+			let block = Block::new(BlockMetadata::BlockMetadata, parent, BTreeSet::new());
+			let commitment = task.execute_block(block, height * 1_000_000).await?;
+			assert_eq!(commitment.height(), height);
+			let executed_block = ExecutedBlock {
+				id: vec![height as u8],
+				height,
+				da_height: height + 1,
+				ledger_block_id: Some(*commitment.block_id()),
+			};
+			task.da_db.add_executed_block(executed_block).await?;
+			task.da_db.set_synced_height(height).await?;
+		}
+
+		let event = BlockCommitmentEvent::Rejected {
+			height: 3,
+			reason: BlockCommitmentRejectionReason::InvalidCommitment,
+			accepted: None,
+		};
+		let replay_height = task.process_commitment_event(event).await?;
+
+		// the ledger and the DA state are reverted together, block 3 is read again
+		assert_eq!(replay_height, Some(3));
+		assert_eq!(task.executor.get_block_head_height()?, 2);
+		assert_eq!(task.da_db.get_synced_height().await?, 3);
+		assert_eq!(task.da_db.get_revert_checkpoint().await?, None);
+		assert!(task.da_db.get_executed_blocks_above(2).await?.is_empty());
+		assert!(task.da_db.has_executed_block(vec![2]).await?);
+		assert!(!task.da_db.has_executed_block(vec![3]).await?);
+
+		Ok(())
+	}
 }
@@ -0,0 +1,202 @@
+//! Slots driving the settlement of executed blocks.
+
+use mcr_settlement_client::McrSettlementClientOperations;
+use movement_types::block::BlockCommitment;
+
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::{interval, Interval, MissedTickBehavior};
+use tracing::{info, warn};
+
+/// How often the slot clock is checked for a slot boundary.
+const SLOT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A clock counting settlement slots.
+#[async_trait]
+pub trait SlotClock: Send + Sync {
+	/// Gets the number of the current slot.
+	async fn current_slot(&self) -> Result<u64, anyhow::Error>;
+}
+
+/// Slots of wall-clock time, aligned on the Unix epoch.
+pub struct WallClockSlots {
+	slot_duration: Duration,
+}
+
+impl WallClockSlots {
+	pub fn new(slot_duration: Duration) -> Self {
+		Self { slot_duration }
+	}
+}
+
+#[async_trait]
+impl SlotClock for WallClockSlots {
+	async fn current_slot(&self) -> Result<u64, anyhow::Error> {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+		Ok((now.as_millis() / self.slot_duration.as_millis().max(1)) as u64)
+	}
+}
+
+/// Slots of a number of blocks of the settlement chain.
+pub struct L1BlockSlots<C> {
+	client: C,
+	slot_blocks: u64,
+}
+
+impl<C> L1BlockSlots<C> {
+	pub fn new(client: C, slot_blocks: u64) -> Self {
+		Self { client, slot_blocks: slot_blocks.max(1) }
+	}
+}
+
+#[async_trait]
+impl<C> SlotClock for L1BlockSlots<C>
+where
+	C: McrSettlementClientOperations + Send + Sync,
+{
+	async fn current_slot(&self) -> Result<u64, anyhow::Error> {
+		Ok(self.client.get_l1_block_number().await? / self.slot_blocks)
+	}
+}
+
+/// Picks the blocks to settle at each slot boundary.
+///
+/// The blocks executed since the last settlement are settled in height order when a slot ends,
+/// so settlement never skips a height.
+/// A slot in which no new block was executed is skipped.
+/// Boundaries missed while the node was busy are coalesced into a single settlement.
+pub struct SettlementSlots {
+	clock: Box<dyn SlotClock>,
+	poll: Interval,
+	current_slot: Option<u64>,
+	executed: BTreeMap<u64, BlockCommitment>,
+	last_settled_height: u64,
+}
+
+impl SettlementSlots {
+	pub fn new(clock: Box<dyn SlotClock>) -> Self {
+		let mut poll = interval(SLOT_POLL_INTERVAL);
+		poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+		Self { clock, poll, current_slot: None, executed: BTreeMap::new(), last_settled_height: 0 }
+	}
+
+	/// Records a block executed in the current slot.
+	pub fn block_executed(&mut self, commitment: BlockCommitment) {
+		self.executed.insert(commitment.height(), commitment);
+	}
+
+	/// Forgets the blocks above a height the ledger was reverted to.
+	pub fn reverted_to(&mut self, height: u64) {
+		self.executed.split_off(&(height + 1));
+		self.last_settled_height = self.last_settled_height.min(height);
+	}
+
+	/// Waits until the slot clock should be checked again.
+	pub async fn tick(&mut self) {
+		self.poll.tick().await;
+	}
+
+	/// Checks the slot clock, returning the blocks to settle in height order if a slot has ended.
+	pub async fn poll(&mut self) -> Result<Vec<BlockCommitment>, anyhow::Error> {
+		let slot = self.clock.current_slot().await?;
+		let previous_slot = self.current_slot.replace(slot);
+		match previous_slot {
+			// the first slot seen is settled when it ends
+			None => return Ok(Vec::new()),
+			Some(previous_slot) if slot <= previous_slot => return Ok(Vec::new()),
+			Some(previous_slot) if slot > previous_slot + 1 => {
+				info!("Coalescing settlement of slots {} to {}", previous_slot, slot - 1);
+			}
+			Some(_) => {}
+		}
+
+		let executed = std::mem::take(&mut self.executed);
+		if executed.is_empty() {
+			info!("No block executed in slot {}, skipping it", slot - 1);
+			return Ok(Vec::new());
+		}
+		let (settled, to_settle): (Vec<_>, Vec<_>) = executed
+			.into_values()
+			.partition(|commitment| commitment.height() <= self.last_settled_height);
+		if !settled.is_empty() {
+			warn!("{} blocks already settled, skipping them in slot {}", settled.len(), slot - 1);
+		}
+		if let Some(commitment) = to_settle.last() {
+			self.last_settled_height = commitment.height();
+		}
+		Ok(to_settle)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use movement_types::block::{Commitment, Id};
+	use std::sync::atomic::{AtomicU64, Ordering};
+	use std::sync::Arc;
+
+	struct ManualSlots(Arc<AtomicU64>);
+
+	#[async_trait]
+	impl SlotClock for ManualSlots {
+		async fn current_slot(&self) -> Result<u64, anyhow::Error> {
+			Ok(self.0.load(Ordering::SeqCst))
+		}
+	}
+
+	fn commitment(height: u64) -> BlockCommitment {
+		BlockCommitment::new(height, Id::new([height as u8; 32]), Commitment::new([0; 32]))
+	}
+
+	#[tokio::test]
+	async fn test_settles_executed_blocks_at_slot_boundaries() -> Result<(), anyhow::Error> {
+		let slot = Arc::new(AtomicU64::new(10));
+		let mut slots = SettlementSlots::new(Box::new(ManualSlots(slot.clone())));
+		assert_eq!(slots.poll().await?, vec![]);
+
+		// every block of the slot is settled, in height order
+		slots.block_executed(commitment(1));
+		slots.block_executed(commitment(2));
+		assert_eq!(slots.poll().await?, vec![]);
+		slot.store(11, Ordering::SeqCst);
+		assert_eq!(slots.poll().await?, vec![commitment(1), commitment(2)]);
+
+		// a slot without a new block is skipped
+		slot.store(12, Ordering::SeqCst);
+		assert_eq!(slots.poll().await?, vec![]);
+
+		// missed boundaries are coalesced
+		slots.block_executed(commitment(3));
+		slots.block_executed(commitment(4));
+		slot.store(15, Ordering::SeqCst);
+		assert_eq!(slots.poll().await?, vec![commitment(3), commitment(4)]);
+		slot.store(16, Ordering::SeqCst);
+		assert_eq!(slots.poll().await?, vec![]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_reverted_blocks_are_not_settled() -> Result<(), anyhow::Error> {
+		let slot = Arc::new(AtomicU64::new(0));
+		let mut slots = SettlementSlots::new(Box::new(ManualSlots(slot.clone())));
+		assert_eq!(slots.poll().await?, vec![]);
+
+		slots.block_executed(commitment(5));
+		slot.store(1, Ordering::SeqCst);
+		assert_eq!(slots.poll().await?, vec![commitment(5)]);
+
+		// the replayed blocks can be settled again
+		slots.block_executed(commitment(6));
+		slots.reverted_to(3);
+		slot.store(2, Ordering::SeqCst);
+		assert_eq!(slots.poll().await?, vec![]);
+		slots.block_executed(commitment(4));
+		slots.block_executed(commitment(5));
+		slot.store(3, Ordering::SeqCst);
+		assert_eq!(slots.poll().await?, vec![commitment(4), commitment(5)]);
+
+		Ok(())
+	}
+}
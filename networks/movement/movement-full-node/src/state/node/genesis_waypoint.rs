@@ -0,0 +1,37 @@
+use crate::common_args::MovementArgs;
+use clap::Parser;
+use maptos_opt_executor::{bootstrap, genesis::GenesisSpec};
+use std::path::PathBuf;
+use tracing::info;
+
+#[derive(Debug, Parser, Clone)]
+#[clap(
+	rename_all = "kebab-case",
+	about = "Computes the genesis waypoint of a genesis spec. If no spec is provided, uses the spec (or default genesis) of this node."
+)]
+pub struct GenesisWaypoint {
+	#[clap(flatten)]
+	pub movement_args: MovementArgs,
+	/// The path to a JSON or YAML genesis spec
+	#[clap(long)]
+	pub spec: Option<PathBuf>,
+}
+
+impl GenesisWaypoint {
+	pub async fn execute(&self) -> Result<(), anyhow::Error> {
+		let config = self.movement_args.config().await?;
+		info!("Loaded config {:?}", config);
+		let chain_config = &config.execution_config.maptos_config.chain;
+
+		let spec = match self.spec.as_ref().or(chain_config.maptos_genesis_spec_path.as_ref()) {
+			Some(path) => GenesisSpec::try_from_path(path)?,
+			None => GenesisSpec::default_for_chain(chain_config.maptos_chain_id),
+		};
+
+		let waypoint = bootstrap::genesis_waypoint(&spec, &chain_config.maptos_private_key)?;
+		// Use println as this is standard (non-logging output)
+		println!("{}", waypoint);
+
+		Ok(())
+	}
+}
@@ -1,4 +1,5 @@
 pub mod commitment;
+pub mod genesis_waypoint;
 
 use clap::Subcommand;
 
@@ -6,12 +7,14 @@ use clap::Subcommand;
 #[clap(rename_all = "kebab-case", about = "Commands for syncing")]
 pub enum Node {
 	Commitment(commitment::Commitment),
+	GenesisWaypoint(genesis_waypoint::GenesisWaypoint),
 }
 
 impl Node {
 	pub async fn execute(&self) -> Result<(), anyhow::Error> {
 		match self {
 			Node::Commitment(commitment) => commitment.execute().await,
+			Node::GenesisWaypoint(genesis_waypoint) => genesis_waypoint.execute().await,
 		}
 	}
 }
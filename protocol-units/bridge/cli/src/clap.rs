@@ -1,4 +1,5 @@
 pub mod eth_to_movement;
+pub mod movement_to_eth;
 use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
@@ -14,4 +15,7 @@ pub enum Commands {
 	/// Ethereum to Movement Labs bridge commands
 	#[command(subcommand)]
 	BridgeEthToMovETH(eth_to_movement::Commands),
+	/// Movement Labs to Ethereum bridge commands
+	#[command(subcommand)]
+	BridgeMovETHToEth(movement_to_eth::Commands),
 }
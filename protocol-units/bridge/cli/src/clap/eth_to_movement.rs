@@ -1,9 +1,12 @@
+use alloy::primitives::Address;
 use alloy::signers::local::PrivateKeySigner;
+use bridge_service::chains::movement::utils::MovementAddress;
 use clap::{Args, Subcommand};
-use ethereum_bridge::types::EthAddress;
-use movement_bridge::utils::MovementAddress;
 use url::Url;
 
+/// Id in the token registry of the asset of the deprecated `--eth-weth-contract`.
+const WETH_ASSET: &str = "WETH";
+
 #[derive(Args, Clone, Debug)]
 pub struct EthSharedArgs {
 	/// Private key of the Ethereum signer
@@ -14,25 +17,59 @@ pub struct EthSharedArgs {
 	#[arg(long, default_value = "http://localhost:8545")]
 	pub eth_rpc_url: Url,
 
-	/// URL for the Ethereum WebSocket
-	#[arg(long, default_value = "ws://localhost:8545")]
-	pub eth_ws_url: Url,
+	/// Id of the asset to bridge in the token registry
+	#[arg(long, default_value = "MOVE")]
+	pub asset: String,
 
-	/// Ethereum contract address for the initiator
+	/// Ethereum contract address of the ERC-20 of the asset
 	#[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
-	pub eth_initiator_contract: EthAddress,
+	pub eth_token_contract: Address,
 
-	/// Ethereum contract address for the counterparty
+	/// Ethereum contract address for the initiator of the asset
 	#[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
-	pub eth_counterparty_contract: EthAddress,
+	pub eth_initiator_contract: Address,
 
-	/// Ethereum contract address for the counterparty
+	/// Ethereum contract address for the counterparty of the asset
 	#[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
-	pub eth_weth_contract: EthAddress,
+	pub eth_counterparty_contract: Address,
 
 	/// Gas limit for Ethereum transactions
 	#[arg(long, default_value_t = 10_000_000_000)]
 	pub eth_gas_limit: u64,
+
+	/// Deprecated: the events are polled over the RPC URL
+	#[arg(long, hide = true)]
+	pub eth_ws_url: Option<Url>,
+
+	/// Deprecated: the ERC-20 contract of the WETH asset, use `--asset WETH --eth-token-contract`
+	#[arg(long, hide = true)]
+	pub eth_weth_contract: Option<Address>,
+}
+
+impl EthSharedArgs {
+	/// Gets the ERC-20 contract of the asset, the deprecated WETH contract is the one of WETH.
+	pub fn token_contract(&self) -> Address {
+		match self.eth_weth_contract {
+			Some(weth_contract) if self.asset == WETH_ASSET => weth_contract,
+			_ => self.eth_token_contract,
+		}
+	}
+
+	/// Warns about the deprecated flags in use.
+	pub fn warn_deprecated_flags(&self) {
+		if self.eth_ws_url.is_some() {
+			eprintln!("Warning: --eth-ws-url is deprecated and ignored, events are read over --eth-rpc-url");
+		}
+		if self.eth_weth_contract.is_some() {
+			eprintln!(
+				"Warning: --eth-weth-contract is deprecated, use --asset {} --eth-token-contract",
+				WETH_ASSET
+			);
+			if self.asset != WETH_ASSET {
+				eprintln!("Warning: --eth-weth-contract is ignored for the asset {}", self.asset);
+			}
+		}
+	}
 }
 
 #[derive(Subcommand)]
@@ -45,8 +82,8 @@ pub enum Commands {
 		/// The recipient address on the movement labs chain
 		recipient: MovementAddress,
 
-		/// The amount of Ethereum to transfer in WEI
-		amount: u64,
+		/// The amount to transfer in the smallest unit of the ERC-20
+		amount: u128,
 	},
 	/// Resume a bridge transfer
 	Resume {
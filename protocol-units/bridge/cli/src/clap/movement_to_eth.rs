@@ -0,0 +1,98 @@
+use alloy::primitives::Address;
+use alloy::signers::local::PrivateKeySigner;
+use clap::{Args, Subcommand};
+use url::Url;
+
+#[derive(Args, Clone, Debug)]
+pub struct MovementSharedArgs {
+	/// Private key of the Movement signer, initiator of the transfers
+	#[arg(long)]
+	pub movement_private_key: String,
+
+	/// URL for the Movement RPC
+	#[arg(long, default_value = "http://localhost:8080")]
+	pub movement_rpc_url: Url,
+
+	/// Id of the asset bridged by the Movement framework modules
+	#[arg(long, default_value = "MOVE")]
+	pub asset: String,
+
+	/// Private key of the Ethereum signer, completing the transfers on the counterparty
+	#[arg(long)]
+	pub eth_private_key: PrivateKeySigner,
+
+	/// URL for the Ethereum RPC
+	#[arg(long, default_value = "http://localhost:8545")]
+	pub eth_rpc_url: Url,
+
+	/// Ethereum contract address for the initiator
+	#[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
+	pub eth_initiator_contract: Address,
+
+	/// Ethereum contract address for the counterparty
+	#[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
+	pub eth_counterparty_contract: Address,
+
+	/// Ethereum contract address of the MOVE token
+	#[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
+	pub eth_move_token_contract: Address,
+
+	/// Gas limit for Ethereum transactions
+	#[arg(long, default_value_t = 10_000_000_000)]
+	pub eth_gas_limit: u64,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+	/// Initiate a bridge transfer
+	Swap {
+		#[command(flatten)]
+		args: MovementSharedArgs,
+
+		/// The recipient address on Ethereum
+		recipient: Address,
+
+		/// The amount to transfer in the smallest unit of the asset
+		amount: u64,
+	},
+	/// Resume a bridge transfer, completing it on Ethereum with the stored pre-image
+	Resume {
+		#[command(flatten)]
+		args: MovementSharedArgs,
+
+		/// The ID of the transfer to resume
+		#[arg(long)]
+		transfer_id: String,
+	},
+	/// Complete a bridge transfer on Ethereum with its pre-image
+	CompleteWithPreimage {
+		#[command(flatten)]
+		args: MovementSharedArgs,
+
+		/// The ID of the transfer to complete
+		#[arg(long)]
+		transfer_id: String,
+
+		/// The pre-image of the hash lock of the transfer, in hex
+		#[arg(long)]
+		pre_image: String,
+	},
+	/// Refund a bridge transfer on Movement after its time lock expired
+	Refund {
+		#[command(flatten)]
+		args: MovementSharedArgs,
+
+		/// The ID of the transfer to refund
+		#[arg(long)]
+		transfer_id: String,
+	},
+	/// Show the state of a bridge transfer on both chains
+	Status {
+		#[command(flatten)]
+		args: MovementSharedArgs,
+
+		/// The ID of the transfer
+		#[arg(long)]
+		transfer_id: String,
+	},
+}
@@ -1,14 +1,18 @@
 use crate::clap::eth_to_movement::{Commands, EthSharedArgs};
 use alloy::primitives::keccak256;
 use anyhow::Result;
-use bridge_shared::bridge_contracts::BridgeContractInitiator;
-use bridge_shared::types::{
-	Amount, AssetType, HashLock, HashLockPreImage, InitiatorAddress, RecipientAddress,
+use bridge_config::common::eth::EthConfig;
+use bridge_service::chains::{
+	ethereum::{client::EthClient, types::EthAddress},
+	movement::utils::MovementAddress,
 };
-use ethereum_bridge::{client::EthClient, types::EthAddress};
-use movement_bridge::utils::MovementAddress;
+use bridge_util::chains::bridge_contracts::BridgeContract;
+use bridge_util::types::{Amount, AssetId, BridgeAddress, HashLock, HashLockPreImage};
 
 pub async fn execute(command: &Commands) -> Result<()> {
+	match command {
+		Commands::Swap { args, .. } | Commands::Resume { args, .. } => args.warn_deprecated_flags(),
+	}
 	match command {
 		Commands::Swap { args, recipient, amount } => initiate_swap(args, recipient, *amount).await,
 		Commands::Resume { args, transfer_id } => resume_swap(args, transfer_id).await,
@@ -18,27 +22,29 @@ pub async fn execute(command: &Commands) -> Result<()> {
 async fn initiate_swap(
 	args: &EthSharedArgs,
 	recipient: &MovementAddress,
-	amount: u64,
+	amount: u128,
 ) -> Result<()> {
-	println!("Initiating swap to {:?} with amount {}", recipient, amount);
+	println!("Initiating swap of {} to {:?} with amount {}", args.asset, recipient, amount);
 
-	let mut client = EthClient::new(args).await?;
+	let mut client = EthClient::new(&EthConfig::try_from(args)?).await?;
 
 	// Get the current block height
 	let current_block = client.get_block_number().await?;
 	println!("Current Ethereum block height: {}", current_block);
 
-	// Convert signer's private key to EthAddress
+	let asset = AssetId(args.asset.clone());
+	let amount = Amount(amount);
 	let initiator = EthAddress(client.get_signer_address());
-	let recipient = RecipientAddress(From::from(recipient));
+	let recipient = BridgeAddress(Vec::from(recipient));
 	let hash_lock_pre_image = HashLockPreImage::random();
 	let hash_lock = HashLock(From::from(keccak256(hash_lock_pre_image)));
-	let amount = Amount(AssetType::EthAndWeth((amount, 0)));
 
 	// TODO: Store the swap details in the local database so they can be resumed in case of failure
 
+	// The initiator contract of the asset escrows the ERC-20 of the transfer.
+	client.approve_initiator_contract(&asset, amount).await?;
 	client
-		.initiate_bridge_transfer(InitiatorAddress(initiator), recipient, hash_lock, amount)
+		.initiate_bridge_transfer(BridgeAddress(initiator), recipient, hash_lock, asset, amount)
 		.await?;
 
 	// Now we need to listen to the blockchain to receive the correct events and match them accordingly.
@@ -51,7 +57,7 @@ async fn initiate_swap(
 	Ok(())
 }
 
-async fn resume_swap(args: &EthSharedArgs, transfer_id: &str) -> Result<()> {
+async fn resume_swap(_args: &EthSharedArgs, transfer_id: &str) -> Result<()> {
 	println!("Resuming transfer with ID: {}", transfer_id);
 
 	Ok(())
@@ -1,4 +1,5 @@
 pub mod clap;
 pub mod eth_to_moveth;
+pub mod moveth_to_eth;
 pub mod state;
 pub mod types;
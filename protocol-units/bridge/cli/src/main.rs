@@ -16,6 +16,9 @@ async fn inner_main() -> anyhow::Result<()> {
 		Commands::BridgeEthToMovETH(command) => {
 			bridge_cli::eth_to_moveth::execute(command).await?;
 		}
+		Commands::BridgeMovETHToEth(command) => {
+			bridge_cli::moveth_to_eth::execute(command).await?;
+		}
 	}
 
 	Ok(())
@@ -0,0 +1,227 @@
+use crate::clap::movement_to_eth::{Commands, MovementSharedArgs};
+use crate::state::{load_swap_state, save_swap_state, SwapState, SwapStatus, SwapType};
+use alloy::primitives::{keccak256, Address};
+use anyhow::{Context, Result};
+use bridge_config::common::{eth::EthConfig, movement::MovementConfig};
+use bridge_service::chains::{
+	ethereum::{client::EthClient, types::CounterpartyState},
+	movement::client_framework::MovementClientFramework,
+};
+use bridge_util::chains::bridge_contracts::BridgeContract;
+use bridge_util::types::{
+	Amount, AssetId, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage,
+};
+
+pub async fn execute(command: &Commands) -> Result<()> {
+	match command {
+		Commands::Swap { args, recipient, amount } => initiate_swap(args, recipient, *amount).await,
+		Commands::Resume { args, transfer_id } => resume_swap(args, transfer_id).await,
+		Commands::CompleteWithPreimage { args, transfer_id, pre_image } => {
+			complete_with_preimage(args, transfer_id, pre_image).await
+		}
+		Commands::Refund { args, transfer_id } => refund_swap(args, transfer_id).await,
+		Commands::Status { args, transfer_id } => swap_status(args, transfer_id).await,
+	}
+}
+
+async fn movement_client(args: &MovementSharedArgs) -> Result<MovementClientFramework> {
+	MovementClientFramework::new(&MovementConfig::try_from(args)?).await
+}
+
+async fn eth_client(args: &MovementSharedArgs) -> Result<EthClient> {
+	EthClient::new(&EthConfig::try_from(args)?).await
+}
+
+fn parse_transfer_id(transfer_id: &str) -> Result<BridgeTransferId> {
+	let bytes = hex::decode(transfer_id.trim_start_matches("0x"))
+		.map_err(|e| anyhow::anyhow!("Failed to decode transfer id {}: {:?}", transfer_id, e))?;
+	let bytes = bytes
+		.try_into()
+		.map_err(|_| anyhow::anyhow!("Transfer id {} is not 32 bytes long", transfer_id))?;
+	Ok(BridgeTransferId(bytes))
+}
+
+fn parse_pre_image(pre_image: &str) -> Result<HashLockPreImage> {
+	let bytes = hex::decode(pre_image.trim_start_matches("0x"))
+		.map_err(|e| anyhow::anyhow!("Failed to decode pre-image: {:?}", e))?;
+	let bytes = bytes
+		.try_into()
+		.map_err(|_| anyhow::anyhow!("Pre-image is not 32 bytes long"))?;
+	Ok(HashLockPreImage(bytes))
+}
+
+/// Updates the status of the swap in the local state, if the swap was initiated from this CLI.
+fn update_swap_status(bridge_transfer_id: BridgeTransferId, status: SwapStatus) -> Result<()> {
+	match load_swap_state(&SwapType::MovementToEth, &hex::encode(bridge_transfer_id.0)) {
+		Ok(mut state) => {
+			state.status = status;
+			save_swap_state(&state)
+		}
+		Err(_) => Ok(()),
+	}
+}
+
+async fn initiate_swap(args: &MovementSharedArgs, recipient: &Address, amount: u64) -> Result<()> {
+	println!("Initiating swap to {} with amount {}", recipient, amount);
+
+	let mut client = movement_client(args).await?;
+
+	// Get the current ledger version
+	let ledger_version = client.rest_client().get_ledger_information().await?.into_inner().version;
+	println!("Current Movement ledger version: {}", ledger_version);
+
+	let hash_lock_pre_image = HashLockPreImage::random();
+	let hash_lock = HashLock(From::from(keccak256(hash_lock_pre_image)));
+
+	let bridge_transfer_id = client
+		.initiate_bridge_transfer_with_id(
+			BridgeAddress(recipient.to_vec()),
+			hash_lock,
+			AssetId(args.asset.clone()),
+			Amount(amount.into()),
+		)
+		.await?;
+	// The transfer is initiated, the pre-image is reported if it can't be stored.
+	let time_lock = client
+		.get_bridge_transfer_details_initiator(bridge_transfer_id)
+		.await?
+		.map(|details| details.time_lock.0)
+		.ok_or_else(|| {
+			anyhow::anyhow!(
+				"Failed to get the time lock of transfer 0x{}, complete it with the pre-image 0x{}",
+				hex::encode(bridge_transfer_id.0),
+				hex::encode(hash_lock_pre_image.0)
+			)
+		})?;
+
+	// The pre-image is needed to complete the transfer on Ethereum.
+	save_swap_state(&SwapState {
+		id: hex::encode(bridge_transfer_id.0),
+		swap_type: SwapType::MovementToEth,
+		block_height: ledger_version,
+		block_height_timeout: time_lock,
+		recipient: recipient.to_string(),
+		amount,
+		status: SwapStatus::Initiated,
+		hash_lock_pre_image: Some(hex::encode(hash_lock_pre_image.0)),
+	})?;
+
+	println!(
+		"Swap initiated successfully with transfer ID: 0x{}",
+		hex::encode(bridge_transfer_id.0)
+	);
+	println!("Resume the swap once the transfer is locked on Ethereum to complete it");
+
+	Ok(())
+}
+
+async fn resume_swap(args: &MovementSharedArgs, transfer_id: &str) -> Result<()> {
+	println!("Resuming transfer with ID: {}", transfer_id);
+
+	let bridge_transfer_id = parse_transfer_id(transfer_id)?;
+	let mut state = load_swap_state(&SwapType::MovementToEth, &hex::encode(bridge_transfer_id.0))
+		.context("No local state for the transfer")?;
+	let pre_image = parse_pre_image(
+		state
+			.hash_lock_pre_image
+			.as_deref()
+			.context("No pre-image stored for the transfer")?,
+	)?;
+	let hash_lock = HashLock(From::from(keccak256(pre_image)));
+
+	let mut client = eth_client(args).await?;
+	let details = client.get_bridge_transfer_details_counterparty(bridge_transfer_id).await?;
+	match details {
+		Some(details) if details.hash_lock == hash_lock => {
+			match CounterpartyState::try_from(details.state)? {
+				CounterpartyState::Pending => {
+					client
+						.counterparty_complete_bridge_transfer(bridge_transfer_id, pre_image)
+						.await?;
+					println!("Transfer completed on Ethereum");
+				}
+				CounterpartyState::Completed => println!("Transfer already completed on Ethereum"),
+				CounterpartyState::Refunded => {
+					return Err(anyhow::anyhow!(
+						"Transfer aborted on Ethereum, refund it on Movement once its time lock expires"
+					));
+				}
+			}
+		}
+		_ => {
+			println!("Transfer not locked on Ethereum yet, resume it later");
+			return Ok(());
+		}
+	}
+
+	state.status = SwapStatus::Completed;
+	save_swap_state(&state)?;
+
+	Ok(())
+}
+
+async fn complete_with_preimage(
+	args: &MovementSharedArgs,
+	transfer_id: &str,
+	pre_image: &str,
+) -> Result<()> {
+	println!("Completing transfer with ID: {}", transfer_id);
+
+	let bridge_transfer_id = parse_transfer_id(transfer_id)?;
+	let pre_image = parse_pre_image(pre_image)?;
+
+	let mut client = eth_client(args).await?;
+	client
+		.counterparty_complete_bridge_transfer(bridge_transfer_id, pre_image)
+		.await?;
+	update_swap_status(bridge_transfer_id, SwapStatus::Completed)?;
+
+	println!("Transfer completed on Ethereum");
+
+	Ok(())
+}
+
+async fn refund_swap(args: &MovementSharedArgs, transfer_id: &str) -> Result<()> {
+	println!("Refunding transfer with ID: {}", transfer_id);
+
+	let bridge_transfer_id = parse_transfer_id(transfer_id)?;
+
+	let mut client = movement_client(args).await?;
+	client.refund_bridge_transfer(bridge_transfer_id).await?;
+	update_swap_status(bridge_transfer_id, SwapStatus::Refunded)?;
+
+	println!("Transfer refunded on Movement");
+
+	Ok(())
+}
+
+async fn swap_status(args: &MovementSharedArgs, transfer_id: &str) -> Result<()> {
+	let bridge_transfer_id = parse_transfer_id(transfer_id)?;
+
+	match load_swap_state(&SwapType::MovementToEth, &hex::encode(bridge_transfer_id.0)) {
+		Ok(state) => println!(
+			"Local state: {:?}, recipient {}, amount {}, time lock {}",
+			state.status, state.recipient, state.amount, state.block_height_timeout
+		),
+		Err(_) => println!("No local state for the transfer"),
+	}
+
+	let mut initiator_client = movement_client(args).await?;
+	match initiator_client.get_bridge_transfer_details_initiator(bridge_transfer_id).await {
+		Ok(Some(details)) => println!("Movement initiator: {:?}", details),
+		Ok(None) => println!("Movement initiator: transfer not found"),
+		Err(err) => println!("Movement initiator: failed to get the transfer: {}", err),
+	}
+
+	let mut counterparty_client = eth_client(args).await?;
+	match counterparty_client
+		.get_bridge_transfer_details_counterparty(bridge_transfer_id)
+		.await
+	{
+		Ok(Some(details)) => println!("Ethereum counterparty: {:?}", details),
+		Ok(None) => println!("Ethereum counterparty: transfer not locked"),
+		Err(err) => println!("Ethereum counterparty: failed to get the transfer: {}", err),
+	}
+
+	Ok(())
+}
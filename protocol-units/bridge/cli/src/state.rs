@@ -13,6 +13,9 @@ pub struct SwapState {
 	pub recipient: String,
 	pub amount: u64,
 	pub status: SwapStatus,
+	/// The pre-image of the hash lock in hex, to complete the transfer on the counterparty chain
+	#[serde(default)]
+	pub hash_lock_pre_image: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -38,24 +41,33 @@ impl std::convert::AsRef<Path> for SwapType {
 	}
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum SwapStatus {
 	Initiated,
 	Completed,
+	Refunded,
 	Failed,
 }
 
+/// Overrides the directory the swap states are stored in.
+pub const STATE_DIR_ENV: &str = "BRIDGE_CLI_STATE_DIR";
+
 fn ensure_state_dir() -> Result<PathBuf> {
-	let proj_dirs = ProjectDirs::from("xyz", "movementlabs", "bridge-cli")
-		.context("Failed to get project directories")?;
-	let state_dir = proj_dirs.data_local_dir();
-	fs::create_dir_all(state_dir)?;
-	Ok(state_dir.to_path_buf())
+	let state_dir = match std::env::var_os(STATE_DIR_ENV) {
+		Some(state_dir) => PathBuf::from(state_dir),
+		None => ProjectDirs::from("xyz", "movementlabs", "bridge-cli")
+			.context("Failed to get project directories")?
+			.data_local_dir()
+			.to_path_buf(),
+	};
+	fs::create_dir_all(&state_dir)?;
+	Ok(state_dir)
 }
 
 pub fn save_swap_state(state: &SwapState) -> Result<()> {
-	let state_dir = ensure_state_dir()?;
-	let file_path = state_dir.join(&state.swap_type).join(format!("{}.json", state.id));
+	let swap_dir = ensure_state_dir()?.join(&state.swap_type);
+	fs::create_dir_all(&swap_dir)?;
+	let file_path = swap_dir.join(format!("{}.json", state.id));
 	let json = serde_json::to_string_pretty(state)?;
 	fs::write(file_path, json)?;
 	Ok(())
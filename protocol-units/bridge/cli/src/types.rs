@@ -1,22 +1,79 @@
 use crate::clap::eth_to_movement::EthSharedArgs;
-use ethereum_bridge::client::Config;
+use crate::clap::movement_to_eth::MovementSharedArgs;
+use anyhow::Context;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, ValidCryptoMaterialStringExt};
+use bridge_config::common::{eth::EthConfig, movement::MovementConfig};
 
-impl From<EthSharedArgs> for Config {
-	fn from(args: EthSharedArgs) -> Self {
-		Self {
-			rpc_url: args.eth_rpc_url,
-			ws_url: args.eth_ws_url,
-			signer_private_key: args.eth_private_key,
-			initiator_contract: Some(args.eth_initiator_contract.0),
-			counterparty_contract: Some(args.eth_counterparty_contract.0),
-			weth_contract: Some(args.eth_weth_contract.0),
+impl TryFrom<&EthSharedArgs> for EthConfig {
+	type Error = anyhow::Error;
+
+	fn try_from(args: &EthSharedArgs) -> Result<Self, Self::Error> {
+		let url = &args.eth_rpc_url;
+		Ok(EthConfig {
+			eth_rpc_connection_protocol: url.scheme().to_string(),
+			eth_rpc_connection_hostname: url
+				.host_str()
+				.context("Missing Ethereum RPC host")?
+				.to_string(),
+			eth_rpc_connection_port: url
+				.port_or_known_default()
+				.context("Missing Ethereum RPC port")?,
+			eth_initiator_contract: args.eth_initiator_contract.to_string(),
+			eth_counterparty_contract: args.eth_counterparty_contract.to_string(),
+			eth_move_token_contract: args.token_contract().to_string(),
+			signer_private_key: args.eth_private_key.to_bytes().to_string(),
 			gas_limit: args.eth_gas_limit,
-		}
+			asset: args.asset.clone(),
+			..EthConfig::default()
+		})
 	}
 }
 
-impl From<&EthSharedArgs> for Config {
-	fn from(args: &EthSharedArgs) -> Self {
-		From::from(args.clone())
+impl TryFrom<&MovementSharedArgs> for MovementConfig {
+	type Error = anyhow::Error;
+
+	fn try_from(args: &MovementSharedArgs) -> Result<Self, Self::Error> {
+		let movement_signer_key =
+			Ed25519PrivateKey::from_encoded_string(&args.movement_private_key)
+				.map_err(|e| anyhow::anyhow!("Failed to parse Movement private key: {:?}", e))?;
+		let url = &args.movement_rpc_url;
+		Ok(MovementConfig {
+			movement_signer_key,
+			mvt_rpc_connection_protocol: url.scheme().to_string(),
+			mvt_rpc_connection_hostname: url
+				.host_str()
+				.context("Missing Movement RPC host")?
+				.to_string(),
+			mvt_rpc_connection_port: url
+				.port_or_known_default()
+				.context("Missing Movement RPC port")?,
+			asset: args.asset.clone(),
+			..MovementConfig::default()
+		})
+	}
+}
+
+impl TryFrom<&MovementSharedArgs> for EthConfig {
+	type Error = anyhow::Error;
+
+	fn try_from(args: &MovementSharedArgs) -> Result<Self, Self::Error> {
+		let url = &args.eth_rpc_url;
+		Ok(EthConfig {
+			eth_rpc_connection_protocol: url.scheme().to_string(),
+			eth_rpc_connection_hostname: url
+				.host_str()
+				.context("Missing Ethereum RPC host")?
+				.to_string(),
+			eth_rpc_connection_port: url
+				.port_or_known_default()
+				.context("Missing Ethereum RPC port")?,
+			eth_initiator_contract: args.eth_initiator_contract.to_string(),
+			eth_counterparty_contract: args.eth_counterparty_contract.to_string(),
+			eth_move_token_contract: args.eth_move_token_contract.to_string(),
+			signer_private_key: args.eth_private_key.to_bytes().to_string(),
+			gas_limit: args.eth_gas_limit,
+			asset: args.asset.clone(),
+			..EthConfig::default()
+		})
 	}
 }
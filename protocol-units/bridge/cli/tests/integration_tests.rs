@@ -1,60 +1,236 @@
-use alloy::{
-	node_bindings::Anvil,
-	primitives::{Address, FixedBytes},
-	providers::ProviderBuilder,
-};
+//! Tests the subcommands of the CLI without the chains, the RPC of a chain is unreachable.
+use alloy::primitives::Address;
+use alloy::signers::local::PrivateKeySigner;
 use bridge_cli::{
-	clap::eth_to_movement::{self, EthSharedArgs},
-	eth_to_moveth,
+	clap::{
+		eth_to_movement,
+		movement_to_eth::{self, MovementSharedArgs},
+		CliOptions, Commands,
+	},
+	moveth_to_eth,
+	state::{load_swap_state, save_swap_state, SwapState, SwapStatus, SwapType, STATE_DIR_ENV},
 };
-use ethereum_bridge::types::{AtomicBridgeInitiator, EthAddress};
-use movement_bridge::utils::MovementAddress;
-use std::str::FromStr;
-use url::Url;
+use bridge_config::common::{eth::EthConfig, movement::MovementConfig};
+use clap::Parser;
+use std::sync::OnceLock;
+use tempfile::TempDir;
 
-#[tokio::test]
-async fn test_swap() -> eyre::Result<()> {
-	// Start Anvil instance
-	let anvil = Anvil::new().try_spawn()?;
-	let rpc_url = anvil.endpoint().parse()?;
-	let provider = ProviderBuilder::new().on_http(rpc_url);
-
-	// Deploy contracts
-	let wallet = anvil.keys()[0].clone();
-
-	let initiator_contract = AtomicBridgeInitiator::deploy(provider).await?;
-
-	// Set up EthSharedArgs
-	let eth_shared_args: EthSharedArgs = EthSharedArgs {
-		eth_private_key: wallet.into(),
-		eth_rpc_url: Url::parse(&anvil.endpoint()).unwrap(),
-		eth_ws_url: Url::parse(&anvil.endpoint().replace("http", "ws")).unwrap(),
-		eth_initiator_contract: EthAddress(*initiator_contract.address()),
-		eth_counterparty_contract: EthAddress(Address::ZERO), // Not needed for this test
-		eth_weth_contract: EthAddress(Address::ZERO),         // Not needed for this test
-		eth_gas_limit: 3000000,
+/// Nothing listens on this port.
+const UNREACHABLE_RPC_URL: &str = "http://127.0.0.1:1";
+
+/// Stores the swap states of the tests in a temporary directory.
+fn use_temp_state_dir() {
+	static STATE_DIR: OnceLock<TempDir> = OnceLock::new();
+	STATE_DIR.get_or_init(|| {
+		let state_dir = tempfile::tempdir().expect("Failed to create the state dir");
+		std::env::set_var(STATE_DIR_ENV, state_dir.path());
+		state_dir
+	});
+}
+
+fn movement_private_key() -> String {
+	format!("0x{}", "11".repeat(32))
+}
+
+fn movement_args(eth_rpc_url: &str) -> Result<MovementSharedArgs, anyhow::Error> {
+	Ok(MovementSharedArgs {
+		movement_private_key: movement_private_key(),
+		movement_rpc_url: "http://localhost:8080".parse()?,
+		asset: "MOVE".to_string(),
+		eth_private_key: PrivateKeySigner::random(),
+		eth_rpc_url: eth_rpc_url.parse()?,
+		eth_initiator_contract: Address::ZERO,
+		eth_counterparty_contract: Address::ZERO,
+		eth_move_token_contract: Address::ZERO,
+		eth_gas_limit: 10_000_000_000,
+	})
+}
+
+fn initiated_swap_state(id: &str) -> SwapState {
+	SwapState {
+		id: id.to_string(),
+		swap_type: SwapType::MovementToEth,
+		block_height: 1,
+		block_height_timeout: 2,
+		recipient: Address::ZERO.to_string(),
+		amount: 100,
+		status: SwapStatus::Initiated,
+		hash_lock_pre_image: Some(hex::encode([2; 32])),
+	}
+}
+
+#[test]
+fn test_parse_movement_to_eth_subcommands() -> Result<(), anyhow::Error> {
+	let movement_key = movement_private_key();
+	let eth_key = PrivateKeySigner::random().to_bytes().to_string();
+	let shared_args =
+		["--movement-private-key", movement_key.as_str(), "--eth-private-key", eth_key.as_str()];
+	let parse = |subcommand: &[&str]| {
+		let command = ["bridge-cli", "bridge-mov-eth-to-eth"];
+		let args = command.iter().chain(subcommand).chain(&shared_args).copied();
+		CliOptions::try_parse_from(args)
 	};
 
-	// Prepare swap parameters
-	let recipient: MovementAddress =
-		"0x000000000000000000000000000000000000000000000000000000000000000A"
-			.parse()
-			.unwrap();
-	let amount = 1000000000000000000u64; // 1 ETH in wei
-
-	// Execute the swap
-	let result = eth_to_moveth::execute(&eth_to_movement::Commands::Swap {
-		args: eth_shared_args,
-		recipient: From::from(recipient),
-		amount,
+	let recipient = "0x00000000000000000000000000000000000000aa";
+	match parse(&["swap", recipient, "100"])?.command {
+		Commands::BridgeMovETHToEth(movement_to_eth::Commands::Swap {
+			args,
+			recipient,
+			amount,
+		}) => {
+			assert_eq!(recipient, "0x00000000000000000000000000000000000000aa".parse::<Address>()?);
+			assert_eq!(amount, 100);
+			assert_eq!(args.asset, "MOVE");
+			assert_eq!(args.movement_rpc_url.as_str(), "http://localhost:8080/");
+			assert_eq!(args.eth_rpc_url.as_str(), "http://localhost:8545/");
+		}
+		_ => panic!("Expected a Movement to Ethereum swap"),
+	}
+
+	let transfer_id = hex::encode([1; 32]);
+	let pre_image = hex::encode([2; 32]);
+	match parse(&[
+		"complete-with-preimage",
+		"--transfer-id",
+		&transfer_id,
+		"--pre-image",
+		&pre_image,
+	])?
+	.command
+	{
+		Commands::BridgeMovETHToEth(movement_to_eth::Commands::CompleteWithPreimage {
+			transfer_id: parsed_id,
+			pre_image: parsed_pre_image,
+			..
+		}) => {
+			assert_eq!(parsed_id, transfer_id);
+			assert_eq!(parsed_pre_image, pre_image);
+		}
+		_ => panic!("Expected a completion with the pre-image"),
+	}
+	for subcommand in ["resume", "refund", "status"] {
+		assert!(parse(&[subcommand, "--transfer-id", &transfer_id]).is_ok());
+		// The transfer id is required.
+		assert!(parse(&[subcommand]).is_err());
+	}
+
+	Ok(())
+}
+
+#[test]
+fn test_parse_eth_to_movement_swap() -> Result<(), anyhow::Error> {
+	let eth_key = PrivateKeySigner::random().to_bytes().to_string();
+	let cli = CliOptions::try_parse_from([
+		"bridge-cli",
+		"bridge-eth-to-mov-eth",
+		"swap",
+		"--eth-private-key",
+		eth_key.as_str(),
+		"--asset",
+		"WETH",
+		"0xa",
+		"1000000000000000000",
+	])?;
+	match cli.command {
+		Commands::BridgeEthToMovETH(eth_to_movement::Commands::Swap { args, amount, .. }) => {
+			assert_eq!(args.asset, "WETH");
+			assert_eq!(amount, 10u128.pow(18));
+		}
+		_ => panic!("Expected an Ethereum to Movement swap"),
+	}
+	Ok(())
+}
+
+#[test]
+fn test_parse_deprecated_eth_flags() -> Result<(), anyhow::Error> {
+	let eth_key = PrivateKeySigner::random().to_bytes().to_string();
+	let weth_contract = "0x00000000000000000000000000000000000000bb";
+	let cli = CliOptions::try_parse_from([
+		"bridge-cli",
+		"bridge-eth-to-mov-eth",
+		"swap",
+		"--eth-private-key",
+		eth_key.as_str(),
+		"--eth-ws-url",
+		"ws://localhost:8545",
+		"--eth-weth-contract",
+		weth_contract,
+		"--asset",
+		"WETH",
+		"0xa",
+		"1",
+	])?;
+	match cli.command {
+		Commands::BridgeEthToMovETH(eth_to_movement::Commands::Swap { args, .. }) => {
+			// The WETH contract is the ERC-20 contract of the WETH asset.
+			let eth_config = EthConfig::try_from(&args)?;
+			assert_eq!(
+				eth_config.eth_move_token_contract,
+				weth_contract.parse::<Address>()?.to_string()
+			);
+
+			let args = eth_to_movement::EthSharedArgs { asset: "MOVE".to_string(), ..args };
+			assert_eq!(args.token_contract(), Address::ZERO);
+		}
+		_ => panic!("Expected an Ethereum to Movement swap"),
+	}
+	Ok(())
+}
+
+#[test]
+fn test_configs_from_movement_args() -> Result<(), anyhow::Error> {
+	let args = movement_args(UNREACHABLE_RPC_URL)?;
+
+	let eth_config = EthConfig::try_from(&args)?;
+	assert_eq!(eth_config.eth_rpc_connection_url(), UNREACHABLE_RPC_URL);
+	assert_eq!(eth_config.eth_counterparty_contract, Address::ZERO.to_string());
+	assert_eq!(eth_config.asset, "MOVE");
+
+	let movement_config = MovementConfig::try_from(&args)?;
+	assert_eq!(movement_config.mvt_rpc_connection_hostname, "localhost");
+	assert_eq!(movement_config.mvt_rpc_connection_port, 8080);
+
+	let args = MovementSharedArgs { movement_private_key: "0x12".to_string(), ..args };
+	assert!(MovementConfig::try_from(&args).is_err());
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_fails_on_rpc_error() -> Result<(), anyhow::Error> {
+	use_temp_state_dir();
+	let transfer_id = hex::encode([1; 32]);
+	save_swap_state(&initiated_swap_state(&transfer_id))?;
+
+	let result = moveth_to_eth::execute(&movement_to_eth::Commands::Resume {
+		args: movement_args(UNREACHABLE_RPC_URL)?,
+		transfer_id: format!("0x{transfer_id}"),
 	})
 	.await;
+	assert!(result.is_err());
+	// The swap is not completed, it can be resumed once Ethereum is reachable.
+	let state = load_swap_state(&SwapType::MovementToEth, &transfer_id)?;
+	assert!(matches!(state.status, SwapStatus::Initiated));
+
+	Ok(())
+}
 
-	assert!(result.is_ok(), "Swap initiation failed: {:?}", result.err());
+#[tokio::test]
+async fn test_resume_needs_local_state() -> Result<(), anyhow::Error> {
+	use_temp_state_dir();
+	let result = moveth_to_eth::execute(&movement_to_eth::Commands::Resume {
+		args: movement_args(UNREACHABLE_RPC_URL)?,
+		transfer_id: hex::encode([3; 32]),
+	})
+	.await;
+	assert!(result.unwrap_err().to_string().contains("No local state"));
 
-	// Check on the contract if we have a bridge transfer initiated
-	let bridge_transfer_id = FixedBytes::from_str("0x1234567890123456789012345678901234567890")?;
-	initiator_contract.bridgeTransfers(bridge_transfer_id).call().await?;
+	// An invalid transfer id is rejected before calling the chains.
+	let result = moveth_to_eth::execute(&movement_to_eth::Commands::Status {
+		args: movement_args(UNREACHABLE_RPC_URL)?,
+		transfer_id: "0x1234".to_string(),
+	})
+	.await;
+	assert!(result.unwrap_err().to_string().contains("not 32 bytes long"));
 
 	Ok(())
 }
@@ -14,6 +14,8 @@ const DEFAULT_ETH_COUNTERPARTY_CONTRACT: &str = "0xccc";
 const DEFAULT_ETH_WETH_CONTRACT: &str = "0xe3e3";
 const DEFAULT_ETH_MOVETOKEN_CONTRACT: &str = "0xe3e2";
 const DEFAULT_ASSET: &str = "MOVE";
+const DEFAULT_ETH_TIME_LOCK_SAFETY_MARGIN_SECS: u64 = 60;
+const DEFAULT_ETH_CONFIRMATION_DEPTH: u64 = 12;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EthConfig {
@@ -48,6 +50,9 @@ pub struct EthConfig {
 
 	#[serde(default = "default_time_lock_secs")]
 	pub time_lock_secs: u64,
+	/// Delay after the expiry of a time lock before the relayer refunds or aborts on Ethereum
+	#[serde(default = "default_eth_time_lock_safety_margin_secs")]
+	pub eth_time_lock_safety_margin_secs: u64,
 
 	#[serde(default = "default_gas_limit")]
 	pub gas_limit: u64,
@@ -57,6 +62,13 @@ pub struct EthConfig {
 	#[serde(default = "default_asset")]
 	pub asset: String,
 
+	/// Number of blocks on top of the block of an event before the relayer processes it
+	#[serde(default = "default_eth_confirmation_depth")]
+	pub eth_confirmation_depth: u64,
+	/// Wait for the finalized block instead of the confirmation depth
+	#[serde(default = "default_eth_use_finalized_block")]
+	pub eth_use_finalized_block: bool,
+
 	#[serde(default = "rest_connection_timeout_secs")]
 	pub rest_connection_timeout_secs: u64,
 }
@@ -99,6 +111,22 @@ env_default!(default_asset, "ASSET", String, DEFAULT_ASSET.to_string());
 
 env_short_default!(default_time_lock_secs, u64, 48 * 60 * 60 as u64); //48h by default
 
+env_default!(
+	default_eth_time_lock_safety_margin_secs,
+	"ETH_TIME_LOCK_SAFETY_MARGIN_SECS",
+	u64,
+	DEFAULT_ETH_TIME_LOCK_SAFETY_MARGIN_SECS
+);
+
+env_default!(
+	default_eth_confirmation_depth,
+	"ETH_CONFIRMATION_DEPTH",
+	u64,
+	DEFAULT_ETH_CONFIRMATION_DEPTH
+);
+
+env_default!(default_eth_use_finalized_block, "ETH_USE_FINALIZED_BLOCK", bool, false);
+
 env_short_default!(default_gas_limit, u64, 10_000_000_000_000_000 as u64);
 
 env_short_default!(default_transaction_send_retries, u32, 10 as u32);
@@ -191,12 +219,15 @@ impl Default for EthConfig {
 			eth_move_token_contract: default_eth_move_token_contract(),
 
 			time_lock_secs: default_time_lock_secs(),
+			eth_time_lock_safety_margin_secs: default_eth_time_lock_safety_margin_secs(),
 
 			signer_private_key: default_signer_private_key(),
 			gas_limit: default_gas_limit(),
 			transaction_send_retries: default_transaction_send_retries(),
 
 			asset: default_asset(),
+			eth_confirmation_depth: default_eth_confirmation_depth(),
+			eth_use_finalized_block: default_eth_use_finalized_block(),
 
 			rest_connection_timeout_secs: rest_connection_timeout_secs(),
 		}
@@ -0,0 +1,129 @@
+use godfig::env_default;
+use serde::{Deserialize, Serialize};
+
+// The default limits are 100k, 1M and 5M MOVE, with 8 decimals.
+const DEFAULT_MAX_TRANSFER_AMOUNT: u128 = 10_000_000_000_000;
+const DEFAULT_HOURLY_VOLUME_CAP: u128 = 100_000_000_000_000;
+const DEFAULT_DAILY_VOLUME_CAP: u128 = 500_000_000_000_000;
+const DEFAULT_MAX_VALIDATION_FAILURES: u32 = 10;
+const DEFAULT_VALIDATION_FAILURE_WINDOW_SECS: u64 = 600;
+
+/// The safety limits of the relayer. When a limit is exceeded, the circuit breaker trips and
+/// the relayer stops locking the new transfers until it is reset.
+///
+/// The amounts are in the smallest unit of the asset on the chain the transfers are initiated
+/// on. The amount and volume limits apply to each asset separately, the assets without their own
+/// limits in `assets` get the default ones.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LimitsConfig {
+	/// Maximum amount of a transfer
+	#[serde(default = "default_max_transfer_amount")]
+	pub max_transfer_amount: u128,
+
+	/// Maximum volume of the transfers initiated on Ethereum in the last hour
+	#[serde(default = "default_eth_hourly_volume_cap")]
+	pub eth_hourly_volume_cap: u128,
+	/// Maximum volume of the transfers initiated on Ethereum in the last day
+	#[serde(default = "default_eth_daily_volume_cap")]
+	pub eth_daily_volume_cap: u128,
+	/// Maximum volume of the transfers initiated on Movement in the last hour
+	#[serde(default = "default_mvt_hourly_volume_cap")]
+	pub mvt_hourly_volume_cap: u128,
+	/// Maximum volume of the transfers initiated on Movement in the last day
+	#[serde(default = "default_mvt_daily_volume_cap")]
+	pub mvt_daily_volume_cap: u128,
+
+	/// Number of invalid events received within the window that trips the circuit breaker
+	#[serde(default = "default_max_validation_failures")]
+	pub max_validation_failures: u32,
+	#[serde(default = "default_validation_failure_window_secs")]
+	pub validation_failure_window_secs: u64,
+
+	/// The amount and volume limits of the assets that don't use the default ones
+	#[serde(default)]
+	pub assets: Vec<AssetLimits>,
+}
+
+/// The amount and volume limits of an asset.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetLimits {
+	/// The id of the asset in the token registry
+	pub asset_id: String,
+	pub max_transfer_amount: u128,
+	pub eth_hourly_volume_cap: u128,
+	pub eth_daily_volume_cap: u128,
+	pub mvt_hourly_volume_cap: u128,
+	pub mvt_daily_volume_cap: u128,
+}
+
+impl LimitsConfig {
+	/// Gets the amount and volume limits of an asset.
+	pub fn for_asset(&self, asset_id: &str) -> AssetLimits {
+		self.assets
+			.iter()
+			.find(|limits| limits.asset_id == asset_id)
+			.cloned()
+			.unwrap_or_else(|| AssetLimits {
+				asset_id: asset_id.to_string(),
+				max_transfer_amount: self.max_transfer_amount,
+				eth_hourly_volume_cap: self.eth_hourly_volume_cap,
+				eth_daily_volume_cap: self.eth_daily_volume_cap,
+				mvt_hourly_volume_cap: self.mvt_hourly_volume_cap,
+				mvt_daily_volume_cap: self.mvt_daily_volume_cap,
+			})
+	}
+}
+
+env_default!(
+	default_max_transfer_amount,
+	"BRIDGE_MAX_TRANSFER_AMOUNT",
+	u128,
+	DEFAULT_MAX_TRANSFER_AMOUNT
+);
+
+env_default!(
+	default_eth_hourly_volume_cap,
+	"ETH_HOURLY_VOLUME_CAP",
+	u128,
+	DEFAULT_HOURLY_VOLUME_CAP
+);
+
+env_default!(default_eth_daily_volume_cap, "ETH_DAILY_VOLUME_CAP", u128, DEFAULT_DAILY_VOLUME_CAP);
+
+env_default!(
+	default_mvt_hourly_volume_cap,
+	"MVT_HOURLY_VOLUME_CAP",
+	u128,
+	DEFAULT_HOURLY_VOLUME_CAP
+);
+
+env_default!(default_mvt_daily_volume_cap, "MVT_DAILY_VOLUME_CAP", u128, DEFAULT_DAILY_VOLUME_CAP);
+
+env_default!(
+	default_max_validation_failures,
+	"BRIDGE_MAX_VALIDATION_FAILURES",
+	u32,
+	DEFAULT_MAX_VALIDATION_FAILURES
+);
+
+env_default!(
+	default_validation_failure_window_secs,
+	"BRIDGE_VALIDATION_FAILURE_WINDOW_SECS",
+	u64,
+	DEFAULT_VALIDATION_FAILURE_WINDOW_SECS
+);
+
+impl Default for LimitsConfig {
+	fn default() -> Self {
+		LimitsConfig {
+			max_transfer_amount: default_max_transfer_amount(),
+			eth_hourly_volume_cap: default_eth_hourly_volume_cap(),
+			eth_daily_volume_cap: default_eth_daily_volume_cap(),
+			mvt_hourly_volume_cap: default_mvt_hourly_volume_cap(),
+			mvt_daily_volume_cap: default_mvt_daily_volume_cap(),
+			max_validation_failures: default_max_validation_failures(),
+			validation_failure_window_secs: default_validation_failure_window_secs(),
+			assets: vec![],
+		}
+	}
+}
@@ -1,5 +1,7 @@
 pub mod eth;
+pub mod limits;
 pub mod movement;
 pub mod testing;
+pub mod tokens;
 
 const DEFAULT_REST_CONNECTION_TIMEOUT: u64 = 5;
@@ -12,6 +12,11 @@ const DEFAULT_REST_LISTENER_HOSTNAME: &str = "0.0.0.0";
 const DEFAULT_GRPC_LISTENER_HOSTNAME: &str = "0.0.0.0";
 const DEFAULT_GRPC_LISTENER_PORT: u16 = 50051;
 const DEFAULT_REST_LISTENER_PORT: u16 = 30883;
+const DEFAULT_MVT_TIME_LOCK_SAFETY_MARGIN_SECS: u64 = 30;
+const DEFAULT_MVT_ASSET: &str = "MOVE";
+const DEFAULT_MVT_GAS_DROP_AMOUNT: u64 = 1_000_000;
+const DEFAULT_MVT_GAS_DROP_RECIPIENT_INTERVAL_SECS: u64 = 86_400;
+const DEFAULT_MVT_GAS_DROP_DAILY_BUDGET: u64 = 100_000_000;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MovementConfig {
@@ -37,6 +42,31 @@ pub struct MovementConfig {
 	#[serde(default = "default_mvt_init_network")]
 	pub mvt_init_network: String,
 
+	/// Id in the token registry of the asset bridged by the framework modules
+	#[serde(default = "default_mvt_asset")]
+	pub asset: String,
+
+	/// Delay after the expiry of a time lock before the relayer refunds or aborts on Movement
+	#[serde(default = "default_mvt_time_lock_safety_margin_secs")]
+	pub mvt_time_lock_safety_margin_secs: u64,
+
+	/// Whether the relayer drops native gas to the recipients of the transfers to Movement,
+	/// disabled until the operator sets the budget and the rate limit
+	#[serde(default = "default_mvt_gas_drop_enabled")]
+	pub gas_drop_enabled: bool,
+	/// Amount of native gas, in octas, dropped to a recipient
+	#[serde(default = "default_mvt_gas_drop_amount")]
+	pub gas_drop_amount: u64,
+	/// Minimum delay between two drops to the same recipient
+	#[serde(default = "default_mvt_gas_drop_recipient_interval_secs")]
+	pub gas_drop_recipient_interval_secs: u64,
+	/// Maximum amount of native gas, in octas, dropped per day
+	#[serde(default = "default_mvt_gas_drop_daily_budget")]
+	pub gas_drop_daily_budget: u64,
+	/// Movement addresses of the recipients that opted out of the gas drop
+	#[serde(default = "default_mvt_gas_drop_opt_out_recipients")]
+	pub gas_drop_opt_out_recipients: Vec<String>,
+
 	/// Endpoint for the REST service
 	#[serde(default = "default_rest_listener_hostname")]
 	pub rest_listener_hostname: String,
@@ -50,6 +80,9 @@ pub struct MovementConfig {
 	pub grpc_listener_hostname: String,
 	#[serde(default = "default_grpc_listener_port")]
 	pub grpc_port: u16,
+	/// Ethereum addresses allowed to sign gRPC requests for any transfer
+	#[serde(default = "default_grpc_authorized_signers")]
+	pub grpc_authorized_signers: Vec<String>,
 	#[serde(default = "rest_connection_timeout_secs")]
 	pub rest_connection_timeout_secs: u64,
 }
@@ -85,6 +118,30 @@ env_default!(
 
 env_default!(default_grpc_listener_port, "GRPC_LISTENER_PORT", u16, DEFAULT_GRPC_LISTENER_PORT);
 
+// The comma-separated list of authorized signers
+pub fn default_grpc_authorized_signers() -> Vec<String> {
+	match std::env::var("GRPC_AUTHORIZED_SIGNERS") {
+		Ok(val) => val
+			.split(',')
+			.map(|signer| signer.trim().to_string())
+			.filter(|signer| !signer.is_empty())
+			.collect(),
+		Err(_) => vec![],
+	}
+}
+
+// The comma-separated list of the recipients that opted out of the gas drop
+pub fn default_mvt_gas_drop_opt_out_recipients() -> Vec<String> {
+	match std::env::var("MVT_GAS_DROP_OPT_OUT_RECIPIENTS") {
+		Ok(val) => val
+			.split(',')
+			.map(|recipient| recipient.trim().to_string())
+			.filter(|recipient| !recipient.is_empty())
+			.collect(),
+		Err(_) => vec![],
+	}
+}
+
 env_default!(
 	default_rest_listener_hostname,
 	"REST_LISTENER_HOSTNAME",
@@ -145,6 +202,33 @@ env_default!(
 
 env_default!(default_mvt_init_network, "MVT_FAUCET_INIT_NETWORK", String, "local".to_string());
 
+env_default!(default_mvt_asset, "MVT_ASSET", String, DEFAULT_MVT_ASSET.to_string());
+
+env_default!(default_mvt_gas_drop_enabled, "MVT_GAS_DROP_ENABLED", bool, false);
+
+env_default!(default_mvt_gas_drop_amount, "MVT_GAS_DROP_AMOUNT", u64, DEFAULT_MVT_GAS_DROP_AMOUNT);
+
+env_default!(
+	default_mvt_gas_drop_recipient_interval_secs,
+	"MVT_GAS_DROP_RECIPIENT_INTERVAL_SECS",
+	u64,
+	DEFAULT_MVT_GAS_DROP_RECIPIENT_INTERVAL_SECS
+);
+
+env_default!(
+	default_mvt_gas_drop_daily_budget,
+	"MVT_GAS_DROP_DAILY_BUDGET",
+	u64,
+	DEFAULT_MVT_GAS_DROP_DAILY_BUDGET
+);
+
+env_default!(
+	default_mvt_time_lock_safety_margin_secs,
+	"MVT_TIME_LOCK_SAFETY_MARGIN_SECS",
+	u64,
+	DEFAULT_MVT_TIME_LOCK_SAFETY_MARGIN_SECS
+);
+
 impl MovementConfig {
 	pub fn mvt_rpc_connection_url(&self) -> String {
 		format!(
@@ -179,11 +263,19 @@ impl MovementConfig {
 			mvt_faucet_connection_hostname: default_mvt_rpc_connection_hostname(),
 			mvt_faucet_connection_port: 30732,
 			mvt_init_network: default_mvt_init_network(),
+			asset: default_mvt_asset(),
+			mvt_time_lock_safety_margin_secs: default_mvt_time_lock_safety_margin_secs(),
+			gas_drop_enabled: default_mvt_gas_drop_enabled(),
+			gas_drop_amount: default_mvt_gas_drop_amount(),
+			gas_drop_recipient_interval_secs: default_mvt_gas_drop_recipient_interval_secs(),
+			gas_drop_daily_budget: default_mvt_gas_drop_daily_budget(),
+			gas_drop_opt_out_recipients: default_mvt_gas_drop_opt_out_recipients(),
 			rest_listener_hostname: default_rest_listener_hostname(),
 			rest_port: default_rest_listener_port(),
 			grpc_protocol: default_grpc_connection_protocol(),
 			grpc_listener_hostname: default_grpc_listener_hostname(),
 			grpc_port: default_grpc_listener_port(),
+			grpc_authorized_signers: default_grpc_authorized_signers(),
 			rest_connection_timeout_secs: rest_connection_timeout_secs(),
 		}
 	}
@@ -201,11 +293,19 @@ impl Default for MovementConfig {
 			mvt_faucet_connection_hostname: default_mvt_rpc_connection_hostname(),
 			mvt_faucet_connection_port: default_mvt_faucet_connection_port(),
 			mvt_init_network: default_mvt_init_network(),
+			asset: default_mvt_asset(),
+			mvt_time_lock_safety_margin_secs: default_mvt_time_lock_safety_margin_secs(),
+			gas_drop_enabled: default_mvt_gas_drop_enabled(),
+			gas_drop_amount: default_mvt_gas_drop_amount(),
+			gas_drop_recipient_interval_secs: default_mvt_gas_drop_recipient_interval_secs(),
+			gas_drop_daily_budget: default_mvt_gas_drop_daily_budget(),
+			gas_drop_opt_out_recipients: default_mvt_gas_drop_opt_out_recipients(),
 			rest_listener_hostname: default_rest_listener_hostname(),
 			rest_port: default_rest_listener_port(),
 			grpc_protocol: default_grpc_connection_protocol(),
 			grpc_listener_hostname: default_grpc_listener_hostname(),
 			grpc_port: default_grpc_listener_port(),
+			grpc_authorized_signers: default_grpc_authorized_signers(),
 			rest_connection_timeout_secs: rest_connection_timeout_secs(),
 		}
 	}
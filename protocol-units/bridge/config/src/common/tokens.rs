@@ -0,0 +1,103 @@
+use crate::common::eth::{default_asset, default_eth_move_token_contract};
+use serde::{Deserialize, Serialize};
+
+/// Metadata address of the fungible asset paired with the native coin on Movement.
+pub const NATIVE_FA_METADATA_ADDRESS: &str = "0xa";
+const DEFAULT_NATIVE_DECIMALS: u8 = 8;
+
+/// An asset bridged between an ERC-20 on Ethereum and a fungible asset on Movement.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenConfig {
+	/// The id of the asset carried by the bridge transfers, e.g. `MOVE`.
+	pub asset_id: String,
+	/// The address of the ERC-20 contract on Ethereum.
+	pub eth_token_contract: String,
+	/// The bridge contracts of the ERC-20 on Ethereum, the contracts of the Eth config if not set.
+	#[serde(default)]
+	pub eth_initiator_contract: Option<String>,
+	#[serde(default)]
+	pub eth_counterparty_contract: Option<String>,
+	/// The decimals of the ERC-20 on Ethereum.
+	pub eth_decimals: u8,
+	/// The address of the fungible asset metadata on Movement.
+	pub mvt_fa_metadata_address: String,
+	/// The address of the bridge modules of the fungible asset on Movement, the framework modules
+	/// if not set.
+	#[serde(default)]
+	pub mvt_bridge_address: Option<String>,
+	/// The decimals of the fungible asset on Movement.
+	pub mvt_decimals: u8,
+}
+
+/// The registry of the assets supported by the bridge.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TokenRegistry {
+	#[serde(default = "default_tokens")]
+	pub tokens: Vec<TokenConfig>,
+}
+
+fn default_tokens() -> Vec<TokenConfig> {
+	vec![TokenConfig {
+		asset_id: default_asset(),
+		eth_token_contract: default_eth_move_token_contract(),
+		eth_initiator_contract: None,
+		eth_counterparty_contract: None,
+		eth_decimals: DEFAULT_NATIVE_DECIMALS,
+		mvt_fa_metadata_address: NATIVE_FA_METADATA_ADDRESS.to_string(),
+		mvt_bridge_address: None,
+		mvt_decimals: DEFAULT_NATIVE_DECIMALS,
+	}]
+}
+
+impl Default for TokenRegistry {
+	fn default() -> Self {
+		TokenRegistry { tokens: default_tokens() }
+	}
+}
+
+impl TokenRegistry {
+	/// Gets the token of an asset id.
+	pub fn get(&self, asset_id: &str) -> Option<&TokenConfig> {
+		self.tokens.iter().find(|token| token.asset_id == asset_id)
+	}
+
+	/// Gets the token of an ERC-20 contract on Ethereum.
+	pub fn find_by_eth_token(&self, eth_token_contract: &str) -> Option<&TokenConfig> {
+		self.tokens
+			.iter()
+			.find(|token| token.eth_token_contract.eq_ignore_ascii_case(eth_token_contract))
+	}
+
+	/// Gets the token of a fungible asset on Movement.
+	pub fn find_by_mvt_fa_metadata(&self, mvt_fa_metadata_address: &str) -> Option<&TokenConfig> {
+		self.tokens.iter().find(|token| {
+			token.mvt_fa_metadata_address.eq_ignore_ascii_case(mvt_fa_metadata_address)
+		})
+	}
+
+	/// Checks each asset, ERC-20 and fungible asset is registered once, so each ERC-20 maps to a
+	/// single fungible asset and back.
+	pub fn validate(&self) -> Result<(), anyhow::Error> {
+		for token in &self.tokens {
+			let is_token =
+				|found: Option<&TokenConfig>| found.is_some_and(|found| std::ptr::eq(found, token));
+			let asset = &token.asset_id;
+			if !is_token(self.get(asset)) {
+				anyhow::bail!("Asset {asset} is registered more than once");
+			}
+			if !is_token(self.find_by_eth_token(&token.eth_token_contract)) {
+				anyhow::bail!(
+					"ERC-20 {} of asset {asset} is registered for another asset",
+					token.eth_token_contract
+				);
+			}
+			if !is_token(self.find_by_mvt_fa_metadata(&token.mvt_fa_metadata_address)) {
+				anyhow::bail!(
+					"Fungible asset {} of asset {asset} is registered for another asset",
+					token.mvt_fa_metadata_address
+				);
+			}
+		}
+		Ok(())
+	}
+}
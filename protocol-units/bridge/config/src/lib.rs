@@ -17,6 +17,14 @@ pub struct Config {
 	/// Optional testing config
 	#[serde(default)]
 	pub testing: common::testing::TestingConfig,
+
+	/// The assets bridged between the two chains.
+	#[serde(default)]
+	pub tokens: common::tokens::TokenRegistry,
+
+	/// The safety limits of the relayer.
+	#[serde(default)]
+	pub limits: common::limits::LimitsConfig,
 }
 
 impl Default for Config {
@@ -25,6 +33,8 @@ impl Default for Config {
 			eth: common::eth::EthConfig::default(),
 			movement: common::movement::MovementConfig::default(),
 			testing: common::testing::TestingConfig::default(),
+			tokens: common::tokens::TokenRegistry::default(),
+			limits: common::limits::LimitsConfig::default(),
 		}
 	}
 }
@@ -41,6 +51,8 @@ impl Config {
 			eth: common::eth::EthConfig::default(),
 			movement: common::movement::MovementConfig::for_test(),
 			testing: common::testing::TestingConfig::default(),
+			tokens: common::tokens::TokenRegistry::default(),
+			limits: common::limits::LimitsConfig::default(),
 		}
 	}
 }
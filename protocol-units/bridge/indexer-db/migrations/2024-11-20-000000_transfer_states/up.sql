@@ -0,0 +1,6 @@
+CREATE TABLE transfer_states (
+    bridge_transfer_id VARCHAR(64) PRIMARY KEY,
+    init_chain VARCHAR(8) NOT NULL,        -- Chain where the transfer was initiated
+    state VARCHAR(32) NOT NULL,            -- Last state of the transfer in the relayer
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE locked_events DROP COLUMN asset_id;
+ALTER TABLE initiated_events DROP COLUMN asset_id;
+ALTER TABLE lock_bridge_transfers DROP COLUMN asset_id;
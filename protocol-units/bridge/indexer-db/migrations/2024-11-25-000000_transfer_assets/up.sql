@@ -0,0 +1,4 @@
+-- The transfers indexed before only bridged the MOVE token.
+ALTER TABLE lock_bridge_transfers ADD COLUMN asset_id VARCHAR(32) NOT NULL DEFAULT 'MOVE';
+ALTER TABLE initiated_events ADD COLUMN asset_id VARCHAR(32) NOT NULL DEFAULT 'MOVE';
+ALTER TABLE locked_events ADD COLUMN asset_id VARCHAR(32) NOT NULL DEFAULT 'MOVE';
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX initiated_events_recipient_idx;
+DROP INDEX initiated_events_initiator_idx;
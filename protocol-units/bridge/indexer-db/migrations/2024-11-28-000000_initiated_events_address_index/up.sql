@@ -0,0 +1,3 @@
+-- The transfers are looked up by the address of their initiator or recipient.
+CREATE INDEX initiated_events_initiator_idx ON initiated_events (initiator);
+CREATE INDEX initiated_events_recipient_idx ON initiated_events (recipient);
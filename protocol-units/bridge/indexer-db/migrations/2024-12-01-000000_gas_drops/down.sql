@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE gas_drop_recipients;
+DROP TABLE gas_drop_budget;
@@ -0,0 +1,12 @@
+-- The gas drop budget spent since the start of its period, in a single row.
+CREATE TABLE gas_drop_budget (
+    id SMALLINT PRIMARY KEY,
+    period_start BIGINT NOT NULL,          -- Unix timestamp in seconds
+    spent BIGINT NOT NULL                  -- Native gas dropped during the period, in octas
+);
+
+-- The last drop to each recipient, within the rate limit interval.
+CREATE TABLE gas_drop_recipients (
+    recipient VARCHAR(64) PRIMARY KEY,
+    last_drop_at BIGINT NOT NULL           -- Unix timestamp in seconds
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE paused_transfers;
+DROP TABLE circuit_breaker_volumes;
+DROP TABLE circuit_breaker_trips;
@@ -0,0 +1,21 @@
+-- The reason why the circuit breaker tripped, in a single row, null while it is closed.
+CREATE TABLE circuit_breaker_trips (
+    id SMALLINT PRIMARY KEY,
+    trip_reason TEXT,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- The transfers locked within the last day, counted in the rolling volumes.
+CREATE TABLE circuit_breaker_volumes (
+    bridge_transfer_id VARCHAR(64) PRIMARY KEY,
+    locked_at BIGINT NOT NULL,             -- Unix timestamp in seconds
+    asset_id VARCHAR(32) NOT NULL,
+    init_chain VARCHAR(8) NOT NULL,        -- Chain where the transfer was initiated
+    amount NUMERIC NOT NULL
+);
+
+-- The transfers whose lock is paused while the circuit breaker is open.
+CREATE TABLE paused_transfers (
+    bridge_transfer_id VARCHAR(64) PRIMARY KEY,
+    paused_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
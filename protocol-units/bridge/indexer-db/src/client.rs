@@ -1,11 +1,23 @@
 use crate::migrations::run_migrations;
 use crate::models::*;
 use crate::schema::*;
+use bigdecimal::{BigDecimal, ToPrimitive};
 use bridge_util::chains::bridge_contracts::BridgeContractEvent;
-use bridge_util::types::BridgeTransferId;
+use bridge_util::events::TransferEvent;
+use bridge_util::states::TransferStateType;
+use bridge_util::types::{
+	Amount, AssetId, BridgeAddress, BridgeTransferDetails, BridgeTransferId, ChainId, HashLock,
+	HashLockPreImage, LockDetails, TimeLock,
+};
 use bridge_util::TransferActionType;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
+use std::collections::HashMap;
+
+/// The id of the single row of the gas drop budget.
+const GAS_DROP_BUDGET_ID: i16 = 1;
+/// The id of the single row of the circuit breaker trip.
+const CIRCUIT_BREAKER_TRIP_ID: i16 = 1;
 
 pub struct Client {
 	conn: PgConnection,
@@ -20,6 +32,120 @@ pub struct BridgeEventPackage {
 	pub refunded_events: Vec<RefundedEvent>,
 }
 
+/// The state of the gas drop policy of the relayer, its budget and its last drops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasDropState {
+	/// Unix timestamp in seconds of the start of the budget period.
+	pub period_start: u64,
+	/// Native gas dropped during the budget period.
+	pub spent: u64,
+	/// The recipients with the unix timestamp in seconds of their last drop.
+	pub last_drops: Vec<(Vec<u8>, u64)>,
+}
+
+/// A locked transfer counted in the rolling volumes of the circuit breaker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedVolume {
+	pub bridge_transfer_id: BridgeTransferId,
+	/// Unix timestamp in seconds of the lock.
+	pub locked_at: u64,
+	pub asset: AssetId,
+	pub init_chain: ChainId,
+	pub amount: Amount,
+}
+
+impl BridgeEventPackage {
+	/// Converts the indexed events of a transfer initiated on `init_chain` into transfer events,
+	/// in the order they occur during the transfer.
+	pub fn into_transfer_events(
+		self,
+		init_chain: ChainId,
+	) -> Result<Vec<TransferEvent<Vec<u8>>>, anyhow::Error> {
+		let counterpart_chain = init_chain.other();
+		let mut events = Vec::new();
+
+		if let Some(event) = self.initiated_events.into_iter().next() {
+			let details = BridgeTransferDetails {
+				bridge_transfer_id: decode_bridge_transfer_id(&event.bridge_transfer_id)?,
+				initiator: BridgeAddress(decode_hex(&event.initiator)?),
+				recipient: BridgeAddress(decode_hex(&event.recipient)?),
+				hash_lock: decode_hash_lock(&event.hash_lock)?,
+				time_lock: TimeLock(event.time_lock as u64),
+				asset: AssetId(event.asset_id),
+				amount: decode_amount(&event.amount)?,
+				state: event.state as u8,
+			};
+			events.push((BridgeContractEvent::Initiated(details), init_chain).into());
+		}
+		if let Some(event) = self.locked_events.into_iter().next() {
+			let details = LockDetails {
+				bridge_transfer_id: decode_bridge_transfer_id(&event.bridge_transfer_id)?,
+				initiator: BridgeAddress(decode_hex(&event.initiator)?),
+				recipient: BridgeAddress(decode_hex(&event.recipient)?),
+				hash_lock: decode_hash_lock(&event.hash_lock)?,
+				time_lock: TimeLock(event.time_lock as u64),
+				asset: AssetId(event.asset_id),
+				amount: decode_amount(&event.amount)?,
+			};
+			events.push((BridgeContractEvent::Locked(details), counterpart_chain).into());
+		}
+		if let Some(event) = self.counter_party_completed_events.into_iter().next() {
+			let pre_image: [u8; 32] = decode_hex(&event.pre_image)?
+				.try_into()
+				.map_err(|_| anyhow::anyhow!("Invalid pre image length: {}", event.pre_image))?;
+			events.push(
+				(
+					BridgeContractEvent::CounterPartyCompleted(
+						decode_bridge_transfer_id(&event.bridge_transfer_id)?,
+						HashLockPreImage(pre_image),
+					),
+					counterpart_chain,
+				)
+					.into(),
+			);
+		}
+		if let Some(event) = self.initiator_completed_events.into_iter().next() {
+			let bridge_transfer_id = decode_bridge_transfer_id(&event.bridge_transfer_id)?;
+			events.push(
+				(BridgeContractEvent::InitiatorCompleted(bridge_transfer_id), init_chain).into(),
+			);
+		}
+		if let Some(event) = self.cancelled_events.into_iter().next() {
+			let bridge_transfer_id = decode_bridge_transfer_id(&event.bridge_transfer_id)?;
+			events.push(
+				(BridgeContractEvent::Cancelled(bridge_transfer_id), counterpart_chain).into(),
+			);
+		}
+		if let Some(event) = self.refunded_events.into_iter().next() {
+			let bridge_transfer_id = decode_bridge_transfer_id(&event.bridge_transfer_id)?;
+			events.push((BridgeContractEvent::Refunded(bridge_transfer_id), init_chain).into());
+		}
+
+		Ok(events)
+	}
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, anyhow::Error> {
+	hex::decode(value).map_err(|e| anyhow::anyhow!("Failed to decode hex value {}: {:?}", value, e))
+}
+
+fn decode_bridge_transfer_id(value: &str) -> Result<BridgeTransferId, anyhow::Error> {
+	BridgeTransferId::parse(value)
+		.map_err(|e| anyhow::anyhow!("Failed to decode bridge transfer id {}: {:?}", value, e))
+}
+
+fn decode_hash_lock(value: &str) -> Result<HashLock, anyhow::Error> {
+	HashLock::parse(value)
+		.map_err(|e| anyhow::anyhow!("Failed to decode hash lock {}: {:?}", value, e))
+}
+
+fn decode_amount(value: &BigDecimal) -> Result<Amount, anyhow::Error> {
+	value
+		.to_u128()
+		.map(Amount)
+		.ok_or(anyhow::anyhow!("Failed to decode amount {}", value))
+}
+
 impl Client {
 	/// Creates a new client with the given connection.
 	pub fn new(conn: PgConnection) -> Self {
@@ -51,6 +177,7 @@ impl Client {
 				hash_lock,
 				initiator,
 				recipient,
+				asset,
 				amount,
 			} => {
 				diesel::insert_into(lock_bridge_transfers::table)
@@ -61,6 +188,7 @@ impl Client {
 						recipient: hex::encode(recipient.0.to_vec()),
 						amount: amount.0.into(),
 						created_at: chrono::Utc::now().naive_utc(),
+						asset_id: asset.0,
 					})
 					.execute(&mut self.conn)?;
 			}
@@ -76,6 +204,9 @@ impl Client {
 			TransferActionType::RefundInitiator => {
 				// do nothing
 			}
+			TransferActionType::AbortCounterparty => {
+				// do nothing
+			}
 			TransferActionType::TransferDone => {
 				// do nothing
 			}
@@ -118,8 +249,9 @@ impl Client {
 						hash_lock: hex::encode(bridge_transfer_details.hash_lock.0.to_vec()),
 						time_lock: bridge_transfer_details.time_lock.0 as i64,
 						amount: bridge_transfer_details.amount.0.into(),
-						state: 0,
+						state: bridge_transfer_details.state as i16,
 						created_at: chrono::Utc::now().naive_utc(),
+						asset_id: bridge_transfer_details.asset.0,
 					})
 					.execute(&mut self.conn)?;
 			}
@@ -133,6 +265,7 @@ impl Client {
 						time_lock: lock_details.time_lock.0 as i64,
 						amount: lock_details.amount.0.into(),
 						created_at: chrono::Utc::now().naive_utc(),
+						asset_id: lock_details.asset.0,
 					})
 					.execute(&mut self.conn)?;
 			}
@@ -216,6 +349,394 @@ impl Client {
 			refunded_events,
 		})
 	}
+
+	/// Finds the initiated events of an initiator, the most recent first.
+	pub fn find_initiated_events_by_initiator(
+		&mut self,
+		initiator: &[u8],
+		offset: i64,
+		limit: i64,
+	) -> Result<Vec<InitiatedEvent>, diesel::result::Error> {
+		initiated_events::table
+			.filter(initiated_events::initiator.eq(hex::encode(initiator)))
+			.order(initiated_events::created_at.desc())
+			.offset(offset)
+			.limit(limit)
+			.load::<InitiatedEvent>(&mut self.conn)
+	}
+
+	/// Finds the initiated events of a recipient, the most recent first.
+	pub fn find_initiated_events_by_recipient(
+		&mut self,
+		recipient: &[u8],
+		offset: i64,
+		limit: i64,
+	) -> Result<Vec<InitiatedEvent>, diesel::result::Error> {
+		initiated_events::table
+			.filter(initiated_events::recipient.eq(hex::encode(recipient)))
+			.order(initiated_events::created_at.desc())
+			.offset(offset)
+			.limit(limit)
+			.load::<InitiatedEvent>(&mut self.conn)
+	}
+
+	/// Gets the chain where a transfer was initiated and its last state.
+	pub fn find_transfer_state(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> Result<Option<(ChainId, TransferStateType)>, anyhow::Error> {
+		let transfer_state = transfer_states::table
+			.find(hex::encode(bridge_transfer_id.0.to_vec()))
+			.first::<TransferState>(&mut self.conn)
+			.optional()?;
+
+		transfer_state
+			.map(|transfer_state| {
+				Ok((transfer_state.init_chain.parse()?, transfer_state.state.parse()?))
+			})
+			.transpose()
+	}
+
+	/// Gets the chain where each of the transfers was initiated and its last state, in a single
+	/// query. The transfers without a state are missing from the map.
+	pub fn find_transfer_states(
+		&mut self,
+		bridge_transfer_ids: &[BridgeTransferId],
+	) -> Result<HashMap<BridgeTransferId, (ChainId, TransferStateType)>, anyhow::Error> {
+		let bridge_transfer_ids: Vec<String> =
+			bridge_transfer_ids.iter().map(|id| hex::encode(id.0.to_vec())).collect();
+		let transfer_states = transfer_states::table
+			.filter(transfer_states::bridge_transfer_id.eq_any(bridge_transfer_ids))
+			.load::<TransferState>(&mut self.conn)?;
+
+		transfer_states
+			.into_iter()
+			.map(|transfer_state| {
+				Ok((
+					decode_bridge_transfer_id(&transfer_state.bridge_transfer_id)?,
+					(transfer_state.init_chain.parse()?, transfer_state.state.parse()?),
+				))
+			})
+			.collect()
+	}
+
+	/// Inserts or updates the state of a transfer.
+	pub fn upsert_transfer_state(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+		init_chain: ChainId,
+		state: TransferStateType,
+	) -> Result<(), diesel::result::Error> {
+		let transfer_state = NewTransferState {
+			bridge_transfer_id: hex::encode(bridge_transfer_id.0.to_vec()),
+			init_chain: init_chain.to_string(),
+			state: state.to_string(),
+			updated_at: chrono::Utc::now().naive_utc(),
+		};
+		diesel::insert_into(transfer_states::table)
+			.values(&transfer_state)
+			.on_conflict(transfer_states::bridge_transfer_id)
+			.do_update()
+			.set(&transfer_state)
+			.execute(&mut self.conn)?;
+		Ok(())
+	}
+
+	/// Gets the reason why the circuit breaker tripped, `None` if it is closed.
+	pub fn find_circuit_breaker_trip(&mut self) -> Result<Option<String>, diesel::result::Error> {
+		Ok(circuit_breaker_trips::table
+			.find(CIRCUIT_BREAKER_TRIP_ID)
+			.first::<CircuitBreakerTrip>(&mut self.conn)
+			.optional()?
+			.and_then(|trip| trip.trip_reason))
+	}
+
+	/// Saves the reason why the circuit breaker tripped, `None` once it is reset.
+	pub fn save_circuit_breaker_trip(
+		&mut self,
+		trip_reason: Option<String>,
+	) -> Result<(), diesel::result::Error> {
+		let trip = CircuitBreakerTrip {
+			id: CIRCUIT_BREAKER_TRIP_ID,
+			trip_reason,
+			updated_at: chrono::Utc::now().naive_utc(),
+		};
+		diesel::insert_into(circuit_breaker_trips::table)
+			.values(&trip)
+			.on_conflict(circuit_breaker_trips::id)
+			.do_update()
+			.set(&trip)
+			.execute(&mut self.conn)?;
+		Ok(())
+	}
+
+	/// Inserts a locked transfer in the rolling volumes, a transfer is counted once.
+	pub fn insert_locked_volume(
+		&mut self,
+		volume: &LockedVolume,
+	) -> Result<(), diesel::result::Error> {
+		diesel::insert_into(circuit_breaker_volumes::table)
+			.values(CircuitBreakerVolume {
+				bridge_transfer_id: hex::encode(volume.bridge_transfer_id.0.to_vec()),
+				locked_at: volume.locked_at as i64,
+				asset_id: volume.asset.0.clone(),
+				init_chain: volume.init_chain.to_string(),
+				amount: volume.amount.0.into(),
+			})
+			.on_conflict_do_nothing()
+			.execute(&mut self.conn)?;
+		Ok(())
+	}
+
+	/// Deletes the locked transfers older than a unix timestamp in seconds from the volumes.
+	pub fn delete_locked_volumes_before(
+		&mut self,
+		before: u64,
+	) -> Result<(), diesel::result::Error> {
+		diesel::delete(
+			circuit_breaker_volumes::table
+				.filter(circuit_breaker_volumes::locked_at.lt(before as i64)),
+		)
+		.execute(&mut self.conn)?;
+		Ok(())
+	}
+
+	/// Gets the transfers locked since a unix timestamp in seconds, the oldest first.
+	pub fn find_locked_volumes(&mut self, since: u64) -> Result<Vec<LockedVolume>, anyhow::Error> {
+		let volumes = circuit_breaker_volumes::table
+			.filter(circuit_breaker_volumes::locked_at.ge(since as i64))
+			.order(circuit_breaker_volumes::locked_at.asc())
+			.load::<CircuitBreakerVolume>(&mut self.conn)?;
+
+		volumes
+			.into_iter()
+			.map(|volume| {
+				Ok(LockedVolume {
+					bridge_transfer_id: decode_bridge_transfer_id(&volume.bridge_transfer_id)?,
+					locked_at: volume.locked_at as u64,
+					asset: AssetId(volume.asset_id),
+					init_chain: volume.init_chain.parse()?,
+					amount: decode_amount(&volume.amount)?,
+				})
+			})
+			.collect()
+	}
+
+	/// Inserts a transfer whose lock is paused by the circuit breaker.
+	pub fn insert_paused_transfer(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> Result<(), diesel::result::Error> {
+		diesel::insert_into(paused_transfers::table)
+			.values(PausedTransfer {
+				bridge_transfer_id: hex::encode(bridge_transfer_id.0.to_vec()),
+				paused_at: chrono::Utc::now().naive_utc(),
+			})
+			.on_conflict_do_nothing()
+			.execute(&mut self.conn)?;
+		Ok(())
+	}
+
+	/// Deletes the paused transfers, once they are released or dropped.
+	pub fn delete_paused_transfers(&mut self) -> Result<(), diesel::result::Error> {
+		diesel::delete(paused_transfers::table).execute(&mut self.conn)?;
+		Ok(())
+	}
+
+	/// Gets the transfers whose lock is paused by the circuit breaker, the first paused first.
+	pub fn find_paused_transfers(&mut self) -> Result<Vec<BridgeTransferId>, anyhow::Error> {
+		paused_transfers::table
+			.order(paused_transfers::paused_at.asc())
+			.load::<PausedTransfer>(&mut self.conn)?
+			.into_iter()
+			.map(|paused| decode_bridge_transfer_id(&paused.bridge_transfer_id))
+			.collect()
+	}
+
+	/// Gets the persisted state of the gas drop policy, if any.
+	pub fn find_gas_drop_state(&mut self) -> Result<Option<GasDropState>, anyhow::Error> {
+		let budget = gas_drop_budget::table
+			.find(GAS_DROP_BUDGET_ID)
+			.first::<GasDropBudget>(&mut self.conn)
+			.optional()?;
+		let Some(budget) = budget else {
+			return Ok(None);
+		};
+		let last_drops = gas_drop_recipients::table
+			.load::<GasDropRecipient>(&mut self.conn)?
+			.into_iter()
+			.map(|drop| Ok((decode_hex(&drop.recipient)?, drop.last_drop_at as u64)))
+			.collect::<Result<_, anyhow::Error>>()?;
+
+		Ok(Some(GasDropState {
+			period_start: budget.period_start as u64,
+			spent: budget.spent as u64,
+			last_drops,
+		}))
+	}
+
+	/// Replaces the persisted state of the gas drop policy, in a single transaction.
+	pub fn save_gas_drop_state(
+		&mut self,
+		state: &GasDropState,
+	) -> Result<(), diesel::result::Error> {
+		let budget = GasDropBudget {
+			id: GAS_DROP_BUDGET_ID,
+			period_start: state.period_start as i64,
+			spent: state.spent as i64,
+		};
+		let recipients: Vec<GasDropRecipient> = state
+			.last_drops
+			.iter()
+			.map(|(recipient, last_drop_at)| GasDropRecipient {
+				recipient: hex::encode(recipient),
+				last_drop_at: *last_drop_at as i64,
+			})
+			.collect();
+
+		self.conn.transaction(|conn| {
+			diesel::insert_into(gas_drop_budget::table)
+				.values(&budget)
+				.on_conflict(gas_drop_budget::id)
+				.do_update()
+				.set(&budget)
+				.execute(conn)?;
+			diesel::delete(gas_drop_recipients::table).execute(conn)?;
+			diesel::insert_into(gas_drop_recipients::table)
+				.values(&recipients)
+				.execute(conn)?;
+			Ok(())
+		})
+	}
+
+	/// Gets the states of the transfers that are not done.
+	pub fn find_pending_transfer_states(
+		&mut self,
+	) -> Result<Vec<(BridgeTransferId, ChainId, TransferStateType)>, anyhow::Error> {
+		let transfer_states = transfer_states::table
+			.filter(transfer_states::state.ne(TransferStateType::Done.to_string()))
+			.order(transfer_states::updated_at.asc())
+			.load::<TransferState>(&mut self.conn)?;
+
+		transfer_states
+			.into_iter()
+			.map(|transfer_state| {
+				Ok((
+					decode_bridge_transfer_id(&transfer_state.bridge_transfer_id)?,
+					transfer_state.init_chain.parse()?,
+					transfer_state.state.parse()?,
+				))
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_into_transfer_events() -> Result<(), anyhow::Error> {
+		let created_at = chrono::Utc::now().naive_utc();
+		let bridge_transfer_id = hex::encode([1; 32]);
+		let package = BridgeEventPackage {
+			initiated_events: vec![InitiatedEvent {
+				id: 1,
+				bridge_transfer_id: bridge_transfer_id.clone(),
+				initiator: hex::encode([2; 20]),
+				recipient: hex::encode([3; 32]),
+				hash_lock: hex::encode([4; 32]),
+				time_lock: 60,
+				amount: BigDecimal::from(100),
+				state: 1,
+				created_at,
+				asset_id: "MOVE".to_string(),
+			}],
+			locked_events: vec![LockedEvent {
+				id: 1,
+				bridge_transfer_id: bridge_transfer_id.clone(),
+				initiator: hex::encode([2; 20]),
+				recipient: hex::encode([3; 32]),
+				hash_lock: hex::encode([4; 32]),
+				time_lock: 30,
+				amount: BigDecimal::from(100),
+				created_at,
+				asset_id: "MOVE".to_string(),
+			}],
+			initiator_completed_events: vec![InitiatorCompletedEvent {
+				id: 1,
+				bridge_transfer_id: bridge_transfer_id.clone(),
+				created_at,
+			}],
+			counter_party_completed_events: vec![CounterPartyCompletedEvent {
+				id: 1,
+				bridge_transfer_id: bridge_transfer_id.clone(),
+				pre_image: hex::encode([5; 32]),
+				created_at,
+			}],
+			cancelled_events: vec![],
+			refunded_events: vec![],
+		};
+
+		let events = package.into_transfer_events(ChainId::ONE)?;
+		let transfer_id = BridgeTransferId([1; 32]);
+		let expected: Vec<TransferEvent<Vec<u8>>> = vec![
+			(
+				BridgeContractEvent::Initiated(BridgeTransferDetails {
+					bridge_transfer_id: transfer_id,
+					initiator: BridgeAddress(vec![2; 20]),
+					recipient: BridgeAddress(vec![3; 32]),
+					hash_lock: HashLock([4; 32]),
+					time_lock: TimeLock(60),
+					asset: AssetId::from("MOVE"),
+					amount: Amount(100),
+					state: 1,
+				}),
+				ChainId::ONE,
+			)
+				.into(),
+			(
+				BridgeContractEvent::Locked(LockDetails {
+					bridge_transfer_id: transfer_id,
+					initiator: BridgeAddress(vec![2; 20]),
+					recipient: BridgeAddress(vec![3; 32]),
+					hash_lock: HashLock([4; 32]),
+					time_lock: TimeLock(30),
+					asset: AssetId::from("MOVE"),
+					amount: Amount(100),
+				}),
+				ChainId::TWO,
+			)
+				.into(),
+			(
+				BridgeContractEvent::CounterPartyCompleted(transfer_id, HashLockPreImage([5; 32])),
+				ChainId::TWO,
+			)
+				.into(),
+			(BridgeContractEvent::InitiatorCompleted(transfer_id), ChainId::ONE).into(),
+		];
+		assert_eq!(events, expected);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_into_transfer_events_rejects_invalid_encoding() {
+		let package = BridgeEventPackage {
+			initiated_events: vec![],
+			locked_events: vec![],
+			initiator_completed_events: vec![],
+			counter_party_completed_events: vec![CounterPartyCompletedEvent {
+				id: 1,
+				bridge_transfer_id: hex::encode([1; 32]),
+				pre_image: hex::encode([5; 16]),
+				created_at: chrono::Utc::now().naive_utc(),
+			}],
+			cancelled_events: vec![],
+			refunded_events: vec![],
+		};
+		assert!(package.into_transfer_events(ChainId::ONE).is_err());
+	}
 }
 
 /*#[cfg(test)]
@@ -12,6 +12,7 @@ pub struct NewLockBridgeTransfer {
 	pub recipient: String,
 	pub amount: BigDecimal,
 	pub created_at: chrono::NaiveDateTime,
+	pub asset_id: String,
 }
 
 #[derive(Debug, Queryable, Insertable)]
@@ -24,6 +25,7 @@ pub struct LockBridgeTransfer {
 	pub recipient: String,
 	pub amount: BigDecimal,
 	pub created_at: chrono::NaiveDateTime,
+	pub asset_id: String,
 }
 
 // WaitAndCompleteInitiator mapping
@@ -56,6 +58,7 @@ pub struct NewInitiatedEvent {
 	pub amount: BigDecimal,
 	pub state: i16,
 	pub created_at: chrono::NaiveDateTime,
+	pub asset_id: String,
 }
 
 #[derive(Debug, Queryable, Insertable)]
@@ -70,6 +73,7 @@ pub struct InitiatedEvent {
 	pub amount: BigDecimal,
 	pub state: i16,
 	pub created_at: chrono::NaiveDateTime,
+	pub asset_id: String,
 }
 
 // LockedEvent mapping
@@ -83,6 +87,7 @@ pub struct NewLockedEvent {
 	pub time_lock: i64,
 	pub amount: BigDecimal,
 	pub created_at: chrono::NaiveDateTime,
+	pub asset_id: String,
 }
 
 #[derive(Debug, Queryable, Insertable)]
@@ -96,6 +101,7 @@ pub struct LockedEvent {
 	pub time_lock: i64,
 	pub amount: BigDecimal,
 	pub created_at: chrono::NaiveDateTime,
+	pub asset_id: String,
 }
 
 // InitiatorCompletedEvent mapping
@@ -163,3 +169,68 @@ pub struct RefundedEvent {
 	pub bridge_transfer_id: String,
 	pub created_at: chrono::NaiveDateTime,
 }
+
+// TransferState mapping
+#[derive(Debug, Insertable, AsChangeset, Default)]
+#[diesel(table_name = transfer_states)]
+pub struct NewTransferState {
+	pub bridge_transfer_id: String,
+	pub init_chain: String,
+	pub state: String,
+	pub updated_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = transfer_states)]
+pub struct TransferState {
+	pub bridge_transfer_id: String,
+	pub init_chain: String,
+	pub state: String,
+	pub updated_at: chrono::NaiveDateTime,
+}
+
+// GasDropBudget mapping
+#[derive(Debug, Queryable, Insertable, AsChangeset)]
+#[diesel(table_name = gas_drop_budget)]
+pub struct GasDropBudget {
+	pub id: i16,
+	pub period_start: i64,
+	pub spent: i64,
+}
+
+// GasDropRecipient mapping
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = gas_drop_recipients)]
+pub struct GasDropRecipient {
+	pub recipient: String,
+	pub last_drop_at: i64,
+}
+
+// CircuitBreakerTrip mapping
+#[derive(Debug, Queryable, Insertable, AsChangeset)]
+#[diesel(table_name = circuit_breaker_trips)]
+#[diesel(treat_none_as_null = true)]
+pub struct CircuitBreakerTrip {
+	pub id: i16,
+	pub trip_reason: Option<String>,
+	pub updated_at: chrono::NaiveDateTime,
+}
+
+// CircuitBreakerVolume mapping
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = circuit_breaker_volumes)]
+pub struct CircuitBreakerVolume {
+	pub bridge_transfer_id: String,
+	pub locked_at: i64,
+	pub asset_id: String,
+	pub init_chain: String,
+	pub amount: BigDecimal,
+}
+
+// PausedTransfer mapping
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = paused_transfers)]
+pub struct PausedTransfer {
+	pub bridge_transfer_id: String,
+	pub paused_at: chrono::NaiveDateTime,
+}
@@ -9,6 +9,7 @@ table! {
 		recipient -> Text,
 		amount -> Numeric,
 		created_at -> Timestamp,
+		asset_id -> Text,
 	}
 }
 
@@ -32,6 +33,7 @@ table! {
 		amount -> Numeric,
 		state -> Int2,
 		created_at -> Timestamp,
+		asset_id -> Text,
 	}
 }
 
@@ -45,6 +47,7 @@ table! {
 		time_lock -> BigInt,
 		amount -> Numeric,
 		created_at -> Timestamp,
+		asset_id -> Text,
 	}
 }
 
@@ -80,3 +83,52 @@ table! {
 		created_at -> Timestamp,
 	}
 }
+
+table! {
+	transfer_states (bridge_transfer_id) {
+		bridge_transfer_id -> Text,
+		init_chain -> Text,
+		state -> Text,
+		updated_at -> Timestamp,
+	}
+}
+
+table! {
+	gas_drop_budget (id) {
+		id -> Int2,
+		period_start -> BigInt,
+		spent -> BigInt,
+	}
+}
+
+table! {
+	gas_drop_recipients (recipient) {
+		recipient -> Text,
+		last_drop_at -> BigInt,
+	}
+}
+
+table! {
+	circuit_breaker_trips (id) {
+		id -> Int2,
+		trip_reason -> Nullable<Text>,
+		updated_at -> Timestamp,
+	}
+}
+
+table! {
+	circuit_breaker_volumes (bridge_transfer_id) {
+		bridge_transfer_id -> Text,
+		locked_at -> BigInt,
+		asset_id -> Text,
+		init_chain -> Text,
+		amount -> Numeric,
+	}
+}
+
+table! {
+	paused_transfers (bridge_transfer_id) {
+		bridge_transfer_id -> Text,
+		paused_at -> Timestamp,
+	}
+}
@@ -23,7 +23,7 @@ pub fn assert_bridge_transfer_details(
 	expected_hash_lock: [u8; 32],
 	expected_sender_address: AccountAddress,
 	expected_recipient: Vec<u8>,
-	expected_amount: u64,
+	expected_amount: u128,
 	expected_state: u8,
 ) {
 	assert_eq!(details.bridge_transfer_id.0, expected_bridge_transfer_id);
@@ -38,7 +38,7 @@ pub fn assert_counterparty_bridge_transfer_details_framework(
 	details: &BridgeTransferDetails<MovementAddress>,
 	expected_sender_address: String,
 	expected_recipient: Vec<u8>,
-	expected_amount: u64,
+	expected_amount: u128,
 	expected_hash_lock: [u8; 32],
 	expected_time_lock: u64,
 ) {
@@ -177,7 +177,8 @@ pub async fn initiate_bridge_transfer_helper(
 			BridgeAddress(MovementAddress(initiator)),
 			BridgeAddress(recipient),
 			HashLock(MovementHash(hash_lock).0),
-			Amount(amount),
+			movement_client.framework_asset().clone(),
+			amount.into(),
 		)
 		.await
 		.expect("Failed to initiate bridge transfer");
@@ -197,7 +198,8 @@ pub async fn initiate_bridge_transfer_helper_framework(
 			BridgeAddress(MovementAddress(initiator)),
 			BridgeAddress(recipient),
 			HashLock(MovementHash(hash_lock).0),
-			Amount(amount),
+			movement_client.framework_asset().clone(),
+			amount.into(),
 		)
 		.await
 		.expect("Failed to initiate bridge transfer");
@@ -11,7 +11,7 @@ use bridge_service::{
 			utils::MovementAddress,
 		},
 	},
-	types::{Amount, BridgeAddress, HashLock, HashLockPreImage},
+	types::{Amount, AssetId, BridgeAddress, HashLock, HashLockPreImage},
 };
 use futures::StreamExt;
 use tracing_subscriber::EnvFilter;
@@ -122,7 +122,8 @@ async fn test_movement_event() -> Result<(), anyhow::Error> {
 			BridgeAddress(MovementAddress(args.initiator.0)),
 			BridgeAddress(args.recipient.clone()),
 			HashLock(args.hash_lock.0),
-			Amount(args.amount),
+			AssetId(config.movement.asset.clone()),
+			args.amount.into(),
 		)
 		.await?;
 
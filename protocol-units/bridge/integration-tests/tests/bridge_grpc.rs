@@ -1,10 +1,31 @@
+use alloy::primitives::keccak256;
+use alloy::signers::{local::PrivateKeySigner, SignerSync};
 use bridge_config::Config;
 use bridge_grpc::{
-	health_check_response::ServingStatus, health_client::HealthClient, health_server::HealthServer,
-	HealthCheckRequest,
+	bridge_server::Bridge,
+	health_check_response::ServingStatus,
+	health_client::HealthClient,
+	health_server::{Health, HealthServer},
+	GetBridgeTransferDetailsRequest, HealthCheckRequest,
 };
-use bridge_service::grpc::HealthCheckService;
+use bridge_integration_tests::{
+	utils as test_utils, HarnessEthClient, HarnessMvtClient, TestHarness,
+};
+use bridge_service::chains::bridge_contracts::{BridgeContract, BridgeContractEvent};
+use bridge_service::chains::ethereum::{
+	client::EthClient, event_monitoring::EthMonitoring, types::EthAddress,
+};
+use bridge_service::chains::movement::{
+	client_framework::MovementClientFramework, event_monitoring::MovementMonitoring,
+	utils::MovementAddress,
+};
+use bridge_service::grpc::{transfer_details_message, BridgeGrpcService, HealthCheckService};
+use bridge_service::types::{
+	Amount, AssetId, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage,
+};
+use futures::StreamExt;
 use std::net::SocketAddr;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tonic::{transport::Server, Request};
 use tracing_subscriber::EnvFilter;
 
@@ -55,3 +76,264 @@ async fn test_grpc_health_check() -> Result<(), anyhow::Error> {
 	grpc_server_handle.abort();
 	Ok(())
 }
+
+#[tokio::test]
+async fn test_grpc_health_check_reports_relayer_health() -> Result<(), anyhow::Error> {
+	let (health_tx, mut health_rx) = tokio::sync::mpsc::channel(10);
+	let health_service = HealthCheckService::new(health_tx);
+	health_service.set_service_status("Bridge", ServingStatus::Serving);
+
+	// Mock the relayer loop, healthy for the first request only.
+	tokio::spawn(async move {
+		let mut healthy = true;
+		while let Some(oneshot_tx) = health_rx.recv().await {
+			let res = if healthy { "OK".to_string() } else { "NOK".to_string() };
+			let _ = oneshot_tx.send(res);
+			healthy = false;
+		}
+	});
+
+	let request = Request::new(HealthCheckRequest { service: "Bridge".to_string() });
+	let response = health_service.check(request).await?.into_inner();
+	assert_eq!(response.status, ServingStatus::Serving as i32);
+
+	let request = Request::new(HealthCheckRequest { service: "Bridge".to_string() });
+	let response = health_service.check(request).await?.into_inner();
+	assert_eq!(response.status, ServingStatus::NotServing as i32);
+
+	// Services that are not registered are unknown, whatever the health of the relayer.
+	let request = Request::new(HealthCheckRequest { service: "Unknown".to_string() });
+	let response = health_service.check(request).await?.into_inner();
+	assert_eq!(response.status, ServingStatus::ServiceUnknown as i32);
+
+	Ok(())
+}
+
+fn signed_request(
+	signer: &PrivateKeySigner,
+	method: &str,
+	bridge_transfer_id: BridgeTransferId,
+) -> Result<Request<GetBridgeTransferDetailsRequest>, anyhow::Error> {
+	let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+	let message = transfer_details_message(method, bridge_transfer_id, timestamp);
+	let signature = signer.sign_message_sync(message.as_bytes())?;
+	Ok(Request::new(GetBridgeTransferDetailsRequest {
+		bridge_transfer_id: bridge_transfer_id.0.to_vec(),
+		signature: signature.as_bytes().to_vec(),
+		timestamp,
+	}))
+}
+
+fn bridge_grpc_service(
+	eth_client: EthClient,
+	movement_client: MovementClientFramework,
+	config: &Config,
+) -> Result<BridgeGrpcService, anyhow::Error> {
+	let mut movement_config = config.movement.clone();
+	movement_config.grpc_authorized_signers = vec![];
+	let (circuit_breaker_tx, _) = tokio::sync::mpsc::channel(1);
+	BridgeGrpcService::new(eth_client, movement_client, &movement_config, circuit_breaker_tx)
+}
+
+#[tokio::test]
+async fn test_grpc_eth_transfer_details() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let (mut eth_client_harness, mvt_client_harness, config) =
+		TestHarness::new_with_eth_and_movement().await?;
+	let service = bridge_grpc_service(
+		eth_client_harness.eth_client.clone(),
+		mvt_client_harness.movement_client.clone(),
+		&config,
+	)?;
+	let initiator = HarnessEthClient::get_initiator_private_key(&config);
+	let recipient = HarnessEthClient::get_recipient_private_key(&config);
+	let other = PrivateKeySigner::random();
+
+	// The details of an initiated transfer are served to its initiator.
+	let (_eth_health_tx, eth_health_rx) = tokio::sync::mpsc::channel(10);
+	let mut eth_monitoring = EthMonitoring::build(&config.eth, eth_health_rx).await?;
+	let hash_lock = HashLock(From::from(keccak256(HashLockPreImage::random())));
+	eth_client_harness
+		.initiate_eth_bridge_transfer(
+			&config,
+			initiator.clone(),
+			MovementAddress(HarnessMvtClient::gen_aptos_account().address()),
+			hash_lock,
+			Amount(1),
+		)
+		.await?;
+	let bridge_transfer_id =
+		match tokio::time::timeout(std::time::Duration::from_secs(30), eth_monitoring.next())
+			.await?
+		{
+			Some(Ok(BridgeContractEvent::Initiated(details))) => details.bridge_transfer_id,
+			event => panic!("Not an Initiated event: {event:?}"),
+		};
+
+	let method = "GetBridgeTransferDetailsInitiatorEth";
+	let response = service
+		.get_bridge_transfer_details_initiator_eth(signed_request(
+			&initiator,
+			method,
+			bridge_transfer_id,
+		)?)
+		.await?
+		.into_inner();
+	assert_eq!(response.hash_lock, hash_lock.0.to_vec());
+	assert_eq!(response.amount, "1");
+	// The Eth contract state of an initiated transfer.
+	assert_eq!(response.state, 0);
+	let err = service
+		.get_bridge_transfer_details_initiator_eth(signed_request(
+			&other,
+			method,
+			bridge_transfer_id,
+		)?)
+		.await
+		.unwrap_err();
+	assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+	// The details of a locked transfer are served to its recipient.
+	let bridge_transfer_id = BridgeTransferId::gen_unique_hash(&mut rand::rngs::OsRng);
+	eth_client_harness
+		.eth_client
+		.lock_bridge_transfer(
+			bridge_transfer_id,
+			hash_lock,
+			BridgeAddress(vec![3; 32]),
+			BridgeAddress(EthAddress(recipient.address())),
+			AssetId(config.eth.asset.clone()),
+			Amount(1),
+		)
+		.await?;
+
+	let method = "GetBridgeTransferDetailsCounterpartyEth";
+	let response = service
+		.get_bridge_transfer_details_counterparty_eth(signed_request(
+			&recipient,
+			method,
+			bridge_transfer_id,
+		)?)
+		.await?
+		.into_inner();
+	assert_eq!(response.recipient, recipient.address().to_vec());
+	assert_eq!(response.amount, "1");
+	let err = service
+		.get_bridge_transfer_details_counterparty_eth(signed_request(
+			&other,
+			method,
+			bridge_transfer_id,
+		)?)
+		.await
+		.unwrap_err();
+	assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+	// A signature for another method is not accepted.
+	let err = service
+		.get_bridge_transfer_details_counterparty_eth(signed_request(
+			&recipient,
+			"GetBridgeTransferDetailsInitiatorEth",
+			bridge_transfer_id,
+		)?)
+		.await
+		.unwrap_err();
+	assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_grpc_movement_transfer_details() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let (eth_client_harness, mut mvt_client_harness, config) =
+		TestHarness::new_with_eth_and_movement().await?;
+	let service = bridge_grpc_service(
+		eth_client_harness.eth_client.clone(),
+		mvt_client_harness.movement_client.clone(),
+		&config,
+	)?;
+	let eth_account = PrivateKeySigner::random();
+	let other = PrivateKeySigner::random();
+	test_utils::fund_and_check_balance_framework(&mut mvt_client_harness, 100_000_000_000).await?;
+
+	// The details of an initiated transfer are served to its Ethereum recipient.
+	let (_mvt_health_tx, mvt_health_rx) = tokio::sync::mpsc::channel(10);
+	let mut mvt_monitoring = MovementMonitoring::build(&config.movement, mvt_health_rx).await?;
+	let hash_lock = HashLock(From::from(keccak256(HashLockPreImage::random())));
+	let movement_signer = mvt_client_harness.movement_client.signer().address();
+	mvt_client_harness
+		.movement_client
+		.initiate_bridge_transfer(
+			BridgeAddress(MovementAddress(movement_signer)),
+			BridgeAddress(eth_account.address().to_vec()),
+			hash_lock,
+			AssetId::from("MOVE"),
+			Amount(1),
+		)
+		.await?;
+	let bridge_transfer_id =
+		match tokio::time::timeout(std::time::Duration::from_secs(30), mvt_monitoring.next())
+			.await?
+		{
+			Some(Ok(BridgeContractEvent::Initiated(details))) => details.bridge_transfer_id,
+			event => panic!("Not an Initiated event: {event:?}"),
+		};
+
+	let method = "GetBridgeTransferDetailsInitiatorMovement";
+	let response = service
+		.get_bridge_transfer_details_initiator_movement(signed_request(
+			&eth_account,
+			method,
+			bridge_transfer_id,
+		)?)
+		.await?
+		.into_inner();
+	assert_eq!(response.recipient, eth_account.address().to_vec());
+	assert_eq!(response.amount, "1");
+	let err = service
+		.get_bridge_transfer_details_initiator_movement(signed_request(
+			&other,
+			method,
+			bridge_transfer_id,
+		)?)
+		.await
+		.unwrap_err();
+	assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+	// The details of a locked transfer are served to its Ethereum initiator.
+	let bridge_transfer_id = BridgeTransferId::gen_unique_hash(&mut rand::rngs::OsRng);
+	mvt_client_harness
+		.movement_client
+		.lock_bridge_transfer(
+			bridge_transfer_id,
+			hash_lock,
+			BridgeAddress(eth_account.address().to_vec()),
+			BridgeAddress(MovementAddress(movement_signer)),
+			AssetId::from("MOVE"),
+			Amount(1),
+		)
+		.await?;
+
+	let method = "GetBridgeTransferDetailsCounterpartyMovement";
+	let response = service
+		.get_bridge_transfer_details_counterparty_movement(signed_request(
+			&eth_account,
+			method,
+			bridge_transfer_id,
+		)?)
+		.await?
+		.into_inner();
+	assert_eq!(response.initiator, format!("0x{}", hex::encode(eth_account.address())));
+	assert_eq!(response.amount, "1");
+	let err = service
+		.get_bridge_transfer_details_counterparty_movement(signed_request(
+			&other,
+			method,
+			bridge_transfer_id,
+		)?)
+		.await
+		.unwrap_err();
+	assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+	Ok(())
+}
@@ -0,0 +1,256 @@
+//! These tests need the bridge indexer database, see `BRIDGE_INDEXER_DATABASE_URL`.
+use bridge_config::common::{limits::LimitsConfig, tokens::TokenRegistry};
+use bridge_indexer_db::client::Client;
+use bridge_service::{
+	circuit_breaker::CircuitBreaker,
+	time_lock::{unix_now, TimeLockScheduler, TransferTimeLock},
+	Runtime, MAX_LOCK_RETRIES,
+};
+use bridge_util::{
+	chains::bridge_contracts::{BridgeContractError, BridgeContractEvent},
+	states::TransferStateType,
+	types::{
+		Amount, AssetId, BridgeAddress, BridgeTransferDetails, BridgeTransferId, ChainId, HashLock,
+		HashLockPreImage, LockDetails, TimeLock,
+	},
+	ActionExecError, TransferAction, TransferActionType, TransferEvent,
+};
+use std::time::Duration;
+
+fn client() -> Result<Client, anyhow::Error> {
+	let mut client = Client::from_env()?;
+	client.run_migrations()?;
+	Ok(client)
+}
+
+fn runtime() -> Result<Runtime, anyhow::Error> {
+	Ok(Runtime::new(
+		Some(client()?),
+		TimeLockScheduler::new(Duration::ZERO, Duration::ZERO),
+		TokenRegistry::default(),
+		CircuitBreaker::new(LimitsConfig::default()),
+	))
+}
+
+fn random_transfer_id() -> BridgeTransferId {
+	BridgeTransferId::gen_unique_hash(&mut rand::thread_rng())
+}
+
+fn initiated_event(transfer_id: BridgeTransferId) -> TransferEvent<Vec<u8>> {
+	let details = BridgeTransferDetails {
+		bridge_transfer_id: transfer_id,
+		initiator: BridgeAddress(vec![1; 20]),
+		recipient: BridgeAddress(vec![2; 32]),
+		hash_lock: HashLock([3; 32]),
+		time_lock: TimeLock(60),
+		asset: AssetId::from("MOVE"),
+		amount: Amount(100),
+		state: 1,
+	};
+	(BridgeContractEvent::Initiated(details), ChainId::ONE).into()
+}
+
+fn locked_event(transfer_id: BridgeTransferId) -> TransferEvent<Vec<u8>> {
+	let details = LockDetails {
+		bridge_transfer_id: transfer_id,
+		initiator: BridgeAddress(vec![1; 20]),
+		recipient: BridgeAddress(vec![2; 32]),
+		hash_lock: HashLock([3; 32]),
+		time_lock: TimeLock(30),
+		asset: AssetId::from("MOVE"),
+		amount: Amount(100),
+	};
+	(BridgeContractEvent::Locked(details), ChainId::TWO).into()
+}
+
+fn counterparty_completed_event(transfer_id: BridgeTransferId) -> TransferEvent<Vec<u8>> {
+	(
+		BridgeContractEvent::CounterPartyCompleted(transfer_id, HashLockPreImage([4; 32])),
+		ChainId::TWO,
+	)
+		.into()
+}
+
+fn restored_actions(
+	runtime: &mut Runtime,
+	transfer_id: BridgeTransferId,
+) -> Result<Vec<TransferAction>, anyhow::Error> {
+	Ok(runtime
+		.restore()?
+		.into_iter()
+		.filter(|action| action.transfer_id == transfer_id)
+		.collect())
+}
+
+#[test]
+fn test_transfer_state_round_trip() -> Result<(), anyhow::Error> {
+	let mut client = client()?;
+	let transfer_id = random_transfer_id();
+	assert_eq!(client.find_transfer_state(transfer_id)?, None);
+
+	client.upsert_transfer_state(transfer_id, ChainId::TWO, TransferStateType::Locked)?;
+	assert_eq!(
+		client.find_transfer_state(transfer_id)?,
+		Some((ChainId::TWO, TransferStateType::Locked))
+	);
+	assert!(client.find_pending_transfer_states()?.contains(&(
+		transfer_id,
+		ChainId::TWO,
+		TransferStateType::Locked
+	)));
+
+	// A done transfer is updated in place and is no longer pending.
+	client.upsert_transfer_state(transfer_id, ChainId::TWO, TransferStateType::Done)?;
+	assert_eq!(
+		client.find_transfer_state(transfer_id)?,
+		Some((ChainId::TWO, TransferStateType::Done))
+	);
+	assert!(!client
+		.find_pending_transfer_states()?
+		.iter()
+		.any(|(pending_id, _, _)| *pending_id == transfer_id));
+
+	Ok(())
+}
+
+#[test]
+fn test_find_transfer_states_batch() -> Result<(), anyhow::Error> {
+	let mut client = client()?;
+	let locked_id = random_transfer_id();
+	let done_id = random_transfer_id();
+	let unknown_id = random_transfer_id();
+	client.upsert_transfer_state(locked_id, ChainId::ONE, TransferStateType::Locked)?;
+	client.upsert_transfer_state(done_id, ChainId::TWO, TransferStateType::Done)?;
+
+	let states = client.find_transfer_states(&[locked_id, done_id, unknown_id])?;
+	assert_eq!(states.len(), 2);
+	assert_eq!(states.get(&locked_id), Some(&(ChainId::ONE, TransferStateType::Locked)));
+	assert_eq!(states.get(&done_id), Some(&(ChainId::TWO, TransferStateType::Done)));
+	assert!(client.find_transfer_states(&[])?.is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn test_indexed_events_round_trip() -> Result<(), anyhow::Error> {
+	let mut client = client()?;
+	let transfer_id = random_transfer_id();
+	let events = vec![
+		initiated_event(transfer_id),
+		locked_event(transfer_id),
+		counterparty_completed_event(transfer_id),
+		(BridgeContractEvent::InitiatorCompleted(transfer_id), ChainId::ONE).into(),
+	];
+	for event in events.clone() {
+		client.insert_bridge_contract_event(event.contract_event)?;
+	}
+
+	let indexed = client
+		.find_all_events_for_bridge_transfer_id(transfer_id)?
+		.into_transfer_events(ChainId::ONE)?;
+	assert_eq!(indexed, events);
+
+	Ok(())
+}
+
+#[test]
+fn test_restore_resumes_last_action() -> Result<(), anyhow::Error> {
+	let transfer_id = random_transfer_id();
+	let mut runtime = runtime()?;
+	let lock_action = runtime.process_event(initiated_event(transfer_id))?;
+	assert!(matches!(lock_action.kind, TransferActionType::LockBridgeTransfer { .. }));
+
+	// The lock was not executed before the restart: it is resumed.
+	let mut restored = runtime()?;
+	let actions = restored_actions(&mut restored, transfer_id)?;
+	assert_eq!(actions.len(), 1);
+	assert!(matches!(actions[0].kind, TransferActionType::LockBridgeTransfer { .. }));
+	assert_eq!(actions[0].chain, ChainId::TWO);
+
+	// Once locked and completed on the counterparty, only the completion is resumed.
+	runtime.process_event(locked_event(transfer_id))?;
+	runtime.process_event(counterparty_completed_event(transfer_id))?;
+	let mut restored = runtime()?;
+	let actions = restored_actions(&mut restored, transfer_id)?;
+	assert_eq!(actions.len(), 1);
+	assert!(matches!(actions[0].kind, TransferActionType::WaitAndCompleteInitiator(..)));
+	assert_eq!(actions[0].chain, ChainId::ONE);
+	assert_eq!(
+		restored
+			.iter_state()
+			.find(|state| state.transfer_id == transfer_id)
+			.map(|s| s.state),
+		Some(TransferStateType::SecretReceived)
+	);
+
+	Ok(())
+}
+
+#[test]
+fn test_restored_refund_waits_for_time_lock() -> Result<(), anyhow::Error> {
+	let transfer_id = random_transfer_id();
+	let mut runtime = runtime()?;
+	let mut action = runtime.process_event(initiated_event(transfer_id))?;
+	for _ in 0..MAX_LOCK_RETRIES {
+		let err = ActionExecError(action, BridgeContractError::CallError);
+		action = runtime.process_action_exec_error(err).unwrap().0;
+	}
+	let err = ActionExecError(action, BridgeContractError::CallError);
+	assert!(runtime.process_action_exec_error(err).is_none());
+
+	// The refund is not submitted on restore, only once the initiator time lock read on chain
+	// expires.
+	let mut restored = runtime()?;
+	assert!(restored_actions(&mut restored, transfer_id)?.is_empty());
+	let initiator_time_lock = TransferTimeLock::Initiator(ChainId::ONE);
+	assert!(restored.pending_time_locks().contains(&(transfer_id, initiator_time_lock)));
+	let time_lock = unix_now() + 60;
+	restored.schedule_time_lock(transfer_id, initiator_time_lock, TimeLock(time_lock));
+	assert!(restored.process_expired_time_locks(time_lock - 1).is_empty());
+	assert_eq!(
+		restored
+			.iter_state()
+			.find(|state| state.transfer_id == transfer_id)
+			.map(|s| s.state),
+		Some(TransferStateType::Refund)
+	);
+	let refunds: Vec<_> = restored
+		.process_expired_time_locks(time_lock)
+		.into_iter()
+		.filter(|action| action.transfer_id == transfer_id)
+		.collect();
+	assert_eq!(refunds.len(), 1);
+	assert!(matches!(refunds[0].kind, TransferActionType::RefundInitiator));
+	assert_eq!(refunds[0].chain, ChainId::ONE);
+
+	Ok(())
+}
+
+#[test]
+fn test_restored_paused_lock_stays_paused() -> Result<(), anyhow::Error> {
+	let transfer_id = random_transfer_id();
+	let mut runtime = runtime()?;
+	let lock_action = runtime.process_event(initiated_event(transfer_id))?;
+	assert!(matches!(lock_action.kind, TransferActionType::LockBridgeTransfer { .. }));
+
+	// The lock is counted once in the persisted volumes.
+	let mut client = client()?;
+	let volumes = client.find_locked_volumes(unix_now() - 60)?;
+	let volume = volumes.iter().find(|volume| volume.bridge_transfer_id == transfer_id);
+	assert_eq!(
+		volume.map(|volume| (volume.init_chain, volume.amount)),
+		Some((ChainId::ONE, Amount(100)))
+	);
+
+	// A lock paused before the restart is not resumed until the circuit breaker is reset.
+	client.insert_paused_transfer(transfer_id)?;
+	assert!(client.find_paused_transfers()?.contains(&transfer_id));
+	let mut restored = runtime()?;
+	assert!(restored_actions(&mut restored, transfer_id)?.is_empty());
+	assert!(restored
+		.circuit_breaker_status(unix_now())
+		.paused_transfers
+		.contains(&transfer_id));
+
+	Ok(())
+}
@@ -1,5 +1,6 @@
 use bridge_config::Config;
-use bridge_service::rest::BridgeRest;
+use bridge_service::rest::{BridgeRest, TransferSummary};
+use poem::http::StatusCode;
 use poem::test::TestClient;
 use std::sync::Arc;
 use tracing_subscriber::EnvFilter;
@@ -18,7 +19,9 @@ async fn test_rest_service_health_endpoint() -> Result<(), anyhow::Error> {
 
 	// Create the REST service, unwrapping the result
 	let (health_tx, mut health_rx) = tokio::sync::mpsc::channel(10);
-	let rest_service = Arc::new(BridgeRest::new(&mock_config.movement, health_tx)?);
+	let (pending_transfers_tx, _pending_transfers_rx) = tokio::sync::mpsc::channel(10);
+	let rest_service =
+		Arc::new(BridgeRest::new(&mock_config.movement, health_tx, pending_transfers_tx, None)?);
 
 	let rest_service_for_task = Arc::clone(&rest_service);
 
@@ -50,3 +53,86 @@ async fn test_rest_service_health_endpoint() -> Result<(), anyhow::Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn test_rest_service_transfer_endpoints() -> Result<(), anyhow::Error> {
+	let mock_config = Config::default();
+	let (health_tx, _health_rx) = tokio::sync::mpsc::channel(10);
+	let (pending_transfers_tx, mut pending_transfers_rx) = tokio::sync::mpsc::channel(10);
+	let rest_service =
+		BridgeRest::new(&mock_config.movement, health_tx, pending_transfers_tx, None)?;
+
+	let pending_transfer = TransferSummary {
+		bridge_transfer_id: hex::encode([1; 32]),
+		init_chain: Some("ONE".to_string()),
+		state: Some("Initialized".to_string()),
+		initiator: hex::encode([2; 20]),
+		recipient: hex::encode([3; 32]),
+		asset_id: "MOVE".to_string(),
+		amount: "100".to_string(),
+		created_at: None,
+	};
+
+	//simulate the bridge loop part
+	let bridge_loop_future = tokio::spawn({
+		let pending_transfer = pending_transfer.clone();
+		async move {
+			while let Some(oneshot_tx) = pending_transfers_rx.recv().await {
+				let _ = oneshot_tx.send(vec![pending_transfer.clone()]);
+			}
+		}
+	});
+
+	let client = TestClient::new(rest_service.create_routes());
+
+	let response = client.get("/transfers/pending").send().await;
+	response.assert_status_is_ok();
+	let transfers: Vec<TransferSummary> = response.json().await.value().deserialize();
+	assert_eq!(transfers, vec![pending_transfer]);
+
+	// The lookups need the indexer db.
+	let response = client.get(format!("/transfers/{}", hex::encode([1; 32]))).send().await;
+	response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+	let response = client.get("/transfers/not-a-transfer-id").send().await;
+	response.assert_status(StatusCode::BAD_REQUEST);
+
+	let response = client.get("/transfers").send().await;
+	response.assert_status(StatusCode::BAD_REQUEST);
+
+	let response = client.get("/transfers?initiator=0x0202&page_size=1000").send().await;
+	response.assert_status(StatusCode::BAD_REQUEST);
+
+	bridge_loop_future.abort();
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_rest_service_pending_transfers_timeout() -> Result<(), anyhow::Error> {
+	let mock_config = Config::default();
+	let (health_tx, _health_rx) = tokio::sync::mpsc::channel(10);
+	let (pending_transfers_tx, mut pending_transfers_rx) = tokio::sync::mpsc::channel(10);
+	let rest_service =
+		BridgeRest::new(&mock_config.movement, health_tx, pending_transfers_tx, None)?;
+
+	// The simulated bridge loop receives the requests but never answers them.
+	let bridge_loop_future = tokio::spawn(async move {
+		let mut requests = Vec::new();
+		while let Some(oneshot_tx) = pending_transfers_rx.recv().await {
+			requests.push(oneshot_tx);
+		}
+	});
+
+	let client = TestClient::new(rest_service.create_routes());
+	let response = tokio::time::timeout(
+		std::time::Duration::from_secs(10),
+		client.get("/transfers/pending").send(),
+	)
+	.await?;
+	response.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+
+	bridge_loop_future.abort();
+
+	Ok(())
+}
@@ -0,0 +1,193 @@
+use alloy::primitives::keccak256;
+use bridge_config::common::{limits::LimitsConfig, tokens::TokenRegistry};
+use bridge_integration_tests::{
+	utils as test_utils, HarnessEthClient, HarnessMvtClient, TestHarness,
+};
+use bridge_service::{
+	chains::{
+		bridge_contracts::{BridgeContract, BridgeContractError, BridgeContractEvent},
+		ethereum::event_monitoring::EthMonitoring,
+		movement::{event_monitoring::MovementMonitoring, utils::MovementAddress},
+	},
+	circuit_breaker::CircuitBreaker,
+	time_lock::{TimeLockScheduler, TransferTimeLock},
+	types::{
+		Amount, AssetId, BridgeAddress, ChainId, HashLock, HashLockPreImage, LockDetails, TimeLock,
+	},
+	Runtime, MAX_LOCK_RETRIES,
+};
+use bridge_util::{ActionExecError, TransferAction, TransferActionType};
+use futures::StreamExt;
+use std::time::{Duration, Instant};
+
+/// The initiator time lock on Movement during the refund test, in seconds.
+const MOVEMENT_TIME_LOCK_SECS: u64 = 10;
+
+fn runtime() -> Runtime {
+	Runtime::new(
+		None,
+		TimeLockScheduler::new(Duration::ZERO, Duration::ZERO),
+		TokenRegistry::default(),
+		CircuitBreaker::new(LimitsConfig::default()),
+	)
+}
+
+fn fail(action: TransferAction) -> ActionExecError {
+	ActionExecError(action, BridgeContractError::CallError)
+}
+
+#[tokio::test]
+async fn test_failed_completion_is_retried_on_eth() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let (mut eth_client_harness, config) = TestHarness::new_only_eth().await?;
+	let (_eth_health_tx, eth_health_rx) = tokio::sync::mpsc::channel(10);
+	let mut eth_monitoring = EthMonitoring::build(&config.eth, eth_health_rx).await?;
+	let mut runtime = runtime();
+
+	let hash_lock_pre_image = HashLockPreImage::random();
+	let hash_lock = HashLock(From::from(keccak256(hash_lock_pre_image)));
+	eth_client_harness
+		.initiate_eth_bridge_transfer(
+			&config,
+			HarnessEthClient::get_initiator_private_key(&config),
+			MovementAddress(HarnessMvtClient::gen_aptos_account().address()),
+			hash_lock,
+			Amount(1),
+		)
+		.await?;
+	let details = match tokio::time::timeout(Duration::from_secs(30), eth_monitoring.next()).await?
+	{
+		Some(Ok(BridgeContractEvent::Initiated(details))) => details,
+		event => panic!("Not an Initiated event: {event:?}"),
+	};
+	let bridge_transfer_id = details.bridge_transfer_id;
+	let lock_details = LockDetails {
+		bridge_transfer_id,
+		initiator: BridgeAddress(details.initiator.0.clone().into()),
+		recipient: BridgeAddress(details.recipient.0.clone()),
+		hash_lock,
+		time_lock: details.time_lock,
+		asset: details.asset.clone(),
+		amount: details.amount,
+	};
+	runtime.process_event((BridgeContractEvent::Initiated(details), ChainId::ONE).into())?;
+
+	// The counterparty is locked then completed on Movement, the initiator is completed on Eth.
+	runtime.process_event::<Vec<u8>>(
+		(BridgeContractEvent::Locked(lock_details), ChainId::TWO).into(),
+	)?;
+	let action = runtime.process_event::<Vec<u8>>(
+		(
+			BridgeContractEvent::CounterPartyCompleted(bridge_transfer_id, hash_lock_pre_image),
+			ChainId::TWO,
+		)
+			.into(),
+	)?;
+	assert_eq!(action.chain, ChainId::ONE);
+
+	// The first completion fails: it is retried after the backoff, without waiting more.
+	let (retried, delay) = runtime.process_action_exec_error(fail(action)).unwrap();
+	let secret = match retried.kind {
+		TransferActionType::WaitAndCompleteInitiator(0, secret) => secret,
+		kind => panic!("Not a completion without wait: {kind:?}"),
+	};
+	tokio::time::sleep(delay).await;
+	eth_client_harness
+		.eth_client
+		.initiator_complete_bridge_transfer(retried.transfer_id, secret)
+		.await?;
+
+	let details = eth_client_harness
+		.eth_client
+		.get_bridge_transfer_details_initiator(bridge_transfer_id)
+		.await?
+		.expect("Bridge transfer not found");
+	// The Eth contract states are initialized, completed then refunded, from 0.
+	assert_eq!(details.state, 1, "Bridge transfer is supposed to be completed.");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_lock_is_refunded_on_movement() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let (mut mvt_client_harness, config) = TestHarness::new_with_movement().await?;
+	test_utils::fund_and_check_balance_framework(&mut mvt_client_harness, 100_000_000_000).await?;
+	mvt_client_harness.init_set_timelock(MOVEMENT_TIME_LOCK_SECS).await?;
+	let (_mvt_health_tx, mvt_health_rx) = tokio::sync::mpsc::channel(10);
+	let mut mvt_monitoring = MovementMonitoring::build(&config.movement, mvt_health_rx).await?;
+	let mut runtime = runtime();
+
+	let hash_lock = HashLock(From::from(keccak256(HashLockPreImage::random())));
+	let initiator = mvt_client_harness.movement_client.signer().address();
+	mvt_client_harness
+		.movement_client
+		.initiate_bridge_transfer(
+			BridgeAddress(MovementAddress(initiator)),
+			BridgeAddress(HarnessEthClient::get_recipeint_address(&config).to_vec()),
+			hash_lock,
+			AssetId::from("MOVE"),
+			Amount(1),
+		)
+		.await?;
+	let details = match tokio::time::timeout(Duration::from_secs(30), mvt_monitoring.next()).await?
+	{
+		Some(Ok(BridgeContractEvent::Initiated(details))) => details,
+		event => panic!("Not an Initiated event: {event:?}"),
+	};
+	assert_eq!(details.time_lock, TimeLock(MOVEMENT_TIME_LOCK_SECS));
+	let bridge_transfer_id = details.bridge_transfer_id;
+	let start = Instant::now();
+	let mut action =
+		runtime.process_event((BridgeContractEvent::Initiated(details), ChainId::TWO).into())?;
+
+	// The lock on Eth keeps failing: the transfer waits for its time lock to be refunded.
+	for _ in 0..MAX_LOCK_RETRIES {
+		action = runtime.process_action_exec_error(fail(action)).unwrap().0;
+	}
+	assert!(runtime.process_action_exec_error(fail(action)).is_none());
+
+	// The refund is scheduled at the time lock read on chain, it is submitted early to be retried.
+	let time_lock = mvt_client_harness
+		.movement_client
+		.get_bridge_transfer_details_initiator(bridge_transfer_id)
+		.await?
+		.expect("Bridge transfer not found")
+		.time_lock;
+	let initiator_time_lock = TransferTimeLock::Initiator(ChainId::TWO);
+	runtime.schedule_time_lock(bridge_transfer_id, initiator_time_lock, time_lock);
+	assert!(runtime.process_expired_time_locks(time_lock.0 - 1).is_empty());
+	let mut refunds = runtime.process_expired_time_locks(time_lock.0);
+	let refund = refunds.pop().expect("No refund once the time lock expired");
+	assert!(matches!(refund.kind, TransferActionType::RefundInitiator));
+	assert_eq!(refund.chain, ChainId::TWO);
+
+	// A refund rejected on chain is retried until it is accepted.
+	let mut refund = refund;
+	loop {
+		match mvt_client_harness
+			.movement_client
+			.refund_bridge_transfer(refund.transfer_id)
+			.await
+		{
+			Ok(()) => break,
+			Err(err) => {
+				let (retried, delay) =
+					runtime.process_action_exec_error(ActionExecError(refund, err)).unwrap();
+				assert!(matches!(retried.kind, TransferActionType::RefundInitiator));
+				assert!(start.elapsed() < Duration::from_secs(MOVEMENT_TIME_LOCK_SECS * 6));
+				tokio::time::sleep(delay).await;
+				refund = retried;
+			}
+		}
+	}
+
+	let details = mvt_client_harness
+		.movement_client
+		.get_bridge_transfer_details_initiator(bridge_transfer_id)
+		.await?
+		.expect("Bridge transfer not found");
+	assert_eq!(details.state, 3, "Bridge transfer is supposed to be refunded.");
+
+	Ok(())
+}
@@ -0,0 +1,302 @@
+//! Tests the relayer loop with mock clients of the chains: the events are sent on the monitoring
+//! streams and the locks executed by the loop are recorded.
+
+use bridge_config::common::{limits::LimitsConfig, tokens::TokenRegistry};
+use bridge_service::{
+	circuit_breaker::{CircuitBreakerCommand, CircuitBreakerStatus, TripReason},
+	rest::TransferSummary,
+	run_bridge,
+	time_lock::TimeLockScheduler,
+};
+use bridge_util::{
+	chains::bridge_contracts::{
+		BridgeContract, BridgeContractEvent, BridgeContractMonitoring, BridgeContractResult,
+	},
+	types::{
+		Amount, AssetId, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
+		BridgeTransferId, ChainId, HashLock, HashLockPreImage, TimeLock,
+	},
+};
+use futures::{channel::mpsc as stream_mpsc, Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A client recording the locks, the other calls succeed without effect.
+#[derive(Clone)]
+struct MockClient {
+	locks_tx: mpsc::UnboundedSender<BridgeTransferId>,
+}
+
+#[async_trait::async_trait]
+impl BridgeContract<Vec<u8>> for MockClient {
+	async fn initiate_bridge_transfer(
+		&mut self,
+		_initiator: BridgeAddress<Vec<u8>>,
+		_recipient: BridgeAddress<Vec<u8>>,
+		_hash_lock: HashLock,
+		_asset: AssetId,
+		_amount: Amount,
+	) -> BridgeContractResult<()> {
+		Ok(())
+	}
+
+	async fn initiator_complete_bridge_transfer(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+		_secret: HashLockPreImage,
+	) -> BridgeContractResult<()> {
+		Ok(())
+	}
+
+	async fn counterparty_complete_bridge_transfer(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+		_secret: HashLockPreImage,
+	) -> BridgeContractResult<()> {
+		Ok(())
+	}
+
+	async fn refund_bridge_transfer(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		Ok(())
+	}
+
+	async fn get_bridge_transfer_details_initiator(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<Option<BridgeTransferDetails<Vec<u8>>>> {
+		Ok(None)
+	}
+
+	async fn get_bridge_transfer_details_counterparty(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<Option<BridgeTransferDetailsCounterparty<Vec<u8>>>> {
+		Ok(None)
+	}
+
+	async fn lock_bridge_transfer(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+		_hash_lock: HashLock,
+		_initiator: BridgeAddress<Vec<u8>>,
+		_recipient: BridgeAddress<Vec<u8>>,
+		_asset: AssetId,
+		_amount: Amount,
+	) -> BridgeContractResult<()> {
+		let _ = self.locks_tx.send(bridge_transfer_id);
+		Ok(())
+	}
+
+	async fn abort_bridge_transfer(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		Ok(())
+	}
+}
+
+/// The events sent by the test on a chain.
+struct MockMonitoring {
+	events_rx: stream_mpsc::UnboundedReceiver<BridgeContractResult<BridgeContractEvent<Vec<u8>>>>,
+}
+
+impl BridgeContractMonitoring for MockMonitoring {
+	type Address = Vec<u8>;
+}
+
+impl Stream for MockMonitoring {
+	type Item = BridgeContractResult<BridgeContractEvent<Vec<u8>>>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.events_rx.poll_next_unpin(cx)
+	}
+}
+
+/// The channels of a relayer loop running on mock chains.
+struct RunningBridge {
+	events_one_tx: stream_mpsc::UnboundedSender<BridgeContractResult<BridgeContractEvent<Vec<u8>>>>,
+	_events_two_tx:
+		stream_mpsc::UnboundedSender<BridgeContractResult<BridgeContractEvent<Vec<u8>>>>,
+	/// The locks executed on chain two.
+	locks_two_rx: mpsc::UnboundedReceiver<BridgeTransferId>,
+	pending_transfers_tx: mpsc::Sender<oneshot::Sender<Vec<TransferSummary>>>,
+	circuit_breaker_tx: mpsc::Sender<CircuitBreakerCommand>,
+	_healthcheck_request_tx: mpsc::Sender<oneshot::Sender<String>>,
+	_healthcheck_rx_one: mpsc::Receiver<oneshot::Sender<bool>>,
+	_healthcheck_rx_two: mpsc::Receiver<oneshot::Sender<bool>>,
+}
+
+impl RunningBridge {
+	fn start(limits: LimitsConfig) -> Self {
+		let (events_one_tx, events_rx_one) = stream_mpsc::unbounded();
+		let (events_two_tx, events_rx_two) = stream_mpsc::unbounded();
+		let (locks_one_tx, _) = mpsc::unbounded_channel();
+		let (locks_two_tx, locks_two_rx) = mpsc::unbounded_channel();
+		let (healthcheck_request_tx, healthcheck_request_rx) = mpsc::channel(10);
+		let (pending_transfers_tx, pending_transfers_rx) = mpsc::channel(10);
+		let (circuit_breaker_tx, circuit_breaker_rx) = mpsc::channel(10);
+		let (healthcheck_tx_one, healthcheck_rx_one) = mpsc::channel(10);
+		let (healthcheck_tx_two, healthcheck_rx_two) = mpsc::channel(10);
+
+		tokio::spawn(run_bridge(
+			MockClient { locks_tx: locks_one_tx },
+			MockMonitoring { events_rx: events_rx_one },
+			MockClient { locks_tx: locks_two_tx },
+			MockMonitoring { events_rx: events_rx_two },
+			healthcheck_request_rx,
+			pending_transfers_rx,
+			circuit_breaker_rx,
+			None,
+			healthcheck_tx_one,
+			healthcheck_tx_two,
+			TimeLockScheduler::new(Duration::ZERO, Duration::ZERO),
+			TokenRegistry::default(),
+			limits,
+			None,
+		));
+
+		RunningBridge {
+			events_one_tx,
+			_events_two_tx: events_two_tx,
+			locks_two_rx,
+			pending_transfers_tx,
+			circuit_breaker_tx,
+			_healthcheck_request_tx: healthcheck_request_tx,
+			_healthcheck_rx_one: healthcheck_rx_one,
+			_healthcheck_rx_two: healthcheck_rx_two,
+		}
+	}
+
+	fn initiate(&self, transfer_id: BridgeTransferId, amount: u128) -> Result<(), anyhow::Error> {
+		let details = BridgeTransferDetails {
+			bridge_transfer_id: transfer_id,
+			initiator: BridgeAddress(vec![1; 20]),
+			recipient: BridgeAddress(vec![2; 32]),
+			hash_lock: HashLock([3; 32]),
+			time_lock: TimeLock(60),
+			asset: AssetId::from("MOVE"),
+			amount: Amount(amount),
+			state: 1,
+		};
+		self.events_one_tx
+			.unbounded_send(Ok(BridgeContractEvent::Initiated(details)))
+			.map_err(|e| anyhow::anyhow!("Failed to send event: {e}"))?;
+		Ok(())
+	}
+
+	async fn next_lock(&mut self) -> Result<BridgeTransferId, anyhow::Error> {
+		tokio::time::timeout(TIMEOUT, self.locks_two_rx.recv())
+			.await?
+			.ok_or_else(|| anyhow::anyhow!("Failed to receive lock: relayer loop stopped"))
+	}
+
+	async fn command(
+		&self,
+		command: impl FnOnce(oneshot::Sender<CircuitBreakerStatus>) -> CircuitBreakerCommand,
+	) -> Result<CircuitBreakerStatus, anyhow::Error> {
+		let (tx, rx) = oneshot::channel();
+		self.circuit_breaker_tx.send(command(tx)).await?;
+		Ok(tokio::time::timeout(TIMEOUT, rx).await??)
+	}
+
+	/// Waits until the circuit breaker trips, the events and the commands are concurrent.
+	async fn wait_for_trip(&self) -> Result<CircuitBreakerStatus, anyhow::Error> {
+		for _ in 0..50 {
+			let status = self.command(CircuitBreakerCommand::Status).await?;
+			if status.trip_reason.is_some() {
+				return Ok(status);
+			}
+			tokio::time::sleep(Duration::from_millis(100)).await;
+		}
+		Err(anyhow::anyhow!("Failed to trip the circuit breaker"))
+	}
+
+	async fn pending_transfers(&self) -> Result<Vec<TransferSummary>, anyhow::Error> {
+		let (tx, rx) = oneshot::channel();
+		self.pending_transfers_tx.send(tx).await?;
+		Ok(tokio::time::timeout(TIMEOUT, rx).await??)
+	}
+}
+
+#[tokio::test]
+async fn test_run_bridge_releases_paused_locks() -> Result<(), anyhow::Error> {
+	let mut bridge =
+		RunningBridge::start(LimitsConfig { max_transfer_amount: 150, ..LimitsConfig::default() });
+	let asset = AssetId::from("MOVE");
+
+	bridge.initiate(BridgeTransferId([1; 32]), 100)?;
+	assert_eq!(bridge.next_lock().await?, BridgeTransferId([1; 32]));
+
+	// The lock of a transfer above the maximum amount is paused.
+	bridge.initiate(BridgeTransferId([2; 32]), 200)?;
+	let status = bridge.wait_for_trip().await?;
+	assert_eq!(
+		status.trip_reason,
+		Some(TripReason::MaxTransferAmount {
+			transfer_id: BridgeTransferId([2; 32]),
+			asset: asset.clone(),
+			amount: 200,
+		})
+	);
+	assert_eq!(status.paused_transfers, vec![BridgeTransferId([2; 32])]);
+	assert_eq!(status.volume(&asset, ChainId::ONE), (100, 100));
+
+	let mut pending_transfers: Vec<_> = bridge
+		.pending_transfers()
+		.await?
+		.into_iter()
+		.map(|transfer| (transfer.bridge_transfer_id, transfer.amount))
+		.collect();
+	pending_transfers.sort();
+	assert_eq!(
+		pending_transfers,
+		vec![(hex::encode([1; 32]), "100".to_string()), (hex::encode([2; 32]), "200".to_string())]
+	);
+
+	// The released lock is executed and counted in the volumes.
+	let status = bridge
+		.command(|status_tx| CircuitBreakerCommand::Reset {
+			release_paused_transfers: true,
+			status_tx,
+		})
+		.await?;
+	assert_eq!(status.trip_reason, None);
+	assert!(status.paused_transfers.is_empty());
+	assert_eq!(status.volume(&asset, ChainId::ONE), (300, 300));
+	assert_eq!(bridge.next_lock().await?, BridgeTransferId([2; 32]));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_run_bridge_drops_paused_locks() -> Result<(), anyhow::Error> {
+	let mut bridge =
+		RunningBridge::start(LimitsConfig { max_transfer_amount: 150, ..LimitsConfig::default() });
+
+	bridge.initiate(BridgeTransferId([1; 32]), 200)?;
+	bridge.wait_for_trip().await?;
+
+	// The dropped lock is not executed, the initiator is refunded once the time lock expires.
+	let status = bridge
+		.command(|status_tx| CircuitBreakerCommand::Reset {
+			release_paused_transfers: false,
+			status_tx,
+		})
+		.await?;
+	assert_eq!(status.trip_reason, None);
+	assert!(status.paused_transfers.is_empty());
+	assert_eq!(status.volume(&AssetId::from("MOVE"), ChainId::ONE), (0, 0));
+
+	// The next transfers are locked again.
+	bridge.initiate(BridgeTransferId([2; 32]), 100)?;
+	assert_eq!(bridge.next_lock().await?, BridgeTransferId([2; 32]));
+
+	Ok(())
+}
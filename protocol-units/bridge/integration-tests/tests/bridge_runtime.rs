@@ -0,0 +1,382 @@
+use bridge_config::common::{
+	limits::{AssetLimits, LimitsConfig},
+	tokens::{TokenConfig, TokenRegistry},
+};
+use bridge_service::{
+	action_retry_backoff,
+	circuit_breaker::{CircuitBreaker, TripReason},
+	time_lock::{unix_now, TimeLockScheduler, TransferTimeLock},
+	Runtime, ACTION_RETRY_MAX_BACKOFF, MAX_LOCK_RETRIES,
+};
+use bridge_util::{
+	chains::bridge_contracts::{BridgeContractError, BridgeContractEvent},
+	types::{
+		Amount, AssetId, BridgeAddress, BridgeTransferDetails, BridgeTransferId, ChainId, HashLock,
+		HashLockPreImage, LockDetails, TimeLock,
+	},
+	ActionExecError, InvalidEventError, TransferAction, TransferActionType, TransferEvent,
+};
+use std::time::Duration;
+
+/// The unix timestamp the transfers are initiated at, their time locks expire after.
+const INITIATED_AT: u64 = 1_700_000_000;
+
+fn initiated_event(transfer_id: BridgeTransferId) -> TransferEvent<Vec<u8>> {
+	initiated_asset_event(transfer_id, AssetId::from("MOVE"))
+}
+
+fn initiated_asset_event(transfer_id: BridgeTransferId, asset: AssetId) -> TransferEvent<Vec<u8>> {
+	initiated_amount_event(transfer_id, asset, Amount(100))
+}
+
+fn initiated_amount_event(
+	transfer_id: BridgeTransferId,
+	asset: AssetId,
+	amount: Amount,
+) -> TransferEvent<Vec<u8>> {
+	let details = BridgeTransferDetails {
+		bridge_transfer_id: transfer_id,
+		initiator: BridgeAddress(vec![1; 20]),
+		recipient: BridgeAddress(vec![2; 32]),
+		hash_lock: HashLock([3; 32]),
+		time_lock: TimeLock(60),
+		asset,
+		amount,
+		state: 1,
+	};
+	(BridgeContractEvent::Initiated(details), ChainId::ONE).into()
+}
+
+fn locked_event(transfer_id: BridgeTransferId) -> TransferEvent<Vec<u8>> {
+	let details = LockDetails {
+		bridge_transfer_id: transfer_id,
+		initiator: BridgeAddress(vec![1; 20]),
+		recipient: BridgeAddress(vec![2; 32]),
+		hash_lock: HashLock([3; 32]),
+		time_lock: TimeLock(30),
+		asset: AssetId::from("MOVE"),
+		amount: Amount(100),
+	};
+	(BridgeContractEvent::Locked(details), ChainId::TWO).into()
+}
+
+/// Schedules the time locks read on chain after the events of the transfer.
+fn schedule_time_locks(runtime: &mut Runtime, transfer_id: BridgeTransferId, locked: bool) {
+	let initiator = TransferTimeLock::Initiator(ChainId::ONE);
+	runtime.schedule_time_lock(transfer_id, initiator, TimeLock(INITIATED_AT + 60));
+	if locked {
+		let counterparty = TransferTimeLock::Counterparty(ChainId::TWO);
+		runtime.schedule_time_lock(transfer_id, counterparty, TimeLock(INITIATED_AT + 30));
+	}
+}
+
+fn runtime() -> Runtime {
+	runtime_with_limits(LimitsConfig::default())
+}
+
+fn runtime_with_limits(limits: LimitsConfig) -> Runtime {
+	Runtime::new(
+		None,
+		TimeLockScheduler::new(Duration::ZERO, Duration::ZERO),
+		TokenRegistry::default(),
+		CircuitBreaker::new(limits),
+	)
+}
+
+fn fail(action: TransferAction) -> ActionExecError {
+	ActionExecError(action, BridgeContractError::CallError)
+}
+
+#[test]
+fn test_action_retry_backoff() {
+	assert_eq!(action_retry_backoff(1), Duration::from_secs(1));
+	assert_eq!(action_retry_backoff(2), Duration::from_secs(2));
+	assert_eq!(action_retry_backoff(4), Duration::from_secs(8));
+	assert_eq!(action_retry_backoff(100), ACTION_RETRY_MAX_BACKOFF);
+}
+
+#[test]
+fn test_failed_lock_is_retried_then_refunded() -> Result<(), anyhow::Error> {
+	let transfer_id = BridgeTransferId([1; 32]);
+	let mut runtime = runtime();
+	let lock_action = runtime.process_event(initiated_event(transfer_id))?;
+	assert_eq!(lock_action.chain, ChainId::TWO);
+	schedule_time_locks(&mut runtime, transfer_id, false);
+
+	// The lock is retried on the counterparty chain with an increasing backoff.
+	let mut action = lock_action;
+	for retry in 1..=MAX_LOCK_RETRIES {
+		let (retried, delay) = runtime.process_action_exec_error(fail(action)).unwrap();
+		assert!(matches!(retried.kind, TransferActionType::LockBridgeTransfer { .. }));
+		assert_eq!(retried.chain, ChainId::TWO);
+		assert_eq!(delay, action_retry_backoff(retry));
+		action = retried;
+	}
+
+	// Then the initiator is refunded on the initiator chain, once its time lock expires.
+	assert!(runtime.process_action_exec_error(fail(action)).is_none());
+	assert!(runtime.process_expired_time_locks(INITIATED_AT + 59).is_empty());
+	let mut actions = runtime.process_expired_time_locks(INITIATED_AT + 60);
+	let refund = actions.pop().unwrap();
+	assert!(matches!(refund.kind, TransferActionType::RefundInitiator));
+	assert_eq!(refund.chain, ChainId::ONE);
+
+	// The refund is retried until the time lock expires.
+	let (retried, delay) = runtime.process_action_exec_error(fail(refund)).unwrap();
+	assert!(matches!(retried.kind, TransferActionType::RefundInitiator));
+	assert_eq!(delay, action_retry_backoff(1));
+
+	Ok(())
+}
+
+#[test]
+fn test_failed_completion_is_retried() -> Result<(), anyhow::Error> {
+	let transfer_id = BridgeTransferId([2; 32]);
+	let mut runtime = runtime();
+	runtime.process_event(initiated_event(transfer_id))?;
+	runtime.process_event(locked_event(transfer_id))?;
+	let complete_action = runtime.process_event(TransferEvent {
+		chain: ChainId::TWO,
+		contract_event: BridgeContractEvent::CounterPartyCompleted(
+			transfer_id,
+			HashLockPreImage([4; 32]),
+		),
+	})?;
+	assert_eq!(complete_action.chain, ChainId::ONE);
+
+	// The completion is never abandoned, its backoff is capped and its retries do not wait more.
+	let kind = TransferActionType::WaitAndCompleteInitiator(30, HashLockPreImage([4; 32]));
+	let mut action = TransferAction { kind, ..complete_action };
+	for _ in 0..(MAX_LOCK_RETRIES * 4) {
+		let (retried, delay) = runtime.process_action_exec_error(fail(action)).unwrap();
+		assert!(matches!(retried.kind, TransferActionType::WaitAndCompleteInitiator(0, _)));
+		assert!(delay <= ACTION_RETRY_MAX_BACKOFF);
+		action = retried;
+	}
+
+	Ok(())
+}
+
+#[test]
+fn test_expired_time_locks_abort_then_refund() -> Result<(), anyhow::Error> {
+	let transfer_id = BridgeTransferId([3; 32]);
+	let mut runtime = runtime();
+	runtime.process_event(initiated_event(transfer_id))?;
+	runtime.process_event(locked_event(transfer_id))?;
+	schedule_time_locks(&mut runtime, transfer_id, true);
+
+	// The counterparty lock expires first and is aborted.
+	assert!(runtime.process_expired_time_locks(INITIATED_AT + 29).is_empty());
+	let actions = runtime.process_expired_time_locks(INITIATED_AT + 30);
+	assert_eq!(actions.len(), 1);
+	assert!(matches!(actions[0].kind, TransferActionType::AbortCounterparty));
+	assert_eq!(actions[0].chain, ChainId::TWO);
+
+	// Then the initiator is refunded.
+	let actions = runtime.process_expired_time_locks(INITIATED_AT + 60);
+	assert_eq!(actions.len(), 1);
+	assert!(matches!(actions[0].kind, TransferActionType::RefundInitiator));
+	assert_eq!(actions[0].chain, ChainId::ONE);
+
+	// A completed transfer is never refunded.
+	let transfer_id = BridgeTransferId([4; 32]);
+	runtime.process_event(initiated_event(transfer_id))?;
+	runtime.process_event(locked_event(transfer_id))?;
+	schedule_time_locks(&mut runtime, transfer_id, true);
+	runtime.process_event(TransferEvent {
+		chain: ChainId::TWO,
+		contract_event: BridgeContractEvent::CounterPartyCompleted(
+			transfer_id,
+			HashLockPreImage([4; 32]),
+		),
+	})?;
+	assert!(runtime.process_expired_time_locks(INITIATED_AT + 120).is_empty());
+
+	// The time lock of a completed transfer read late is not scheduled.
+	schedule_time_locks(&mut runtime, transfer_id, true);
+	runtime.process_event(TransferEvent {
+		chain: ChainId::ONE,
+		contract_event: BridgeContractEvent::InitiatorCompleted(transfer_id),
+	})?;
+	schedule_time_locks(&mut runtime, transfer_id, true);
+	assert!(runtime.process_expired_time_locks(INITIATED_AT + 120).is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn test_unregistered_asset_is_rejected() -> Result<(), anyhow::Error> {
+	let mut runtime = runtime();
+	let result = runtime
+		.process_event(initiated_asset_event(BridgeTransferId([1; 32]), AssetId::from("UNKNOWN")));
+	assert!(matches!(result, Err(InvalidEventError::UnsupportedAsset(_))));
+
+	let lock_action = runtime.process_event(initiated_event(BridgeTransferId([2; 32])))?;
+	assert!(matches!(lock_action.kind, TransferActionType::LockBridgeTransfer { .. }));
+	Ok(())
+}
+
+/// Registers WETH, with 18 decimals on Ethereum and 8 decimals on Movement.
+fn weth_runtime(limits: LimitsConfig) -> Runtime {
+	let mut tokens = TokenRegistry::default();
+	tokens.tokens.push(TokenConfig {
+		asset_id: "WETH".to_string(),
+		eth_token_contract: "0x0000000000000000000000000000000000000001".to_string(),
+		eth_initiator_contract: Some("0x0000000000000000000000000000000000000002".to_string()),
+		eth_counterparty_contract: Some("0x0000000000000000000000000000000000000003".to_string()),
+		eth_decimals: 18,
+		mvt_fa_metadata_address: "0xb".to_string(),
+		mvt_bridge_address: Some("0xc".to_string()),
+		mvt_decimals: 8,
+	});
+	Runtime::new(
+		None,
+		TimeLockScheduler::new(Duration::ZERO, Duration::ZERO),
+		tokens,
+		CircuitBreaker::new(limits),
+	)
+}
+
+#[test]
+fn test_lock_amount_is_converted_to_counterparty_decimals() -> Result<(), anyhow::Error> {
+	let mut runtime = weth_runtime(LimitsConfig::default());
+
+	let event = initiated_amount_event(
+		BridgeTransferId([1; 32]),
+		AssetId::from("WETH"),
+		Amount(5 * 10u128.pow(10)),
+	);
+	let lock_action = runtime.process_event(event)?;
+	match lock_action.kind {
+		TransferActionType::LockBridgeTransfer { amount, .. } => assert_eq!(amount, Amount(5)),
+		kind => panic!("Unexpected action {}", kind),
+	}
+
+	// An amount below the precision of the fungible asset can't be locked on Movement.
+	let event = initiated_amount_event(
+		BridgeTransferId([2; 32]),
+		AssetId::from("WETH"),
+		Amount(5 * 10u128.pow(10) + 1),
+	);
+	assert!(matches!(runtime.process_event(event), Err(InvalidEventError::BadEvent(_))));
+
+	Ok(())
+}
+
+#[test]
+fn test_circuit_breaker_pauses_locks_until_reset() -> Result<(), anyhow::Error> {
+	let mut runtime =
+		runtime_with_limits(LimitsConfig { eth_hourly_volume_cap: 150, ..LimitsConfig::default() });
+	let lock_action = runtime.process_event(initiated_event(BridgeTransferId([1; 32])))?;
+	assert!(matches!(lock_action.kind, TransferActionType::LockBridgeTransfer { .. }));
+
+	// The second transfer exceeds the hourly volume cap, its lock and the next ones are paused.
+	for id in [2, 3] {
+		let action = runtime.process_event(initiated_event(BridgeTransferId([id; 32])))?;
+		assert!(matches!(action.kind, TransferActionType::NoAction));
+	}
+	let status = runtime.circuit_breaker_status(unix_now());
+	let asset = AssetId::from("MOVE");
+	assert_eq!(status.trip_reason, Some(TripReason::HourlyVolumeCap(asset.clone(), ChainId::ONE)));
+	assert_eq!(status.paused_transfers, vec![BridgeTransferId([2; 32]), BridgeTransferId([3; 32])]);
+	assert_eq!(status.volume(&asset, ChainId::ONE), (100, 100));
+
+	// The locks of the transfers refunded while paused are not released.
+	for id in [1, 2, 3] {
+		schedule_time_locks(&mut runtime, BridgeTransferId([id; 32]), false);
+	}
+	runtime.process_expired_time_locks(INITIATED_AT + 60);
+	assert!(runtime.reset_circuit_breaker(true, unix_now()).is_empty());
+	let status = runtime.circuit_breaker_status(unix_now());
+	assert_eq!(status.trip_reason, None);
+	assert!(status.paused_transfers.is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn test_circuit_breaker_releases_paused_locks() -> Result<(), anyhow::Error> {
+	let mut runtime =
+		runtime_with_limits(LimitsConfig { max_transfer_amount: 50, ..LimitsConfig::default() });
+	let transfer_id = BridgeTransferId([1; 32]);
+	let action = runtime.process_event(initiated_event(transfer_id))?;
+	assert!(matches!(action.kind, TransferActionType::NoAction));
+
+	let released = runtime.reset_circuit_breaker(true, unix_now());
+	assert_eq!(released.len(), 1);
+	assert_eq!(released[0].transfer_id, transfer_id);
+	assert_eq!(released[0].chain, ChainId::TWO);
+	assert!(matches!(released[0].kind, TransferActionType::LockBridgeTransfer { .. }));
+	// The released lock is counted in the volumes.
+	let status = runtime.circuit_breaker_status(unix_now());
+	assert_eq!(status.volume(&AssetId::from("MOVE"), ChainId::ONE), (100, 100));
+
+	// The dropped locks are not executed.
+	let action = runtime.process_event(initiated_event(BridgeTransferId([2; 32])))?;
+	assert!(matches!(action.kind, TransferActionType::NoAction));
+	assert!(runtime.reset_circuit_breaker(false, unix_now()).is_empty());
+	assert!(runtime.circuit_breaker_status(unix_now()).paused_transfers.is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn test_invalid_events_trip_circuit_breaker() -> Result<(), anyhow::Error> {
+	let mut runtime =
+		runtime_with_limits(LimitsConfig { max_validation_failures: 2, ..LimitsConfig::default() });
+	for id in [1, 2] {
+		let result = runtime.process_event(locked_event(BridgeTransferId([id; 32])));
+		assert!(matches!(result, Err(InvalidEventError::StateNotFound)));
+	}
+	let action = runtime.process_event(initiated_event(BridgeTransferId([3; 32])))?;
+	assert!(matches!(action.kind, TransferActionType::NoAction));
+	assert_eq!(
+		runtime.circuit_breaker_status(unix_now()).trip_reason,
+		Some(TripReason::ValidationFailures(2))
+	);
+	Ok(())
+}
+
+#[test]
+fn test_circuit_breaker_limits_each_asset() -> Result<(), anyhow::Error> {
+	let one_weth = 10u128.pow(18);
+	let mut runtime = weth_runtime(LimitsConfig {
+		max_transfer_amount: 1_000,
+		eth_hourly_volume_cap: 1_000,
+		assets: vec![AssetLimits {
+			asset_id: "WETH".to_string(),
+			max_transfer_amount: 2 * one_weth,
+			eth_hourly_volume_cap: 3 * one_weth,
+			eth_daily_volume_cap: 3 * one_weth,
+			mvt_hourly_volume_cap: 3 * one_weth,
+			mvt_daily_volume_cap: 3 * one_weth,
+		}],
+		..LimitsConfig::default()
+	});
+	let weth = AssetId::from("WETH");
+	let move_asset = AssetId::from("MOVE");
+
+	// The amounts of WETH are limited in its own decimals, not by the limits of MOVE.
+	for id in [1, 2] {
+		let event =
+			initiated_amount_event(BridgeTransferId([id; 32]), weth.clone(), Amount(one_weth));
+		let action = runtime.process_event(event)?;
+		assert!(matches!(action.kind, TransferActionType::LockBridgeTransfer { .. }));
+	}
+	let action = runtime.process_event(initiated_event(BridgeTransferId([3; 32])))?;
+	assert!(matches!(action.kind, TransferActionType::LockBridgeTransfer { .. }));
+	let status = runtime.circuit_breaker_status(unix_now());
+	assert_eq!(status.volume(&weth, ChainId::ONE), (2 * one_weth, 2 * one_weth));
+	assert_eq!(status.volume(&move_asset, ChainId::ONE), (100, 100));
+
+	let event =
+		initiated_amount_event(BridgeTransferId([4; 32]), weth.clone(), Amount(2 * one_weth));
+	let action = runtime.process_event(event)?;
+	assert!(matches!(action.kind, TransferActionType::NoAction));
+	assert_eq!(
+		runtime.circuit_breaker_status(unix_now()).trip_reason,
+		Some(TripReason::HourlyVolumeCap(weth, ChainId::ONE))
+	);
+
+	Ok(())
+}
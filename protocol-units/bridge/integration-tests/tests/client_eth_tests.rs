@@ -6,7 +6,9 @@ use bridge_integration_tests::HarnessMvtClient;
 use bridge_integration_tests::TestHarness;
 use bridge_service::chains::bridge_contracts::{BridgeContract, BridgeContractEvent};
 use bridge_service::chains::ethereum::{event_monitoring::EthMonitoring, types::EthAddress};
-use bridge_service::types::{Amount, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage};
+use bridge_service::types::{
+	Amount, AssetId, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage,
+};
 use futures::StreamExt;
 use std::str::FromStr;
 use tokio::{self};
@@ -28,6 +30,7 @@ async fn test_eth_client_counterparty_complete_transfer() {
 		hash_lock,
 		BridgeAddress(vec![3; 32]),
 		BridgeAddress(EthAddress(HarnessEthClient::get_recipeint_address(&config))),
+		AssetId(config.eth.asset.clone()),
 		amount,
 	)
 	.await;
@@ -61,6 +64,7 @@ async fn test_eth_client_lock_transfer() -> Result<(), anyhow::Error> {
 			hash_lock,
 			BridgeAddress(vec![3; 32]),
 			BridgeAddress(EthAddress(HarnessEthClient::get_recipeint_address(&config))),
+			AssetId(config.eth.asset.clone()),
 			amount,
 		)
 		.await;
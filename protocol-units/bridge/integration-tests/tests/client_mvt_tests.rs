@@ -11,7 +11,7 @@ use bridge_service::{
 		bridge_contracts::BridgeContract,
 		movement::utils::{MovementAddress, MovementHash},
 	},
-	types::{Amount, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage},
+	types::{Amount, AssetId, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage},
 };
 use chrono::Utc;
 use futures::StreamExt;
@@ -52,6 +52,7 @@ async fn test_movement_client_counterparty_complete_transfer() -> Result<(), any
 			hash_lock,
 			BridgeAddress(initiator.clone()),
 			BridgeAddress(MovementAddress(recipient.clone())),
+			AssetId::from("MOVE"),
 			amount,
 		)
 		.await
@@ -92,7 +93,7 @@ async fn test_movement_client_counterparty_complete_transfer() -> Result<(), any
 	assert_eq!(details.hash_lock.0, hash_lock.0);
 	assert_eq!(&details.initiator.0, &initiator, "Initiator address does not match");
 	assert_eq!(details.recipient.0, MovementAddress(recipient));
-	assert_eq!(details.amount.0, *amount);
+	assert_eq!(details.amount, amount);
 	assert_eq!(details.state, 2, "Bridge transfer is supposed to be completed but it's not.");
 
 	Ok(())
@@ -115,7 +116,8 @@ async fn test_movement_client_initiate_transfer() -> Result<(), anyhow::Error> {
 				BridgeAddress(MovementAddress(args.initiator.0)),
 				BridgeAddress(args.recipient.clone()),
 				HashLock(args.hash_lock.0),
-				Amount(args.amount),
+				AssetId::from("MOVE"),
+				args.amount.into(),
 			)
 			.await?;
 
@@ -198,7 +200,8 @@ async fn test_movement_client_abort_transfer() -> Result<(), anyhow::Error> {
 			HashLock(args.hash_lock.0),
 			BridgeAddress(args.initiator.clone()),
 			BridgeAddress(args.recipient.clone().into()),
-			Amount(args.amount),
+			AssetId::from("MOVE"),
+			args.amount.into(),
 		)
 		.await
 		.expect("Failed to lock bridge transfer");
@@ -238,7 +241,7 @@ async fn test_movement_client_abort_transfer() -> Result<(), anyhow::Error> {
 	assert_eq!(details.hash_lock.0, args.hash_lock.0);
 	assert_eq!(&details.initiator.0, &args.initiator, "Initiator address does not match");
 	assert_eq!(details.recipient.0, args.recipient);
-	assert_eq!(details.amount.0, args.amount);
+	assert_eq!(details.amount, Amount::from(args.amount));
 	assert_eq!(details.state, 3, "Bridge transfer is supposed to be cancelled but it's not.");
 
 	Ok(())
@@ -258,7 +261,8 @@ async fn test_movement_client_initiator_complete_transfer() -> Result<(), anyhow
 			BridgeAddress(MovementAddress(args.initiator.0)),
 			BridgeAddress(args.recipient.clone()),
 			HashLock(args.hash_lock.0),
-			Amount(args.amount),
+			AssetId::from("MOVE"),
+			args.amount.into(),
 		)
 		.await?;
 
@@ -333,7 +337,8 @@ async fn test_movement_client_refund_transfer() -> Result<(), anyhow::Error> {
 			BridgeAddress(MovementAddress(args.initiator.0)),
 			BridgeAddress(args.recipient.clone()),
 			HashLock(args.hash_lock.0),
-			Amount(args.amount),
+			AssetId::from("MOVE"),
+			args.amount.into(),
 		)
 		.await?;
 
@@ -0,0 +1,83 @@
+//! Restarts the Eth monitoring while an event waits for its confirmation. The test has its own
+//! binary: the monitoring of the other tests would save the cursor in the same store file.
+use alloy::primitives::keccak256;
+use bridge_integration_tests::{HarnessEthClient, HarnessMvtClient, TestHarness};
+use bridge_service::{
+	chains::{
+		bridge_contracts::BridgeContractEvent, ethereum::event_monitoring::EthMonitoring,
+		movement::utils::MovementAddress,
+	},
+	types::{Amount, HashLock, HashLockPreImage},
+};
+use futures::StreamExt;
+use std::time::{Duration, Instant};
+
+/// Gets the hash lock of the transfers initiated before the timeout.
+async fn initiated_transfers(
+	monitoring: &mut EthMonitoring,
+	timeout: Duration,
+) -> Result<Vec<HashLock>, anyhow::Error> {
+	let deadline = Instant::now() + timeout;
+	let mut hash_locks = Vec::new();
+	while let Ok(event) =
+		tokio::time::timeout(deadline.saturating_duration_since(Instant::now()), monitoring.next())
+			.await
+	{
+		match event {
+			Some(Ok(BridgeContractEvent::Initiated(details))) => hash_locks.push(details.hash_lock),
+			Some(Ok(_)) => (),
+			Some(Err(err)) => tracing::warn!("Eth monitoring error: {err:?}"),
+			None => anyhow::bail!("Eth monitoring stopped"),
+		}
+	}
+	Ok(hash_locks)
+}
+
+/// Initiates a transfer on Eth, each of its transactions is mined in a block.
+async fn initiate_transfer(
+	eth_client_harness: &HarnessEthClient,
+	config: &bridge_config::Config,
+) -> Result<HashLock, anyhow::Error> {
+	let recipient = HarnessMvtClient::gen_aptos_account();
+	let hash_lock = HashLock(From::from(keccak256(HashLockPreImage::random())));
+	eth_client_harness
+		.initiate_eth_bridge_transfer(
+			config,
+			HarnessEthClient::get_initiator_private_key(config),
+			MovementAddress(recipient.address()),
+			hash_lock,
+			Amount(1),
+		)
+		.await?;
+	Ok(hash_lock)
+}
+
+#[tokio::test]
+async fn test_buffered_event_confirmed_after_restart() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let (eth_client_harness, mut config) = TestHarness::new_only_eth().await?;
+	// The test chain mines a block per transaction.
+	config.eth.eth_confirmation_depth = 2;
+	config.eth.eth_use_finalized_block = false;
+
+	let (_health_tx, health_rx) = tokio::sync::mpsc::channel(10);
+	let mut monitoring = EthMonitoring::build(&config.eth, health_rx).await?;
+	initiated_transfers(&mut monitoring, Duration::from_secs(3)).await?;
+
+	// The Initiated event waits for two more blocks when the relayer stops.
+	let hash_lock = initiate_transfer(&eth_client_harness, &config).await?;
+	assert!(!initiated_transfers(&mut monitoring, Duration::from_secs(3))
+		.await?
+		.contains(&hash_lock));
+	drop(monitoring);
+
+	// Its block is confirmed while the relayer is down.
+	initiate_transfer(&eth_client_harness, &config).await?;
+
+	let (_health_tx, health_rx) = tokio::sync::mpsc::channel(10);
+	let mut restarted = EthMonitoring::build(&config.eth, health_rx).await?;
+	let hash_locks = initiated_transfers(&mut restarted, Duration::from_secs(10)).await?;
+	assert!(hash_locks.contains(&hash_lock), "The buffered Initiated event was lost on restart");
+
+	Ok(())
+}
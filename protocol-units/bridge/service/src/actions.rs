@@ -1,4 +1,4 @@
-use crate::chains::movement::utils as movement_utils;
+use crate::chains::movement::gas_drop::GasDropper;
 use bridge_util::chains::bridge_contracts::BridgeContract;
 use bridge_util::chains::bridge_contracts::BridgeContractError;
 use bridge_util::types::BridgeAddress;
@@ -11,6 +11,7 @@ use std::pin::Pin;
 pub fn process_action<A>(
 	action: TransferAction,
 	mut client: impl BridgeContract<A> + 'static,
+	gas_dropper: Option<GasDropper>,
 ) -> Option<Pin<Box<dyn Future<Output = Result<(), ActionExecError>> + Send>>>
 where
 	A: Clone + Send + TryFrom<Vec<u8>>,
@@ -22,12 +23,14 @@ where
 			hash_lock,
 			initiator,
 			recipient,
+			asset,
 			amount,
 		} => {
 			let future = async move {
-				if recipient.0.len() == 32 {
-					if let Err(e) = movement_utils::fund_recipient(&recipient).await {
-						return Err(ActionExecError(action.clone(), e));
+				// The gas drop is a courtesy to the recipient; the transfer doesn't depend on it.
+				if let Some(gas_dropper) = gas_dropper {
+					if let Err(err) = gas_dropper.drop_gas(&recipient).await {
+						tracing::warn!("Gas drop to recipient {recipient:?} failed: {err}");
 					}
 				}
 				tracing::info!("Before client.lock_bridge_transfer");
@@ -42,6 +45,7 @@ where
 								BridgeContractError::BadAddressEncoding("lock bridge transfer fail to convert recipient address to vec<u8>".to_string()),
 							)
 						})?),
+						asset,
 						amount,
 					)
 					.await
@@ -52,7 +56,7 @@ where
 		TransferActionType::WaitAndCompleteInitiator(wait_time_sec, secret) => {
 			let future = async move {
 				if wait_time_sec != 0 {
-					let _ = tokio::time::sleep(tokio::time::Duration::from_secs(wait_time_sec));
+					tokio::time::sleep(tokio::time::Duration::from_secs(wait_time_sec)).await;
 				}
 				client
 					.initiator_complete_bridge_transfer(action.transfer_id, secret)
@@ -61,7 +65,24 @@ where
 			};
 			Some(Box::pin(future))
 		}
-		TransferActionType::RefundInitiator => None,
+		TransferActionType::RefundInitiator => {
+			let future = async move {
+				client
+					.refund_bridge_transfer(action.transfer_id)
+					.await
+					.map_err(|err| ActionExecError(action, err))
+			};
+			Some(Box::pin(future))
+		}
+		TransferActionType::AbortCounterparty => {
+			let future = async move {
+				client
+					.abort_bridge_transfer(action.transfer_id)
+					.await
+					.map_err(|err| ActionExecError(action, err))
+			};
+			Some(Box::pin(future))
+		}
 		TransferActionType::TransferDone => None,
 		TransferActionType::NoAction => None,
 	}
@@ -1,8 +1,9 @@
 use super::types::{
-	AlloyProvider, AssetKind, AtomicBridgeCounterpartyMOVE, AtomicBridgeInitiatorMOVE,
-	CounterpartyContract, EthAddress, InitiatorContract,
+	AlloyProvider, AtomicBridgeCounterpartyMOVE, AtomicBridgeInitiatorMOVE, EthAddress,
+	InitiatorContract, MockMOVEToken,
 };
 use super::utils::{calculate_storage_slot, send_transaction, send_transaction_rules};
+use crate::grpc::BridgeGrpcService;
 use alloy::{
 	network::EthereumWallet,
 	primitives::{Address, FixedBytes, U256},
@@ -12,11 +13,11 @@ use alloy::{
 };
 use alloy_primitives::Uint;
 use alloy_rlp::Decodable;
-use bridge_config::common::eth::EthConfig;
+use bridge_config::common::{eth::EthConfig, tokens::TokenRegistry};
 use bridge_grpc::bridge_server::BridgeServer;
 use bridge_util::chains::bridge_contracts::{BridgeContractError, BridgeContractResult};
 use bridge_util::types::{
-	Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
+	Amount, AssetId, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
 	BridgeTransferId, HashLock, HashLockPreImage, TimeLock,
 };
 use std::{fmt::Debug, net::SocketAddr};
@@ -34,8 +35,43 @@ pub struct Config {
 	pub movetoken_contract: Address,
 	pub gas_limit: u128,
 	pub transaction_send_retries: u32,
-	pub asset: AssetKind,
+	/// The bridge contracts of each ERC-20, the first ones are the contracts of the Eth config
+	pub tokens: Vec<TokenContracts>,
 }
+
+/// The bridge contracts deployed for an ERC-20 token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenContracts {
+	pub asset: AssetId,
+	pub token_contract: Address,
+	pub initiator_contract: Address,
+	pub counterparty_contract: Address,
+}
+
+impl Config {
+	/// Adds the bridge contracts of the ERC-20 tokens of the registry.
+	/// A token without contracts must be the asset of the Eth config contracts.
+	pub fn with_tokens(mut self, tokens: &TokenRegistry) -> Result<Self, anyhow::Error> {
+		for token in &tokens.tokens {
+			let asset = AssetId(token.asset_id.clone());
+			match (&token.eth_initiator_contract, &token.eth_counterparty_contract) {
+				(Some(initiator_contract), Some(counterparty_contract)) => {
+					self.tokens.retain(|contracts| contracts.asset != asset);
+					self.tokens.push(TokenContracts {
+						asset,
+						token_contract: token.eth_token_contract.parse()?,
+						initiator_contract: initiator_contract.parse()?,
+						counterparty_contract: counterparty_contract.parse()?,
+					});
+				}
+				(None, None) if self.tokens.iter().any(|contracts| contracts.asset == asset) => (),
+				_ => anyhow::bail!("Missing Eth bridge contracts of asset {asset}"),
+			}
+		}
+		Ok(self)
+	}
+}
+
 impl TryFrom<&EthConfig> for Config {
 	type Error = anyhow::Error;
 
@@ -51,7 +87,12 @@ impl TryFrom<&EthConfig> for Config {
 			movetoken_contract: conf.eth_move_token_contract.parse()?,
 			gas_limit: conf.gas_limit.into(),
 			transaction_send_retries: conf.transaction_send_retries,
-			asset: conf.asset.clone().into(),
+			tokens: vec![TokenContracts {
+				asset: AssetId(conf.asset.clone()),
+				token_contract: conf.eth_move_token_contract.parse()?,
+				initiator_contract: conf.eth_initiator_contract.parse()?,
+				counterparty_contract: conf.eth_counterparty_contract.parse()?,
+			}],
 		})
 	}
 }
@@ -80,14 +121,25 @@ struct EthBridgeTransferDetailsCounterparty {
 pub struct EthClient {
 	pub rpc_provider: AlloyProvider,
 	initiator_contract: InitiatorContract,
-	counterparty_contract: CounterpartyContract,
 	pub config: Config,
 	signer_address: Address,
 }
 
 impl EthClient {
+	/// Builds a client of the bridge contracts of the Eth config.
 	pub async fn new(config: &EthConfig) -> Result<Self, anyhow::Error> {
-		let config: Config = config.try_into()?;
+		Self::build(config.try_into()?).await
+	}
+
+	/// Builds a client of the bridge contracts of the Eth config and of the registered tokens.
+	pub async fn new_with_tokens(
+		config: &EthConfig,
+		tokens: &TokenRegistry,
+	) -> Result<Self, anyhow::Error> {
+		Self::build(Config::try_from(config)?.with_tokens(tokens)?).await
+	}
+
+	async fn build(config: Config) -> Result<Self, anyhow::Error> {
 		let signer_address = config.signer_private_key.address();
 		let rpc_provider = ProviderBuilder::new()
 			.with_recommended_fillers()
@@ -97,27 +149,19 @@ impl EthClient {
 
 		let initiator_contract =
 			AtomicBridgeInitiatorMOVE::new(config.initiator_contract, rpc_provider.clone());
-		let counterparty_contract =
-			AtomicBridgeCounterpartyMOVE::new(config.counterparty_contract, rpc_provider.clone());
-
-		Ok(EthClient {
-			rpc_provider,
-			initiator_contract,
-			counterparty_contract,
-			config: config.clone(),
-			signer_address,
-		})
+
+		Ok(EthClient { rpc_provider, initiator_contract, config: config.clone(), signer_address })
 	}
 
 	/// Start the gRPC server
-	/// internally this passes a cloned self `EthClient` as the service.
+	/// internally this serves the Ethereum transfer details of a cloned self `EthClient`.
 	pub async fn serve_grpc(
 		&self,
 		grpc_addr: SocketAddr,
 	) -> Result<(), Box<dyn std::error::Error>> {
 		tracing::info!("Starting gRPC server at: {:?}", grpc_addr);
 		Server::builder()
-			.add_service(BridgeServer::new(self.clone()))
+			.add_service(BridgeServer::new(BridgeGrpcService::eth_only(self.clone())))
 			.serve(grpc_addr)
 			.await?;
 
@@ -173,6 +217,141 @@ impl EthClient {
 	pub fn counterparty_contract_address(&self) -> Address {
 		self.config.counterparty_contract
 	}
+
+	/// Approves the initiator contract of an asset to escrow an amount of its ERC-20 from the
+	/// signer, before the signer initiates a transfer.
+	pub async fn approve_initiator_contract(
+		&self,
+		asset: &AssetId,
+		amount: Amount,
+	) -> BridgeContractResult<()> {
+		let contracts = self.token_contracts(asset)?;
+		let token = MockMOVEToken::new(contracts.token_contract, self.rpc_provider.clone());
+		let call = token
+			.approve(contracts.initiator_contract, U256::from(amount.0))
+			.from(self.signer_address);
+		send_transaction(
+			call,
+			self.signer_address,
+			&send_transaction_rules(),
+			self.config.transaction_send_retries,
+			self.config.gas_limit,
+		)
+		.await
+		.map_err(|e| {
+			BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+		})?;
+		Ok(())
+	}
+
+	/// Gets the bridge contracts of the ERC-20 of an asset.
+	fn token_contracts(&self, asset: &AssetId) -> BridgeContractResult<&TokenContracts> {
+		self.config
+			.tokens
+			.iter()
+			.find(|contracts| contracts.asset == *asset)
+			.ok_or_else(|| BridgeContractError::UnsupportedAsset(asset.clone()))
+	}
+
+	/// Reads the storage slot of a transfer in a bridge contract.
+	async fn read_transfer_storage(
+		&self,
+		contract: Address,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<[u8; 32]> {
+		let mapping_slot = U256::from(0); // the mapping is the zeroth slot in the contract
+		let storage_slot = calculate_storage_slot(bridge_transfer_id.0, mapping_slot);
+		let storage: U256 =
+			self.rpc_provider.get_storage_at(contract, storage_slot).await.map_err(|_| {
+				BridgeContractError::GenericError("could not find storage".to_string())
+			})?;
+		Ok(storage.to_be_bytes::<32>())
+	}
+
+	/// Finds the initiator contract of a transfer among the contracts of the tokens.
+	async fn find_initiator_transfer(
+		&self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<(&TokenContracts, EthBridgeTransferDetails)> {
+		let mut last_err = BridgeContractError::GenericError("no bridge contract".to_string());
+		for contracts in &self.config.tokens {
+			// The other contracts are searched if a contract can't be read.
+			let storage_bytes = match self
+				.read_transfer_storage(contracts.initiator_contract, bridge_transfer_id)
+				.await
+			{
+				Ok(storage_bytes) => storage_bytes,
+				Err(err) => {
+					last_err = err;
+					continue;
+				}
+			};
+			match EthBridgeTransferDetails::decode(&mut &storage_bytes[..]) {
+				Ok(details) => return Ok((contracts, details)),
+				Err(_) => {
+					last_err =
+						BridgeContractError::GenericError("could not decode storage".to_string())
+				}
+			}
+		}
+		Err(last_err)
+	}
+
+	/// Finds the counterparty contract of a transfer among the contracts of the tokens.
+	async fn find_counterparty_transfer(
+		&self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<(&TokenContracts, EthBridgeTransferDetailsCounterparty)> {
+		let mut last_err = BridgeContractError::GenericError("no bridge contract".to_string());
+		for contracts in &self.config.tokens {
+			let storage_bytes = match self
+				.read_transfer_storage(contracts.counterparty_contract, bridge_transfer_id)
+				.await
+			{
+				Ok(storage_bytes) => storage_bytes,
+				Err(err) => {
+					last_err = err;
+					continue;
+				}
+			};
+			match EthBridgeTransferDetailsCounterparty::decode(&mut &storage_bytes[..]) {
+				Ok(details) => return Ok((contracts, details)),
+				Err(_) => {
+					last_err =
+						BridgeContractError::GenericError("could not decode storage".to_string())
+				}
+			}
+		}
+		Err(last_err)
+	}
+
+	/// Gets the initiator contract holding a transfer, without reading it if there's one token.
+	async fn initiator_contract_of(
+		&self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<Address> {
+		match self.config.tokens.as_slice() {
+			[contracts] => Ok(contracts.initiator_contract),
+			_ => self
+				.find_initiator_transfer(bridge_transfer_id)
+				.await
+				.map(|(contracts, _)| contracts.initiator_contract),
+		}
+	}
+
+	/// Gets the counterparty contract holding a transfer, without reading it if there's one token.
+	async fn counterparty_contract_of(
+		&self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<Address> {
+		match self.config.tokens.as_slice() {
+			[contracts] => Ok(contracts.counterparty_contract),
+			_ => self
+				.find_counterparty_transfer(bridge_transfer_id)
+				.await
+				.map(|(contracts, _)| contracts.counterparty_contract),
+		}
+	}
 }
 
 #[async_trait::async_trait]
@@ -185,17 +364,17 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		initiator: BridgeAddress<EthAddress>,
 		recipient: BridgeAddress<Vec<u8>>,
 		hash_lock: HashLock,
-		amount: Amount, // the ETH amount
+		asset: AssetId,
+		amount: Amount, // the ERC-20 amount
 	) -> BridgeContractResult<()> {
+		let initiator_contract = self.token_contracts(&asset)?.initiator_contract;
 		let recipient_bytes: [u8; 32] = recipient.0.try_into().map_err(|e| {
 			BridgeContractError::ConversionFailed(format!(
 				"Failed to convert in [u8; 32] recipient: {e:?}"
 			))
 		})?;
-		let contract = AtomicBridgeInitiatorMOVE::new(
-			self.config.initiator_contract,
-			self.rpc_provider.clone(),
-		);
+		let contract =
+			AtomicBridgeInitiatorMOVE::new(initiator_contract, self.rpc_provider.clone());
 		let call = contract
 			.initiateBridgeTransfer(
 				U256::from(amount.0),
@@ -233,10 +412,9 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 			.try_into()
 			.map_err(|_| generic_error("Could not convert pre-image to [u8; 32]"))?;
 		info! {"Pre-image: {:?}", pre_image};
-		let contract = AtomicBridgeInitiatorMOVE::new(
-			self.config.initiator_contract,
-			self.rpc_provider.clone(),
-		);
+		let initiator_contract = self.initiator_contract_of(bridge_transfer_id).await?;
+		let contract =
+			AtomicBridgeInitiatorMOVE::new(initiator_contract, self.rpc_provider.clone());
 		let call = contract
 			.completeBridgeTransfer(FixedBytes(bridge_transfer_id.0), FixedBytes(pre_image));
 		send_transaction(
@@ -269,10 +447,9 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 			.try_into()
 			.map_err(|_| generic_error("Could not convert pre-image to [u8; 32]"))?;
 
-		let contract = AtomicBridgeCounterpartyMOVE::new(
-			self.config.counterparty_contract,
-			self.rpc_provider.clone(),
-		);
+		let counterparty_contract = self.counterparty_contract_of(bridge_transfer_id).await?;
+		let contract =
+			AtomicBridgeCounterpartyMOVE::new(counterparty_contract, self.rpc_provider.clone());
 
 		let call = contract
 			.completeBridgeTransfer(FixedBytes(bridge_transfer_id.0), FixedBytes(pre_image));
@@ -295,10 +472,9 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<()> {
-		let contract = AtomicBridgeInitiatorMOVE::new(
-			self.config.initiator_contract,
-			self.rpc_provider.clone(),
-		);
+		let initiator_contract = self.initiator_contract_of(bridge_transfer_id).await?;
+		let contract =
+			AtomicBridgeInitiatorMOVE::new(initiator_contract, self.rpc_provider.clone());
 		tracing::info!("Bridge transfer ID: {:?}", bridge_transfer_id);
 		let call = contract.refundBridgeTransfer(FixedBytes(bridge_transfer_id.0));
 
@@ -323,21 +499,23 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		hash_lock: HashLock,
 		initiator: BridgeAddress<Vec<u8>>,
 		recipient: BridgeAddress<EthAddress>,
+		asset: AssetId,
 		amount: Amount,
 	) -> BridgeContractResult<()> {
 		tracing::info!("Begin lockBridgeTransfer");
+		let counterparty_contract = self.token_contracts(&asset)?.counterparty_contract;
 		let initiator: [u8; 32] = initiator.0.try_into().map_err(|_| {
 			BridgeContractError::ConversionFailed("lock_bridge_transfer initiator".to_string())
 		})?;
-		let call = self
-			.counterparty_contract
+		let contract =
+			AtomicBridgeCounterpartyMOVE::new(counterparty_contract, self.rpc_provider.clone());
+		let call = contract
 			.lockBridgeTransfer(
 				FixedBytes(initiator),
 				FixedBytes(bridge_transfer_id.0),
 				FixedBytes(hash_lock.0),
 				*recipient.0,
-				U256::try_from(amount.0)
-					.map_err(|_| BridgeContractError::ConversionFailed("U256".to_string()))?,
+				U256::from(amount.0),
 			)
 			.from(self.signer_address);
 
@@ -367,10 +545,9 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<()> {
-		let contract = AtomicBridgeCounterpartyMOVE::new(
-			self.config.counterparty_contract,
-			self.rpc_provider.clone(),
-		);
+		let counterparty_contract = self.counterparty_contract_of(bridge_transfer_id).await?;
+		let contract =
+			AtomicBridgeCounterpartyMOVE::new(counterparty_contract, self.rpc_provider.clone());
 		let call = contract.abortBridgeTransfer(FixedBytes(bridge_transfer_id.0));
 		send_transaction(
 			call,
@@ -403,22 +580,7 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<Option<BridgeTransferDetails<EthAddress>>> {
-		let generic_error = |desc| BridgeContractError::GenericError(String::from(desc));
-
-		let mapping_slot = U256::from(0); // the mapping is the zeroth slot in the contract
-		let key = bridge_transfer_id.0.clone();
-		let storage_slot = calculate_storage_slot(key, mapping_slot);
-		let storage: U256 = self
-			.rpc_provider
-			.get_storage_at(self.initiator_contract_address(), storage_slot)
-			.await
-			.map_err(|_| generic_error("could not find storage"))?;
-		let storage_bytes = storage.to_be_bytes::<32>();
-
-		println!("storage_bytes: {:?}", storage_bytes);
-		let mut storage_slice = &storage_bytes[..];
-		let eth_details = EthBridgeTransferDetails::decode(&mut storage_slice)
-			.map_err(|_| generic_error("could not decode storage"))?;
+		let (contracts, eth_details) = self.find_initiator_transfer(bridge_transfer_id).await?;
 
 		Ok(Some(BridgeTransferDetails {
 			bridge_transfer_id,
@@ -426,7 +588,11 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 			recipient: BridgeAddress(eth_details.recipient.to_vec()),
 			hash_lock: HashLock(eth_details.hash_lock),
 			time_lock: TimeLock(eth_details.time_lock.wrapping_to::<u64>()),
-			amount: eth_details.amount.into(),
+			asset: contracts.asset.clone(),
+			amount: eth_details
+				.amount
+				.try_into()
+				.map_err(|e| BridgeContractError::ConversionFailed(format!("{e}")))?,
 			state: eth_details.state,
 		}))
 	}
@@ -435,22 +601,7 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<Option<BridgeTransferDetailsCounterparty<EthAddress>>> {
-		let generic_error = |desc| BridgeContractError::GenericError(String::from(desc));
-
-		let mapping_slot = U256::from(0); // the mapping is the zeroth slot in the contract
-		let key = bridge_transfer_id.0.clone();
-		let storage_slot = calculate_storage_slot(key, mapping_slot);
-		let storage: U256 = self
-			.rpc_provider
-			.get_storage_at(self.initiator_contract_address(), storage_slot)
-			.await
-			.map_err(|_| generic_error("could not find storage"))?;
-		let storage_bytes = storage.to_be_bytes::<32>();
-
-		println!("storage_bytes: {:?}", storage_bytes);
-		let mut storage_slice = &storage_bytes[..];
-		let eth_details = EthBridgeTransferDetailsCounterparty::decode(&mut storage_slice)
-			.map_err(|_| generic_error("could not decode storage"))?;
+		let (contracts, eth_details) = self.find_counterparty_transfer(bridge_transfer_id).await?;
 
 		Ok(Some(BridgeTransferDetailsCounterparty {
 			bridge_transfer_id,
@@ -458,7 +609,11 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 			recipient: BridgeAddress(eth_details.recipient),
 			hash_lock: HashLock(eth_details.hash_lock),
 			time_lock: TimeLock(eth_details.time_lock.wrapping_to::<u64>()),
-			amount: eth_details.amount.into(),
+			asset: contracts.asset.clone(),
+			amount: eth_details
+				.amount
+				.try_into()
+				.map_err(|e| BridgeContractError::ConversionFailed(format!("{e}")))?,
 			state: eth_details.state,
 		}))
 	}
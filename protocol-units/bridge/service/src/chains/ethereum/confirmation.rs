@@ -0,0 +1,158 @@
+//! Buffers the Ethereum events until their block is confirmed, so the events removed by a reorg
+//! are retracted before they reach the relayer.
+//!
+//! The buffer is not persisted: the first unconfirmed block is saved instead, and the events of
+//! the blocks from it are queried again after a restart.
+
+use alloy::primitives::B256;
+
+/// The position of a log in the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LogPosition {
+	pub block_number: u64,
+	pub log_index: u64,
+	pub block_hash: B256,
+}
+
+/// The events of the unconfirmed blocks, refreshed on each poll of the chain.
+///
+/// Each poll queries the events from the first unconfirmed block to the latest block. This view
+/// replaces the buffered events: a buffered event missing from it was removed by a reorg and is
+/// retracted. The events in the blocks up to the safe block are then confirmed.
+pub struct ConfirmationBuffer<E> {
+	next_block: Option<u64>,
+	pending: Vec<(LogPosition, E)>,
+}
+
+impl<E> Default for ConfirmationBuffer<E> {
+	fn default() -> Self {
+		ConfirmationBuffer { next_block: None, pending: Vec::new() }
+	}
+}
+
+impl<E> ConfirmationBuffer<E> {
+	/// Resumes the monitoring from the first unconfirmed block saved before a restart.
+	pub fn resume_from(next_block: u64) -> Self {
+		ConfirmationBuffer { next_block: Some(next_block), pending: Vec::new() }
+	}
+
+	/// Gets the first unconfirmed block once the events are queried, the cursor to save.
+	pub fn cursor(&self) -> Option<u64> {
+		self.next_block
+	}
+
+	/// Gets the first unconfirmed block, from which the events are queried.
+	///
+	/// On the first poll, the events are monitored from the block after the safe block.
+	pub fn next_block(&mut self, safe_block: u64) -> u64 {
+		*self.next_block.get_or_insert(safe_block.saturating_add(1))
+	}
+
+	/// Gets the number of buffered events.
+	pub fn pending_len(&self) -> usize {
+		self.pending.len()
+	}
+
+	/// Replaces the buffered events with the events queried from the first unconfirmed block,
+	/// returning the confirmed events in chain order and the retracted ones.
+	pub fn update(
+		&mut self,
+		mut events: Vec<(LogPosition, E)>,
+		safe_block: u64,
+	) -> (Vec<E>, Vec<E>) {
+		let next_block = self.next_block(safe_block);
+		events.retain(|(position, _)| position.block_number >= next_block);
+		events.sort_by_key(|(position, _)| *position);
+
+		let retracted = std::mem::take(&mut self.pending)
+			.into_iter()
+			.filter(|(position, _)| !events.iter().any(|(current, _)| current == position))
+			.map(|(_, event)| event)
+			.collect();
+
+		let mut confirmed = Vec::new();
+		for (position, event) in events {
+			if position.block_number <= safe_block {
+				confirmed.push(event);
+			} else {
+				self.pending.push((position, event));
+			}
+		}
+		if safe_block >= next_block {
+			self.next_block = Some(safe_block + 1);
+		}
+		(confirmed, retracted)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn position(block_number: u64, log_index: u64, fork: u8) -> LogPosition {
+		LogPosition { block_number, log_index, block_hash: B256::repeat_byte(fork) }
+	}
+
+	#[test]
+	fn test_events_confirmed_at_safe_block() {
+		let mut buffer = ConfirmationBuffer::default();
+		assert_eq!(buffer.next_block(100), 101);
+
+		let events = vec![(position(102, 0, 1), "b"), (position(101, 3, 1), "a")];
+		let (confirmed, retracted) = buffer.update(events.clone(), 100);
+		assert!(confirmed.is_empty());
+		assert!(retracted.is_empty());
+		assert_eq!(buffer.pending_len(), 2);
+
+		let (confirmed, retracted) = buffer.update(events.clone(), 101);
+		assert_eq!(confirmed, vec!["a"]);
+		assert!(retracted.is_empty());
+		assert_eq!(buffer.next_block(101), 102);
+
+		// The already confirmed events are not emitted again.
+		let (confirmed, _) = buffer.update(events, 102);
+		assert_eq!(confirmed, vec!["b"]);
+		assert_eq!(buffer.pending_len(), 0);
+	}
+
+	#[test]
+	fn test_reorged_events_retracted() {
+		let mut buffer = ConfirmationBuffer::default();
+		buffer.next_block(100);
+
+		let events = vec![(position(101, 0, 1), "a"), (position(102, 0, 1), "b")];
+		buffer.update(events, 100);
+
+		// Block 102 is replaced by a block without the event, and 101 keeps its hash.
+		let (confirmed, retracted) = buffer.update(vec![(position(101, 0, 1), "a")], 100);
+		assert!(confirmed.is_empty());
+		assert_eq!(retracted, vec!["b"]);
+
+		// The event of block 101 is included in another block 101.
+		let (confirmed, retracted) = buffer.update(vec![(position(101, 1, 2), "a")], 101);
+		assert_eq!(confirmed, vec!["a"]);
+		assert_eq!(retracted, vec!["a"]);
+		assert_eq!(buffer.pending_len(), 0);
+	}
+
+	#[test]
+	fn test_buffered_events_confirmed_after_restart() {
+		let mut buffer = ConfirmationBuffer::default();
+		buffer.next_block(100);
+		let (confirmed, _) = buffer.update(vec![(position(101, 0, 1), "a")], 100);
+		assert!(confirmed.is_empty());
+		assert_eq!(buffer.pending_len(), 1);
+		let cursor = buffer.cursor().unwrap();
+		assert_eq!(cursor, 101);
+
+		// The relayer restarts once block 101 is below the safe block: the events buffered before
+		// are queried again from the saved cursor and confirmed.
+		let mut restarted = ConfirmationBuffer::resume_from(cursor);
+		assert_eq!(restarted.next_block(105), 101);
+		let events = vec![(position(101, 0, 1), "a"), (position(104, 2, 1), "b")];
+		let (confirmed, retracted) = restarted.update(events, 105);
+		assert_eq!(confirmed, vec!["a", "b"]);
+		assert!(retracted.is_empty());
+		assert_eq!(restarted.cursor(), Some(106));
+	}
+}
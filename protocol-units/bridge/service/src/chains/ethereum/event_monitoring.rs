@@ -1,26 +1,59 @@
+use super::confirmation::{ConfirmationBuffer, LogPosition};
 use super::types::EthAddress;
 use crate::chains::ethereum::types::AtomicBridgeCounterpartyMOVE;
 use crate::chains::ethereum::types::AtomicBridgeInitiatorMOVE;
+use crate::chains::monitoring::{
+	load_store_file, poll_next_event, save_store_file, MonitoringMessage,
+};
 use alloy::eips::BlockNumberOrTag;
 use alloy::primitives::Address;
 use alloy::providers::Provider;
 use alloy::providers::ProviderBuilder;
+use alloy::rpc::types::Log;
 use alloy_network::EthereumWallet;
-use bridge_config::common::eth::EthConfig;
+use bridge_config::common::{eth::EthConfig, tokens::TokenRegistry};
 use bridge_util::chains::bridge_contracts::BridgeContractError;
 use bridge_util::chains::bridge_contracts::BridgeContractEvent;
 use bridge_util::chains::bridge_contracts::BridgeContractMonitoring;
 use bridge_util::chains::bridge_contracts::BridgeContractResult;
 use bridge_util::types::HashLockPreImage;
 use bridge_util::types::LockDetails;
-use bridge_util::types::{BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock};
+use bridge_util::types::{
+	Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock,
+};
 use futures::SinkExt;
-use futures::{channel::mpsc::UnboundedReceiver, Stream, StreamExt};
-use std::{pin::Pin, task::Poll};
+use futures::{
+	channel::mpsc::{UnboundedReceiver, UnboundedSender},
+	Future, Stream,
+};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, pin::Pin, task::Poll, time::Duration};
 use tokio::sync::{mpsc, oneshot};
 
+/// The first unconfirmed block, saved once the events of the blocks before are processed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct EthPullingState {
+	next_block: u64,
+}
+
+impl EthPullingState {
+	/// Gets the store file of the events of the bridge contracts, named after the initiator
+	/// contract of the Eth config.
+	fn get_store_file_path(initiator_contract: Address) -> PathBuf {
+		let dot_movement = dot_movement::DotMovement::try_from_env()
+			.unwrap_or(dot_movement::DotMovement::new(".movement"));
+		bridge_config::get_config_path(&dot_movement)
+			.join(format!("eth-pullstate-{initiator_contract}.store"))
+	}
+}
+
+/// Streams the confirmed events of the Eth bridge contracts.
+///
+/// The first unconfirmed block is saved once the confirmed events are processed by the relayer
+/// loop. After a restart, the events are queried again from it, so the events still buffered
+/// before the restart are not skipped.
 pub struct EthMonitoring {
-	listener: UnboundedReceiver<BridgeContractResult<BridgeContractEvent<EthAddress>>>,
+	listener: UnboundedReceiver<MonitoringMessage<EthAddress>>,
 }
 
 impl BridgeContractMonitoring for EthMonitoring {
@@ -28,11 +61,30 @@ impl BridgeContractMonitoring for EthMonitoring {
 }
 
 impl EthMonitoring {
+	/// Monitors the bridge contracts of the Eth config.
 	pub async fn build(
 		config: &EthConfig,
+		health_check_rx: mpsc::Receiver<oneshot::Sender<bool>>,
+	) -> Result<Self, anyhow::Error> {
+		Self::build_for(config, config.try_into()?, health_check_rx).await
+	}
+
+	/// Monitors the bridge contracts of the Eth config and of the registered tokens.
+	pub async fn build_with_tokens(
+		config: &EthConfig,
+		tokens: &TokenRegistry,
+		health_check_rx: mpsc::Receiver<oneshot::Sender<bool>>,
+	) -> Result<Self, anyhow::Error> {
+		let client_config =
+			crate::chains::ethereum::client::Config::try_from(config)?.with_tokens(tokens)?;
+		Self::build_for(config, client_config, health_check_rx).await
+	}
+
+	async fn build_for(
+		config: &EthConfig,
+		client_config: crate::chains::ethereum::client::Config,
 		mut health_check_rx: mpsc::Receiver<oneshot::Sender<bool>>,
 	) -> Result<Self, anyhow::Error> {
-		let client_config: crate::chains::ethereum::client::Config = config.try_into()?;
 		let rpc_provider = ProviderBuilder::new()
 			.with_recommended_fillers()
 			.wallet(EthereumWallet::from(client_config.signer_private_key.clone()))
@@ -40,27 +92,44 @@ impl EthMonitoring {
 			.await?;
 
 		tracing::info!(
-			"Start Eth monitoring with initiator:{} counterpart:{}",
-			config.eth_initiator_contract,
-			config.eth_counterparty_contract
+			"Start Eth monitoring with contracts:{:?} confirmation depth:{} finalized:{}",
+			client_config.tokens,
+			config.eth_confirmation_depth,
+			config.eth_use_finalized_block,
 		);
 
-		let (mut sender, listener) = futures::channel::mpsc::unbounded::<
-			BridgeContractResult<BridgeContractEvent<EthAddress>>,
-		>();
+		let (mut sender, listener) =
+			futures::channel::mpsc::unbounded::<MonitoringMessage<EthAddress>>();
+
+		// Resume from the first unconfirmed block saved before a restart.
+		let store_path = EthPullingState::get_store_file_path(client_config.initiator_contract);
+		let mut pull_state = load_store_file::<EthPullingState>(&store_path).await?;
+		let mut confirmations = match pull_state {
+			Some(state) => ConfirmationBuffer::resume_from(state.next_block),
+			None => ConfirmationBuffer::default(),
+		};
 
 		tokio::spawn({
 			let config = config.clone();
+			// Each ERC-20 is bridged by its own contracts, their events are of its asset.
+			let token_contracts: Vec<_> = client_config
+				.tokens
+				.iter()
+				.map(|contracts| {
+					(
+						contracts.asset.clone(),
+						AtomicBridgeInitiatorMOVE::new(
+							contracts.initiator_contract,
+							rpc_provider.clone(),
+						),
+						AtomicBridgeCounterpartyMOVE::new(
+							contracts.counterparty_contract,
+							rpc_provider.clone(),
+						),
+					)
+				})
+				.collect();
 			async move {
-				let initiator_contract = AtomicBridgeInitiatorMOVE::new(
-					config.eth_initiator_contract.parse().unwrap(), //If unwrap start fail. Config must be updated.
-					rpc_provider.clone(),
-				);
-				let counterpart_contract = AtomicBridgeCounterpartyMOVE::new(
-					config.eth_counterparty_contract.parse().unwrap(), //If unwrap start fail. Config must be updated.
-					rpc_provider.clone(),
-				);
-				let mut last_processed_block = 0;
 				loop {
 					//Check if there's a health check request
 					match health_check_rx.try_recv() {
@@ -86,333 +155,300 @@ impl EthMonitoring {
 					{
 						Ok(Ok(block_number)) => block_number,
 						Ok(Err(err)) => {
-							if sender
-								.send(Err(BridgeContractError::OnChainError(format!(
-									"Eth get blocknumber request failed: {err}"
-								))))
-								.await
-								.is_err()
+							if !send_error(
+								&mut sender,
+								format!("Eth get blocknumber request failed: {err}"),
+							)
+							.await
 							{
-								tracing::error!("Failed to send event to listener channel");
 								break;
 							}
 							let _ = tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
 							continue;
 						}
 						Err(err) => {
-							if sender
-								.send(Err(BridgeContractError::OnChainError(format!(
-									"Eth get blocknumber timeout: {err}"
-								))))
-								.await
-								.is_err()
+							if !send_error(
+								&mut sender,
+								format!("Eth get blocknumber timeout: {err}"),
+							)
+							.await
 							{
-								tracing::error!("Failed to send event to listener channel");
 								break;
 							}
 							let _ = tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
 							continue;
 						}
 					};
-					if last_processed_block < block_number {
-						last_processed_block = block_number;
-						let initiator_initiate_event_filter = initiator_contract
-							.BridgeTransferInitiated_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
-						// event BridgeTransferCompleted(bytes32 indexed _bridgeTransferId, bytes32 pre_image);
-						let initiator_trcompleted_event_filter = initiator_contract
-							.BridgeTransferCompleted_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
-						// event BridgeTransferRefunded(bytes32 indexed _bridgeTransferId);
-						let initiator_trrefund_event_filter = initiator_contract
-							.BridgeTransferRefunded_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
-						let counterpart_trlocked_event_filter = counterpart_contract
-							.BridgeTransferLocked_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
-						let counterpart_trcompleted_event_filter = counterpart_contract
-							.BridgeTransferCompleted_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
-						//event BridgeTransferAborted(bytes32 indexed bridgeTransferId);
-						let counterpart_trcaborted_event_filter = counterpart_contract
-							.BridgeTransferAborted_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
-
-						//Initiator event stream
+					// The events are confirmed once their block is the safe block or below.
+					let safe_block = if config.eth_use_finalized_block {
 						match tokio::time::timeout(
 							tokio::time::Duration::from_secs(config.rest_connection_timeout_secs),
-							initiator_initiate_event_filter.query(),
+							rpc_provider.get_block_by_number(BlockNumberOrTag::Finalized, false),
 						)
 						.await
 						{
-							Ok(Ok(events)) => {
-								for (initiated, _log) in events {
-									let event = {
-										// BridgeTransferInitiated(bridgeTransferId, originator, recipient, totalAmount, hashLock, initiatorTimeLockDuration);
-										let details: BridgeTransferDetails<EthAddress> =
-											BridgeTransferDetails {
-												bridge_transfer_id: BridgeTransferId(
-													*initiated._bridgeTransferId,
-												),
-												initiator: BridgeAddress(EthAddress(
-													Address::from(initiated._originator),
-												)),
-												recipient: BridgeAddress(
-													initiated._recipient.to_vec(),
-												),
-												hash_lock: HashLock(*initiated._hashLock),
-												time_lock: initiated._timeLock.into(),
-												amount: initiated.amount.into(),
-												state: 0,
-											};
-										BridgeContractEvent::Initiated(details)
-									};
-									if sender.send(Ok(event)).await.is_err() {
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
-								}
-							}
-							Ok(Err(_)) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError("Eth monitoring query initiator_initiate_event_filter timeout.".to_string())))
-									.await
-									.is_err()
-								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
-								}
-							}
-							Err(err) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError(err.to_string())))
+							Ok(Ok(block)) => match block.and_then(|block| block.header.number) {
+								Some(finalized_block) => finalized_block,
+								None => {
+									if !send_error(
+										&mut sender,
+										"Eth finalized block not found".to_string(),
+									)
 									.await
-									.is_err()
-								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
-								}
-							}
-						}
-						match tokio::time::timeout(
-							tokio::time::Duration::from_secs(config.rest_connection_timeout_secs),initiator_trcompleted_event_filter.query()).await {
-							Ok(Ok(events)) => {
-								for (completed, _log) in events {
-									if sender
-										.send(Ok(BridgeContractEvent::InitiatorCompleted(
-											BridgeTransferId(*completed._bridgeTransferId),
-										)))
-										.await
-										.is_err()
 									{
-										tracing::error!("Failed to send event to listener channel");
 										break;
 									}
+									let _ = tokio::time::sleep(tokio::time::Duration::from_secs(5))
+										.await;
+									continue;
 								}
-							}
-							Ok(Err(_)) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError("Eth monitoring query initiator_trcompleted_event_filter timeout.".to_string())))
-									.await
-									.is_err()
+							},
+							Ok(Err(err)) => {
+								if !send_error(
+									&mut sender,
+									format!("Eth get finalized block request failed: {err}"),
+								)
+								.await
 								{
-									tracing::error!("Failed to send event to listener channel");
 									break;
 								}
+								let _ =
+									tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+								continue;
 							}
 							Err(err) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError(err.to_string())))
-									.await
-									.is_err()
+								if !send_error(
+									&mut sender,
+									format!("Eth get finalized block timeout: {err}"),
+								)
+								.await
 								{
-									tracing::error!("Failed to send event to listener channel");
 									break;
 								}
+								let _ =
+									tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+								continue;
 							}
 						}
-						match tokio::time::timeout(
-							tokio::time::Duration::from_secs(config.rest_connection_timeout_secs),
-							initiator_trrefund_event_filter.query(),
-						)
-						.await
-						{
-							Ok(Ok(events)) => {
-								for (refund, _log) in events {
-									if sender
-										.send(Ok(BridgeContractEvent::Refunded(BridgeTransferId(
-											*refund._bridgeTransferId,
-										))))
-										.await
-										.is_err()
-									{
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
-								}
-							}
-							Ok(Err(_)) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError("Eth monitoring query initiator_trrefund_event_filter timeout.".to_string())))
-									.await
-									.is_err()
-								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
-								}
-							}
-							Err(err) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError(err.to_string())))
-									.await
-									.is_err()
+					} else {
+						block_number.saturating_sub(config.eth_confirmation_depth)
+					};
+
+					// Query the events of the unconfirmed blocks.
+					let from_block = confirmations.next_block(safe_block);
+					let events = if from_block > block_number {
+						Vec::new()
+					} else {
+						let from_block = BlockNumberOrTag::Number(from_block);
+						let to_block = BlockNumberOrTag::Number(block_number);
+						let timeout =
+							tokio::time::Duration::from_secs(config.rest_connection_timeout_secs);
+						let query = async {
+							let mut events = Vec::new();
+							for (asset, initiator_contract, counterpart_contract) in
+								&token_contracts
+							{
+								let initiator_initiate_event_filter = initiator_contract
+									.BridgeTransferInitiated_filter()
+									.from_block(from_block)
+									.to_block(to_block);
+								// event BridgeTransferCompleted(bytes32 indexed _bridgeTransferId, bytes32 pre_image);
+								let initiator_trcompleted_event_filter = initiator_contract
+									.BridgeTransferCompleted_filter()
+									.from_block(from_block)
+									.to_block(to_block);
+								// event BridgeTransferRefunded(bytes32 indexed _bridgeTransferId);
+								let initiator_trrefund_event_filter = initiator_contract
+									.BridgeTransferRefunded_filter()
+									.from_block(from_block)
+									.to_block(to_block);
+								let counterpart_trlocked_event_filter = counterpart_contract
+									.BridgeTransferLocked_filter()
+									.from_block(from_block)
+									.to_block(to_block);
+								let counterpart_trcompleted_event_filter = counterpart_contract
+									.BridgeTransferCompleted_filter()
+									.from_block(from_block)
+									.to_block(to_block);
+								//event BridgeTransferAborted(bytes32 indexed bridgeTransferId);
+								let counterpart_trcaborted_event_filter = counterpart_contract
+									.BridgeTransferAborted_filter()
+									.from_block(from_block)
+									.to_block(to_block);
+
+								for (initiated, log) in query_logs(
+									initiator_initiate_event_filter.query(),
+									timeout,
+									"initiator_initiate_event_filter",
+								)
+								.await?
 								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
-								}
-							}
-						}
-						tracing::info!("Querying counterpart_trlocked_event_filter");
-						let start_time = tokio::time::Instant::now();
-						match tokio::time::timeout(
-							tokio::time::Duration::from_secs(30),
-							counterpart_trlocked_event_filter.query(),
-						)
-						.await
-						{
-							Ok(Ok(events)) => {
-								tracing::info!(
-									"Query completed for counterpart_trlocked_event_filter in {:?}",
-									start_time.elapsed()
-								);
-								for (trlocked, _log) in events {
-									let event = {
-										// BridgeTransferLocked(bridgeTransferId, originator, recipient, totalAmount, hashLock, initiatorTimeLockDuration);
-										let details: LockDetails<EthAddress> = LockDetails {
+									// BridgeTransferInitiated(bridgeTransferId, originator, recipient, totalAmount, hashLock, initiatorTimeLockDuration);
+									let details: BridgeTransferDetails<EthAddress> =
+										BridgeTransferDetails {
 											bridge_transfer_id: BridgeTransferId(
-												*trlocked.bridgeTransferId,
+												*initiated._bridgeTransferId,
 											),
-											initiator: BridgeAddress([0, 32].into()), // TODO add the originator fields. trlocked.originator.to_vec()
-											recipient: BridgeAddress(EthAddress(Address::from(
-												trlocked.recipient,
+											initiator: BridgeAddress(EthAddress(Address::from(
+												initiated._originator,
 											))),
-											amount: trlocked.amount.into(),
-											hash_lock: HashLock(*trlocked.hashLock),
-											time_lock: trlocked.timeLock.into(),
+											recipient: BridgeAddress(initiated._recipient.to_vec()),
+											hash_lock: HashLock(*initiated._hashLock),
+											time_lock: initiated._timeLock.into(),
+											asset: asset.clone(),
+											amount: Amount::try_from(initiated.amount).map_err(
+												|e| {
+													BridgeContractError::ConversionFailed(format!(
+														"{e}"
+													))
+												},
+											)?,
+											state: 0,
 										};
-										BridgeContractEvent::Locked(details)
-									};
-									if sender.send(Ok(event)).await.is_err() {
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
+									events.extend(log_position(&log).map(|position| {
+										(position, BridgeContractEvent::Initiated(details))
+									}));
 								}
-							}
-							Ok(Err(_)) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError("Eth monitoring query counterpart_trlocked_event_filter timeout.".to_string())))
-									.await
-									.is_err()
+								for (completed, log) in query_logs(
+									initiator_trcompleted_event_filter.query(),
+									timeout,
+									"initiator_trcompleted_event_filter",
+								)
+								.await?
 								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
+									let event = BridgeContractEvent::InitiatorCompleted(
+										BridgeTransferId(*completed._bridgeTransferId),
+									);
+									events.extend(
+										log_position(&log).map(|position| (position, event)),
+									);
 								}
-							}
-							Err(err) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError(err.to_string())))
-									.await
-									.is_err()
+								for (refund, log) in query_logs(
+									initiator_trrefund_event_filter.query(),
+									timeout,
+									"initiator_trrefund_event_filter",
+								)
+								.await?
 								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
+									let event = BridgeContractEvent::Refunded(BridgeTransferId(
+										*refund._bridgeTransferId,
+									));
+									events.extend(
+										log_position(&log).map(|position| (position, event)),
+									);
 								}
-							}
-						}
-						match tokio::time::timeout(
-							tokio::time::Duration::from_secs(config.rest_connection_timeout_secs),
-							counterpart_trcompleted_event_filter.query(),
-						)
-						.await
-						{
-							Ok(Ok(events)) => {
-								for (completed, _log) in events {
-									if sender
-										.send(Ok(BridgeContractEvent::CounterPartyCompleted(
-											BridgeTransferId(*completed.bridgeTransferId),
-											HashLockPreImage(*completed.pre_image),
-										)))
-										.await
-										.is_err()
-									{
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
-								}
-							}
-							Ok(Err(_)) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError("Eth monitoring query counterpart_trcompleted_event_filter timeout.".to_string())))
-									.await
-									.is_err()
+								for (trlocked, log) in query_logs(
+									counterpart_trlocked_event_filter.query(),
+									tokio::time::Duration::from_secs(30),
+									"counterpart_trlocked_event_filter",
+								)
+								.await?
 								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
+									// BridgeTransferLocked(bridgeTransferId, originator, recipient, totalAmount, hashLock, initiatorTimeLockDuration);
+									let details: LockDetails<EthAddress> = LockDetails {
+										bridge_transfer_id: BridgeTransferId(
+											*trlocked.bridgeTransferId,
+										),
+										initiator: BridgeAddress([0, 32].into()), // TODO add the originator fields. trlocked.originator.to_vec()
+										recipient: BridgeAddress(EthAddress(Address::from(
+											trlocked.recipient,
+										))),
+										asset: asset.clone(),
+										amount: Amount::try_from(trlocked.amount).map_err(|e| {
+											BridgeContractError::ConversionFailed(format!("{e}"))
+										})?,
+										hash_lock: HashLock(*trlocked.hashLock),
+										time_lock: trlocked.timeLock.into(),
+									};
+									events.extend(log_position(&log).map(|position| {
+										(position, BridgeContractEvent::Locked(details))
+									}));
 								}
-							}
-							Err(err) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError(err.to_string())))
-									.await
-									.is_err()
+								for (completed, log) in query_logs(
+									counterpart_trcompleted_event_filter.query(),
+									timeout,
+									"counterpart_trcompleted_event_filter",
+								)
+								.await?
 								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
+									let event = BridgeContractEvent::CounterPartyCompleted(
+										BridgeTransferId(*completed.bridgeTransferId),
+										HashLockPreImage(*completed.pre_image),
+									);
+									events.extend(
+										log_position(&log).map(|position| (position, event)),
+									);
 								}
-							}
-						}
-						match tokio::time::timeout(
-							tokio::time::Duration::from_secs(config.rest_connection_timeout_secs),
-							counterpart_trcaborted_event_filter.query(),
-						)
-						.await
-						{
-							Ok(Ok(events)) => {
-								for (aborted, _log) in events {
-									if sender
-										.send(Ok(BridgeContractEvent::Cancelled(BridgeTransferId(
-											*aborted.bridgeTransferId,
-										))))
-										.await
-										.is_err()
-									{
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
-								}
-							}
-							Ok(Err(_)) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError("Eth monitoring query counterpart_trcaborted_event_filter timeout.".to_string())))
-									.await
-									.is_err()
+								for (aborted, log) in query_logs(
+									counterpart_trcaborted_event_filter.query(),
+									timeout,
+									"counterpart_trcaborted_event_filter",
+								)
+								.await?
 								{
-									tracing::error!("Failed to send event to listener channel");
-									break;
+									let event = BridgeContractEvent::Cancelled(BridgeTransferId(
+										*aborted.bridgeTransferId,
+									));
+									events.extend(
+										log_position(&log).map(|position| (position, event)),
+									);
 								}
 							}
+							Ok::<_, BridgeContractError>(events)
+						};
+						match query.await {
+							Ok(events) => events,
 							Err(err) => {
-								if sender
-									.send(Err(BridgeContractError::OnChainError(err.to_string())))
-									.await
-									.is_err()
-								{
+								// A partial view of the blocks would retract the missing events.
+								if sender.send(MonitoringMessage::Event(Err(err))).await.is_err() {
 									tracing::error!("Failed to send event to listener channel");
 									break;
 								}
+								let _ =
+									tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+								continue;
+							}
+						}
+					};
+
+					let (confirmed, retracted) = confirmations.update(events, safe_block);
+					for event in retracted {
+						tracing::warn!(
+							"Eth event removed by a reorg before its confirmation: {event:?}"
+						);
+					}
+					let mut listener_dropped = false;
+					for event in confirmed {
+						if sender.send(MonitoringMessage::Event(Ok(event))).await.is_err() {
+							tracing::error!("Failed to send event to listener channel");
+							listener_dropped = true;
+							break;
+						}
+					}
+					if listener_dropped {
+						break;
+					}
+
+					let next_block =
+						confirmations.cursor().map(|next_block| EthPullingState { next_block });
+					if next_block != pull_state {
+						// Wait for the confirmed events to be indexed before saving the cursor.
+						let (checkpoint_tx, checkpoint_rx) = oneshot::channel();
+						if sender.send(MonitoringMessage::Checkpoint(checkpoint_tx)).await.is_err()
+							|| checkpoint_rx.await.is_err()
+						{
+							tracing::error!(
+								"Eth monitoring listener closed before the events were indexed"
+							);
+							break;
+						}
+						pull_state = next_block;
+						if let Some(state) = &pull_state {
+							if let Err(err) = save_store_file(state, &store_path).await {
+								tracing::error!("Eth monitoring unable to store the file state because:{err} for state:{state:?}");
 							}
 						}
-					} // end match
+					}
 
 					let _ = tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
 				} // end loop
@@ -423,11 +459,55 @@ impl EthMonitoring {
 	}
 }
 
+/// Sends an error to the listener, returning false if the listener is dropped.
+async fn send_error(
+	sender: &mut UnboundedSender<MonitoringMessage<EthAddress>>,
+	error: String,
+) -> bool {
+	if sender
+		.send(MonitoringMessage::Event(Err(BridgeContractError::OnChainError(error))))
+		.await
+		.is_err()
+	{
+		tracing::error!("Failed to send event to listener channel");
+		return false;
+	}
+	true
+}
+
+/// Runs an event query with a timeout.
+async fn query_logs<E>(
+	query: impl Future<Output = Result<Vec<(E, Log)>, alloy::contract::Error>>,
+	timeout: Duration,
+	filter_name: &str,
+) -> BridgeContractResult<Vec<(E, Log)>> {
+	match tokio::time::timeout(timeout, query).await {
+		Ok(Ok(events)) => Ok(events),
+		Ok(Err(err)) => Err(BridgeContractError::OnChainError(format!(
+			"Eth monitoring query {filter_name} failed: {err}"
+		))),
+		Err(_) => Err(BridgeContractError::OnChainError(format!(
+			"Eth monitoring query {filter_name} timeout."
+		))),
+	}
+}
+
+/// Gets the position of a log, if it is mined and not removed.
+fn log_position(log: &Log) -> Option<LogPosition> {
+	if log.removed {
+		return None;
+	}
+	Some(LogPosition {
+		block_number: log.block_number?,
+		log_index: log.log_index?,
+		block_hash: log.block_hash?,
+	})
+}
+
 impl Stream for EthMonitoring {
 	type Item = BridgeContractResult<BridgeContractEvent<EthAddress>>;
 
 	fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Option<Self::Item>> {
-		let this = self.get_mut();
-		this.listener.poll_next_unpin(cx)
+		poll_next_event(&mut self.get_mut().listener, cx)
 	}
 }
@@ -1,4 +1,5 @@
 pub mod client;
+pub mod confirmation;
 pub mod event_monitoring;
 pub mod types;
 pub mod utils;
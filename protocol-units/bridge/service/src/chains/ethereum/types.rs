@@ -11,6 +11,7 @@ use bridge_util::types::Amount;
 use bridge_util::types::BridgeAddress;
 use bridge_util::types::BridgeTransferDetails;
 use bridge_util::types::BridgeTransferId;
+use bridge_util::types::ConversionError;
 use bridge_util::types::HashLock;
 use bridge_util::types::HashLockPreImage;
 use bridge_util::types::LockDetails;
@@ -40,23 +41,24 @@ alloy::sol!(
 	"abis/MockMOVEToken.json"
 );
 
-/// Specifies the kind of asset being transferred,
-/// This will associate the client with its respective ABIs
-#[derive(Debug, Clone, Default)]
-pub enum AssetKind {
-	/// This will initialize the client with the WETH Bridge ABIs
-	Weth,
-	/// This will initialize the client with the MOVE Bridge ABIs
-	#[default]
-	Move,
+/// The state of a transfer in the counterparty contract, `MessageState` in
+/// `AtomicBridgeCounterpartyMOVE.sol`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterpartyState {
+	Pending,
+	Completed,
+	Refunded,
 }
 
-impl From<String> for AssetKind {
-	fn from(asset: String) -> Self {
-		match asset.as_str() {
-			"WETH" => AssetKind::Weth,
-			"MOVE" => AssetKind::Move,
-			_ => panic!("Invalid asset kind"),
+impl TryFrom<u8> for CounterpartyState {
+	type Error = ConversionError;
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(CounterpartyState::Pending),
+			1 => Ok(CounterpartyState::Completed),
+			2 => Ok(CounterpartyState::Refunded),
+			_ => Err(ConversionError::InvalidValue("CounterpartyState", value.to_string())),
 		}
 	}
 }
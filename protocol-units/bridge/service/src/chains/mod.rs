@@ -1,3 +1,4 @@
 pub mod ethereum;
+pub(crate) mod monitoring;
 pub mod movement;
 pub use bridge_util::chains::*;
@@ -0,0 +1,69 @@
+//! The parts shared by the monitoring streams of the chains: the checkpoints acknowledged once
+//! the relayer loop processed the events sent before, and the store files of the pull cursors.
+
+use super::bridge_contracts::{BridgeContractEvent, BridgeContractResult};
+use futures::{channel::mpsc::UnboundedReceiver, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{path::Path, task::Poll};
+use tokio::fs::{self, File};
+use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+use tokio::sync::oneshot;
+
+/// A message from a pulling loop to its monitoring stream.
+pub(crate) enum MonitoringMessage<A> {
+	Event(BridgeContractResult<BridgeContractEvent<A>>),
+	/// Acknowledged once the events sent before are processed by the relayer loop.
+	Checkpoint(oneshot::Sender<()>),
+}
+
+/// Polls the next event, acknowledging the checkpoints on the way.
+///
+/// The relayer loop processes and indexes an event before it polls the next one, so the events
+/// sent before a checkpoint are indexed when the stream is polled past it.
+pub(crate) fn poll_next_event<A>(
+	listener: &mut UnboundedReceiver<MonitoringMessage<A>>,
+	cx: &mut std::task::Context,
+) -> Poll<Option<BridgeContractResult<BridgeContractEvent<A>>>> {
+	loop {
+		match listener.poll_next_unpin(cx) {
+			Poll::Ready(Some(MonitoringMessage::Event(event))) => return Poll::Ready(Some(event)),
+			Poll::Ready(Some(MonitoringMessage::Checkpoint(checkpoint_tx))) => {
+				let _ = checkpoint_tx.send(());
+			}
+			Poll::Ready(None) => return Poll::Ready(None),
+			Poll::Pending => return Poll::Pending,
+		}
+	}
+}
+
+/// Writes a state to a temporary file renamed over the store file, so a crash leaves either the
+/// previous or the new state in the store.
+pub(crate) async fn save_store_file<T: Serialize>(state: &T, path: &Path) -> io::Result<()> {
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent).await?;
+	}
+
+	let json = serde_json::to_string(state)?;
+	let tmp_path = path.with_extension("store.tmp");
+	let mut file = File::create(&tmp_path).await?;
+	file.write_all(json.as_bytes()).await?;
+	file.sync_all().await?;
+	fs::rename(&tmp_path, path).await?;
+
+	// Persist the rename in the directory.
+	if let Some(parent) = path.parent() {
+		File::open(parent).await?.sync_all().await?;
+	}
+	Ok(())
+}
+
+/// Reads a state from a store file, if it exists.
+pub(crate) async fn load_store_file<T: DeserializeOwned>(path: &Path) -> io::Result<Option<T>> {
+	if !fs::try_exists(path).await? {
+		return Ok(None);
+	}
+	let mut file = File::open(path).await?;
+	let mut json = String::new();
+	file.read_to_string(&mut json).await?;
+	Ok(Some(serde_json::from_str(&json)?))
+}
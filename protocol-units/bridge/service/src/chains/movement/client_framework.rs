@@ -1,17 +1,19 @@
 use super::utils::{self, MovementAddress};
 use anyhow::{Context, Result};
-use aptos_api_types::{EntryFunctionId, MoveModuleId, ViewRequest};
+use aptos_api_types::{
+	EntryFunctionId, MoveModuleId, Transaction as AptosTransaction, ViewRequest,
+};
 use aptos_sdk::{
 	move_types::identifier::Identifier,
 	rest_client::{Client, Response},
 	types::LocalAccount,
 };
 use aptos_types::account_address::AccountAddress;
-use bridge_config::common::movement::MovementConfig;
+use bridge_config::common::{movement::MovementConfig, tokens::TokenRegistry};
 use bridge_util::{
 	chains::bridge_contracts::{BridgeContract, BridgeContractError, BridgeContractResult},
 	types::{
-		Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
+		Amount, AssetId, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
 		BridgeTransferId, HashLock, HashLockPreImage, TimeLock,
 	},
 };
@@ -27,6 +29,8 @@ pub const FRAMEWORK_ADDRESS: AccountAddress = AccountAddress::new([
 
 pub const INITIATOR_MODULE_NAME: &str = "atomic_bridge_initiator";
 pub const COUNTERPARTY_MODULE_NAME: &str = "atomic_bridge_counterparty";
+const INITIATOR_DETAILS_FUNCTION: &str = "get_bridge_transfer_details_initiator";
+const COUNTERPARTY_DETAILS_FUNCTION: &str = "get_bridge_transfer_details_counterparty";
 const DUMMY_ADDRESS: AccountAddress = AccountAddress::new([0; 32]);
 
 #[allow(dead_code)]
@@ -46,10 +50,44 @@ pub struct MovementClientFramework {
 	pub rest_client: Client,
 	///The signer account
 	signer: Arc<LocalAccount>,
+	///The address of the bridge modules of each asset, the first ones are the framework modules
+	pub bridge_modules: Vec<(AssetId, AccountAddress)>,
+}
+
+/// Gets the address of the bridge modules of each asset: the framework modules bridge the asset of
+/// the Movement config, the other registered assets name the address of their modules.
+pub fn bridge_modules(
+	config: &MovementConfig,
+	tokens: &TokenRegistry,
+) -> Result<Vec<(AssetId, AccountAddress)>, anyhow::Error> {
+	let framework_asset = AssetId(config.asset.clone());
+	let mut bridge_modules = vec![(framework_asset.clone(), FRAMEWORK_ADDRESS)];
+	for token in &tokens.tokens {
+		let asset = AssetId(token.asset_id.clone());
+		match &token.mvt_bridge_address {
+			Some(address) => {
+				bridge_modules.retain(|(module_asset, _)| *module_asset != asset);
+				bridge_modules.push((asset, AccountAddress::from_hex_literal(address)?));
+			}
+			None if asset == framework_asset => (),
+			None => anyhow::bail!("Missing Movement bridge modules of asset {asset}"),
+		}
+	}
+	Ok(bridge_modules)
 }
 
 impl MovementClientFramework {
+	/// Builds a client of the framework modules.
 	pub async fn new(config: &MovementConfig) -> Result<Self, anyhow::Error> {
+		Self::new_with_tokens(config, &TokenRegistry { tokens: vec![] }).await
+	}
+
+	/// Builds a client of the framework modules and of the modules of the registered tokens.
+	pub async fn new_with_tokens(
+		config: &MovementConfig,
+		tokens: &TokenRegistry,
+	) -> Result<Self, anyhow::Error> {
+		let bridge_modules = bridge_modules(config, tokens)?;
 		let node_connection_url = Url::from_str(config.mvt_rpc_connection_url().as_str())
 			.map_err(|_| BridgeContractError::SerializationError)?;
 
@@ -58,7 +96,12 @@ impl MovementClientFramework {
 		let signer =
 			utils::create_local_account(config.movement_signer_key.clone(), &rest_client).await?;
 		let native_address = AccountAddress::from_hex_literal(&config.movement_native_address)?;
-		Ok(MovementClientFramework { native_address, rest_client, signer: Arc::new(signer) })
+		Ok(MovementClientFramework {
+			native_address,
+			rest_client,
+			signer: Arc::new(signer),
+			bridge_modules,
+		})
 	}
 
 	pub fn rest_client(&self) -> &Client {
@@ -69,6 +112,181 @@ impl MovementClientFramework {
 		&self.signer
 	}
 
+	/// Gets the asset bridged by the framework modules.
+	pub fn framework_asset(&self) -> &AssetId {
+		&self.bridge_modules[0].0
+	}
+
+	/// Gets the address of the bridge modules of an asset.
+	fn bridge_module_address(&self, asset: &AssetId) -> BridgeContractResult<AccountAddress> {
+		self.bridge_modules
+			.iter()
+			.find(|(module_asset, _)| module_asset == asset)
+			.map(|(_, address)| *address)
+			.ok_or_else(|| BridgeContractError::UnsupportedAsset(asset.clone()))
+	}
+
+	/// Converts an amount to the u64 amount of the fungible assets.
+	fn fa_amount(amount: Amount) -> BridgeContractResult<u64> {
+		u64::try_from(amount.0).map_err(|_| {
+			BridgeContractError::ConversionFailed(format!(
+				"Amount {} overflows the Movement u64 amount",
+				amount.0
+			))
+		})
+	}
+
+	/// Views the details of a transfer in the bridge modules at an address.
+	async fn view_transfer_details(
+		&self,
+		module_address: AccountAddress,
+		function: &str,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<serde_json::Value> {
+		let bridge_transfer_id_hex = format!("0x{}", hex::encode(bridge_transfer_id.0));
+
+		let view_request = ViewRequest {
+			function: EntryFunctionId {
+				module: MoveModuleId {
+					address: module_address.into(),
+					name: aptos_api_types::IdentifierWrapper(
+						Identifier::new("atomic_bridge_store")
+							.map_err(|_| BridgeContractError::FunctionViewError)?,
+					),
+				},
+				name: aptos_api_types::IdentifierWrapper(
+					Identifier::new(function)
+						.map_err(|_| BridgeContractError::FunctionViewError)?,
+				),
+			},
+			type_arguments: vec![],
+			arguments: vec![serde_json::json!(bridge_transfer_id_hex)],
+		};
+
+		let response: Response<Vec<serde_json::Value>> = self
+			.rest_client
+			.view(&view_request, None)
+			.await
+			.map_err(|_| BridgeContractError::CallError)?;
+
+		let mut values = response.into_inner();
+
+		if values.len() != 1 {
+			return Err(BridgeContractError::InvalidResponseLength);
+		}
+
+		Ok(values.remove(0))
+	}
+
+	/// Finds the bridge modules holding a transfer, with its details.
+	async fn find_transfer_details(
+		&self,
+		function: &str,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<(AssetId, AccountAddress, serde_json::Value)> {
+		let mut last_err = BridgeContractError::CallError;
+		for (asset, module_address) in &self.bridge_modules {
+			match self.view_transfer_details(*module_address, function, bridge_transfer_id).await {
+				Ok(value) => return Ok((asset.clone(), *module_address, value)),
+				Err(err) => last_err = err,
+			}
+		}
+		Err(last_err)
+	}
+
+	/// Gets the bridge modules holding a transfer, without viewing it if there's one asset.
+	async fn bridge_module_of(
+		&self,
+		function: &str,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<AccountAddress> {
+		match self.bridge_modules.as_slice() {
+			[(_, module_address)] => Ok(*module_address),
+			_ => self
+				.find_transfer_details(function, bridge_transfer_id)
+				.await
+				.map(|(_, module_address, _)| module_address),
+		}
+	}
+
+	/// Transfers native gas from the signer account to a recipient.
+	pub async fn transfer_gas(
+		&self,
+		recipient: AccountAddress,
+		amount: u64,
+	) -> Result<(), BridgeContractError> {
+		let args =
+			vec![utils::serialize_address_initiator(&recipient)?, utils::serialize_u64(&amount)?];
+
+		let payload = utils::make_aptos_payload(
+			FRAMEWORK_ADDRESS,
+			"aptos_account",
+			"transfer",
+			Vec::new(),
+			args,
+		);
+
+		utils::send_and_confirm_aptos_transaction(&self.rest_client, self.signer.as_ref(), payload)
+			.await
+			.map_err(|_| BridgeContractError::FundingError)?;
+
+		Ok(())
+	}
+
+	/// Initiates a bridge transfer from the signer account.
+	/// Returns the id of the transfer, read from the initiated event of the transaction.
+	pub async fn initiate_bridge_transfer_with_id(
+		&mut self,
+		recipient: BridgeAddress<Vec<u8>>,
+		hash_lock: HashLock,
+		asset: AssetId,
+		amount: Amount,
+	) -> BridgeContractResult<BridgeTransferId> {
+		let transaction =
+			self.send_initiate_bridge_transfer(recipient, hash_lock, asset, amount).await?;
+		let bridge_transfer_id = utils::extract_bridge_transfer_id(transaction)
+			.ok_or(BridgeContractError::TransferIdExtractionError)?;
+		let bridge_transfer_id = hex::decode(bridge_transfer_id.trim_start_matches("0x"))
+			.map_err(|_| BridgeContractError::TransferIdExtractionError)?;
+		Ok(BridgeTransferId(
+			bridge_transfer_id
+				.try_into()
+				.map_err(|_| BridgeContractError::TransferIdExtractionError)?,
+		))
+	}
+
+	async fn send_initiate_bridge_transfer(
+		&self,
+		recipient: BridgeAddress<Vec<u8>>,
+		hash_lock: HashLock,
+		asset: AssetId,
+		amount: Amount,
+	) -> BridgeContractResult<AptosTransaction> {
+		debug!("Amount value: {:?}", amount);
+		let module_address = self.bridge_module_address(&asset)?;
+		let amount = Self::fa_amount(amount)?;
+
+		let serialized_hash_lock = utils::serialize_vec_initiator(&hash_lock.0[..])?;
+
+		let args = vec![
+			utils::serialize_vec_initiator(&recipient.0)?,
+			serialized_hash_lock,
+			utils::serialize_u64_initiator(&amount)?,
+		];
+
+		let payload = utils::make_aptos_payload(
+			module_address,
+			INITIATOR_MODULE_NAME,
+			"initiate_bridge_transfer",
+			Vec::new(),
+			args,
+		);
+
+		utils::send_and_confirm_aptos_transaction(&self.rest_client, self.signer.as_ref(), payload)
+			.await
+			.map_err(|_| BridgeContractError::InitiateTransferError)
+	}
+
 	pub async fn initiator_set_timelock(
 		&mut self,
 		time_lock: u64,
@@ -119,34 +337,10 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		_initiator: BridgeAddress<MovementAddress>,
 		recipient: BridgeAddress<Vec<u8>>,
 		hash_lock: HashLock,
+		asset: AssetId,
 		amount: Amount,
 	) -> BridgeContractResult<()> {
-		debug!("Amount value: {:?}", amount);
-
-		let serialized_hash_lock = utils::serialize_vec_initiator(&hash_lock.0[..])?;
-
-		let args = vec![
-			utils::serialize_vec_initiator(&recipient.0)?,
-			serialized_hash_lock,
-			utils::serialize_u64_initiator(&amount)?,
-		];
-
-		let payload = utils::make_aptos_payload(
-			FRAMEWORK_ADDRESS,
-			"atomic_bridge_initiator",
-			"initiate_bridge_transfer",
-			Vec::new(),
-			args,
-		);
-
-		let _ = utils::send_and_confirm_aptos_transaction(
-			&self.rest_client,
-			self.signer.as_ref(),
-			payload,
-		)
-		.await
-		.map_err(|_| BridgeContractError::InitiateTransferError)?;
-
+		self.send_initiate_bridge_transfer(recipient, hash_lock, asset, amount).await?;
 		Ok(())
 	}
 
@@ -168,8 +362,10 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			utils::serialize_vec_initiator(unpadded_preimage)?,
 		];
 
+		let module_address =
+			self.bridge_module_of(INITIATOR_DETAILS_FUNCTION, bridge_transfer_id).await?;
 		let payload = utils::make_aptos_payload(
-			FRAMEWORK_ADDRESS,
+			module_address,
 			INITIATOR_MODULE_NAME,
 			"complete_bridge_transfer",
 			Vec::new(),
@@ -204,8 +400,10 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			utils::serialize_vec(&unpadded_preimage)?,
 		];
 
+		let module_address =
+			self.bridge_module_of(COUNTERPARTY_DETAILS_FUNCTION, bridge_transfer_id).await?;
 		let payload = utils::make_aptos_payload(
-			FRAMEWORK_ADDRESS,
+			module_address,
 			COUNTERPARTY_MODULE_NAME,
 			"complete_bridge_transfer",
 			Vec::new(),
@@ -238,9 +436,12 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		hash_lock: HashLock,
 		initiator: BridgeAddress<Vec<u8>>,
 		recipient: BridgeAddress<MovementAddress>,
+		asset: AssetId,
 		amount: Amount,
 	) -> BridgeContractResult<()> {
 		debug!("Starting lock bridge transfer");
+		let module_address = self.bridge_module_address(&asset)?;
+		let amount = Self::fa_amount(amount)?;
 		debug!("Initiator: {:?}", initiator.0);
 
 		let args = vec![
@@ -252,7 +453,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		];
 
 		let payload = utils::make_aptos_payload(
-			FRAMEWORK_ADDRESS,
+			module_address,
 			COUNTERPARTY_MODULE_NAME,
 			"lock_bridge_transfer_assets",
 			Vec::new(),
@@ -276,9 +477,11 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 	) -> BridgeContractResult<()> {
 		let args = vec![utils::serialize_vec_initiator(&bridge_transfer_id.0[..])?];
 
+		let module_address =
+			self.bridge_module_of(INITIATOR_DETAILS_FUNCTION, bridge_transfer_id).await?;
 		let payload = utils::make_aptos_payload(
-			FRAMEWORK_ADDRESS,
-			"atomic_bridge_initiator",
+			module_address,
+			INITIATOR_MODULE_NAME,
 			"refund_bridge_transfer",
 			Vec::new(),
 			args,
@@ -296,8 +499,10 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<()> {
 		let args3 = vec![utils::serialize_vec(&bridge_transfer_id.0[..])?];
+		let module_address =
+			self.bridge_module_of(COUNTERPARTY_DETAILS_FUNCTION, bridge_transfer_id).await?;
 		let payload = utils::make_aptos_payload(
-			FRAMEWORK_ADDRESS,
+			module_address,
 			COUNTERPARTY_MODULE_NAME,
 			"abort_bridge_transfer",
 			Vec::new(),
@@ -313,39 +518,9 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<Option<BridgeTransferDetails<MovementAddress>>> {
-		let bridge_transfer_id_hex = format!("0x{}", hex::encode(bridge_transfer_id.0));
-
-		let view_request = ViewRequest {
-			function: EntryFunctionId {
-				module: MoveModuleId {
-					address: FRAMEWORK_ADDRESS.clone().into(),
-					name: aptos_api_types::IdentifierWrapper(
-						Identifier::new("atomic_bridge_store")
-							.map_err(|_| BridgeContractError::FunctionViewError)?,
-					),
-				},
-				name: aptos_api_types::IdentifierWrapper(
-					Identifier::new("get_bridge_transfer_details_initiator")
-						.map_err(|_| BridgeContractError::FunctionViewError)?,
-				),
-			},
-			type_arguments: vec![],
-			arguments: vec![serde_json::json!(bridge_transfer_id_hex)],
-		};
-
-		let response: Response<Vec<serde_json::Value>> = self
-			.rest_client
-			.view(&view_request, None)
-			.await
-			.map_err(|_| BridgeContractError::CallError)?;
-
-		let values = response.inner();
-
-		if values.len() != 1 {
-			return Err(BridgeContractError::InvalidResponseLength);
-		}
-
-		let value = &values[0];
+		let (asset, _, value) = self
+			.find_transfer_details(INITIATOR_DETAILS_FUNCTION, bridge_transfer_id)
+			.await?;
 
 		let originator_address = AccountAddress::from_hex_literal(
 			value["addresses"]["initiator"]
@@ -386,7 +561,8 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			bridge_transfer_id,
 			initiator: BridgeAddress(MovementAddress(originator_address)),
 			recipient: BridgeAddress(recipient_bytes),
-			amount: Amount(amount),
+			asset,
+			amount: amount.into(),
 			hash_lock: HashLock(hash_lock_array),
 			time_lock: TimeLock(time_lock),
 			state,
@@ -399,39 +575,9 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<Option<BridgeTransferDetailsCounterparty<MovementAddress>>> {
-		let bridge_transfer_id_hex = format!("0x{}", hex::encode(bridge_transfer_id.0));
-
-		let view_request = ViewRequest {
-			function: EntryFunctionId {
-				module: MoveModuleId {
-					address: FRAMEWORK_ADDRESS.clone().into(),
-					name: aptos_api_types::IdentifierWrapper(
-						Identifier::new("atomic_bridge_store")
-							.map_err(|_| BridgeContractError::FunctionViewError)?,
-					),
-				},
-				name: aptos_api_types::IdentifierWrapper(
-					Identifier::new("get_bridge_transfer_details_counterparty")
-						.map_err(|_| BridgeContractError::FunctionViewError)?,
-				),
-			},
-			type_arguments: vec![],
-			arguments: vec![serde_json::json!(bridge_transfer_id_hex)],
-		};
-
-		let response: Response<Vec<serde_json::Value>> = self
-			.rest_client
-			.view(&view_request, None)
-			.await
-			.map_err(|_| BridgeContractError::CallError)?;
-
-		let values = response.inner();
-
-		if values.len() != 1 {
-			return Err(BridgeContractError::InvalidResponseLength);
-		}
-
-		let value = &values[0];
+		let (asset, _, value) = self
+			.find_transfer_details(COUNTERPARTY_DETAILS_FUNCTION, bridge_transfer_id)
+			.await?;
 
 		let originator_address_bytes = hex::decode(
 			&value["addresses"]["initiator"]["inner"]
@@ -472,7 +618,8 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			bridge_transfer_id,
 			initiator: BridgeAddress(originator_address_bytes),
 			recipient: BridgeAddress(MovementAddress(recipient)),
-			amount: Amount(amount),
+			asset,
+			amount: amount.into(),
 			hash_lock: HashLock(hash_lock_array),
 			time_lock: TimeLock(time_lock),
 			state,
@@ -949,6 +1096,7 @@ impl MovementClientFramework {
 				native_address: DUMMY_ADDRESS,
 				rest_client,
 				signer: Arc::new(LocalAccount::generate(&mut rng)),
+				bridge_modules: vec![(AssetId(MovementConfig::default().asset), FRAMEWORK_ADDRESS)],
 			},
 			child,
 		))
@@ -1,38 +1,40 @@
 use super::{
-	client_framework::{MovementClientFramework, FRAMEWORK_ADDRESS},
+	client_framework::{self, MovementClientFramework, FRAMEWORK_ADDRESS},
 	utils::MovementAddress,
 };
 use crate::{
-	chains::bridge_contracts::{
-		BridgeContractError, BridgeContractEvent, BridgeContractEventType,
-		BridgeContractMonitoring, BridgeContractResult,
+	chains::{
+		bridge_contracts::{
+			BridgeContractError, BridgeContractEvent, BridgeContractEventType,
+			BridgeContractMonitoring, BridgeContractResult,
+		},
+		monitoring::{load_store_file, poll_next_event, save_store_file, MonitoringMessage},
 	},
 	types::{
-		Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock, HashLockPreImage,
-		LockDetails, TimeLock,
+		Amount, AssetId, BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock,
+		HashLockPreImage, LockDetails, TimeLock,
 	},
 };
 use anyhow::Result;
 use aptos_sdk::{
 	rest_client::aptos_api_types::VersionedEvent, types::account_address::AccountAddress,
 };
-use bridge_config::common::movement::MovementConfig;
+use bridge_config::common::{movement::MovementConfig, tokens::TokenRegistry};
 
 use futures::{
 	channel::mpsc::{self as futurempsc},
-	SinkExt, Stream, StreamExt,
+	SinkExt, Stream,
 };
 use hex::FromHex;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::{pin::Pin, task::Poll};
-use tokio::fs::{self, File};
-use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+use std::{path::Path, pin::Pin, task::Poll};
+use tokio::io;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 
 const PULL_STATE_FILE_NAME: &str = "pullstate.store";
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct MvtPullingState {
 	initiator_init: u64,
 	initiator_complete: u64,
@@ -43,39 +45,35 @@ struct MvtPullingState {
 }
 
 impl MvtPullingState {
-	async fn save_to_store_file(&self) -> io::Result<()> {
-		let path = MvtPullingState::get_store_file_path();
-		if let Some(parent) = path.parent() {
-			fs::create_dir_all(parent).await?;
-		}
-
-		let json = serde_json::to_string(self)?;
-		let mut file = File::create(path.as_path()).await?;
-		file.write_all(json.as_bytes()).await?;
-		Ok(())
+	async fn save_to_store_file(&self, module_address: AccountAddress) -> io::Result<()> {
+		self.save(&MvtPullingState::get_store_file_path(module_address)).await
 	}
 
 	// Read the state from a JSON file
-	async fn build_from_store_file() -> io::Result<MvtPullingState> {
-		let path = MvtPullingState::get_store_file_path();
-
-		let state = if fs::try_exists(&path).await? {
-			let mut file = File::open(path.as_path()).await?;
-			let mut json = String::new();
-			file.read_to_string(&mut json).await?;
-			let state = serde_json::from_str(&json)?;
-			state
-		} else {
-			// Return a default state if the file does not exist
-			MvtPullingState::default()
-		};
-		Ok(state)
+	async fn build_from_store_file(module_address: AccountAddress) -> io::Result<MvtPullingState> {
+		MvtPullingState::load(&MvtPullingState::get_store_file_path(module_address)).await
 	}
 
-	fn get_store_file_path() -> std::path::PathBuf {
+	async fn save(&self, path: &Path) -> io::Result<()> {
+		save_store_file(self, path).await
+	}
+
+	async fn load(path: &Path) -> io::Result<MvtPullingState> {
+		// Return a default state if the file does not exist
+		Ok(load_store_file(path).await?.unwrap_or_default())
+	}
+
+	/// Gets the store file of the events of the bridge modules at an address, the framework
+	/// modules keep the original store file.
+	fn get_store_file_path(module_address: AccountAddress) -> std::path::PathBuf {
 		let dot_movement = dot_movement::DotMovement::try_from_env()
 			.unwrap_or(dot_movement::DotMovement::new(".movement"));
-		bridge_config::get_config_path(&dot_movement).join(PULL_STATE_FILE_NAME)
+		let file_name = if module_address == FRAMEWORK_ADDRESS {
+			PULL_STATE_FILE_NAME.to_string()
+		} else {
+			format!("pullstate-{}.store", module_address.to_hex())
+		};
+		bridge_config::get_config_path(&dot_movement).join(file_name)
 	}
 
 	fn update_state_with_event(
@@ -135,9 +133,13 @@ impl MvtPullingState {
 	}
 }
 
+/// Streams the events of the Movement bridge modules.
+///
+/// The relayer loop processes and indexes an event before it polls the next one, so the events
+/// sent before a checkpoint are indexed when the stream is polled past it. The pull state is saved
+/// only then, a crash before does not skip events that were never indexed.
 pub struct MovementMonitoring {
-	listener:
-		futurempsc::UnboundedReceiver<BridgeContractResult<BridgeContractEvent<MovementAddress>>>,
+	listener: futurempsc::UnboundedReceiver<MonitoringMessage<MovementAddress>>,
 }
 
 impl BridgeContractMonitoring for MovementMonitoring {
@@ -145,17 +147,30 @@ impl BridgeContractMonitoring for MovementMonitoring {
 }
 
 impl MovementMonitoring {
+	/// Monitors the framework modules.
 	pub async fn build(
 		config: &MovementConfig,
+		health_check_rx: mpsc::Receiver<oneshot::Sender<bool>>,
+	) -> Result<Self, anyhow::Error> {
+		Self::build_with_tokens(config, &TokenRegistry { tokens: vec![] }, health_check_rx).await
+	}
+
+	/// Monitors the framework modules and the modules of the registered tokens.
+	pub async fn build_with_tokens(
+		config: &MovementConfig,
+		tokens: &TokenRegistry,
 		mut health_check_rx: mpsc::Receiver<oneshot::Sender<bool>>,
 	) -> Result<Self, anyhow::Error> {
 		// Spawn a task to forward events to the listener channel
-		let (mut sender, listener) = futures::channel::mpsc::unbounded::<
-			BridgeContractResult<BridgeContractEvent<MovementAddress>>,
-		>();
-
-		//read the pull state
-		let mut pull_state = MvtPullingState::build_from_store_file().await?;
+		let (mut sender, listener) =
+			futures::channel::mpsc::unbounded::<MonitoringMessage<MovementAddress>>();
+
+		//read the pull state of the modules of each asset
+		let mut modules = Vec::new();
+		for (asset, module_address) in client_framework::bridge_modules(config, tokens)? {
+			let pull_state = MvtPullingState::build_from_store_file(module_address).await?;
+			modules.push((asset, module_address, pull_state));
+		}
 
 		tokio::spawn({
 			let config = config.clone();
@@ -176,58 +191,75 @@ impl MovementMonitoring {
 						}
 					}
 
-					let mut init_event_list = match pool_initiator_contract(
-						FRAMEWORK_ADDRESS,
-						&config.mvt_rpc_connection_url(),
-						&pull_state,
-						config.rest_connection_timeout_secs,
-					)
-					.await
-					{
-						Ok(evs) => evs.into_iter().map(|ev| Ok(ev)).collect(),
-						Err(err) => vec![Err(err)],
-					};
-					let mut counterpart_event_list = match pool_counterparty_contract(
-						FRAMEWORK_ADDRESS,
-						&config.mvt_rpc_connection_url(),
-						&pull_state,
-						config.rest_connection_timeout_secs,
-					)
-					.await
-					{
-						Ok(evs) => evs.into_iter().map(|ev| Ok(ev)).collect(),
-						Err(err) => vec![Err(err)],
-					};
-
-					//extract event sequence_number and update pull state
-					let (event_list, new_pull_state) =
-						init_event_list.drain(..).chain(counterpart_event_list.drain(..)).fold(
-							(Vec::new(), pull_state.clone()),
-							|(mut events, mut state), event| {
-								match event {
-									Ok((ev, seq)) => {
-										state.update_state_with_event(&ev, seq);
-										events.push(Ok(ev));
+					for (asset, module_address, pull_state) in modules.iter_mut() {
+						let mut init_event_list = match pool_initiator_contract(
+							*module_address,
+							&config.mvt_rpc_connection_url(),
+							pull_state,
+							config.rest_connection_timeout_secs,
+							asset,
+						)
+						.await
+						{
+							Ok(evs) => evs.into_iter().map(|ev| Ok(ev)).collect(),
+							Err(err) => vec![Err(err)],
+						};
+						let mut counterpart_event_list = match pool_counterparty_contract(
+							*module_address,
+							&config.mvt_rpc_connection_url(),
+							pull_state,
+							config.rest_connection_timeout_secs,
+							asset,
+						)
+						.await
+						{
+							Ok(evs) => evs.into_iter().map(|ev| Ok(ev)).collect(),
+							Err(err) => vec![Err(err)],
+						};
+
+						//extract event sequence_number and update pull state
+						let (event_list, new_pull_state) =
+							init_event_list.drain(..).chain(counterpart_event_list.drain(..)).fold(
+								(Vec::new(), pull_state.clone()),
+								|(mut events, mut state), event| {
+									match event {
+										Ok((ev, seq)) => {
+											state.update_state_with_event(&ev, seq);
+											events.push(Ok(ev));
+										}
+										Err(err) => {
+											state.update_state_with_error(&err);
+											events.push(Err(err));
+										}
 									}
-									Err(err) => {
-										state.update_state_with_error(&err);
-										events.push(Err(err));
-									}
-								}
-								(events, state)
-							},
-						);
-
-					for event in event_list {
-						if sender.send(event).await.is_err() {
-							tracing::error!("Failed to send event to listener channel");
-							break;
+									(events, state)
+								},
+							);
+
+						for event in event_list {
+							if sender.send(MonitoringMessage::Event(event)).await.is_err() {
+								tracing::error!("Failed to send event to listener channel");
+								break;
+							}
+						}
+						if new_pull_state != *pull_state {
+							*pull_state = new_pull_state;
+							// Wait for the events to be indexed before saving the pull state.
+							let (checkpoint_tx, checkpoint_rx) = oneshot::channel();
+							if sender
+								.send(MonitoringMessage::Checkpoint(checkpoint_tx))
+								.await
+								.is_err() || checkpoint_rx.await.is_err()
+							{
+								tracing::error!(
+									"MVT monitoring listener closed before the events were indexed"
+								);
+								return;
+							}
+							if let Err(err) = pull_state.save_to_store_file(*module_address).await {
+								tracing::error!("MVT monitoring unable to store the file state because:{err} for state:{pull_state:?}");
+							}
 						}
-					}
-					pull_state = new_pull_state;
-
-					if let Err(err) = pull_state.save_to_store_file().await {
-						tracing::error!("MVT monitoring unable to store the file state because:{err} for state:{pull_state:?}");
 					}
 					let _ = tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 				}
@@ -242,8 +274,7 @@ impl Stream for MovementMonitoring {
 	type Item = BridgeContractResult<BridgeContractEvent<MovementAddress>>;
 
 	fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Option<Self::Item>> {
-		let this = self.get_mut();
-		this.listener.poll_next_unpin(cx)
+		poll_next_event(&mut self.get_mut().listener, cx)
 	}
 }
 
@@ -262,6 +293,7 @@ async fn pool_initiator_contract(
 	rest_url: &str,
 	pull_state: &MvtPullingState,
 	timeout_sec: u64,
+	asset: &AssetId,
 ) -> BridgeContractResult<Vec<(BridgeContractEvent<MovementAddress>, u64)>> {
 	let struct_tag = format!(
 		"{}::atomic_bridge_initiator::BridgeInitiatorEvents",
@@ -280,7 +312,7 @@ async fn pool_initiator_contract(
 	.into_iter()
 	.map(|e| {
 		let data: BridgeInitEventData = serde_json::from_str(&e.data.to_string())?;
-		let transfer_details = BridgeTransferDetails::try_from(data)?;
+		let transfer_details = BridgeTransferDetails::try_from((data, asset.clone()))?;
 		Ok((BridgeContractEvent::Initiated(transfer_details), e.sequence_number.into()))
 	})
 	.collect::<Result<Vec<_>>>()
@@ -364,10 +396,11 @@ async fn pool_counterparty_contract(
 	rest_url: &str,
 	pull_state: &MvtPullingState,
 	timeout_sec: u64,
+	asset: &AssetId,
 ) -> BridgeContractResult<Vec<(BridgeContractEvent<MovementAddress>, u64)>> {
 	let struct_tag = format!(
 		"{}::atomic_bridge_counterparty::BridgeCounterpartyEvents",
-		FRAMEWORK_ADDRESS.to_string()
+		framework_address.to_string()
 	);
 
 	// Get locked events
@@ -383,7 +416,7 @@ async fn pool_counterparty_contract(
 	.into_iter()
 	.map(|e| {
 		let data: BridgeInitEventData = serde_json::from_str(&e.data.to_string())?;
-		let transfer_details = LockDetails::try_from(data)?;
+		let transfer_details = LockDetails::try_from((data, asset.clone()))?;
 		println!("Transfer details: {:?}", transfer_details);
 		Ok((BridgeContractEvent::Locked(transfer_details), e.sequence_number.into()))
 	})
@@ -514,10 +547,10 @@ where
 	s.parse::<u64>().map_err(serde::de::Error::custom)
 }
 
-impl TryFrom<BridgeInitEventData> for BridgeTransferDetails<MovementAddress> {
+impl TryFrom<(BridgeInitEventData, AssetId)> for BridgeTransferDetails<MovementAddress> {
 	type Error = BridgeContractError;
 
-	fn try_from(data: BridgeInitEventData) -> Result<Self, Self::Error> {
+	fn try_from((data, asset): (BridgeInitEventData, AssetId)) -> Result<Self, Self::Error> {
 		Ok(BridgeTransferDetails {
 			bridge_transfer_id: BridgeTransferId(data.bridge_transfer_id.try_into().map_err(
 				|e| {
@@ -539,16 +572,17 @@ impl TryFrom<BridgeInitEventData> for BridgeTransferDetails<MovementAddress> {
 				))
 			})?),
 			time_lock: TimeLock(data.time_lock),
-			amount: Amount(data.amount),
+			asset,
+			amount: data.amount.into(),
 			state: 0,
 		})
 	}
 }
 
-impl TryFrom<BridgeInitEventData> for LockDetails<MovementAddress> {
+impl TryFrom<(BridgeInitEventData, AssetId)> for LockDetails<MovementAddress> {
 	type Error = BridgeContractError;
 
-	fn try_from(data: BridgeInitEventData) -> Result<Self, Self::Error> {
+	fn try_from((data, asset): (BridgeInitEventData, AssetId)) -> Result<Self, Self::Error> {
 		Ok(LockDetails {
 			bridge_transfer_id: BridgeTransferId(data.bridge_transfer_id.try_into().map_err(
 				|e| {
@@ -570,7 +604,8 @@ impl TryFrom<BridgeInitEventData> for LockDetails<MovementAddress> {
 				))
 			})?),
 			time_lock: TimeLock(data.time_lock),
-			amount: Amount(data.amount),
+			asset,
+			amount: data.amount.into(),
 		})
 	}
 }
@@ -667,3 +702,57 @@ async fn get_account_events(
 		)))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::StreamExt;
+	use tokio::fs;
+
+	#[tokio::test]
+	async fn test_pull_state_store_round_trip() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join(PULL_STATE_FILE_NAME);
+
+		assert_eq!(MvtPullingState::load(&path).await?, MvtPullingState::default());
+
+		let mut state = MvtPullingState::default();
+		state.initiator_init = 3;
+		state.counterpart_lock = 5;
+		state.save(&path).await?;
+		assert_eq!(MvtPullingState::load(&path).await?, state);
+		assert!(!fs::try_exists(path.with_extension("store.tmp")).await?);
+
+		state.counterpart_lock = 6;
+		state.save(&path).await?;
+		assert_eq!(MvtPullingState::load(&path).await?, state);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_checkpoint_waits_for_the_events_to_be_processed() -> Result<(), anyhow::Error> {
+		let (mut sender, listener) = futurempsc::unbounded();
+		let mut monitoring = MovementMonitoring { listener };
+		let (checkpoint_tx, mut checkpoint_rx) = oneshot::channel();
+		let transfer_id = BridgeTransferId([1; 32]);
+		sender
+			.send(MonitoringMessage::Event(Ok(BridgeContractEvent::Refunded(transfer_id))))
+			.await?;
+		sender.send(MonitoringMessage::Checkpoint(checkpoint_tx)).await?;
+
+		// The event is being processed, the checkpoint is not acknowledged yet.
+		assert!(matches!(
+			monitoring.next().await,
+			Some(Ok(BridgeContractEvent::Refunded(id))) if id == transfer_id
+		));
+		assert!(checkpoint_rx.try_recv().is_err());
+
+		// Polling past the checkpoint acknowledges it.
+		drop(sender);
+		assert!(monitoring.next().await.is_none());
+		checkpoint_rx.await?;
+
+		Ok(())
+	}
+}
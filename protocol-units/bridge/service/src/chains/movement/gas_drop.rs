@@ -0,0 +1,304 @@
+//! Drops native gas to the recipients of the transfers to Movement, so they can pay the fees
+//! of their first transactions with the bridged funds.
+//!
+//! The budget and the last drops are persisted in the indexer database, so a restart of the
+//! relayer does not reset them.
+
+use super::client_framework::MovementClientFramework;
+use crate::time_lock::unix_now;
+use aptos_sdk::types::account_address::AccountAddress;
+use bridge_config::common::movement::MovementConfig;
+use bridge_indexer_db::client::{Client, GasDropState};
+use bridge_util::{chains::bridge_contracts::BridgeContractError, types::BridgeAddress};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+const BUDGET_PERIOD_SECS: u64 = 86_400;
+
+/// The decision of the policy for a drop to a recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasDropDecision {
+	/// Drop the amount to the recipient.
+	Drop(u64),
+	/// The recipient opted out of the gas drop.
+	RecipientOptedOut,
+	/// The recipient already received a drop during the rate limit interval.
+	RecipientRateLimited,
+	/// The drop would exceed the daily budget.
+	BudgetExhausted,
+}
+
+/// Limits the gas dropped per recipient and per day, the times are unix timestamps in seconds.
+pub struct GasDropPolicy {
+	amount: u64,
+	recipient_interval_secs: u64,
+	daily_budget: u64,
+	opted_out: HashSet<AccountAddress>,
+	last_drops: HashMap<AccountAddress, u64>,
+	budget_period_start: u64,
+	spent: u64,
+}
+
+impl GasDropPolicy {
+	pub fn new(amount: u64, recipient_interval_secs: u64, daily_budget: u64, now: u64) -> Self {
+		GasDropPolicy {
+			amount,
+			recipient_interval_secs,
+			daily_budget,
+			opted_out: HashSet::new(),
+			last_drops: HashMap::new(),
+			budget_period_start: now,
+			spent: 0,
+		}
+	}
+
+	pub fn from_config(config: &MovementConfig, now: u64) -> Result<Self, anyhow::Error> {
+		let mut policy = GasDropPolicy::new(
+			config.gas_drop_amount,
+			config.gas_drop_recipient_interval_secs,
+			config.gas_drop_daily_budget,
+			now,
+		);
+		for recipient in &config.gas_drop_opt_out_recipients {
+			let recipient = AccountAddress::from_hex_literal(recipient).map_err(|e| {
+				anyhow::anyhow!("Failed to parse gas drop opt-out recipient {}: {:?}", recipient, e)
+			})?;
+			policy.opt_out(recipient);
+		}
+		Ok(policy)
+	}
+
+	/// Excludes a recipient from the gas drop.
+	pub fn opt_out(&mut self, recipient: AccountAddress) {
+		self.opted_out.insert(recipient);
+	}
+
+	/// Restores the budget and the last drops persisted before a restart.
+	pub fn restore(&mut self, state: GasDropState) -> Result<(), anyhow::Error> {
+		self.budget_period_start = state.period_start;
+		self.spent = state.spent;
+		self.last_drops = state
+			.last_drops
+			.into_iter()
+			.map(|(recipient, last_drop)| {
+				let recipient = AccountAddress::from_bytes(&recipient)
+					.map_err(|e| anyhow::anyhow!("Failed to decode gas drop recipient: {:?}", e))?;
+				Ok((recipient, last_drop))
+			})
+			.collect::<Result<_, anyhow::Error>>()?;
+		Ok(())
+	}
+
+	/// Gets the budget and the last drops to persist.
+	pub fn state(&self) -> GasDropState {
+		GasDropState {
+			period_start: self.budget_period_start,
+			spent: self.spent,
+			last_drops: self
+				.last_drops
+				.iter()
+				.map(|(recipient, last_drop)| (recipient.to_vec(), *last_drop))
+				.collect(),
+		}
+	}
+
+	/// Decides whether to drop gas to a recipient, reserving the amount if so.
+	pub fn reserve(&mut self, recipient: AccountAddress, now: u64) -> GasDropDecision {
+		if self.opted_out.contains(&recipient) {
+			return GasDropDecision::RecipientOptedOut;
+		}
+		if now.saturating_sub(self.budget_period_start) >= BUDGET_PERIOD_SECS {
+			self.budget_period_start = now;
+			self.spent = 0;
+		}
+		let recipient_interval_secs = self.recipient_interval_secs;
+		self.last_drops
+			.retain(|_, last_drop| now.saturating_sub(*last_drop) < recipient_interval_secs);
+
+		if self.last_drops.contains_key(&recipient) {
+			return GasDropDecision::RecipientRateLimited;
+		}
+		match self.spent.checked_add(self.amount) {
+			Some(spent) if spent <= self.daily_budget => {
+				self.spent = spent;
+				self.last_drops.insert(recipient, now);
+				GasDropDecision::Drop(self.amount)
+			}
+			_ => GasDropDecision::BudgetExhausted,
+		}
+	}
+
+	/// Releases the amount reserved for a drop that failed.
+	pub fn release(&mut self, recipient: AccountAddress, amount: u64) {
+		self.spent = self.spent.saturating_sub(amount);
+		self.last_drops.remove(&recipient);
+	}
+}
+
+/// The policy with the indexer database it is persisted in.
+struct GasDropStore {
+	policy: GasDropPolicy,
+	indexer_db_client: Option<Client>,
+}
+
+impl GasDropStore {
+	fn save(&mut self) -> Result<(), BridgeContractError> {
+		if let Some(client) = &mut self.indexer_db_client {
+			client.save_gas_drop_state(&self.policy.state()).map_err(|err| {
+				BridgeContractError::GenericError(format!("Failed to save gas drop state: {err}"))
+			})?;
+		}
+		Ok(())
+	}
+}
+
+/// Transfers native gas from the relayer account according to the policy.
+#[derive(Clone)]
+pub struct GasDropper {
+	client: MovementClientFramework,
+	store: Arc<Mutex<GasDropStore>>,
+}
+
+impl GasDropper {
+	/// Creates a dropper persisting its policy in the indexer database, if any. The policy is
+	/// restored from the state persisted before.
+	pub fn new(
+		client: MovementClientFramework,
+		mut policy: GasDropPolicy,
+		mut indexer_db_client: Option<Client>,
+	) -> Result<Self, anyhow::Error> {
+		if let Some(db_client) = &mut indexer_db_client {
+			if let Some(state) = db_client.find_gas_drop_state()? {
+				policy.restore(state)?;
+			}
+		}
+		let store = GasDropStore { policy, indexer_db_client };
+		Ok(GasDropper { client, store: Arc::new(Mutex::new(store)) })
+	}
+
+	/// Drops gas to a recipient, unless the policy rejects it.
+	pub async fn drop_gas(
+		&self,
+		recipient: &BridgeAddress<Vec<u8>>,
+	) -> Result<(), BridgeContractError> {
+		let recipient = AccountAddress::from_bytes(&recipient.0)
+			.map_err(|_| BridgeContractError::SerializationError)?;
+
+		// The reservation is persisted before the drop, a restart does not drop twice.
+		let decision = {
+			let mut store = self.store.lock().await;
+			let decision = store.policy.reserve(recipient, unix_now());
+			if let GasDropDecision::Drop(amount) = decision {
+				if let Err(err) = store.save() {
+					store.policy.release(recipient, amount);
+					return Err(err);
+				}
+			}
+			decision
+		};
+		match decision {
+			GasDropDecision::Drop(amount) => {
+				if let Err(err) = self.client.transfer_gas(recipient, amount).await {
+					let mut store = self.store.lock().await;
+					store.policy.release(recipient, amount);
+					if let Err(save_err) = store.save() {
+						tracing::warn!("Failed to release gas drop to {recipient}: {save_err}");
+					}
+					return Err(err);
+				}
+				tracing::info!("Dropped {amount} gas to recipient {recipient}");
+			}
+			GasDropDecision::RecipientOptedOut => {
+				tracing::info!("Skip gas drop to recipient {recipient}: opted out");
+			}
+			GasDropDecision::RecipientRateLimited => {
+				tracing::info!("Skip gas drop to recipient {recipient}: rate limited");
+			}
+			GasDropDecision::BudgetExhausted => {
+				tracing::warn!("Skip gas drop to recipient {recipient}: daily budget exhausted");
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const NOW: u64 = 1_700_000_000;
+
+	#[test]
+	fn test_recipient_rate_limit() {
+		let mut policy = GasDropPolicy::new(10, 60, 1_000, NOW);
+		let recipient = AccountAddress::new([1; 32]);
+
+		assert_eq!(policy.reserve(recipient, NOW), GasDropDecision::Drop(10));
+		assert_eq!(policy.reserve(recipient, NOW + 59), GasDropDecision::RecipientRateLimited);
+		assert_eq!(
+			policy.reserve(AccountAddress::new([2; 32]), NOW + 59),
+			GasDropDecision::Drop(10)
+		);
+		assert_eq!(policy.reserve(recipient, NOW + 60), GasDropDecision::Drop(10));
+	}
+
+	#[test]
+	fn test_daily_budget() {
+		let mut policy = GasDropPolicy::new(10, 60, 20, NOW);
+
+		assert_eq!(policy.reserve(AccountAddress::new([1; 32]), NOW), GasDropDecision::Drop(10));
+		assert_eq!(policy.reserve(AccountAddress::new([2; 32]), NOW), GasDropDecision::Drop(10));
+		assert_eq!(
+			policy.reserve(AccountAddress::new([3; 32]), NOW),
+			GasDropDecision::BudgetExhausted
+		);
+
+		// A failed drop gives its amount back to the budget.
+		policy.release(AccountAddress::new([2; 32]), 10);
+		assert_eq!(policy.reserve(AccountAddress::new([3; 32]), NOW), GasDropDecision::Drop(10));
+
+		assert_eq!(
+			policy.reserve(AccountAddress::new([4; 32]), NOW + BUDGET_PERIOD_SECS),
+			GasDropDecision::Drop(10)
+		);
+	}
+
+	#[test]
+	fn test_recipient_opt_out() -> Result<(), anyhow::Error> {
+		let config = MovementConfig {
+			gas_drop_amount: 10,
+			gas_drop_opt_out_recipients: vec!["0x1".to_string()],
+			..MovementConfig::for_test()
+		};
+		let mut policy = GasDropPolicy::from_config(&config, NOW)?;
+
+		assert_eq!(policy.reserve(AccountAddress::ONE, NOW), GasDropDecision::RecipientOptedOut);
+		assert_eq!(policy.reserve(AccountAddress::new([2; 32]), NOW), GasDropDecision::Drop(10));
+		Ok(())
+	}
+
+	#[test]
+	fn test_restore_persisted_state() -> Result<(), anyhow::Error> {
+		let mut policy = GasDropPolicy::new(10, 60, 20, NOW);
+		assert_eq!(policy.reserve(AccountAddress::new([1; 32]), NOW), GasDropDecision::Drop(10));
+		assert_eq!(policy.reserve(AccountAddress::new([2; 32]), NOW), GasDropDecision::Drop(10));
+
+		// A restarted relayer keeps the spent budget and the rate limits of the recipients.
+		let mut restarted = GasDropPolicy::new(10, 60, 20, NOW + 30);
+		restarted.restore(policy.state())?;
+		assert_eq!(
+			restarted.reserve(AccountAddress::new([1; 32]), NOW + 30),
+			GasDropDecision::RecipientRateLimited
+		);
+		assert_eq!(
+			restarted.reserve(AccountAddress::new([3; 32]), NOW + 30),
+			GasDropDecision::BudgetExhausted
+		);
+		assert_eq!(
+			restarted.reserve(AccountAddress::new([3; 32]), NOW + BUDGET_PERIOD_SECS),
+			GasDropDecision::Drop(10)
+		);
+		Ok(())
+	}
+}
@@ -1,3 +1,4 @@
 pub mod client_framework;
 pub mod event_monitoring;
+pub mod gas_drop;
 pub mod utils;
@@ -11,7 +11,7 @@ use aptos_sdk::{
 			EntryFunctionId, MoveType, Transaction as AptosTransaction, TransactionInfo,
 			ViewRequest,
 		},
-		Client as RestClient, FaucetClient, Transaction,
+		Client as RestClient, Transaction,
 	},
 	transaction_builder::TransactionFactory,
 	types::{
@@ -34,14 +34,10 @@ use std::str::FromStr;
 use thiserror::Error;
 use tiny_keccak::{Hasher, Keccak};
 use tracing::log::{error, info};
-use url::Url;
 
 use super::client_framework::MovementClientFramework;
 pub type TestRng = StdRng;
 
-const MOVEMENT_RPC_URL: &str = "https://testnet.bardock.movementnetwork.xyz";
-const MOVEMENT_FAUCET_URL: &str = "https://faucet.testnet.bardock.movementnetwork.xyz";
-
 pub trait RngSeededClone: Rng + SeedableRng {
 	fn seeded_clone(&mut self) -> Self;
 }
@@ -424,29 +420,3 @@ pub fn to_eip55(address: &str) -> String {
 		})
 		.collect()
 }
-
-pub async fn fund_recipient(recipient: &BridgeAddress<Vec<u8>>) -> Result<(), BridgeContractError> {
-	// Parse URLs
-	let faucet_url =
-		Url::parse(MOVEMENT_FAUCET_URL).map_err(|_| BridgeContractError::InvalidUrl)?;
-	let rest_url = Url::parse(MOVEMENT_RPC_URL).map_err(|_| BridgeContractError::InvalidUrl)?;
-
-	// Create clients
-	let faucet_client = FaucetClient::new(faucet_url, rest_url);
-
-	// Convert recipient to AccountAddress
-	let recipient: [u8; 32] = recipient
-		.0
-		.clone()
-		.try_into()
-		.map_err(|_| BridgeContractError::SerializationError)?;
-	let account_address = AccountAddress::new(recipient);
-
-	// Execute the funding transaction
-	faucet_client
-		.fund(account_address, 100_000_000)
-		.await
-		.map_err(|_| BridgeContractError::FundingError)?;
-
-	Ok(())
-}
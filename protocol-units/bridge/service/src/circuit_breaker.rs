@@ -0,0 +1,492 @@
+//! Enforces the safety limits of the relayer. When a limit is exceeded the circuit breaker trips
+//! and the locks of the new transfers are paused, until an admin resets it.
+
+use bridge_config::common::limits::LimitsConfig;
+use bridge_indexer_db::client::LockedVolume;
+use bridge_util::types::{Amount, AssetId, BridgeTransferId, ChainId};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use tokio::sync::oneshot;
+
+const HOUR_SECS: u64 = 3_600;
+/// The window of the daily volume caps, the older transfers no longer count.
+pub const DAY_SECS: u64 = 86_400;
+
+/// The reason why the circuit breaker tripped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TripReason {
+	/// A transfer exceeds the maximum amount of its asset.
+	MaxTransferAmount { transfer_id: BridgeTransferId, asset: AssetId, amount: u128 },
+	/// The volume of an asset initiated on a chain in the last hour exceeds its cap.
+	HourlyVolumeCap(AssetId, ChainId),
+	/// The volume of an asset initiated on a chain in the last day exceeds its cap.
+	DailyVolumeCap(AssetId, ChainId),
+	/// Too many invalid events were received within the window.
+	ValidationFailures(usize),
+}
+
+impl fmt::Display for TripReason {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			TripReason::MaxTransferAmount { transfer_id, asset, amount } => {
+				write!(
+					f,
+					"Transfer {transfer_id} amount {amount} {asset} exceeds the maximum amount"
+				)
+			}
+			TripReason::HourlyVolumeCap(asset, chain) => {
+				write!(f, "Hourly volume cap of {asset} on chain {chain} exceeded")
+			}
+			TripReason::DailyVolumeCap(asset, chain) => {
+				write!(f, "Daily volume cap of {asset} on chain {chain} exceeded")
+			}
+			TripReason::ValidationFailures(count) => {
+				write!(f, "{count} invalid events received within the window")
+			}
+		}
+	}
+}
+
+impl TripReason {
+	/// Encodes the reason to persist it, it is decoded with `FromStr`.
+	/// The asset id comes last, it is the only field that can contain the separator.
+	pub fn encode(&self) -> String {
+		match self {
+			TripReason::MaxTransferAmount { transfer_id, asset, amount } => {
+				format!("MaxTransferAmount:{}:{amount}:{asset}", hex::encode(transfer_id.0))
+			}
+			TripReason::HourlyVolumeCap(asset, chain) => format!("HourlyVolumeCap:{chain}:{asset}"),
+			TripReason::DailyVolumeCap(asset, chain) => format!("DailyVolumeCap:{chain}:{asset}"),
+			TripReason::ValidationFailures(count) => format!("ValidationFailures:{count}"),
+		}
+	}
+}
+
+impl FromStr for TripReason {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = || anyhow::anyhow!("Invalid circuit breaker trip reason: {}", s);
+		let (kind, fields) = s.split_once(':').ok_or_else(invalid)?;
+		match kind {
+			"MaxTransferAmount" => {
+				let mut fields = fields.splitn(3, ':');
+				let (Some(transfer_id), Some(amount), Some(asset)) =
+					(fields.next(), fields.next(), fields.next())
+				else {
+					return Err(invalid());
+				};
+				Ok(TripReason::MaxTransferAmount {
+					transfer_id: BridgeTransferId::parse(transfer_id).map_err(|_| invalid())?,
+					asset: AssetId::from(asset),
+					amount: amount.parse().map_err(|_| invalid())?,
+				})
+			}
+			"HourlyVolumeCap" | "DailyVolumeCap" => {
+				let (chain, asset) = fields.split_once(':').ok_or_else(invalid)?;
+				let (asset, chain) = (AssetId::from(asset), chain.parse().map_err(|_| invalid())?);
+				if kind == "HourlyVolumeCap" {
+					Ok(TripReason::HourlyVolumeCap(asset, chain))
+				} else {
+					Ok(TripReason::DailyVolumeCap(asset, chain))
+				}
+			}
+			"ValidationFailures" => {
+				Ok(TripReason::ValidationFailures(fields.parse().map_err(|_| invalid())?))
+			}
+			_ => Err(invalid()),
+		}
+	}
+}
+
+/// The volumes of an asset initiated on a chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetVolume {
+	pub asset: AssetId,
+	pub chain: ChainId,
+	pub hourly: u128,
+	pub daily: u128,
+}
+
+/// The state of the circuit breaker, reported to the admin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerStatus {
+	pub trip_reason: Option<TripReason>,
+	pub paused_transfers: Vec<BridgeTransferId>,
+	/// The volumes of the assets locked in the last day, by asset and initiating chain.
+	pub volumes: Vec<AssetVolume>,
+	pub recent_validation_failures: usize,
+}
+
+impl CircuitBreakerStatus {
+	/// Gets the volumes of an asset initiated on a chain, zero if none was locked.
+	pub fn volume(&self, asset: &AssetId, chain: ChainId) -> (u128, u128) {
+		self.volumes
+			.iter()
+			.find(|volume| volume.asset == *asset && volume.chain == chain)
+			.map(|volume| (volume.hourly, volume.daily))
+			.unwrap_or_default()
+	}
+}
+
+/// The admin commands of the circuit breaker, executed by the relayer loop.
+#[derive(Debug)]
+pub enum CircuitBreakerCommand {
+	Status(oneshot::Sender<CircuitBreakerStatus>),
+	/// Resets the circuit breaker. The paused locks are executed if they are released, otherwise
+	/// they are dropped and the initiators are refunded once their time lock expires.
+	Reset {
+		release_paused_transfers: bool,
+		status_tx: oneshot::Sender<CircuitBreakerStatus>,
+	},
+}
+
+/// Tracks the volumes of the locked transfers and the invalid events against the limits.
+/// The times are unix timestamps in seconds.
+pub struct CircuitBreaker {
+	limits: LimitsConfig,
+	/// The transfers locked in the last day, the oldest first.
+	transfers: VecDeque<LockedVolume>,
+	validation_failures: VecDeque<u64>,
+	trip_reason: Option<TripReason>,
+}
+
+impl CircuitBreaker {
+	pub fn new(limits: LimitsConfig) -> Self {
+		CircuitBreaker {
+			limits,
+			transfers: VecDeque::new(),
+			validation_failures: VecDeque::new(),
+			trip_reason: None,
+		}
+	}
+
+	/// Restores the trip reason and the volumes persisted before a restart.
+	pub fn restore(&mut self, trip_reason: Option<TripReason>, transfers: Vec<LockedVolume>) {
+		self.trip_reason = trip_reason;
+		self.transfers = transfers.into();
+	}
+
+	pub fn trip_reason(&self) -> Option<&TripReason> {
+		self.trip_reason.as_ref()
+	}
+
+	/// Checks that a transfer of an asset initiated on `chain` can be locked. The circuit
+	/// breaker trips if the transfer exceeds a limit of its asset. A transfer already counted in
+	/// the volumes, resumed after a restart, is not checked again.
+	pub fn check_transfer(
+		&mut self,
+		chain: ChainId,
+		transfer_id: BridgeTransferId,
+		asset: &AssetId,
+		amount: u128,
+		now: u64,
+	) -> Result<(), TripReason> {
+		if let Some(reason) = &self.trip_reason {
+			return Err(reason.clone());
+		}
+		self.prune(now);
+		if self.is_counted(transfer_id) {
+			return Ok(());
+		}
+		let limits = self.limits.for_asset(&asset.0);
+		let (hourly_cap, daily_cap) = match chain {
+			ChainId::ONE => (limits.eth_hourly_volume_cap, limits.eth_daily_volume_cap),
+			ChainId::TWO => (limits.mvt_hourly_volume_cap, limits.mvt_daily_volume_cap),
+		};
+		let reason = if amount > limits.max_transfer_amount {
+			TripReason::MaxTransferAmount { transfer_id, asset: asset.clone(), amount }
+		} else if self.volume(asset, chain, HOUR_SECS, now).saturating_add(amount) > hourly_cap {
+			TripReason::HourlyVolumeCap(asset.clone(), chain)
+		} else if self.volume(asset, chain, DAY_SECS, now).saturating_add(amount) > daily_cap {
+			TripReason::DailyVolumeCap(asset.clone(), chain)
+		} else {
+			return Ok(());
+		};
+		self.trip_reason = Some(reason.clone());
+		Err(reason)
+	}
+
+	/// Counts a locked transfer in the volumes, without checking the limits. Returns the volume
+	/// to persist, `None` if the transfer was already counted.
+	pub fn record_transfer(
+		&mut self,
+		chain: ChainId,
+		transfer_id: BridgeTransferId,
+		asset: &AssetId,
+		amount: u128,
+		now: u64,
+	) -> Option<LockedVolume> {
+		if self.is_counted(transfer_id) {
+			return None;
+		}
+		let volume = LockedVolume {
+			bridge_transfer_id: transfer_id,
+			locked_at: now,
+			asset: asset.clone(),
+			init_chain: chain,
+			amount: Amount(amount),
+		};
+		self.transfers.push_back(volume.clone());
+		Some(volume)
+	}
+
+	/// Records an invalid event. Returns the trip reason if the circuit breaker trips on it.
+	pub fn record_validation_failure(&mut self, now: u64) -> Option<TripReason> {
+		self.validation_failures.push_back(now);
+		self.prune(now);
+		let count = self.validation_failures.len();
+		if self.trip_reason.is_some() || count < self.limits.max_validation_failures as usize {
+			return None;
+		}
+		let reason = TripReason::ValidationFailures(count);
+		self.trip_reason = Some(reason.clone());
+		Some(reason)
+	}
+
+	/// Closes the circuit breaker. The volumes are kept, so the caps still apply.
+	pub fn reset(&mut self) {
+		self.trip_reason = None;
+		self.validation_failures.clear();
+	}
+
+	pub fn status(
+		&mut self,
+		paused_transfers: Vec<BridgeTransferId>,
+		now: u64,
+	) -> CircuitBreakerStatus {
+		self.prune(now);
+		let mut volumes = BTreeMap::new();
+		for transfer in &self.transfers {
+			volumes
+				.entry((transfer.asset.0.clone(), transfer.init_chain))
+				.or_insert_with(|| AssetVolume {
+					asset: transfer.asset.clone(),
+					chain: transfer.init_chain,
+					hourly: self.volume(&transfer.asset, transfer.init_chain, HOUR_SECS, now),
+					daily: self.volume(&transfer.asset, transfer.init_chain, DAY_SECS, now),
+				});
+		}
+		CircuitBreakerStatus {
+			trip_reason: self.trip_reason.clone(),
+			paused_transfers,
+			volumes: volumes.into_values().collect(),
+			recent_validation_failures: self.validation_failures.len(),
+		}
+	}
+
+	fn is_counted(&self, transfer_id: BridgeTransferId) -> bool {
+		self.transfers.iter().any(|transfer| transfer.bridge_transfer_id == transfer_id)
+	}
+
+	/// Gets the volume of an asset initiated on a chain within the window before `now`.
+	fn volume(&self, asset: &AssetId, chain: ChainId, window: u64, now: u64) -> u128 {
+		self.transfers
+			.iter()
+			.filter(|transfer| {
+				transfer.asset == *asset
+					&& transfer.init_chain == chain
+					&& now.saturating_sub(transfer.locked_at) < window
+			})
+			.fold(0u128, |volume, transfer| volume.saturating_add(transfer.amount.0))
+	}
+
+	/// Removes the transfers and the invalid events older than their window.
+	fn prune(&mut self, now: u64) {
+		while let Some(transfer) = self.transfers.front() {
+			if now.saturating_sub(transfer.locked_at) < DAY_SECS {
+				break;
+			}
+			self.transfers.pop_front();
+		}
+		let failure_window = self.limits.validation_failure_window_secs;
+		while let Some(time) = self.validation_failures.front() {
+			if now.saturating_sub(*time) < failure_window {
+				break;
+			}
+			self.validation_failures.pop_front();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bridge_config::common::limits::AssetLimits;
+
+	const NOW: u64 = 1_700_000_000;
+
+	fn limits() -> LimitsConfig {
+		LimitsConfig {
+			max_transfer_amount: 100,
+			eth_hourly_volume_cap: 200,
+			eth_daily_volume_cap: 300,
+			mvt_hourly_volume_cap: 1_000,
+			mvt_daily_volume_cap: 1_000,
+			max_validation_failures: 3,
+			validation_failure_window_secs: 60,
+			assets: vec![AssetLimits {
+				asset_id: "WETH".to_string(),
+				max_transfer_amount: 10,
+				eth_hourly_volume_cap: 10,
+				eth_daily_volume_cap: 10,
+				mvt_hourly_volume_cap: 10,
+				mvt_daily_volume_cap: 10,
+			}],
+		}
+	}
+
+	fn transfer_id(n: u8) -> BridgeTransferId {
+		BridgeTransferId([n; 32])
+	}
+
+	#[test]
+	fn test_max_transfer_amount_trips() {
+		let mut breaker = CircuitBreaker::new(limits());
+		let asset = AssetId::from("MOVE");
+
+		assert_eq!(breaker.check_transfer(ChainId::ONE, transfer_id(1), &asset, 100, NOW), Ok(()));
+		let reason = TripReason::MaxTransferAmount {
+			transfer_id: transfer_id(2),
+			asset: asset.clone(),
+			amount: 101,
+		};
+		assert_eq!(
+			breaker.check_transfer(ChainId::ONE, transfer_id(2), &asset, 101, NOW),
+			Err(reason.clone())
+		);
+
+		// An open circuit breaker rejects any transfer until it is reset.
+		assert_eq!(
+			breaker.check_transfer(ChainId::TWO, transfer_id(3), &asset, 1, NOW),
+			Err(reason)
+		);
+		breaker.reset();
+		assert_eq!(breaker.check_transfer(ChainId::TWO, transfer_id(3), &asset, 1, NOW), Ok(()));
+	}
+
+	#[test]
+	fn test_rolling_volume_caps() {
+		let mut breaker = CircuitBreaker::new(limits());
+		let asset = AssetId::from("MOVE");
+		let lock = |breaker: &mut CircuitBreaker, chain, n, amount, now| {
+			let result = breaker.check_transfer(chain, transfer_id(n), &asset, amount, now);
+			if result.is_ok() {
+				breaker.record_transfer(chain, transfer_id(n), &asset, amount, now);
+			}
+			result
+		};
+
+		assert_eq!(lock(&mut breaker, ChainId::ONE, 1, 100, NOW), Ok(()));
+		assert_eq!(lock(&mut breaker, ChainId::ONE, 2, 100, NOW), Ok(()));
+		// The volume of Movement is capped separately.
+		assert_eq!(lock(&mut breaker, ChainId::TWO, 3, 100, NOW), Ok(()));
+		assert_eq!(
+			lock(&mut breaker, ChainId::ONE, 4, 1, NOW),
+			Err(TripReason::HourlyVolumeCap(asset.clone(), ChainId::ONE))
+		);
+
+		// An hour later the hourly volume is freed, but not the daily volume.
+		let later = NOW + HOUR_SECS;
+		breaker.reset();
+		assert_eq!(lock(&mut breaker, ChainId::ONE, 5, 100, later), Ok(()));
+		assert_eq!(
+			lock(&mut breaker, ChainId::ONE, 6, 1, later),
+			Err(TripReason::DailyVolumeCap(asset.clone(), ChainId::ONE))
+		);
+		let status = breaker.status(vec![], later);
+		assert_eq!(status.volume(&asset, ChainId::ONE), (100, 300));
+		assert_eq!(status.volume(&asset, ChainId::TWO), (0, 100));
+
+		let next_day = NOW + DAY_SECS;
+		breaker.reset();
+		assert_eq!(lock(&mut breaker, ChainId::ONE, 7, 100, next_day), Ok(()));
+		let status = breaker.status(vec![], next_day);
+		assert_eq!(status.volume(&asset, ChainId::ONE), (100, 200));
+	}
+
+	#[test]
+	fn test_limits_per_asset() {
+		let mut breaker = CircuitBreaker::new(limits());
+		let weth = AssetId::from("WETH");
+		let move_asset = AssetId::from("MOVE");
+
+		breaker.record_transfer(ChainId::ONE, transfer_id(1), &move_asset, 100, NOW);
+		// The volume of another asset does not count against the caps of WETH.
+		assert_eq!(breaker.check_transfer(ChainId::ONE, transfer_id(2), &weth, 10, NOW), Ok(()));
+		breaker.record_transfer(ChainId::ONE, transfer_id(2), &weth, 10, NOW);
+		assert_eq!(
+			breaker.check_transfer(ChainId::ONE, transfer_id(3), &weth, 1, NOW),
+			Err(TripReason::HourlyVolumeCap(weth.clone(), ChainId::ONE))
+		);
+		let status = breaker.status(vec![], NOW);
+		assert_eq!(
+			status.volumes,
+			vec![
+				AssetVolume { asset: move_asset, chain: ChainId::ONE, hourly: 100, daily: 100 },
+				AssetVolume { asset: weth, chain: ChainId::ONE, hourly: 10, daily: 10 },
+			]
+		);
+	}
+
+	#[test]
+	fn test_restore_counts_each_transfer_once() {
+		let mut breaker = CircuitBreaker::new(limits());
+		let asset = AssetId::from("MOVE");
+		let volume = breaker
+			.record_transfer(ChainId::ONE, transfer_id(1), &asset, 200, NOW)
+			.expect("transfer recorded");
+		assert_eq!(breaker.record_transfer(ChainId::ONE, transfer_id(1), &asset, 200, NOW), None);
+
+		let mut restarted = CircuitBreaker::new(limits());
+		restarted.restore(None, vec![volume]);
+		// The restored transfer is resumed without being counted twice.
+		assert_eq!(
+			restarted.check_transfer(ChainId::ONE, transfer_id(1), &asset, 200, NOW),
+			Ok(())
+		);
+		assert_eq!(
+			restarted.check_transfer(ChainId::ONE, transfer_id(2), &asset, 1, NOW),
+			Err(TripReason::HourlyVolumeCap(asset.clone(), ChainId::ONE))
+		);
+	}
+
+	#[test]
+	fn test_trip_reason_encoding() {
+		let reasons = [
+			TripReason::MaxTransferAmount {
+				transfer_id: transfer_id(1),
+				asset: AssetId::from("MOVE"),
+				amount: 101,
+			},
+			TripReason::HourlyVolumeCap(AssetId::from("W:ETH"), ChainId::ONE),
+			TripReason::DailyVolumeCap(AssetId::from("MOVE"), ChainId::TWO),
+			TripReason::ValidationFailures(3),
+		];
+		for reason in reasons {
+			assert_eq!(reason.encode().parse::<TripReason>().ok(), Some(reason));
+		}
+		assert!("Unknown:1".parse::<TripReason>().is_err());
+	}
+
+	#[test]
+	fn test_validation_failures_trip() {
+		let mut breaker = CircuitBreaker::new(limits());
+
+		assert_eq!(breaker.record_validation_failure(NOW), None);
+		assert_eq!(breaker.record_validation_failure(NOW), None);
+		// The failures older than the window are not counted.
+		let later = NOW + 60;
+		assert_eq!(breaker.record_validation_failure(later), None);
+		assert_eq!(breaker.record_validation_failure(later), None);
+		assert_eq!(
+			breaker.record_validation_failure(later),
+			Some(TripReason::ValidationFailures(3))
+		);
+		assert_eq!(breaker.trip_reason(), Some(&TripReason::ValidationFailures(3)));
+
+		breaker.reset();
+		assert_eq!(breaker.status(vec![], later).recent_validation_failures, 0);
+	}
+}
@@ -1,18 +1,37 @@
-use crate::chains::ethereum::client::EthClient;
+use crate::chains::{
+	ethereum::client::EthClient, movement::client_framework::MovementClientFramework,
+};
+use crate::circuit_breaker::{CircuitBreakerCommand, CircuitBreakerStatus};
+use alloy::primitives::{Address, Signature};
+use bridge_config::common::movement::MovementConfig;
 use bridge_grpc::{
 	bridge_server::Bridge, health_check_response::ServingStatus, health_server::Health,
-	BridgeTransferDetailsResponse, GetBridgeTransferDetailsRequest, HealthCheckRequest,
-	HealthCheckResponse,
+	AssetVolume, BridgeTransferDetailsResponse, CircuitBreakerStatusResponse,
+	GetBridgeTransferDetailsRequest, GetCircuitBreakerStatusRequest, HealthCheckRequest,
+	HealthCheckResponse, ResetCircuitBreakerRequest,
 };
+use bridge_util::chains::bridge_contracts::{BridgeContract, BridgeContractError};
+use bridge_util::types::BridgeTransferId;
 use futures::Stream;
+use std::collections::HashMap;
 use std::pin::Pin;
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, oneshot};
 use tonic::{Request, Response, Status};
 
+const RELAYER_HEALTH_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(2);
+const HEALTH_WATCH_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(5);
+const CIRCUIT_BREAKER_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(5);
+/// Maximum difference between the timestamp of a signed request and the time it is received.
+const REQUEST_MAX_AGE_SECS: u64 = 300;
+
 /// A gRPC Health Check Service
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct HealthCheckService {
-	status: std::sync::Mutex<std::collections::HashMap<String, ServingStatus>>,
+	status: Arc<std::sync::Mutex<HashMap<String, ServingStatus>>>,
+	/// Requests the health of the relayer loop, the serving services are not serving without it.
+	relayer_health_tx: Option<mpsc::Sender<oneshot::Sender<String>>>,
 }
 
 // Define a stream that will be used for the Watch method
@@ -40,61 +59,655 @@ impl Health for HealthCheckService {
 		request: Request<HealthCheckRequest>,
 	) -> Result<Response<HealthCheckResponse>, Status> {
 		let service = request.into_inner().service;
-		let status_map = self.status.lock().map_err(|_| Status::aborted("lock failed."))?;
-		let status = status_map.get(&service).cloned().unwrap_or(ServingStatus::ServiceUnknown);
+		let status = self.service_status(&service).await?;
 
 		Ok(Response::new(HealthCheckResponse { status: status.into() }))
 	}
 
 	async fn watch(
 		&self,
-		_request: Request<HealthCheckRequest>,
+		request: Request<HealthCheckRequest>,
 	) -> Result<Response<Self::WatchStream>, Status> {
+		let service = request.into_inner().service;
 		// Create an mpsc channel for the stream
 		let (tx, rx) = mpsc::channel(4);
-		let status_update = HealthCheckResponse { status: ServingStatus::Serving.into() };
-		tx.send(Ok(status_update)).await.map_err(|_| Status::aborted("send failed."))?;
+		let health_service = self.clone();
+
+		// Send the status of the service each time it changes, until the stream is dropped.
+		tokio::spawn(async move {
+			let mut last_status = None;
+			let mut interval = tokio::time::interval(HEALTH_WATCH_INTERVAL);
+			loop {
+				tokio::select! {
+					_ = tx.closed() => break,
+					_ = interval.tick() => (),
+				}
+				let status = match health_service.service_status(&service).await {
+					Ok(status) => status,
+					Err(err) => {
+						let _ = tx.send(Err(err)).await;
+						break;
+					}
+				};
+				if last_status != Some(status) {
+					let status_update = HealthCheckResponse { status: status.into() };
+					if tx.send(Ok(status_update)).await.is_err() {
+						break;
+					}
+					last_status = Some(status);
+				}
+			}
+		});
 
 		Ok(Response::new(HealthWatchStream { receiver: rx }))
 	}
 }
 
 impl HealthCheckService {
+	/// Creates a service reporting the health of the relayer loop for the serving services.
+	pub fn new(relayer_health_tx: mpsc::Sender<oneshot::Sender<String>>) -> Self {
+		Self { status: Default::default(), relayer_health_tx: Some(relayer_health_tx) }
+	}
+
 	// Set the health status of a service
 	pub fn set_service_status(&self, service: &str, status: ServingStatus) {
 		let mut status_map = self.status.lock().unwrap();
 		status_map.insert(service.to_string(), status);
 	}
+
+	/// Gets the status of a service, a serving service is not serving when the relayer is unhealthy.
+	async fn service_status(&self, service: &str) -> Result<ServingStatus, Status> {
+		let status = {
+			let status_map = self.status.lock().map_err(|_| Status::aborted("lock failed."))?;
+			status_map.get(service).cloned().unwrap_or(ServingStatus::ServiceUnknown)
+		};
+		match (&self.relayer_health_tx, status) {
+			(Some(relayer_health_tx), ServingStatus::Serving) => {
+				if is_relayer_healthy(relayer_health_tx).await {
+					Ok(ServingStatus::Serving)
+				} else {
+					Ok(ServingStatus::NotServing)
+				}
+			}
+			_ => Ok(status),
+		}
+	}
+}
+
+async fn is_relayer_healthy(relayer_health_tx: &mpsc::Sender<oneshot::Sender<String>>) -> bool {
+	let (tx, rx) = oneshot::channel();
+	match tokio::time::timeout(RELAYER_HEALTH_TIMEOUT, relayer_health_tx.send(tx)).await {
+		Ok(Ok(())) => (),
+		_ => return false,
+	}
+	matches!(tokio::time::timeout(RELAYER_HEALTH_TIMEOUT, rx).await, Ok(Ok(res)) if res == "OK")
+}
+
+/// The gRPC service `Bridge`, serving the details of the transfers on both chains.
+///
+/// A request is signed with an Ethereum key, see [`transfer_details_message`]. The signer must be
+/// an authorized signer or the Ethereum address of the transfer, the initiator or the recipient
+/// depending on the direction of the transfer.
+///
+/// The circuit breaker admin commands are signed by an authorized signer, see [`admin_message`].
+#[derive(Clone)]
+pub struct BridgeGrpcService {
+	eth_client: EthClient,
+	/// The Movement RPCs are not served without a Movement client.
+	movement_client: Option<MovementClientFramework>,
+	authorized_signers: Vec<Address>,
+	/// Sends the circuit breaker admin commands to the relayer loop.
+	circuit_breaker_tx: mpsc::Sender<CircuitBreakerCommand>,
+}
+
+impl BridgeGrpcService {
+	pub fn new(
+		eth_client: EthClient,
+		movement_client: MovementClientFramework,
+		config: &MovementConfig,
+		circuit_breaker_tx: mpsc::Sender<CircuitBreakerCommand>,
+	) -> Result<Self, anyhow::Error> {
+		let authorized_signers = config
+			.grpc_authorized_signers
+			.iter()
+			.map(|signer| {
+				signer.parse().map_err(|e| {
+					anyhow::anyhow!("Failed to parse authorized signer {}: {:?}", signer, e)
+				})
+			})
+			.collect::<Result<Vec<Address>, anyhow::Error>>()?;
+		Ok(Self {
+			eth_client,
+			movement_client: Some(movement_client),
+			authorized_signers,
+			circuit_breaker_tx,
+		})
+	}
+
+	/// Creates a service serving the Ethereum RPCs only, to the Ethereum address of the transfers.
+	/// The circuit breaker admin commands are unavailable.
+	pub fn eth_only(eth_client: EthClient) -> Self {
+		let (circuit_breaker_tx, _) = mpsc::channel(1);
+		Self { eth_client, movement_client: None, authorized_signers: vec![], circuit_breaker_tx }
+	}
+
+	fn movement_client(&self) -> Result<MovementClientFramework, Status> {
+		self.movement_client
+			.clone()
+			.ok_or_else(|| Status::unimplemented("The Movement client is not configured"))
+	}
+
+	/// Checks that the signer of a request can read the transfer of an Ethereum address.
+	fn authorize(&self, signer: &Address, eth_address: &[u8]) -> Result<(), Status> {
+		if self.authorized_signers.contains(signer) || signer.as_slice() == eth_address {
+			Ok(())
+		} else {
+			Err(Status::permission_denied(format!("Signer {signer} is not allowed")))
+		}
+	}
+
+	/// Checks that the signer of an admin request is an authorized signer.
+	fn authorize_admin(&self, signer: &Address) -> Result<(), Status> {
+		if self.authorized_signers.contains(signer) {
+			Ok(())
+		} else {
+			Err(Status::permission_denied(format!("Signer {signer} is not an admin")))
+		}
+	}
+
+	/// Executes a circuit breaker command in the relayer loop and waits for its status.
+	async fn circuit_breaker_command(
+		&self,
+		command: impl FnOnce(oneshot::Sender<CircuitBreakerStatus>) -> CircuitBreakerCommand,
+	) -> Result<CircuitBreakerStatus, Status> {
+		let (tx, rx) = oneshot::channel();
+		self.circuit_breaker_tx
+			.send(command(tx))
+			.await
+			.map_err(|_| Status::unavailable("Relayer loop is not running"))?;
+		match tokio::time::timeout(CIRCUIT_BREAKER_TIMEOUT, rx).await {
+			Ok(Ok(status)) => Ok(status),
+			_ => Err(Status::unavailable("Relayer loop did not answer")),
+		}
+	}
+}
+
+/// Gets the message signed by an admin for a command at a unix timestamp in seconds.
+pub fn admin_message(command: &str, timestamp: u64) -> String {
+	format!("{command}:{timestamp}")
+}
+
+/// Gets the message signed for a transfer details request at a unix timestamp in seconds,
+/// `method` is the name of the RPC, e.g. `GetBridgeTransferDetailsInitiatorEth`.
+pub fn transfer_details_message(
+	method: &str,
+	bridge_transfer_id: BridgeTransferId,
+	timestamp: u64,
+) -> String {
+	admin_message(&format!("{method}:{}", hex::encode(bridge_transfer_id.0)), timestamp)
+}
+
+/// Recovers the address that signed an admin command, a stale request is rejected.
+fn authenticate_admin(
+	command: &str,
+	timestamp: u64,
+	signature: &[u8],
+	now: u64,
+) -> Result<Address, Status> {
+	if now.abs_diff(timestamp) > REQUEST_MAX_AGE_SECS {
+		return Err(Status::unauthenticated(format!("Stale request timestamp: {timestamp}")));
+	}
+	let signature = Signature::try_from(signature)
+		.map_err(|e| Status::unauthenticated(format!("Invalid signature: {e}")))?;
+	signature
+		.recover_address_from_msg(admin_message(command, timestamp))
+		.map_err(|e| Status::unauthenticated(format!("Invalid signature: {e}")))
+}
+
+fn unix_timestamp() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn circuit_breaker_response(status: CircuitBreakerStatus) -> CircuitBreakerStatusResponse {
+	CircuitBreakerStatusResponse {
+		tripped: status.trip_reason.is_some(),
+		trip_reason: status.trip_reason.map(|reason| reason.to_string()).unwrap_or_default(),
+		paused_transfer_ids: status.paused_transfers.iter().map(|id| id.0.to_vec()).collect(),
+		recent_validation_failures: status.recent_validation_failures as u32,
+		volumes: status
+			.volumes
+			.into_iter()
+			.map(|volume| AssetVolume {
+				asset_id: volume.asset.0,
+				chain: volume.chain.to_string(),
+				hourly_volume: volume.hourly.to_string(),
+				daily_volume: volume.daily.to_string(),
+			})
+			.collect(),
+	}
+}
+
+/// Gets the bridge transfer id of a request to `method` and recovers the address that signed it,
+/// a stale request is rejected.
+fn authenticate(
+	method: &str,
+	request: &GetBridgeTransferDetailsRequest,
+	now: u64,
+) -> Result<(BridgeTransferId, Address), Status> {
+	let bridge_transfer_id = BridgeTransferId(
+		request
+			.bridge_transfer_id
+			.clone()
+			.try_into()
+			.map_err(|_| Status::invalid_argument("Invalid bridge transfer id"))?,
+	);
+	let command = format!("{method}:{}", hex::encode(bridge_transfer_id.0));
+	let signer = authenticate_admin(&command, request.timestamp, &request.signature, now)?;
+	Ok((bridge_transfer_id, signer))
+}
+
+fn contract_error(err: BridgeContractError) -> Status {
+	Status::internal(format!("Failed to get bridge transfer details: {err}"))
+}
+
+fn transfer_not_found(bridge_transfer_id: BridgeTransferId) -> Status {
+	Status::not_found(format!("Bridge transfer not found: {bridge_transfer_id}"))
 }
 
-/// Implement the gRPC service `Bridge` for the Ethereum Bridge Client
 #[tonic::async_trait]
-impl Bridge for EthClient {
+impl Bridge for BridgeGrpcService {
 	async fn get_bridge_transfer_details_initiator_eth(
 		&self,
-		_request: Request<GetBridgeTransferDetailsRequest>,
+		request: Request<GetBridgeTransferDetailsRequest>,
 	) -> Result<Response<BridgeTransferDetailsResponse>, Status> {
-		unimplemented!()
+		let (bridge_transfer_id, signer) = authenticate(
+			"GetBridgeTransferDetailsInitiatorEth",
+			request.get_ref(),
+			unix_timestamp(),
+		)?;
+		let details = self
+			.eth_client
+			.clone()
+			.get_bridge_transfer_details_initiator(bridge_transfer_id)
+			.await
+			.map_err(contract_error)?
+			.ok_or_else(|| transfer_not_found(bridge_transfer_id))?;
+		self.authorize(&signer, details.initiator.0 .0.as_slice())?;
+
+		Ok(Response::new(BridgeTransferDetailsResponse {
+			initiator: details.initiator.0 .0.to_string(),
+			recipient: details.recipient.0,
+			hash_lock: details.hash_lock.0.to_vec(),
+			time_lock: details.time_lock.0,
+			amount: details.amount.0.to_string(),
+			asset_id: details.asset.0,
+			state: details.state.into(),
+			error_message: String::new(),
+		}))
 	}
 
 	async fn get_bridge_transfer_details_counterparty_eth(
 		&self,
-		_request: Request<GetBridgeTransferDetailsRequest>,
+		request: Request<GetBridgeTransferDetailsRequest>,
 	) -> Result<Response<BridgeTransferDetailsResponse>, Status> {
-		unimplemented!()
+		let (bridge_transfer_id, signer) = authenticate(
+			"GetBridgeTransferDetailsCounterpartyEth",
+			request.get_ref(),
+			unix_timestamp(),
+		)?;
+		let details = self
+			.eth_client
+			.clone()
+			.get_bridge_transfer_details_counterparty(bridge_transfer_id)
+			.await
+			.map_err(contract_error)?
+			.ok_or_else(|| transfer_not_found(bridge_transfer_id))?;
+		self.authorize(&signer, details.recipient.0 .0.as_slice())?;
+
+		Ok(Response::new(BridgeTransferDetailsResponse {
+			initiator: format!("0x{}", hex::encode(&details.initiator.0)),
+			recipient: details.recipient.0.into(),
+			hash_lock: details.hash_lock.0.to_vec(),
+			time_lock: details.time_lock.0,
+			amount: details.amount.0.to_string(),
+			asset_id: details.asset.0,
+			state: details.state.into(),
+			error_message: String::new(),
+		}))
 	}
 
 	async fn get_bridge_transfer_details_initiator_movement(
 		&self,
-		_request: Request<GetBridgeTransferDetailsRequest>,
+		request: Request<GetBridgeTransferDetailsRequest>,
 	) -> Result<Response<BridgeTransferDetailsResponse>, Status> {
-		unimplemented!()
+		let (bridge_transfer_id, signer) = authenticate(
+			"GetBridgeTransferDetailsInitiatorMovement",
+			request.get_ref(),
+			unix_timestamp(),
+		)?;
+		let details = self
+			.movement_client()?
+			.get_bridge_transfer_details_initiator(bridge_transfer_id)
+			.await
+			.map_err(contract_error)?
+			.ok_or_else(|| transfer_not_found(bridge_transfer_id))?;
+		self.authorize(&signer, &details.recipient.0)?;
+
+		Ok(Response::new(BridgeTransferDetailsResponse {
+			initiator: details.initiator.0.to_string(),
+			recipient: details.recipient.0,
+			hash_lock: details.hash_lock.0.to_vec(),
+			time_lock: details.time_lock.0,
+			amount: details.amount.0.to_string(),
+			asset_id: details.asset.0,
+			state: details.state.into(),
+			error_message: String::new(),
+		}))
 	}
 
 	async fn get_bridge_transfer_details_counterparty_movement(
 		&self,
-		_request: Request<GetBridgeTransferDetailsRequest>,
+		request: Request<GetBridgeTransferDetailsRequest>,
 	) -> Result<Response<BridgeTransferDetailsResponse>, Status> {
-		unimplemented!()
+		let (bridge_transfer_id, signer) = authenticate(
+			"GetBridgeTransferDetailsCounterpartyMovement",
+			request.get_ref(),
+			unix_timestamp(),
+		)?;
+		let details = self
+			.movement_client()?
+			.get_bridge_transfer_details_counterparty(bridge_transfer_id)
+			.await
+			.map_err(contract_error)?
+			.ok_or_else(|| transfer_not_found(bridge_transfer_id))?;
+		self.authorize(&signer, &details.initiator.0)?;
+
+		Ok(Response::new(BridgeTransferDetailsResponse {
+			initiator: format!("0x{}", hex::encode(&details.initiator.0)),
+			recipient: details.recipient.0.into(),
+			hash_lock: details.hash_lock.0.to_vec(),
+			time_lock: details.time_lock.0,
+			amount: details.amount.0.to_string(),
+			asset_id: details.asset.0,
+			state: details.state.into(),
+			error_message: String::new(),
+		}))
+	}
+
+	async fn get_circuit_breaker_status(
+		&self,
+		request: Request<GetCircuitBreakerStatusRequest>,
+	) -> Result<Response<CircuitBreakerStatusResponse>, Status> {
+		let request = request.into_inner();
+		let signer = authenticate_admin(
+			"GetCircuitBreakerStatus",
+			request.timestamp,
+			&request.signature,
+			unix_timestamp(),
+		)?;
+		self.authorize_admin(&signer)?;
+
+		let status = self.circuit_breaker_command(CircuitBreakerCommand::Status).await?;
+		Ok(Response::new(circuit_breaker_response(status)))
+	}
+
+	async fn reset_circuit_breaker(
+		&self,
+		request: Request<ResetCircuitBreakerRequest>,
+	) -> Result<Response<CircuitBreakerStatusResponse>, Status> {
+		let request = request.into_inner();
+		let release_paused_transfers = request.release_paused_transfers;
+		let signer = authenticate_admin(
+			&format!("ResetCircuitBreaker:{release_paused_transfers}"),
+			request.timestamp,
+			&request.signature,
+			unix_timestamp(),
+		)?;
+		self.authorize_admin(&signer)?;
+		tracing::info!("Circuit breaker reset by {signer}");
+
+		let status = self
+			.circuit_breaker_command(|status_tx| CircuitBreakerCommand::Reset {
+				release_paused_transfers,
+				status_tx,
+			})
+			.await?;
+		Ok(Response::new(circuit_breaker_response(status)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::circuit_breaker::{AssetVolume as CircuitBreakerVolume, TripReason};
+	use alloy::signers::{local::PrivateKeySigner, SignerSync};
+	use bridge_config::common::eth::EthConfig;
+	use bridge_util::types::{AssetId, ChainId};
+
+	/// Creates a service with an admin, the commands sent to the relayer loop are received by the
+	/// test. The Ethereum client is not connected, the admin RPCs don't call the chains.
+	async fn admin_service(
+		admin: Address,
+	) -> Result<(BridgeGrpcService, mpsc::Receiver<CircuitBreakerCommand>), anyhow::Error> {
+		let contract = Address::ZERO.to_string();
+		let config = EthConfig {
+			eth_initiator_contract: contract.clone(),
+			eth_counterparty_contract: contract.clone(),
+			eth_move_token_contract: contract,
+			..EthConfig::default()
+		};
+		let (circuit_breaker_tx, circuit_breaker_rx) = mpsc::channel(1);
+		let service = BridgeGrpcService {
+			eth_client: EthClient::new(&config).await?,
+			movement_client: None,
+			authorized_signers: vec![admin],
+			circuit_breaker_tx,
+		};
+		Ok((service, circuit_breaker_rx))
+	}
+
+	fn sign_admin_command(
+		signer: &PrivateKeySigner,
+		command: &str,
+		timestamp: u64,
+	) -> Result<Vec<u8>, anyhow::Error> {
+		let signature = signer.sign_message_sync(admin_message(command, timestamp).as_bytes())?;
+		Ok(signature.as_bytes().to_vec())
+	}
+
+	fn tripped_status() -> CircuitBreakerStatus {
+		CircuitBreakerStatus {
+			trip_reason: Some(TripReason::HourlyVolumeCap(AssetId::from("MOVE"), ChainId::ONE)),
+			paused_transfers: vec![BridgeTransferId([1; 32])],
+			volumes: vec![CircuitBreakerVolume {
+				asset: AssetId::from("MOVE"),
+				chain: ChainId::ONE,
+				hourly: 200,
+				daily: 300,
+			}],
+			recent_validation_failures: 0,
+		}
+	}
+
+	#[test]
+	fn test_authenticate_recovers_signer() -> Result<(), anyhow::Error> {
+		let signer = PrivateKeySigner::random();
+		let bridge_transfer_id = BridgeTransferId([1; 32]);
+		let timestamp = 1_700_000_000;
+		let method = "GetBridgeTransferDetailsInitiatorEth";
+		let message = transfer_details_message(method, bridge_transfer_id, timestamp);
+		let signature = signer.sign_message_sync(message.as_bytes())?;
+
+		let request = GetBridgeTransferDetailsRequest {
+			bridge_transfer_id: bridge_transfer_id.0.to_vec(),
+			signature: signature.as_bytes().to_vec(),
+			timestamp,
+		};
+		assert_eq!(
+			authenticate(method, &request, timestamp + 10)?,
+			(bridge_transfer_id, signer.address())
+		);
+
+		// The signature does not authenticate another method or another transfer id.
+		let (_, recovered) =
+			authenticate("GetBridgeTransferDetailsCounterpartyEth", &request, timestamp)?;
+		assert_ne!(recovered, signer.address());
+		let request = GetBridgeTransferDetailsRequest {
+			bridge_transfer_id: [2; 32].to_vec(),
+			signature: signature.as_bytes().to_vec(),
+			timestamp,
+		};
+		let (_, recovered) = authenticate(method, &request, timestamp)?;
+		assert_ne!(recovered, signer.address());
+
+		// A replayed request is rejected once stale.
+		let request = GetBridgeTransferDetailsRequest {
+			bridge_transfer_id: bridge_transfer_id.0.to_vec(),
+			signature: signature.as_bytes().to_vec(),
+			timestamp,
+		};
+		let stale = timestamp + REQUEST_MAX_AGE_SECS + 1;
+		assert_eq!(
+			authenticate(method, &request, stale).unwrap_err().code(),
+			tonic::Code::Unauthenticated
+		);
+
+		let request = GetBridgeTransferDetailsRequest {
+			bridge_transfer_id: bridge_transfer_id.0.to_vec(),
+			signature: vec![],
+			timestamp,
+		};
+		assert_eq!(
+			authenticate(method, &request, timestamp).unwrap_err().code(),
+			tonic::Code::Unauthenticated
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_authenticate_admin_rejects_stale_requests() -> Result<(), anyhow::Error> {
+		let signer = PrivateKeySigner::random();
+		let timestamp = 1_700_000_000;
+		let command = "ResetCircuitBreaker:true";
+		let signature = signer.sign_message_sync(admin_message(command, timestamp).as_bytes())?;
+		let signature = signature.as_bytes();
+
+		assert_eq!(
+			authenticate_admin(command, timestamp, &signature, timestamp + 10)?,
+			signer.address()
+		);
+
+		// The signature of a command does not authenticate another command.
+		let recovered =
+			authenticate_admin("ResetCircuitBreaker:false", timestamp, &signature, timestamp)?;
+		assert_ne!(recovered, signer.address());
+
+		let stale = timestamp + REQUEST_MAX_AGE_SECS + 1;
+		assert_eq!(
+			authenticate_admin(command, timestamp, &signature, stale).unwrap_err().code(),
+			tonic::Code::Unauthenticated
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_circuit_breaker_status() -> Result<(), anyhow::Error> {
+		let admin = PrivateKeySigner::random();
+		let (service, mut circuit_breaker_rx) = admin_service(admin.address()).await?;
+		let timestamp = unix_timestamp();
+		let request = GetCircuitBreakerStatusRequest {
+			timestamp,
+			signature: sign_admin_command(&admin, "GetCircuitBreakerStatus", timestamp)?,
+		};
+
+		let (response, ()) =
+			tokio::join!(service.get_circuit_breaker_status(Request::new(request)), async {
+				match circuit_breaker_rx.recv().await {
+					Some(CircuitBreakerCommand::Status(status_tx)) => {
+						status_tx.send(tripped_status()).expect("Status sent");
+					}
+					command => panic!("Unexpected circuit breaker command: {command:?}"),
+				}
+			});
+		let response = response?.into_inner();
+		assert!(response.tripped);
+		assert_eq!(response.trip_reason, "Hourly volume cap of MOVE on chain ONE exceeded");
+		assert_eq!(response.paused_transfer_ids, vec![vec![1; 32]]);
+		assert_eq!(
+			response.volumes,
+			vec![AssetVolume {
+				asset_id: "MOVE".to_string(),
+				chain: "ONE".to_string(),
+				hourly_volume: "200".to_string(),
+				daily_volume: "300".to_string(),
+			}]
+		);
+
+		// The relayer loop is not running anymore.
+		drop(circuit_breaker_rx);
+		let request = GetCircuitBreakerStatusRequest {
+			timestamp,
+			signature: sign_admin_command(&admin, "GetCircuitBreakerStatus", timestamp)?,
+		};
+		let status = service.get_circuit_breaker_status(Request::new(request)).await.unwrap_err();
+		assert_eq!(status.code(), tonic::Code::Unavailable);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_reset_circuit_breaker() -> Result<(), anyhow::Error> {
+		let admin = PrivateKeySigner::random();
+		let (service, mut circuit_breaker_rx) = admin_service(admin.address()).await?;
+		let timestamp = unix_timestamp();
+		let request = ResetCircuitBreakerRequest {
+			timestamp,
+			signature: sign_admin_command(&admin, "ResetCircuitBreaker:true", timestamp)?,
+			release_paused_transfers: true,
+		};
+
+		let (response, released) =
+			tokio::join!(service.reset_circuit_breaker(Request::new(request)), async {
+				match circuit_breaker_rx.recv().await {
+					Some(CircuitBreakerCommand::Reset { release_paused_transfers, status_tx }) => {
+						let status = CircuitBreakerStatus {
+							trip_reason: None,
+							paused_transfers: vec![],
+							..tripped_status()
+						};
+						status_tx.send(status).expect("Status sent");
+						release_paused_transfers
+					}
+					command => panic!("Unexpected circuit breaker command: {command:?}"),
+				}
+			});
+		assert!(released);
+		let response = response?.into_inner();
+		assert!(!response.tripped);
+		assert!(response.paused_transfer_ids.is_empty());
+
+		// The signature of a reset without release does not authenticate a release.
+		let request = ResetCircuitBreakerRequest {
+			timestamp,
+			signature: sign_admin_command(&admin, "ResetCircuitBreaker:false", timestamp)?,
+			release_paused_transfers: true,
+		};
+		let status = service.reset_circuit_breaker(Request::new(request)).await.unwrap_err();
+		assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+		// Only the admins can reset the circuit breaker.
+		let other = PrivateKeySigner::random();
+		let request = ResetCircuitBreakerRequest {
+			timestamp,
+			signature: sign_admin_command(&other, "ResetCircuitBreaker:true", timestamp)?,
+			release_paused_transfers: true,
+		};
+		let status = service.reset_circuit_breaker(Request::new(request)).await.unwrap_err();
+		assert_eq!(status.code(), tonic::Code::PermissionDenied);
+		assert!(circuit_breaker_rx.try_recv().is_err());
+
+		Ok(())
 	}
 }
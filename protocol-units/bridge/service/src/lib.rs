@@ -1,16 +1,31 @@
 use crate::actions::process_action;
-use bridge_indexer_db::client::Client as IndexerClient;
+use crate::chains::movement::gas_drop::GasDropper;
+use crate::circuit_breaker::{
+	CircuitBreaker, CircuitBreakerCommand, CircuitBreakerStatus, TripReason, DAY_SECS,
+};
+use crate::rest::TransferSummary;
+use bridge_config::common::{limits::LimitsConfig, tokens::TokenRegistry};
+use bridge_indexer_db::client::{Client as IndexerClient, LockedVolume};
 use bridge_util::{
 	actions::{ActionExecError, TransferAction, TransferActionType},
-	chains::bridge_contracts::{BridgeContract, BridgeContractEvent, BridgeContractMonitoring},
+	chains::bridge_contracts::{
+		BridgeContract, BridgeContractEvent, BridgeContractMonitoring, BridgeContractResult,
+	},
 	events::{InvalidEventError, TransferEvent},
 	states::{TransferState, TransferStateType},
-	types::{BridgeTransferId, ChainId},
+	types::{Amount, AssetId, BridgeTransferId, ChainId, TimeLock},
 };
 use futures::stream::FuturesUnordered;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet},
+	marker::PhantomData,
+	sync::Arc,
+	time::Duration,
+};
+use time_lock::{unix_now, TimeLockScheduler, TransferTimeLock};
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tokio::{select, sync::Mutex};
 use tokio_stream::StreamExt;
 
@@ -18,8 +33,25 @@ pub use bridge_util::types;
 
 mod actions;
 pub mod chains;
+pub mod circuit_breaker;
 pub mod grpc;
 pub mod rest;
+pub mod time_lock;
+
+/// Number of times a failed lock is retried before refunding the initiator.
+pub const MAX_LOCK_RETRIES: usize = 5;
+/// Delay before the first retry of a failed action, doubled on each retry.
+pub const ACTION_RETRY_BASE_BACKOFF: Duration = Duration::from_secs(1);
+/// Maximum delay between two retries of a failed action.
+pub const ACTION_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Gets the delay before retrying an action that failed `retry` times.
+pub fn action_retry_backoff(retry: usize) -> Duration {
+	let exponent = retry.saturating_sub(1).min(16) as u32;
+	ACTION_RETRY_BASE_BACKOFF
+		.saturating_mul(2u32.pow(exponent))
+		.min(ACTION_RETRY_MAX_BACKOFF)
+}
 
 #[derive(Debug)]
 struct HeathCheckStatus {
@@ -52,30 +84,42 @@ pub async fn run_bridge<
 	client_two: impl BridgeContract<A2> + 'static,
 	mut stream_two: impl BridgeContractMonitoring<Address = A2>,
 	mut healthcheck_request_rx: mpsc::Receiver<oneshot::Sender<String>>,
+	mut pending_transfers_rx: mpsc::Receiver<oneshot::Sender<Vec<TransferSummary>>>,
+	mut circuit_breaker_rx: mpsc::Receiver<CircuitBreakerCommand>,
 	indexer_db_client: Option<IndexerClient>,
 	healthcheck_tx_one: mpsc::Sender<oneshot::Sender<bool>>,
 	healthcheck_tx_two: mpsc::Sender<oneshot::Sender<bool>>,
+	time_lock_scheduler: TimeLockScheduler,
+	tokens: TokenRegistry,
+	limits: LimitsConfig,
+	gas_dropper: Option<GasDropper>,
 ) -> Result<(), anyhow::Error>
 where
 	Vec<u8>: From<A1>,
 	Vec<u8>: From<A2>,
 {
-	let mut state_runtime = Runtime::new(indexer_db_client);
+	let mut state_runtime =
+		Runtime::new(indexer_db_client, time_lock_scheduler, tokens, CircuitBreaker::new(limits));
 
-	let mut client_exec_result_futures_one = FuturesUnordered::new();
-	let mut client_exec_result_futures_two = FuturesUnordered::new();
+	let mut executor = ActionExecutor::new(client_one, client_two, gas_dropper);
 	let mut health_check_result_futures = FuturesUnordered::new();
 
-	//only one client can use at a time.
-	let client_lock_one = Arc::new(Mutex::new(()));
-	let client_lock_two = Arc::new(Mutex::new(()));
-
 	let mut tranfer_log_interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
 	let mut monitoring_health_check_interval =
 		tokio::time::interval(tokio::time::Duration::from_secs(5));
+	let mut time_lock_interval = tokio::time::interval(tokio::time::Duration::from_secs(10));
 
 	let mut health_status = HeathCheckStatus::new();
 
+	// Restore the transfers in progress before the restart and resume their pending actions.
+	for action in state_runtime.restore()? {
+		tracing::info!("Resume action after restart: {action}");
+		executor.execute(action, Duration::ZERO);
+	}
+	for (transfer_id, transfer_time_lock) in state_runtime.pending_time_locks() {
+		executor.read_time_lock(transfer_id, transfer_time_lock, 0);
+	}
+
 	loop {
 		select! {
 			//Manage REST HealthCheck request
@@ -90,6 +134,30 @@ where
 				}
 
 			}
+			// Manage REST pending transfers request
+			Some(oneshot_tx) = pending_transfers_rx.recv() => {
+				let transfers = state_runtime.iter_state().map(TransferSummary::from).collect();
+				if oneshot_tx.send(transfers).is_err() {
+					tracing::warn!("Pending transfers oneshot channel closed abnormally");
+				}
+			}
+			// Manage the circuit breaker admin commands
+			Some(command) = circuit_breaker_rx.recv() => {
+				let status_tx = match command {
+					CircuitBreakerCommand::Status(status_tx) => status_tx,
+					CircuitBreakerCommand::Reset { release_paused_transfers, status_tx } => {
+						let now = unix_now();
+						for action in state_runtime.reset_circuit_breaker(release_paused_transfers, now) {
+							tracing::info!("Release paused action: {action}");
+							executor.execute(action, Duration::ZERO);
+						}
+						status_tx
+					}
+				};
+				if status_tx.send(state_runtime.circuit_breaker_status(unix_now())).is_err() {
+					tracing::warn!("Circuit breaker oneshot channel closed abnormally");
+				}
+			}
 			// verify that monitoring heath check still works.
 			_ = monitoring_health_check_interval.tick() => {
 				//Chain one monitoring health check.
@@ -125,6 +193,33 @@ where
 					}
 				}
 			}
+			// Schedule the time locks read on chain.
+			Some(res) = executor.time_lock_futures.next() => {
+				match res {
+					Ok((transfer_id, transfer_time_lock, _, Ok(Some(time_lock)))) => {
+						state_runtime.schedule_time_lock(transfer_id, transfer_time_lock, time_lock);
+					}
+					Ok((transfer_id, transfer_time_lock, _, Ok(None))) => {
+						tracing::warn!("No {transfer_time_lock:?} time lock on chain for transfer {transfer_id}");
+					}
+					Ok((transfer_id, transfer_time_lock, retry, Err(err))) => {
+						tracing::warn!("Failed to read the {transfer_time_lock:?} time lock of transfer {transfer_id}: {err}");
+						executor.read_time_lock(transfer_id, transfer_time_lock, retry + 1);
+					}
+					Err(err)=>{
+						// Tokio execution fail. Process should exit.
+						tracing::error!("Error during time lock read tokio task execution exiting: {err}");
+						return Err(err.into());
+					}
+				}
+			}
+			// Refund or abort the transfers whose time lock expired.
+			_ = time_lock_interval.tick() => {
+				for action in state_runtime.process_expired_time_locks(unix_now()) {
+					tracing::info!("Time lock expired, execute action: {action}");
+					executor.execute(action, Duration::ZERO);
+				}
+			}
 			// Log all current transfer
 			_ = tranfer_log_interval.tick() => {
 				//format logs
@@ -138,39 +233,16 @@ where
 				match event_res_one {
 					Ok(event_one) => {
 						let event : TransferEvent<A1> = (event_one, ChainId::ONE).into();
+						let transfer_time_lock = event_time_lock(&event);
 						tracing::info!("Receive event from chain ONE:{} ", event.contract_event);
 						match state_runtime.process_event(event) {
 							Ok(action) => {
-								//Execute action
-								match action.chain {
-									ChainId::ONE => {
-										let fut = process_action(action, client_one.clone());
-										if let Some(fut) = fut {
-											let jh = tokio::spawn({
-												let client_lock_clone = client_lock_one.clone();
-												async move {
-													let _lock = client_lock_clone.lock().await;
-													fut.await
-												}
-											});
-											client_exec_result_futures_one.push(jh);
-										}
-
-									},
-									ChainId::TWO => {
-										let fut = process_action(action, client_two.clone());
-										if let Some(fut) = fut {
-											let jh = tokio::spawn({
-												let client_lock_clone = client_lock_two.clone();
-												async move {
-													let _lock = client_lock_clone.lock().await;
-													fut.await
-												}
-											});
-											client_exec_result_futures_two.push(jh);
-										}
-									}
+								// Read the time lock set on chain by the event.
+								if let Some(transfer_time_lock) = transfer_time_lock {
+									executor.read_time_lock(action.transfer_id, transfer_time_lock, 0);
 								}
+								//Execute action
+								executor.execute(action, Duration::ZERO);
 							},
 							Err(err) => tracing::warn!("Received an invalid event: {err}"),
 						}
@@ -183,27 +255,16 @@ where
 				match event_res_two {
 					Ok(event_two) => {
 						let event : TransferEvent<A2> = (event_two, ChainId::TWO).into();
+						let transfer_time_lock = event_time_lock(&event);
 						tracing::info!("Receive event from chain TWO :{}", event.contract_event);
 						match state_runtime.process_event(event) {
 							Ok(action) => {
-								//Execute action
-								match action.chain {
-									ChainId::ONE => {
-										let fut = process_action(action, client_one.clone());
-										if let Some(fut) = fut {
-											let jh = tokio::spawn(fut);
-											client_exec_result_futures_one.push(jh);
-										}
-
-									},
-									ChainId::TWO => {
-										let fut = process_action(action, client_two.clone());
-										if let Some(fut) = fut {
-											let jh = tokio::spawn(fut);
-											client_exec_result_futures_two.push(jh);
-										}
-									}
+								// Read the time lock set on chain by the event.
+								if let Some(transfer_time_lock) = transfer_time_lock {
+									executor.read_time_lock(action.transfer_id, transfer_time_lock, 0);
 								}
+								//Execute action
+								executor.execute(action, Duration::ZERO);
 							},
 							Err(err) => tracing::warn!("Received an invalid event: {err}"),
 						}
@@ -212,15 +273,15 @@ where
 				}
 			}
 			// Wait on client tx execution result.
-			Some(res) = client_exec_result_futures_one.next() => {
+			Some(res) = executor.futures_one.next() => {
 				match res {
 					//Client execution ok.
 					Ok(Ok(_)) => (),
 					Ok(Err(err)) => {
 						// Manage Tx execution error
-						let action = state_runtime.process_action_exec_error(err);
-						// TODO execute action the same way as normal event.
-						// TODO refactor to avopid code duplication.
+						if let Some((action, delay)) = state_runtime.process_action_exec_error(err) {
+							executor.execute(action, delay);
+						}
 					}
 					Err(err)=>{
 						// Tokio execution fail. Process should exit.
@@ -229,15 +290,15 @@ where
 					}
 				}
 			}
-			Some(res) = client_exec_result_futures_two.next() => {
+			Some(res) = executor.futures_two.next() => {
 				match res {
 					//Client execution ok.
 					Ok(Ok(_)) => (),
 					Ok(Err(err)) => {
 						// Manage Tx execution error
-						let action = state_runtime.process_action_exec_error(err);
-						// TODO execute action the same way as normal event.
-						// TODO refactor to avopid code duplication.
+						if let Some((action, delay)) = state_runtime.process_action_exec_error(err) {
+							executor.execute(action, delay);
+						}
 					}
 					Err(err)=>{
 						// Tokio execution fail. Process should exit.
@@ -250,6 +311,152 @@ where
 	}
 }
 
+type ActionJoinHandle = JoinHandle<Result<(), ActionExecError>>;
+
+/// The time lock of a transfer read on chain, with the number of failed reads before.
+type TimeLockJoinHandle =
+	JoinHandle<(BridgeTransferId, TransferTimeLock, usize, BridgeContractResult<Option<TimeLock>>)>;
+
+/// Gets the time lock set on chain by an event: the initiator time lock when the transfer is
+/// initiated and the counterparty one when it is locked.
+fn event_time_lock<A>(event: &TransferEvent<A>) -> Option<TransferTimeLock> {
+	match event.contract_event {
+		BridgeContractEvent::Initiated(_) => Some(TransferTimeLock::Initiator(event.chain)),
+		BridgeContractEvent::Locked(_) => Some(TransferTimeLock::Counterparty(event.chain)),
+		_ => None,
+	}
+}
+
+/// Executes the actions on the client of their chain.
+struct ActionExecutor<A1, A2, C1, C2> {
+	client_one: C1,
+	client_two: C2,
+	//only one client can use at a time.
+	client_lock_one: Arc<Mutex<()>>,
+	client_lock_two: Arc<Mutex<()>>,
+	futures_one: FuturesUnordered<ActionJoinHandle>,
+	futures_two: FuturesUnordered<ActionJoinHandle>,
+	time_lock_futures: FuturesUnordered<TimeLockJoinHandle>,
+	/// Drops gas to the recipients of the locks on chain two
+	gas_dropper: Option<GasDropper>,
+	_addresses: PhantomData<(A1, A2)>,
+}
+
+impl<A1, A2, C1, C2> ActionExecutor<A1, A2, C1, C2>
+where
+	A1: Send + TryFrom<Vec<u8>> + std::clone::Clone + 'static,
+	A2: Send + TryFrom<Vec<u8>> + std::clone::Clone + 'static,
+	C1: BridgeContract<A1> + 'static,
+	C2: BridgeContract<A2> + 'static,
+{
+	fn new(client_one: C1, client_two: C2, gas_dropper: Option<GasDropper>) -> Self {
+		ActionExecutor {
+			client_one,
+			client_two,
+			client_lock_one: Arc::new(Mutex::new(())),
+			client_lock_two: Arc::new(Mutex::new(())),
+			futures_one: FuturesUnordered::new(),
+			futures_two: FuturesUnordered::new(),
+			time_lock_futures: FuturesUnordered::new(),
+			gas_dropper,
+			_addresses: PhantomData,
+		}
+	}
+
+	/// Executes an action after a delay.
+	fn execute(&mut self, action: TransferAction, delay: Duration) {
+		match action.chain {
+			ChainId::ONE => self.futures_one.extend(spawn_action(
+				action,
+				delay,
+				self.client_one.clone(),
+				self.client_lock_one.clone(),
+				None,
+			)),
+			ChainId::TWO => self.futures_two.extend(spawn_action(
+				action,
+				delay,
+				self.client_two.clone(),
+				self.client_lock_two.clone(),
+				self.gas_dropper.clone(),
+			)),
+		}
+	}
+
+	/// Reads a time lock of a transfer on the chain of its contract, after `retry` failed reads.
+	fn read_time_lock(
+		&mut self,
+		transfer_id: BridgeTransferId,
+		transfer_time_lock: TransferTimeLock,
+		retry: usize,
+	) {
+		let delay = if retry == 0 { Duration::ZERO } else { action_retry_backoff(retry) };
+		let handle = match transfer_time_lock {
+			TransferTimeLock::Initiator(ChainId::ONE)
+			| TransferTimeLock::Counterparty(ChainId::ONE) => spawn_time_lock_read(
+				self.client_one.clone(),
+				transfer_id,
+				transfer_time_lock,
+				retry,
+				delay,
+			),
+			TransferTimeLock::Initiator(ChainId::TWO)
+			| TransferTimeLock::Counterparty(ChainId::TWO) => spawn_time_lock_read(
+				self.client_two.clone(),
+				transfer_id,
+				transfer_time_lock,
+				retry,
+				delay,
+			),
+		};
+		self.time_lock_futures.push(handle);
+	}
+}
+
+fn spawn_time_lock_read<A>(
+	mut client: impl BridgeContract<A> + 'static,
+	transfer_id: BridgeTransferId,
+	transfer_time_lock: TransferTimeLock,
+	retry: usize,
+	delay: Duration,
+) -> TimeLockJoinHandle
+where
+	A: Send + 'static,
+{
+	tokio::spawn(async move {
+		tokio::time::sleep(delay).await;
+		let time_lock = match transfer_time_lock {
+			TransferTimeLock::Initiator(_) => client
+				.get_bridge_transfer_details_initiator(transfer_id)
+				.await
+				.map(|details| details.map(|details| details.time_lock)),
+			TransferTimeLock::Counterparty(_) => client
+				.get_bridge_transfer_details_counterparty(transfer_id)
+				.await
+				.map(|details| details.map(|details| details.time_lock)),
+		};
+		(transfer_id, transfer_time_lock, retry, time_lock)
+	})
+}
+
+fn spawn_action<A>(
+	action: TransferAction,
+	delay: Duration,
+	client: impl BridgeContract<A> + 'static,
+	client_lock: Arc<Mutex<()>>,
+	gas_dropper: Option<GasDropper>,
+) -> Option<ActionJoinHandle>
+where
+	A: Send + TryFrom<Vec<u8>> + std::clone::Clone + 'static,
+{
+	let fut = process_action(action, client, gas_dropper)?;
+	Some(tokio::spawn(async move {
+		tokio::time::sleep(delay).await;
+		let _lock = client_lock.lock().await;
+		fut.await
+	}))
+}
+
 async fn check_monitoring_loop_heath(
 	healthcheck_tx: mpsc::Sender<oneshot::Sender<bool>>,
 ) -> Result<bool, String> {
@@ -272,14 +479,32 @@ async fn check_monitoring_loop_heath(
 	Ok(res)
 }
 
-struct Runtime {
+/// The states of the transfers processed by the relayer.
+pub struct Runtime {
 	swap_state_map: HashMap<BridgeTransferId, TransferState>,
 	indexer_db_client: Option<IndexerClient>,
+	time_locks: TimeLockScheduler,
+	tokens: TokenRegistry,
+	circuit_breaker: CircuitBreaker,
+	/// The locks paused while the circuit breaker is open.
+	paused_locks: Vec<TransferAction>,
 }
 
 impl Runtime {
-	pub fn new(indexer_db_client: Option<IndexerClient>) -> Self {
-		Runtime { swap_state_map: HashMap::new(), indexer_db_client }
+	pub fn new(
+		indexer_db_client: Option<IndexerClient>,
+		time_locks: TimeLockScheduler,
+		tokens: TokenRegistry,
+		circuit_breaker: CircuitBreaker,
+	) -> Self {
+		Runtime {
+			swap_state_map: HashMap::new(),
+			indexer_db_client,
+			time_locks,
+			tokens,
+			circuit_breaker,
+			paused_locks: Vec::new(),
+		}
 	}
 
 	pub fn iter_state(&self) -> impl Iterator<Item = &TransferState> {
@@ -329,6 +554,111 @@ impl Runtime {
 		}
 	}
 
+	fn persist_transfer_state(
+		indexer_db_client: &mut Option<IndexerClient>,
+		state: &TransferState,
+	) -> Result<(), InvalidEventError> {
+		match indexer_db_client {
+			Some(client) => {
+				client
+					.upsert_transfer_state(state.transfer_id, state.init_chain, state.state)
+					.map_err(|err| {
+						tracing::warn!("Fail to persist transfer state :{err}");
+						InvalidEventError::IndexingFailed(err.to_string())
+					})?;
+				tracing::info!("persist_transfer_state(success): {state}");
+				Ok(())
+			}
+			None => {
+				tracing::warn!("No indexer db client found. Transfer state not persisted");
+				Ok(())
+			}
+		}
+	}
+
+	/// Rebuilds the state of the pending transfers by replaying their indexed events.
+	/// The circuit breaker is restored first, so the resumed locks are checked against it.
+	/// Returns the actions to resume for these transfers.
+	pub fn restore(&mut self) -> Result<Vec<TransferAction>, anyhow::Error> {
+		let (pending_transfers, paused_transfers) = match self.indexer_db_client {
+			Some(ref mut client) => {
+				let trip_reason = client
+					.find_circuit_breaker_trip()?
+					.map(|reason| reason.parse::<TripReason>())
+					.transpose()?;
+				let volumes = client.find_locked_volumes(unix_now().saturating_sub(DAY_SECS))?;
+				let paused_transfers: HashSet<_> =
+					client.find_paused_transfers()?.into_iter().collect();
+				tracing::info!(
+					"Restore circuit breaker, trip reason: {trip_reason:?}, {} locked transfers, \
+					 {} paused transfers",
+					volumes.len(),
+					paused_transfers.len()
+				);
+				self.circuit_breaker.restore(trip_reason, volumes);
+
+				let mut pending_transfers = vec![];
+				for (transfer_id, init_chain, state_type) in
+					client.find_pending_transfer_states()?
+				{
+					let events = client
+						.find_all_events_for_bridge_transfer_id(transfer_id)?
+						.into_transfer_events(init_chain)?;
+					pending_transfers.push((transfer_id, init_chain, state_type, events));
+				}
+				(pending_transfers, paused_transfers)
+			}
+			None => {
+				tracing::warn!("No indexer db client found. Transfer states not restored");
+				return Ok(vec![]);
+			}
+		};
+
+		let mut actions = vec![];
+		for (transfer_id, init_chain, state_type, events) in pending_transfers {
+			let mut resumed_action = None;
+			for event in events {
+				if let Err(err) = self.validate_state(&event) {
+					tracing::warn!("Skip invalid indexed event for transfer {transfer_id}: {err}");
+					continue;
+				}
+				let (state, action) = self.apply_event(event);
+				if state.state != TransferStateType::Done {
+					self.swap_state_map.insert(state.transfer_id, state);
+				}
+				// Only the action of the last event is pending, the previous ones were executed.
+				resumed_action =
+					(!matches!(action.kind, TransferActionType::NoAction)).then_some(action);
+			}
+			// The refund is decided by the relayer after failed actions, not by a contract event.
+			// It is submitted once the time lock of the initiator expires, like before the restart.
+			if state_type == TransferStateType::Refund {
+				if let Some(state) = self.swap_state_map.get_mut(&transfer_id) {
+					let (new_state_type, _) = state.transition_to_refund();
+					state.state = new_state_type;
+					resumed_action = None;
+				}
+			}
+			match self.swap_state_map.get(&transfer_id) {
+				Some(state) => tracing::info!("Restored transfer {state}"),
+				None => {
+					tracing::warn!("Transfer {transfer_id} could not be restored");
+					continue;
+				}
+			}
+			match resumed_action {
+				// The locks paused before the restart stay paused until the circuit breaker is reset.
+				Some(action) if paused_transfers.contains(&action.transfer_id) => {
+					tracing::info!("Transfer {transfer_id} lock stays paused");
+					self.paused_locks.push(action);
+				}
+				Some(action) => actions.extend(self.guard_lock(action)),
+				None => (),
+			}
+		}
+		Ok(actions)
+	}
+
 	pub fn process_event<A>(
 		&mut self,
 		event: TransferEvent<A>,
@@ -337,19 +667,196 @@ impl Runtime {
 		A: Into<Vec<u8>> + std::clone::Clone + std::fmt::Debug,
 	{
 		tracing::info!("Event received: {:?}", event);
-		self.validate_state(&event)?;
+		if let Err(err) = self.validate_state(&event) {
+			if let Some(reason) = self.circuit_breaker.record_validation_failure(unix_now()) {
+				tracing::error!("Circuit breaker tripped, locks are paused: {reason}");
+				self.persist_trip_reason();
+			}
+			return Err(err);
+		}
 		let indexer_event = event.clone();
+
+		// The state is persisted first, so that a transfer with indexed events is always restored.
+		let (state, action) = self.apply_event(event);
+		let persisted = Self::persist_transfer_state(&mut self.indexer_db_client, &state);
+		if state.state != TransferStateType::Done {
+			self.swap_state_map.insert(state.transfer_id, state);
+		}
+		persisted?;
 		self.index_event(indexer_event)?;
+
+		// index action
+		// todo: really this should come after process_action completion, but the current use of process_action is hacky
+		self.index_transfer_action(action.clone())?;
+
+		let (chain, transfer_id) = (action.chain, action.transfer_id);
+		Ok(self.guard_lock(action).unwrap_or(TransferAction {
+			chain,
+			transfer_id,
+			kind: TransferActionType::NoAction,
+		}))
+	}
+
+	/// Checks a lock against the safety limits of the asset of its transfer. The lock is paused
+	/// if the circuit breaker is open or trips on it, the other actions are not limited.
+	fn guard_lock(&mut self, action: TransferAction) -> Option<TransferAction> {
+		if !matches!(action.kind, TransferActionType::LockBridgeTransfer { .. }) {
+			return Some(action);
+		}
+		// The limits apply to the amount initiated, in the decimals of the initiating chain.
+		let (init_chain, asset, amount) = match self.swap_state_map.get(&action.transfer_id) {
+			Some(state) => (state.init_chain, state.asset.clone(), state.amount.0),
+			None => {
+				tracing::error!(
+					"No state for the lock of transfer {}, pause it",
+					action.transfer_id
+				);
+				self.pause_lock(action);
+				return None;
+			}
+		};
+		let now = unix_now();
+		match self.circuit_breaker.check_transfer(
+			init_chain,
+			action.transfer_id,
+			&asset,
+			amount,
+			now,
+		) {
+			Ok(()) => {
+				if let Some(volume) = self.circuit_breaker.record_transfer(
+					init_chain,
+					action.transfer_id,
+					&asset,
+					amount,
+					now,
+				) {
+					self.persist_locked_volume(&volume);
+				}
+				Some(action)
+			}
+			Err(reason) => {
+				tracing::error!(
+					"Circuit breaker open, pause lock of transfer {}: {reason}",
+					action.transfer_id
+				);
+				self.persist_trip_reason();
+				self.pause_lock(action);
+				None
+			}
+		}
+	}
+
+	fn pause_lock(&mut self, action: TransferAction) {
+		if let Some(client) = &mut self.indexer_db_client {
+			if let Err(err) = client.insert_paused_transfer(action.transfer_id) {
+				tracing::warn!("Fail to persist paused transfer {} :{err}", action.transfer_id);
+			}
+		}
+		self.paused_locks.push(action);
+	}
+
+	/// Persists the trip reason of the circuit breaker, `None` once it is reset.
+	fn persist_trip_reason(&mut self) {
+		if let Some(client) = &mut self.indexer_db_client {
+			let trip_reason = self.circuit_breaker.trip_reason().map(TripReason::encode);
+			if let Err(err) = client.save_circuit_breaker_trip(trip_reason) {
+				tracing::warn!("Fail to persist circuit breaker trip reason :{err}");
+			}
+		}
+	}
+
+	/// Persists a locked transfer counted in the volumes, the transfers older than a day are
+	/// removed as they no longer count.
+	fn persist_locked_volume(&mut self, volume: &LockedVolume) {
+		if let Some(client) = &mut self.indexer_db_client {
+			let persisted = client.insert_locked_volume(volume).and_then(|()| {
+				client.delete_locked_volumes_before(volume.locked_at.saturating_sub(DAY_SECS))
+			});
+			if let Err(err) = persisted {
+				tracing::warn!(
+					"Fail to persist locked volume of {} :{err}",
+					volume.bridge_transfer_id
+				);
+			}
+		}
+	}
+
+	/// Gets the status of the circuit breaker at `now`, a unix timestamp in seconds.
+	pub fn circuit_breaker_status(&mut self, now: u64) -> CircuitBreakerStatus {
+		let paused_transfers = self.paused_locks.iter().map(|action| action.transfer_id).collect();
+		self.circuit_breaker.status(paused_transfers, now)
+	}
+
+	/// Resets the circuit breaker. Returns the paused locks to execute if they are released,
+	/// the locks of the transfers refunded in the meantime are dropped.
+	pub fn reset_circuit_breaker(
+		&mut self,
+		release_paused_transfers: bool,
+		now: u64,
+	) -> Vec<TransferAction> {
+		self.circuit_breaker.reset();
+		self.persist_trip_reason();
+		if let Some(client) = &mut self.indexer_db_client {
+			if let Err(err) = client.delete_paused_transfers() {
+				tracing::warn!("Fail to remove persisted paused transfers :{err}");
+			}
+		}
+		let paused_locks = std::mem::take(&mut self.paused_locks);
+		if !release_paused_transfers {
+			tracing::info!(
+				"Circuit breaker reset, drop {} paused locks to refund them",
+				paused_locks.len()
+			);
+			return vec![];
+		}
+		tracing::info!("Circuit breaker reset, release {} paused locks", paused_locks.len());
+		let mut actions = vec![];
+		for action in paused_locks {
+			let (init_chain, asset, amount) = match self.swap_state_map.get(&action.transfer_id) {
+				Some(state) if state.state == TransferStateType::Initialized => {
+					(state.init_chain, state.asset.clone(), state.amount.0)
+				}
+				_ => {
+					tracing::info!("Drop paused lock of refunded transfer {}", action.transfer_id);
+					continue;
+				}
+			};
+			if let Some(volume) = self.circuit_breaker.record_transfer(
+				init_chain,
+				action.transfer_id,
+				&asset,
+				amount,
+				now,
+			) {
+				self.persist_locked_volume(&volume);
+			}
+			actions.push(action);
+		}
+		actions
+	}
+
+	/// Applies a validated event to the state of its transfer.
+	/// The state is removed from the map and returned with the action to execute.
+	fn apply_event<A>(&mut self, event: TransferEvent<A>) -> (TransferState, TransferAction)
+	where
+		A: Into<Vec<u8>> + std::clone::Clone + std::fmt::Debug,
+	{
 		let event_transfer_id = event.contract_event.bridge_transfer_id();
 		let state_opt = self.swap_state_map.remove(&event_transfer_id);
 		//create swap state if need
+		// The time locks are scheduled once read on chain, see `schedule_time_lock`.
 		let mut state = if let BridgeContractEvent::Initiated(detail) = event.contract_event {
 			let (state, mut action) =
 				TransferState::transition_from_initiated(event.chain, event_transfer_id, detail);
 			action.chain = state.init_chain.other();
-			self.swap_state_map.insert(state.transfer_id, state);
-			self.index_transfer_action(action.clone())?;
-			return Ok(action);
+			if let TransferActionType::LockBridgeTransfer { amount, .. } = &mut action.kind {
+				//tested before in validate_state() the amount can be converted
+				*amount = self
+					.lock_amount(state.init_chain, &state.asset, state.amount)
+					.expect("Lock amount validated before");
+			}
+			return (state, action);
 		} else {
 			//tested before in validate_state() state can be unwrap
 			state_opt.unwrap()
@@ -364,12 +871,15 @@ impl Runtime {
 				(action_kind, state.init_chain)
 			}
 			BridgeContractEvent::CounterPartyCompleted(_, preimage) => {
+				// The secret is revealed, the transfer is completed instead of refunded.
+				self.time_locks.cancel(event_transfer_id);
 				let (new_state, action_kind) =
 					state.transition_from_counterpart_completed(event_transfer_id, preimage);
 				state = new_state;
 				(action_kind, state.init_chain)
 			}
 			BridgeContractEvent::InitiatorCompleted(_) => {
+				self.time_locks.cancel(event_transfer_id);
 				let (new_state, action_kind) =
 					state.transition_from_initiator_completed(event_transfer_id);
 				state = new_state;
@@ -377,12 +887,14 @@ impl Runtime {
 				(action_kind, state.init_chain)
 			}
 			BridgeContractEvent::Cancelled(_) => {
+				self.time_locks.cancel_counterparty(event_transfer_id);
 				let (new_state, action_kind) = state.transition_from_cancelled(event_transfer_id);
 				state = new_state;
 
 				(action_kind, state.init_chain)
 			}
 			BridgeContractEvent::Refunded(_) => {
+				self.time_locks.cancel(event_transfer_id);
 				let (new_state, action_kind) = state.transition_from_refunded(event_transfer_id);
 				state = new_state;
 
@@ -392,15 +904,111 @@ impl Runtime {
 
 		let action =
 			TransferAction { chain: chain_id, transfer_id: state.transfer_id, kind: action_kind };
+		(state, action)
+	}
 
-		// index action
-		// todo: really this should come after process_action completion, but the current use of process_action is hacky
-		self.index_transfer_action(action.clone())?;
+	/// Schedules the expiry of a time lock of a transfer, `time_lock` is the unix timestamp in
+	/// seconds read from its contract.
+	pub fn schedule_time_lock(
+		&mut self,
+		transfer_id: BridgeTransferId,
+		transfer_time_lock: TransferTimeLock,
+		time_lock: TimeLock,
+	) {
+		match self.swap_state_map.get(&transfer_id) {
+			Some(state) => {
+				tracing::info!(
+					"Schedule {transfer_time_lock:?} time lock {time_lock:?} of {state}"
+				);
+				self.time_locks.schedule(transfer_id, transfer_time_lock, time_lock);
+			}
+			None => tracing::info!("Skip the time lock of transfer {transfer_id}, it is done"),
+		}
+	}
 
-		if state.state != TransferStateType::Done {
-			self.swap_state_map.insert(state.transfer_id, state);
+	/// Gets the time locks the pending transfers can still expire on, to read them on chain.
+	pub fn pending_time_locks(&self) -> Vec<(BridgeTransferId, TransferTimeLock)> {
+		let mut time_locks = vec![];
+		for state in self.swap_state_map.values() {
+			if matches!(
+				state.state,
+				TransferStateType::Initialized
+					| TransferStateType::Locked
+					| TransferStateType::Refund
+			) {
+				time_locks.push((state.transfer_id, TransferTimeLock::Initiator(state.init_chain)));
+			}
+			if state.state == TransferStateType::Locked {
+				time_locks.push((
+					state.transfer_id,
+					TransferTimeLock::Counterparty(state.init_chain.other()),
+				));
+			}
 		}
-		Ok(action)
+		time_locks
+	}
+
+	/// Gets the refunds and aborts to submit for the time locks expired at `now`,
+	/// a unix timestamp in seconds.
+	pub fn process_expired_time_locks(&mut self, now: u64) -> Vec<TransferAction> {
+		let mut actions = vec![];
+		for (transfer_id, expired) in self.time_locks.take_expired(now) {
+			let state = match self.swap_state_map.get_mut(&transfer_id) {
+				Some(state) => state,
+				None => continue,
+			};
+			match expired {
+				// The counterparty was not completed, abort the lock.
+				TransferTimeLock::Counterparty(chain)
+					if state.state == TransferStateType::Locked =>
+				{
+					tracing::info!("Counterparty time lock expired, abort transfer {state}");
+					actions.push(TransferAction {
+						chain,
+						transfer_id,
+						kind: TransferActionType::AbortCounterparty,
+					});
+				}
+				// The secret was not revealed, refund the initiator.
+				TransferTimeLock::Initiator(chain)
+					if matches!(
+						state.state,
+						TransferStateType::Initialized
+							| TransferStateType::Locked | TransferStateType::Refund
+					) =>
+				{
+					tracing::info!("Initiator time lock expired, refund transfer {state}");
+					let (new_state_type, action_kind) = state.transition_to_refund();
+					state.state = new_state_type;
+					let _ = Self::persist_transfer_state(&mut self.indexer_db_client, state);
+					actions.push(TransferAction { chain, transfer_id, kind: action_kind });
+				}
+				_ => (),
+			}
+		}
+		actions
+	}
+
+	/// Converts the amount of a transfer initiated on a chain to the decimals of its asset on the
+	/// other chain, where it is locked.
+	fn lock_amount(
+		&self,
+		init_chain: ChainId,
+		asset: &AssetId,
+		amount: Amount,
+	) -> Result<Amount, InvalidEventError> {
+		let token = self
+			.tokens
+			.get(&asset.0)
+			.ok_or(InvalidEventError::UnsupportedAsset(asset.clone()))?;
+		// Chain one is Ethereum and chain two is Movement.
+		let (from_decimals, to_decimals) = match init_chain {
+			ChainId::ONE => (token.eth_decimals, token.mvt_decimals),
+			ChainId::TWO => (token.mvt_decimals, token.eth_decimals),
+		};
+		amount.convert_decimals(from_decimals, to_decimals).map_err(|err| {
+			InvalidEventError::BadEvent(format!("Amount of asset {asset} can't be locked: {err}"))
+		})
 	}
 
 	fn validate_state<A: std::fmt::Debug>(
@@ -421,6 +1029,10 @@ impl Runtime {
 		} else {
 			tracing::info!("No existing state found for transfer ID {:?}", event_transfer_id);
 		}
+		// Only the assets of the token registry are bridged, in amounts the other chain can lock.
+		if let BridgeContractEvent::Initiated(details) = &event.contract_event {
+			self.lock_amount(event.chain, &details.asset, details.amount)?;
+		}
 		//validate the associated swap_state.
 		swap_state_opt
 			.as_ref()
@@ -438,38 +1050,51 @@ impl Runtime {
 		Ok(())
 	}
 
-	fn process_action_exec_error(&mut self, action_err: ActionExecError) -> Option<TransferAction> {
+	/// Decides what to do after the execution of an action failed.
+	/// Returns the action to execute next, with the delay to wait before executing it.
+	pub fn process_action_exec_error(
+		&mut self,
+		action_err: ActionExecError,
+	) -> Option<(TransferAction, Duration)> {
 		// Manage Tx execution error
 		let (action, err) = action_err.inner();
 		tracing::warn!("Client execution error for action:{action} err:{err}");
-		// retry 5 time an action in error then abort.
 		match self.swap_state_map.get_mut(&action.transfer_id) {
 			Some(state) => {
 				state.retry_on_error += 1;
-				if state.retry_on_error > 5 {
-					// Depending on the action cancel transfer
-					match action.kind {
-						TransferActionType::LockBridgeTransfer { .. } => {
-							//Lock fail. Refund initiator
-							let (new_state_type, action_kind) = state.transition_to_refund();
-							state.state = new_state_type;
-							let action = TransferAction {
-								chain: state.init_chain,
-								transfer_id: state.transfer_id,
-								kind: action_kind,
-							};
-							Some(action)
-						}
-						TransferActionType::WaitAndCompleteInitiator(..) => {
-							todo!()
-						}
-						TransferActionType::RefundInitiator => None, //will wait automatic refund
-						TransferActionType::TransferDone => None,
-						TransferActionType::NoAction => None,
+				match action.kind {
+					// Retry the lock a few times then refund the initiator, once its time lock expires.
+					TransferActionType::LockBridgeTransfer { .. }
+						if state.retry_on_error > MAX_LOCK_RETRIES =>
+					{
+						let (new_state_type, _) = state.transition_to_refund();
+						state.state = new_state_type;
+						state.retry_on_error = 0;
+						tracing::info!(
+							"Lock failed, wait for the time lock to refund transfer {state}"
+						);
+						let _ = Self::persist_transfer_state(&mut self.indexer_db_client, state);
+						None
 					}
-				} else {
-					//Rerun the action.
-					Some(action)
+					// Once the secret is revealed the initiator must be completed, the retries only
+					// wait for the backoff.
+					TransferActionType::WaitAndCompleteInitiator(_, secret) => {
+						let delay = action_retry_backoff(state.retry_on_error);
+						tracing::info!("Retry action:{action} in {delay:?}");
+						let kind = TransferActionType::WaitAndCompleteInitiator(0, secret);
+						Some((TransferAction { kind, ..action }, delay))
+					}
+					// Refunds and aborts are submitted after their time lock: they are retried
+					// until they succeed.
+					TransferActionType::LockBridgeTransfer { .. }
+					| TransferActionType::RefundInitiator
+					| TransferActionType::AbortCounterparty => {
+						let delay = action_retry_backoff(state.retry_on_error);
+						tracing::info!("Retry action:{action} in {delay:?}");
+						Some((action, delay))
+					}
+					TransferActionType::TransferDone => None,
+					TransferActionType::NoAction => None,
 				}
 			}
 			None => {
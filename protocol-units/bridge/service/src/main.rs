@@ -8,14 +8,17 @@ use bridge_service::{
 	chains::{
 		ethereum::{client::EthClient, event_monitoring::EthMonitoring},
 		movement::{
-			client_framework::MovementClientFramework, event_monitoring::MovementMonitoring,
+			client_framework::MovementClientFramework,
+			event_monitoring::MovementMonitoring,
+			gas_drop::{GasDropPolicy, GasDropper},
 		},
 	},
-	grpc::HealthCheckService,
+	grpc::{BridgeGrpcService, HealthCheckService},
 	rest::BridgeRest,
+	time_lock::{unix_now, TimeLockScheduler},
 };
 use godfig::{backend::config_file::ConfigFile, Godfig};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 use tonic::transport::Server;
 
 #[tokio::main]
@@ -43,18 +46,51 @@ async fn main() -> Result<()> {
 
 	tracing::info!("Bridge config loaded: {bridge_config:?}");
 
+	// The assets bridged by the contracts of both chains must be registered, each ERC-20 with a
+	// single fungible asset.
+	bridge_config.tokens.validate()?;
+	for asset in [&bridge_config.eth.asset, &bridge_config.movement.asset] {
+		if bridge_config.tokens.get(asset).is_none() {
+			anyhow::bail!("Asset {asset} of the bridge contracts is missing in the token registry");
+		}
+	}
+
 	let (eth_health_tx, eth_health_rx) = tokio::sync::mpsc::channel(10);
-	let one_stream = EthMonitoring::build(&bridge_config.eth, eth_health_rx).await.unwrap();
-	let one_client = EthClient::new(&bridge_config.eth).await.unwrap();
-	let two_client = MovementClientFramework::new(&bridge_config.movement).await.unwrap();
+	let one_stream =
+		EthMonitoring::build_with_tokens(&bridge_config.eth, &bridge_config.tokens, eth_health_rx)
+			.await
+			.unwrap();
+	let one_client = EthClient::new_with_tokens(&bridge_config.eth, &bridge_config.tokens)
+		.await
+		.unwrap();
+	let two_client =
+		MovementClientFramework::new_with_tokens(&bridge_config.movement, &bridge_config.tokens)
+			.await
+			.unwrap();
 	let (mvt_health_tx, mvt_health_rx) = tokio::sync::mpsc::channel(10);
-	let two_stream =
-		MovementMonitoring::build(&bridge_config.movement, mvt_health_rx).await.unwrap();
+	let two_stream = MovementMonitoring::build_with_tokens(
+		&bridge_config.movement,
+		&bridge_config.tokens,
+		mvt_health_rx,
+	)
+	.await
+	.unwrap();
+
+	// Requests the health of the relayer loop, for the REST and gRPC health checks.
+	let (health_tx, health_rx) = tokio::sync::mpsc::channel(10);
+
+	// Sends the circuit breaker admin commands of the gRPC service to the relayer loop.
+	let (circuit_breaker_tx, circuit_breaker_rx) = tokio::sync::mpsc::channel(10);
 
-	let one_client_for_grpc = one_client.clone();
+	let bridge_grpc_service = BridgeGrpcService::new(
+		one_client.clone(),
+		two_client.clone(),
+		&bridge_config.movement,
+		circuit_breaker_tx,
+	)?;
 
 	// Initialize the gRPC health check service
-	let health_service = HealthCheckService::default();
+	let health_service = HealthCheckService::new(health_tx.clone());
 	health_service.set_service_status("", ServingStatus::Serving);
 	health_service.set_service_status("Bridge", ServingStatus::Serving);
 
@@ -65,19 +101,32 @@ async fn main() -> Result<()> {
 	.parse()
 	.unwrap();
 
+	// Start the gRPC server on a specific address (e.g., localhost:50051)
 	let grpc_jh = tokio::spawn(async move {
 		Server::builder()
 			.add_service(HealthServer::new(health_service))
-			.add_service(BridgeServer::new(one_client_for_grpc))
+			.add_service(BridgeServer::new(bridge_grpc_service))
 			.serve(grpc_addr)
 			.await
 	});
 
-	// Initialize the gRPC health check service
-	let (health_tx, health_rx) = tokio::sync::mpsc::channel(10);
-	// Start the gRPC server on a specific address (e.g., localhost:50051)
+	// Requests the transfers in progress in the relayer loop, for the REST service.
+	let (pending_transfers_tx, pending_transfers_rx) = tokio::sync::mpsc::channel(10);
+	// The REST service queries the indexer db with its own connection.
+	let rest_indexer_db_client = match Client::from_env() {
+		Ok(client) => Some(client),
+		Err(e) => {
+			tracing::warn!("Failed to create REST indexer db client: {e:?}");
+			None
+		}
+	};
 	// Create and run the REST service
-	let rest_service = BridgeRest::new(&bridge_config.movement, health_tx)?;
+	let rest_service = BridgeRest::new(
+		&bridge_config.movement,
+		health_tx,
+		pending_transfers_tx,
+		rest_indexer_db_client,
+	)?;
 	let rest_service_future = rest_service.run_service();
 	let rest_jh = tokio::spawn(rest_service_future);
 
@@ -93,6 +142,27 @@ async fn main() -> Result<()> {
 		}
 	};
 
+	let time_lock_scheduler = TimeLockScheduler::new(
+		Duration::from_secs(bridge_config.eth.eth_time_lock_safety_margin_secs),
+		Duration::from_secs(bridge_config.movement.mvt_time_lock_safety_margin_secs),
+	);
+
+	let gas_dropper = if bridge_config.movement.gas_drop_enabled {
+		let policy = GasDropPolicy::from_config(&bridge_config.movement, unix_now())?;
+		// The dropper persists its budget with its own connection to the indexer database.
+		let gas_drop_db_client = match Client::from_env() {
+			Ok(client) => Some(client),
+			Err(e) => {
+				tracing::warn!("Gas drop budget is not persisted, no indexer db client: {e:?}");
+				None
+			}
+		};
+		Some(GasDropper::new(two_client.clone(), policy, gas_drop_db_client)?)
+	} else {
+		tracing::info!("Gas drop to the recipients on Movement is disabled");
+		None
+	};
+
 	let loop_jh = tokio::spawn(async move {
 		bridge_service::run_bridge(
 			one_client,
@@ -100,9 +170,15 @@ async fn main() -> Result<()> {
 			two_client,
 			two_stream,
 			health_rx,
+			pending_transfers_rx,
+			circuit_breaker_rx,
 			indexer_db_client,
 			eth_health_tx,
 			mvt_health_tx,
+			time_lock_scheduler,
+			bridge_config.tokens,
+			bridge_config.limits,
+			gas_dropper,
 		)
 		.await
 	});
@@ -1,18 +1,196 @@
 use anyhow::Error;
 use bridge_config::common::movement::MovementConfig;
+use bridge_indexer_db::client::{BridgeEventPackage, Client as IndexerClient};
+use bridge_indexer_db::models::InitiatedEvent;
+use bridge_util::states::TransferState;
+use bridge_util::types::{BridgeAddress, BridgeTransferId};
 use futures::prelude::*;
 use poem::{
-	get, handler, listener::TcpListener, middleware::Tracing, web::Data, EndpointExt, IntoResponse,
-	Response, Route, Server,
+	get, handler,
+	http::StatusCode,
+	listener::TcpListener,
+	middleware::Tracing,
+	web::{Data, Json, Path, Query},
+	EndpointExt, IntoResponse, Response, Route, Server,
 };
+use serde::{Deserialize, Serialize};
 use std::future::Future;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::info;
 
+/// Number of transfers per page when the request doesn't set it.
+const DEFAULT_PAGE_SIZE: i64 = 20;
+/// Maximum number of transfers per page.
+const MAX_PAGE_SIZE: i64 = 100;
+/// Maximum time to wait for the relayer loop to answer a request.
+const RELAYER_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// A transfer, as listed by the REST service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferSummary {
+	pub bridge_transfer_id: String,
+	pub init_chain: Option<String>,
+	pub state: Option<String>,
+	pub initiator: String,
+	pub recipient: String,
+	pub asset_id: String,
+	pub amount: String,
+	/// When the initiated event was indexed, in RFC 3339
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub created_at: Option<String>,
+}
+
+impl From<&TransferState> for TransferSummary {
+	fn from(state: &TransferState) -> Self {
+		let initiator: BridgeAddress<Vec<u8>> = state.intiator_address.clone().into();
+		let recipient: BridgeAddress<Vec<u8>> = state.counter_part_address.clone().into();
+		TransferSummary {
+			bridge_transfer_id: hex::encode(state.transfer_id.0),
+			init_chain: Some(state.init_chain.to_string()),
+			state: Some(state.state.to_string()),
+			initiator: hex::encode(initiator.0),
+			recipient: hex::encode(recipient.0),
+			asset_id: state.asset.to_string(),
+			amount: state.amount.0.to_string(),
+			created_at: None,
+		}
+	}
+}
+
+/// An event of a transfer, in its timeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event")]
+pub enum TransferEventEntry {
+	Initiated {
+		initiator: String,
+		recipient: String,
+		hash_lock: String,
+		time_lock: i64,
+		asset_id: String,
+		amount: String,
+		created_at: String,
+	},
+	Locked {
+		initiator: String,
+		recipient: String,
+		hash_lock: String,
+		time_lock: i64,
+		asset_id: String,
+		amount: String,
+		created_at: String,
+	},
+	CounterPartyCompleted {
+		pre_image: String,
+		created_at: String,
+	},
+	InitiatorCompleted {
+		created_at: String,
+	},
+	Cancelled {
+		created_at: String,
+	},
+	Refunded {
+		created_at: String,
+	},
+}
+
+/// A transfer with the timeline of its indexed events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferDetails {
+	pub bridge_transfer_id: String,
+	pub init_chain: Option<String>,
+	pub state: Option<String>,
+	pub events: Vec<TransferEventEntry>,
+}
+
+/// Orders the indexed events of a transfer by the time they were indexed.
+fn event_timeline(events: BridgeEventPackage) -> Vec<TransferEventEntry> {
+	let mut timeline = Vec::new();
+	for event in events.initiated_events {
+		timeline.push((
+			event.created_at,
+			TransferEventEntry::Initiated {
+				initiator: event.initiator,
+				recipient: event.recipient,
+				hash_lock: event.hash_lock,
+				time_lock: event.time_lock,
+				asset_id: event.asset_id,
+				amount: event.amount.to_string(),
+				created_at: event.created_at.and_utc().to_rfc3339(),
+			},
+		));
+	}
+	for event in events.locked_events {
+		timeline.push((
+			event.created_at,
+			TransferEventEntry::Locked {
+				initiator: event.initiator,
+				recipient: event.recipient,
+				hash_lock: event.hash_lock,
+				time_lock: event.time_lock,
+				asset_id: event.asset_id,
+				amount: event.amount.to_string(),
+				created_at: event.created_at.and_utc().to_rfc3339(),
+			},
+		));
+	}
+	for event in events.counter_party_completed_events {
+		timeline.push((
+			event.created_at,
+			TransferEventEntry::CounterPartyCompleted {
+				pre_image: event.pre_image,
+				created_at: event.created_at.and_utc().to_rfc3339(),
+			},
+		));
+	}
+	for event in events.initiator_completed_events {
+		let created_at = event.created_at.and_utc().to_rfc3339();
+		timeline.push((event.created_at, TransferEventEntry::InitiatorCompleted { created_at }));
+	}
+	for event in events.cancelled_events {
+		let created_at = event.created_at.and_utc().to_rfc3339();
+		timeline.push((event.created_at, TransferEventEntry::Cancelled { created_at }));
+	}
+	for event in events.refunded_events {
+		let created_at = event.created_at.and_utc().to_rfc3339();
+		timeline.push((event.created_at, TransferEventEntry::Refunded { created_at }));
+	}
+	// The sort is stable, so the events indexed at the same time keep the order of the transfer.
+	timeline.sort_by_key(|(created_at, _)| *created_at);
+	timeline.into_iter().map(|(_, entry)| entry).collect()
+}
+
 struct RestContext {
 	request_tx: mpsc::Sender<oneshot::Sender<String>>,
+	pending_transfers_tx: mpsc::Sender<oneshot::Sender<Vec<TransferSummary>>>,
+	indexer_db_client: Option<Arc<Mutex<IndexerClient>>>,
+}
+
+impl RestContext {
+	/// Runs a query on the indexer db, outside of the async runtime.
+	async fn query_indexer<T, F>(&self, query: F) -> poem::Result<T>
+	where
+		T: Send + 'static,
+		F: FnOnce(&mut IndexerClient) -> Result<T, anyhow::Error> + Send + 'static,
+	{
+		let client = self.indexer_db_client.clone().ok_or_else(|| {
+			poem::Error::from_string("No indexer db configured", StatusCode::SERVICE_UNAVAILABLE)
+		})?;
+		tokio::task::spawn_blocking(move || {
+			let mut client = client
+				.lock()
+				.map_err(|e| anyhow::anyhow!("Failed to lock indexer db client: {:?}", e))?;
+			query(&mut client)
+		})
+		.await
+		.map_err(|e| anyhow::anyhow!("Failed to run indexer db query: {:?}", e))?
+		.map_err(|e| {
+			tracing::warn!("Indexer db query failed: {e:?}");
+			poem::Error::from_status(StatusCode::INTERNAL_SERVER_ERROR)
+		})
+	}
 }
 
 pub struct BridgeRest {
@@ -26,10 +204,16 @@ impl BridgeRest {
 	pub fn new(
 		conf: &MovementConfig,
 		request_tx: mpsc::Sender<oneshot::Sender<String>>,
+		pending_transfers_tx: mpsc::Sender<oneshot::Sender<Vec<TransferSummary>>>,
+		indexer_db_client: Option<IndexerClient>,
 	) -> Result<Self, anyhow::Error> {
 		let url = format!("{}:{}", conf.rest_listener_hostname, conf.rest_port);
 
-		let context = RestContext { request_tx };
+		let context = RestContext {
+			request_tx,
+			pending_transfers_tx,
+			indexer_db_client: indexer_db_client.map(|client| Arc::new(Mutex::new(client))),
+		};
 		Ok(Self { url, context: Arc::new(context) })
 	}
 
@@ -42,14 +226,153 @@ impl BridgeRest {
 	}
 
 	pub fn create_routes(&self) -> impl EndpointExt {
-		Route::new().at("/health", get(health)).with(Tracing).data(self.context.clone())
+		Route::new()
+			.at("/health", get(health))
+			.at("/transfers", get(list_transfers))
+			.at("/transfers/pending", get(pending_transfers))
+			.at("/transfers/:bridge_transfer_id", get(get_transfer))
+			.with(Tracing)
+			.data(self.context.clone())
 	}
 }
 
 #[handler]
 async fn health(context: Data<&Arc<RestContext>>) -> Result<Response, anyhow::Error> {
 	let (tx, rx) = oneshot::channel();
-	tokio::time::timeout(std::time::Duration::from_secs(2), context.request_tx.send(tx)).await??;
-	let resp = rx.await?;
+	tokio::time::timeout(RELAYER_REQUEST_TIMEOUT, context.request_tx.send(tx)).await??;
+	let resp = tokio::time::timeout(RELAYER_REQUEST_TIMEOUT, rx).await??;
 	Ok(resp.into_response())
 }
+
+fn parse_hex(name: &str, value: &str) -> poem::Result<Vec<u8>> {
+	hex::decode(value.trim_start_matches("0x"))
+		.map_err(|_| poem::Error::from_string(format!("Invalid {name}"), StatusCode::BAD_REQUEST))
+}
+
+#[handler]
+async fn get_transfer(
+	context: Data<&Arc<RestContext>>,
+	Path(bridge_transfer_id): Path<String>,
+) -> poem::Result<Json<TransferDetails>> {
+	let bridge_transfer_id = BridgeTransferId::parse(bridge_transfer_id.trim_start_matches("0x"))
+		.map_err(|_| {
+		poem::Error::from_string("Invalid bridge transfer id", StatusCode::BAD_REQUEST)
+	})?;
+
+	let (events, transfer_state) = context
+		.query_indexer(move |client| {
+			let events = client.find_all_events_for_bridge_transfer_id(bridge_transfer_id)?;
+			let transfer_state = client.find_transfer_state(bridge_transfer_id)?;
+			Ok((events, transfer_state))
+		})
+		.await?;
+
+	let events = event_timeline(events);
+	if events.is_empty() && transfer_state.is_none() {
+		return Err(poem::Error::from_status(StatusCode::NOT_FOUND));
+	}
+	Ok(Json(TransferDetails {
+		bridge_transfer_id: hex::encode(bridge_transfer_id.0),
+		init_chain: transfer_state.map(|(init_chain, _)| init_chain.to_string()),
+		state: transfer_state.map(|(_, state)| state.to_string()),
+		events,
+	}))
+}
+
+#[derive(Debug, Deserialize)]
+struct ListTransfersParams {
+	initiator: Option<String>,
+	recipient: Option<String>,
+	page: Option<i64>,
+	page_size: Option<i64>,
+}
+
+#[handler]
+async fn list_transfers(
+	context: Data<&Arc<RestContext>>,
+	Query(params): Query<ListTransfersParams>,
+) -> poem::Result<Json<Vec<TransferSummary>>> {
+	let page = params.page.unwrap_or(0);
+	let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+	if page < 0 || !(1..=MAX_PAGE_SIZE).contains(&page_size) {
+		return Err(poem::Error::from_string(
+			format!("Invalid page, the page size must be between 1 and {MAX_PAGE_SIZE}"),
+			StatusCode::BAD_REQUEST,
+		));
+	}
+	let offset = page.saturating_mul(page_size);
+
+	let transfers = match (params.initiator, params.recipient) {
+		(Some(initiator), None) => {
+			let initiator = parse_hex("initiator", &initiator)?;
+			context
+				.query_indexer(move |client| {
+					let events =
+						client.find_initiated_events_by_initiator(&initiator, offset, page_size)?;
+					summarize_initiated_events(client, events)
+				})
+				.await?
+		}
+		(None, Some(recipient)) => {
+			let recipient = parse_hex("recipient", &recipient)?;
+			context
+				.query_indexer(move |client| {
+					let events =
+						client.find_initiated_events_by_recipient(&recipient, offset, page_size)?;
+					summarize_initiated_events(client, events)
+				})
+				.await?
+		}
+		_ => {
+			return Err(poem::Error::from_string(
+				"Either an initiator or a recipient is required",
+				StatusCode::BAD_REQUEST,
+			))
+		}
+	};
+	Ok(Json(transfers))
+}
+
+fn summarize_initiated_events(
+	client: &mut IndexerClient,
+	events: Vec<InitiatedEvent>,
+) -> Result<Vec<TransferSummary>, anyhow::Error> {
+	let bridge_transfer_ids = events
+		.iter()
+		.map(|event| {
+			BridgeTransferId::parse(&event.bridge_transfer_id)
+				.map_err(|e| anyhow::anyhow!("Failed to decode bridge transfer id: {:?}", e))
+		})
+		.collect::<Result<Vec<_>, anyhow::Error>>()?;
+	// The states of the page are read in one query, not one per transfer.
+	let transfer_states = client.find_transfer_states(&bridge_transfer_ids)?;
+
+	Ok(events
+		.into_iter()
+		.zip(bridge_transfer_ids)
+		.map(|(event, bridge_transfer_id)| {
+			let transfer_state = transfer_states.get(&bridge_transfer_id);
+			TransferSummary {
+				bridge_transfer_id: event.bridge_transfer_id,
+				init_chain: transfer_state.map(|(init_chain, _)| init_chain.to_string()),
+				state: transfer_state.map(|(_, state)| state.to_string()),
+				initiator: event.initiator,
+				recipient: event.recipient,
+				asset_id: event.asset_id,
+				amount: event.amount.to_string(),
+				created_at: Some(event.created_at.and_utc().to_rfc3339()),
+			}
+		})
+		.collect())
+}
+
+#[handler]
+async fn pending_transfers(
+	context: Data<&Arc<RestContext>>,
+) -> Result<Json<Vec<TransferSummary>>, anyhow::Error> {
+	let (tx, rx) = oneshot::channel();
+	tokio::time::timeout(RELAYER_REQUEST_TIMEOUT, context.pending_transfers_tx.send(tx)).await??;
+	// The relayer loop answers between two events, it may be busy processing one.
+	let transfers = tokio::time::timeout(RELAYER_REQUEST_TIMEOUT, rx).await??;
+	Ok(Json(transfers))
+}
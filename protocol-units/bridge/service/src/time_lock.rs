@@ -0,0 +1,191 @@
+//! Tracks the time locks of the transfers, to refund or abort them once they expire.
+
+use bridge_util::types::{BridgeTransferId, ChainId, TimeLock};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A time lock of a transfer, with the chain of its contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferTimeLock {
+	/// Once expired, the initiator can be refunded.
+	Initiator(ChainId),
+	/// Once expired, the counterparty lock can be aborted.
+	Counterparty(ChainId),
+}
+
+#[derive(Debug, Default)]
+struct Deadlines {
+	initiator: Option<(ChainId, u64)>,
+	counterparty: Option<(ChainId, u64)>,
+}
+
+/// Gets the current unix timestamp in seconds, the time of the time lock deadlines.
+pub fn unix_now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Schedules the expiry of the time locks of the transfers on both chains.
+///
+/// The time locks are the unix timestamps in seconds read from the bridge contracts, when the
+/// transfer is initiated or locked on chain. A deadline is delayed by the safety margin of the
+/// chain, to never precede the expiry on chain when the clocks of the relayer and the chain drift.
+pub struct TimeLockScheduler {
+	safety_margin_one: Duration,
+	safety_margin_two: Duration,
+	deadlines: HashMap<BridgeTransferId, Deadlines>,
+}
+
+impl TimeLockScheduler {
+	pub fn new(safety_margin_one: Duration, safety_margin_two: Duration) -> Self {
+		TimeLockScheduler { safety_margin_one, safety_margin_two, deadlines: HashMap::new() }
+	}
+
+	fn deadline(&self, chain: ChainId, time_lock: TimeLock) -> u64 {
+		let safety_margin = match chain {
+			ChainId::ONE => self.safety_margin_one,
+			ChainId::TWO => self.safety_margin_two,
+		};
+		time_lock.0.saturating_add(safety_margin.as_secs())
+	}
+
+	/// Schedules the expiry of a time lock of a transfer, `time_lock` is its on-chain timestamp.
+	pub fn schedule(
+		&mut self,
+		transfer_id: BridgeTransferId,
+		transfer_time_lock: TransferTimeLock,
+		time_lock: TimeLock,
+	) {
+		let deadlines = self.deadlines.entry(transfer_id).or_default();
+		match transfer_time_lock {
+			TransferTimeLock::Initiator(chain) => {
+				deadlines.initiator = Some((chain, self.deadline(chain, time_lock)))
+			}
+			TransferTimeLock::Counterparty(chain) => {
+				deadlines.counterparty = Some((chain, self.deadline(chain, time_lock)))
+			}
+		}
+	}
+
+	/// Cancels the expiry of the counterparty time lock of a transfer.
+	pub fn cancel_counterparty(&mut self, transfer_id: BridgeTransferId) {
+		if let Some(deadlines) = self.deadlines.get_mut(&transfer_id) {
+			deadlines.counterparty = None;
+		}
+	}
+
+	/// Cancels the expiry of both time locks of a transfer.
+	pub fn cancel(&mut self, transfer_id: BridgeTransferId) {
+		self.deadlines.remove(&transfer_id);
+	}
+
+	/// Takes the time locks expired at `now`, a unix timestamp in seconds.
+	pub fn take_expired(&mut self, now: u64) -> Vec<(BridgeTransferId, TransferTimeLock)> {
+		let mut expired = vec![];
+		for (transfer_id, deadlines) in self.deadlines.iter_mut() {
+			if let Some((chain, deadline)) = deadlines.counterparty {
+				if deadline <= now {
+					deadlines.counterparty = None;
+					expired.push((*transfer_id, TransferTimeLock::Counterparty(chain)));
+				}
+			}
+			if let Some((chain, deadline)) = deadlines.initiator {
+				if deadline <= now {
+					deadlines.initiator = None;
+					expired.push((*transfer_id, TransferTimeLock::Initiator(chain)));
+				}
+			}
+		}
+		self.deadlines.retain(|_, deadlines| {
+			deadlines.initiator.is_some() || deadlines.counterparty.is_some()
+		});
+		expired
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_take_expired_with_safety_margins() {
+		let mut scheduler = TimeLockScheduler::new(Duration::from_secs(10), Duration::from_secs(5));
+		let transfer_id = BridgeTransferId([1; 32]);
+		let now = 1_700_000_000;
+		scheduler.schedule(
+			transfer_id,
+			TransferTimeLock::Initiator(ChainId::ONE),
+			TimeLock(now + 100),
+		);
+		scheduler.schedule(
+			transfer_id,
+			TransferTimeLock::Counterparty(ChainId::TWO),
+			TimeLock(now + 50),
+		);
+
+		assert!(scheduler.take_expired(now + 54).is_empty());
+		assert_eq!(
+			scheduler.take_expired(now + 55),
+			vec![(transfer_id, TransferTimeLock::Counterparty(ChainId::TWO))]
+		);
+		assert!(scheduler.take_expired(now + 109).is_empty());
+		assert_eq!(
+			scheduler.take_expired(now + 110),
+			vec![(transfer_id, TransferTimeLock::Initiator(ChainId::ONE))]
+		);
+		assert!(scheduler.deadlines.is_empty());
+	}
+
+	#[test]
+	fn test_schedule_replaces_deadline() {
+		let mut scheduler = TimeLockScheduler::new(Duration::ZERO, Duration::ZERO);
+		let transfer_id = BridgeTransferId([1; 32]);
+		let now = 1_700_000_000;
+		scheduler.schedule(
+			transfer_id,
+			TransferTimeLock::Initiator(ChainId::ONE),
+			TimeLock(now + 10),
+		);
+		scheduler.schedule(
+			transfer_id,
+			TransferTimeLock::Initiator(ChainId::ONE),
+			TimeLock(now + 20),
+		);
+
+		assert!(scheduler.take_expired(now + 10).is_empty());
+		assert_eq!(
+			scheduler.take_expired(now + 20),
+			vec![(transfer_id, TransferTimeLock::Initiator(ChainId::ONE))]
+		);
+	}
+
+	#[test]
+	fn test_cancel_deadlines() {
+		let mut scheduler = TimeLockScheduler::new(Duration::ZERO, Duration::ZERO);
+		let transfer_id = BridgeTransferId([1; 32]);
+		let now = 1_700_000_000;
+		scheduler.schedule(
+			transfer_id,
+			TransferTimeLock::Initiator(ChainId::ONE),
+			TimeLock(now + 10),
+		);
+		scheduler.schedule(
+			transfer_id,
+			TransferTimeLock::Counterparty(ChainId::TWO),
+			TimeLock(now + 10),
+		);
+
+		scheduler.cancel_counterparty(transfer_id);
+		assert_eq!(
+			scheduler.take_expired(now + 10),
+			vec![(transfer_id, TransferTimeLock::Initiator(ChainId::ONE))]
+		);
+
+		scheduler.schedule(
+			transfer_id,
+			TransferTimeLock::Initiator(ChainId::ONE),
+			TimeLock(now + 10),
+		);
+		scheduler.cancel(transfer_id);
+		assert!(scheduler.take_expired(now + 10).is_empty());
+	}
+}
@@ -49,6 +49,8 @@ pub async fn setup_local_ethereum(config: &mut BridgeConfig) -> Result<(), anyho
 	println!("ICI setup_local_ethereum {:?}", config.eth.eth_rpc_connection_url());
 	let signer_private_key = config.eth.signer_private_key.parse::<PrivateKeySigner>()?;
 	let rpc_url = config.eth.eth_rpc_connection_url();
+	// The local chain only mines a block per transaction and never reorgs.
+	config.eth.eth_confirmation_depth = 0;
 
 	tracing::info!("Bridge deploy setup_local_ethereum");
 	config.eth.eth_initiator_contract = deploy_eth_initiator_contract(config).await?.to_string();
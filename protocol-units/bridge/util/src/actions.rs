@@ -1,6 +1,6 @@
 use crate::chains::bridge_contracts::BridgeContractError;
 use crate::types::ChainId;
-use crate::types::{Amount, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage};
+use crate::types::{Amount, AssetId, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage};
 use std::fmt;
 use thiserror::Error;
 
@@ -38,10 +38,12 @@ pub enum TransferActionType {
 		hash_lock: HashLock,
 		initiator: BridgeAddress<Vec<u8>>,
 		recipient: BridgeAddress<Vec<u8>>,
+		asset: AssetId,
 		amount: Amount,
 	},
 	WaitAndCompleteInitiator(u64, HashLockPreImage),
 	RefundInitiator,
+	AbortCounterparty,
 	TransferDone,
 	NoAction,
 }
@@ -52,6 +54,7 @@ impl fmt::Display for TransferActionType {
 			TransferActionType::LockBridgeTransfer { .. } => "LockBridgeTransfer",
 			TransferActionType::WaitAndCompleteInitiator(..) => "WaitAndCompleteInitiator",
 			TransferActionType::RefundInitiator => "RefundInitiator",
+			TransferActionType::AbortCounterparty => "AbortCounterparty",
 			TransferActionType::TransferDone => "TransferDone",
 			TransferActionType::NoAction => "NoAction",
 		};
@@ -4,7 +4,8 @@ use thiserror::Error;
 use tokio_stream::Stream;
 
 use crate::types::{
-	Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock, HashLockPreImage,
+	Amount, AssetId, BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock,
+	HashLockPreImage,
 };
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -59,6 +60,8 @@ pub enum BridgeContractError {
 	BadAddressEncoding(String),
 	#[error("Error during deserializing an event :{1:?} : {0}")]
 	EventDeserializingFail(String, BridgeContractEventType),
+	#[error("Asset not supported by the bridge contract: {0}")]
+	UnsupportedAsset(AssetId),
 }
 
 impl BridgeContractError {
@@ -153,6 +156,7 @@ pub trait BridgeContract<A>: Clone + Unpin + Send + Sync {
 		initiator: BridgeAddress<A>,
 		recipient: BridgeAddress<Vec<u8>>,
 		hash_lock: HashLock,
+		asset: AssetId,
 		amount: Amount,
 	) -> BridgeContractResult<()>;
 
@@ -189,6 +193,7 @@ pub trait BridgeContract<A>: Clone + Unpin + Send + Sync {
 		hash_lock: HashLock,
 		initiator: BridgeAddress<Vec<u8>>,
 		recipient: BridgeAddress<A>,
+		asset: AssetId,
 		amount: Amount,
 	) -> BridgeContractResult<()>;
 
@@ -1,5 +1,5 @@
 use crate::chains::bridge_contracts::BridgeContractEvent;
-use crate::types::ChainId;
+use crate::types::{AssetId, ChainId};
 use std::fmt;
 use thiserror::Error;
 
@@ -15,6 +15,8 @@ pub enum InvalidEventError {
 	StateNotFound,
 	#[error("Error during event indexing:{0}")]
 	IndexingFailed(String),
+	#[error("Receive an event for an asset missing in the token registry: {0}")]
+	UnsupportedAsset(AssetId),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
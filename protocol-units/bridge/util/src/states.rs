@@ -1,14 +1,17 @@
 use crate::chains::bridge_contracts::BridgeContractEvent;
 use crate::events::{InvalidEventError, TransferEvent};
 use crate::types::Amount;
+use crate::types::AssetId;
 use crate::types::BridgeAddress;
 use crate::types::BridgeTransferDetails;
+use crate::types::ConversionError;
 use crate::types::HashLockPreImage;
 use crate::types::LockDetails;
 use crate::types::{BridgeTransferId, ChainId, HashLock, TimeLock};
 use crate::TransferAction;
 use crate::TransferActionType;
 use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TransferAddress(Vec<u8>);
@@ -50,6 +53,22 @@ impl fmt::Display for TransferStateType {
 	}
 }
 
+impl FromStr for TransferStateType {
+	type Err = ConversionError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"Initialized" => Ok(Self::Initialized),
+			"Locked" => Ok(Self::Locked),
+			"SecretReceived" => Ok(Self::SecretReceived),
+			"CompletedIntiator" => Ok(Self::CompletedIntiator),
+			"Done" => Ok(Self::Done),
+			"Refund" => Ok(Self::Refund),
+			_ => Err(ConversionError::InvalidValue("TransferStateType", s.to_string())),
+		}
+	}
+}
+
 #[allow(dead_code)]
 pub struct TransferState {
 	pub state: TransferStateType,
@@ -59,6 +78,7 @@ pub struct TransferState {
 	pub counter_part_address: TransferAddress,
 	pub hash_lock: HashLock,
 	pub time_lock: TimeLock,
+	pub asset: AssetId,
 	pub amount: Amount,
 	pub contract_state: u8,
 	//Max number time action are retry for the whole transfer.
@@ -85,6 +105,15 @@ impl TransferState {
 				// already present invalid
 				Err(InvalidEventError::InitAnAlreadyExist)
 			}
+			// Lock event must be of the asset of the transfer.
+			(BridgeContractEvent::Locked(details), TransferStateType::Initialized)
+				if details.asset != self.asset =>
+			{
+				Err(InvalidEventError::BadEvent(format!(
+					"Received a locked event of asset {} for transfer_id: {} of asset {}",
+					details.asset, self.transfer_id, self.asset
+				)))
+			}
 			// Lock event must on on the counter part chain.
 			(BridgeContractEvent::Locked(_), TransferStateType::Initialized) => (event.chain
 				!= self.init_chain)
@@ -129,6 +158,7 @@ impl TransferState {
 			counter_part_address: detail.recipient.clone().into(),
 			hash_lock: detail.hash_lock,
 			time_lock: detail.time_lock,
+			asset: detail.asset.clone(),
 			amount: detail.amount,
 			contract_state: detail.state,
 			retry_on_error: 0,
@@ -139,6 +169,7 @@ impl TransferState {
 			hash_lock: detail.hash_lock,
 			initiator: BridgeAddress(detail.initiator.0.into()),
 			recipient: BridgeAddress(detail.recipient.0.into()),
+			asset: detail.asset,
 			amount: detail.amount,
 		};
 		let action = TransferAction { chain: chain_id, transfer_id, kind: action_type };
@@ -178,7 +209,8 @@ impl TransferState {
 		mut self,
 		_transfer_id: BridgeTransferId,
 	) -> (Self, TransferActionType) {
-		self.state = TransferStateType::Done;
+		// The counterparty lock is aborted, the initiator is refunded when its time lock expires.
+		self.state = TransferStateType::Refund;
 		let action_type = TransferActionType::NoAction;
 		(self, action_type)
 	}
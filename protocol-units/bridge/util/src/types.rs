@@ -6,6 +6,7 @@ use rand::Rng;
 use serde::Deserialize;
 use std::convert::TryFrom;
 use std::fmt;
+use std::str::FromStr;
 use std::{fmt::Debug, hash::Hash};
 use thiserror::Error;
 
@@ -48,6 +49,18 @@ impl fmt::Display for ChainId {
 	}
 }
 
+impl FromStr for ChainId {
+	type Err = ConversionError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"ONE" => Ok(ChainId::ONE),
+			"TWO" => Ok(ChainId::TWO),
+			_ => Err(ConversionError::InvalidValue("ChainId", s.to_string())),
+		}
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub struct BridgeTransferId(pub BridgeHash);
 
@@ -181,14 +194,72 @@ impl From<Uint<256, 4>> for TimeLock {
 	}
 }
 
+/// The amount of a transfer, in the smallest unit of its asset.
+/// It's wide enough for the 18 decimals ERC-20 tokens.
 #[derive(Deref, DerefMut, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
-pub struct Amount(pub u64);
+pub struct Amount(pub u128);
 
-impl From<Uint<256, 4>> for Amount {
-	fn from(value: Uint<256, 4>) -> Self {
-		// Extract the lower 64 bits.
-		let lower_64_bits = value.as_limbs()[0];
-		Amount(lower_64_bits)
+impl TryFrom<Uint<256, 4>> for Amount {
+	type Error = ConversionError;
+
+	fn try_from(value: Uint<256, 4>) -> Result<Self, Self::Error> {
+		u128::try_from(value)
+			.map(Amount)
+			.map_err(|_| ConversionError::InvalidValue("Amount", value.to_string()))
+	}
+}
+
+impl Amount {
+	/// Converts the amount between two decimals of its asset.
+	/// Fails if the amount overflows or if it has a remainder below the target decimals.
+	pub fn convert_decimals(
+		self,
+		from_decimals: u8,
+		to_decimals: u8,
+	) -> Result<Self, ConversionError> {
+		let invalid = || {
+			ConversionError::InvalidValue(
+				"Amount",
+				format!("{} from {from_decimals} to {to_decimals} decimals", self.0),
+			)
+		};
+		if to_decimals >= from_decimals {
+			let scale =
+				10u128.checked_pow((to_decimals - from_decimals).into()).ok_or_else(invalid)?;
+			self.0.checked_mul(scale).map(Amount).ok_or_else(invalid)
+		} else {
+			let scale =
+				10u128.checked_pow((from_decimals - to_decimals).into()).ok_or_else(invalid)?;
+			(self.0 % scale == 0).then_some(Amount(self.0 / scale)).ok_or_else(invalid)
+		}
+	}
+}
+
+impl From<u64> for Amount {
+	fn from(value: u64) -> Self {
+		Amount(value as u128)
+	}
+}
+
+/// The identifier of a bridged asset in the token registry, e.g. `MOVE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct AssetId(pub String);
+
+impl fmt::Display for AssetId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+impl From<&str> for AssetId {
+	fn from(value: &str) -> Self {
+		AssetId(value.to_string())
+	}
+}
+
+impl From<String> for AssetId {
+	fn from(value: String) -> Self {
+		AssetId(value)
 	}
 }
 
@@ -196,6 +267,8 @@ impl From<Uint<256, 4>> for Amount {
 pub enum ConversionError {
 	#[error("Invalid conversion from AssetType to Uint")]
 	InvalidConversion,
+	#[error("Invalid {0} value: {1}")]
+	InvalidValue(&'static str, String),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
@@ -205,6 +278,7 @@ pub struct BridgeTransferDetails<A> {
 	pub recipient: BridgeAddress<Vec<u8>>,
 	pub hash_lock: HashLock,
 	pub time_lock: TimeLock,
+	pub asset: AssetId,
 	pub amount: Amount,
 	pub state: u8,
 }
@@ -216,6 +290,7 @@ pub struct BridgeTransferDetailsCounterparty<A> {
 	pub recipient: BridgeAddress<A>,
 	pub hash_lock: HashLock,
 	pub time_lock: TimeLock,
+	pub asset: AssetId,
 	pub amount: Amount,
 	pub state: u8,
 }
@@ -227,5 +302,28 @@ pub struct LockDetails<A> {
 	pub recipient: BridgeAddress<A>,
 	pub hash_lock: HashLock,
 	pub time_lock: TimeLock,
+	pub asset: AssetId,
 	pub amount: Amount,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_amount_try_from_uint_rejects_overflow() {
+		let max = Uint::<256, 4>::from(u128::MAX);
+		assert_eq!(Amount::try_from(max).unwrap(), Amount(u128::MAX));
+		assert!(Amount::try_from(max + Uint::<256, 4>::from(1)).is_err());
+	}
+
+	#[test]
+	fn test_amount_convert_decimals() {
+		assert_eq!(Amount(15).convert_decimals(8, 18).unwrap(), Amount(15 * 10u128.pow(10)));
+		assert_eq!(Amount(15 * 10u128.pow(10)).convert_decimals(18, 8).unwrap(), Amount(15));
+		assert_eq!(Amount(15).convert_decimals(8, 8).unwrap(), Amount(15));
+		// The remainder below the target decimals can't be bridged.
+		assert!(Amount(15 * 10u128.pow(10) + 1).convert_decimals(18, 8).is_err());
+		assert!(Amount(u128::MAX).convert_decimals(8, 18).is_err());
+	}
+}
@@ -9,9 +9,13 @@ pub use aptos_types::{
 	block_executor::partitioner::ExecutableTransactions,
 	block_metadata::BlockMetadata,
 	transaction::signature_verified_transaction::SignatureVerifiedTransaction,
-	transaction::{SignedTransaction, Transaction},
+	transaction::{SignedTransaction, Transaction, Version},
 };
 use maptos_execution_util::config::Config;
+pub use maptos_opt_executor::executor::diagnostics::{
+	BlockDiagnostics, TransactionWriteSetSummary,
+};
+pub use maptos_opt_executor::executor::simulation::BlockSimulation;
 use movement_types::block::BlockCommitment;
 
 use async_trait::async_trait;
@@ -73,6 +77,25 @@ pub trait DynOptFinExecutor {
 		timestamp: u64,
 	) -> Result<BlockMetadata, anyhow::Error>;
 
+	/// Executes a block against the ledger state at a version, without committing it
+	async fn simulate_block_opt(
+		&self,
+		block: ExecutableBlock,
+		version: Version,
+	) -> Result<BlockSimulation, anyhow::Error>;
+
+	/// Gets the block committed at a height, with the version of the ledger it was executed on
+	fn get_executable_block_at_height(
+		&self,
+		block_height: u64,
+	) -> Result<(ExecutableBlock, Version), anyhow::Error>;
+
+	/// Gets the diagnostics of the block committed at a height
+	async fn get_block_diagnostics_at_height(
+		&self,
+		block_height: u64,
+	) -> Result<BlockDiagnostics, anyhow::Error>;
+
 	/// Decrements transactions in flight on the transaction channel.
 	fn decrement_transactions_in_flight(&self, count: u64);
 
@@ -1,6 +1,6 @@
 use crate::{
-	BlockMetadata, DynOptFinExecutor, ExecutableBlock, HashValue, MakeOptFinServices, Services,
-	SignedTransaction,
+	BlockDiagnostics, BlockMetadata, BlockSimulation, DynOptFinExecutor, ExecutableBlock,
+	HashValue, MakeOptFinServices, Services, SignedTransaction, Version,
 };
 use maptos_execution_util::config::Config;
 use maptos_fin_view::FinalityView;
@@ -10,6 +10,7 @@ use movement_types::block::BlockCommitment;
 use anyhow::format_err;
 use async_trait::async_trait;
 use tokio::sync::mpsc::Sender;
+use tokio::try_join;
 use tracing::debug;
 
 use std::future::Future;
@@ -65,10 +66,19 @@ impl DynOptFinExecutor for Executor {
 			opt_context.node_config().clone(),
 		);
 		let indexer_runtime = opt_context.run_indexer_grpc_service()?;
+		let receipts_service = self.executor.receipts_service();
 		let background = async move {
 			// The indexer runtime should live as long as the Tx pipe.
 			let _indexer_runtime = indexer_runtime;
-			background.run().await?;
+			match receipts_service {
+				Some(receipts_service) => {
+					try_join!(
+						async move { background.run().await.map_err(anyhow::Error::from) },
+						receipts_service.run()
+					)?;
+				}
+				None => background.run().await?,
+			}
 			Ok(())
 		};
 		Ok((Context { opt_context, fin_service }, background))
@@ -115,6 +125,7 @@ impl DynOptFinExecutor for Executor {
 				));
 			}
 		}
+		// The finalized block is kept by the revert, so the finality view is still valid.
 		self.executor.revert_block_head_to(block_height).await
 	}
 
@@ -136,6 +147,29 @@ impl DynOptFinExecutor for Executor {
 		Ok(BlockMetadata::new(block_id, epoch, round, signer.author(), vec![], vec![], timestamp))
 	}
 
+	async fn simulate_block_opt(
+		&self,
+		block: ExecutableBlock,
+		version: Version,
+	) -> Result<BlockSimulation, anyhow::Error> {
+		debug!("Simulating block {:?} at version {}", block.block_id, version);
+		self.executor.simulate_block(block, version).await
+	}
+
+	fn get_executable_block_at_height(
+		&self,
+		block_height: u64,
+	) -> Result<(ExecutableBlock, Version), anyhow::Error> {
+		self.executor.get_executable_block_at_height(block_height)
+	}
+
+	async fn get_block_diagnostics_at_height(
+		&self,
+		block_height: u64,
+	) -> Result<BlockDiagnostics, anyhow::Error> {
+		self.executor.get_block_diagnostics_at_height(block_height).await
+	}
+
 	fn decrement_transactions_in_flight(&self, count: u64) {
 		self.executor.decrement_transactions_in_flight(count)
 	}
@@ -1,6 +1,5 @@
 use super::{Error, NullMempool, TransactionPipe};
-
-use maptos_execution_util::config::mempool::Config as MempoolConfig;
+use crate::gc_account_sequence_number::UsedSequenceNumberPool;
 
 use aptos_config::config::NodeConfig;
 use aptos_mempool::MempoolClientRequest;
@@ -32,10 +31,10 @@ impl BackgroundTask {
 		transaction_sender: mpsc::Sender<(u64, SignedTransaction)>,
 		db_reader: Arc<dyn DbReader>,
 		node_config: &NodeConfig,
-		mempool_config: &MempoolConfig,
 		whitelist_config: &WhitelistConfig,
 		transactions_in_flight: Arc<RwLock<GcCounter>>,
 		transactions_in_flight_limit: Option<u64>,
+		used_sequence_number_pool: Arc<RwLock<UsedSequenceNumberPool>>,
 	) -> Result<Self, anyhow::Error> {
 		Ok(Self {
 			inner: BackgroundInner::Full(TransactionPipe::new(
@@ -43,10 +42,10 @@ impl BackgroundTask {
 				transaction_sender,
 				db_reader,
 				node_config,
-				mempool_config,
 				whitelist_config,
 				transactions_in_flight,
 				transactions_in_flight_limit,
+				used_sequence_number_pool,
 			)?),
 		})
 	}
@@ -2,8 +2,6 @@
 
 use super::Error;
 
-use maptos_execution_util::config::mempool::Config as MempoolConfig;
-
 use aptos_config::config::NodeConfig;
 use aptos_mempool::core_mempool::CoreMempool;
 use aptos_mempool::SubmissionStatus;
@@ -44,8 +42,8 @@ pub struct TransactionPipe {
 	in_flight_limit: Option<u64>,
 	// Timestamp of the last garbage collection
 	last_gc: Instant,
-	// The pool of used sequence numbers
-	used_sequence_number_pool: UsedSequenceNumberPool,
+	// Shared reference on the pool of used sequence numbers
+	used_sequence_number_pool: Arc<RwLock<UsedSequenceNumberPool>>,
 	/// The accounts whitelisted for ingress
 	whitelisted_accounts: Option<HashSet<AccountAddress>>,
 }
@@ -61,10 +59,10 @@ impl TransactionPipe {
 		transaction_sender: mpsc::Sender<(u64, SignedTransaction)>,
 		db_reader: Arc<dyn DbReader>,
 		node_config: &NodeConfig,
-		mempool_config: &MempoolConfig,
 		whitelist_config: &WhitelistConfig,
 		transactions_in_flight: Arc<RwLock<GcCounter>>,
 		transactions_in_flight_limit: Option<u64>,
+		used_sequence_number_pool: Arc<RwLock<UsedSequenceNumberPool>>,
 	) -> Result<Self, anyhow::Error> {
 		let whitelisted_accounts = whitelist_config.whitelisted_accounts()?;
 		info!("Whitelisted accounts: {:?}", whitelisted_accounts);
@@ -77,10 +75,7 @@ impl TransactionPipe {
 			transactions_in_flight,
 			in_flight_limit: transactions_in_flight_limit,
 			last_gc: Instant::now(),
-			used_sequence_number_pool: UsedSequenceNumberPool::new(
-				mempool_config.sequence_number_ttl_ms,
-				mempool_config.gc_slot_duration_ms,
-			),
+			used_sequence_number_pool,
 			whitelisted_accounts,
 		})
 	}
@@ -138,7 +133,11 @@ impl TransactionPipe {
 			let epoch_ms_now = chrono::Utc::now().timestamp_millis() as u64;
 
 			// garbage collect the used sequence number pool
-			self.used_sequence_number_pool.gc(epoch_ms_now);
+			{
+				// unwrap because failure indicates poisoned lock
+				let mut used_sequence_number_pool = self.used_sequence_number_pool.write().unwrap();
+				used_sequence_number_pool.gc(epoch_ms_now);
+			}
 
 			// garbage collect the transactions in flight
 			{
@@ -163,6 +162,8 @@ impl TransactionPipe {
 		// check against the used sequence number pool
 		let used_sequence_number = self
 			.used_sequence_number_pool
+			.read()
+			.unwrap()
 			.get_sequence_number(&transaction.sender())
 			.unwrap_or(0);
 
@@ -298,11 +299,15 @@ impl TransactionPipe {
 					"Setting used sequence number for {:?} to {:?}",
 					sender, transaction_sequence_number
 				);
-				self.used_sequence_number_pool.set_sequence_number(
-					&sender,
-					transaction_sequence_number,
-					now,
-				);
+				{
+					let mut used_sequence_number_pool =
+						self.used_sequence_number_pool.write().unwrap();
+					used_sequence_number_pool.set_sequence_number(
+						&sender,
+						transaction_sequence_number,
+						now,
+					);
+				}
 			}
 			_ => {
 				warn!("Transaction not accepted: {:?}", status);
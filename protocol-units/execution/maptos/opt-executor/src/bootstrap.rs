@@ -1,81 +1,138 @@
+use aptos_cached_packages::aptos_stdlib;
 use aptos_config::config::NodeConfig;
 use aptos_config::config::StorageDirPaths;
-use aptos_crypto::ed25519::Ed25519PublicKey;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey};
 use aptos_db::AptosDB;
 use aptos_executor::db_bootstrapper;
-use aptos_storage_interface::DbReaderWriter;
+use aptos_storage_interface::{state_view::DbStateViewAtVersion, DbReaderWriter};
+use aptos_temppath::TempPath;
 use aptos_types::{
-	chain_id::ChainId,
+	account_config::aptos_test_root_address,
 	on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig},
-	transaction::{ChangeSet, Transaction, WriteSetPayload},
+	transaction::{
+		signature_verified_transaction::into_signature_verified_block, ChangeSet, ExecutionStatus,
+		RawTransaction, Transaction, TransactionPayload, TransactionStatus, WriteSetPayload,
+	},
 	validator_signer::ValidatorSigner,
+	waypoint::Waypoint,
 };
 use aptos_vm::AptosVM;
-use aptos_vm_genesis::{
-	default_gas_schedule, encode_genesis_change_set, GenesisConfiguration, TestValidator, Validator,
-};
+use aptos_vm_genesis::{encode_genesis_change_set, TestValidator, Validator};
 use tracing::warn;
 
+use crate::genesis::GenesisSpec;
+
 use std::path::Path;
 
+const GENESIS_FUNDING_MAX_GAS_AMOUNT: u64 = 100_000;
+const GENESIS_FUNDING_GAS_UNIT_PRICE: u64 = 100;
+
 fn genesis_change_set_and_validators(
-	chain_id: ChainId,
+	spec: &GenesisSpec,
 	count: Option<usize>,
-	public_key: &Ed25519PublicKey, //Core resource account.
-) -> (ChangeSet, Vec<TestValidator>) {
-	let framework = aptos_cached_packages::head_release_bundle();
+	core_resources_key: &Ed25519PrivateKey,
+) -> Result<(ChangeSet, Vec<TestValidator>), anyhow::Error> {
+	let framework = spec.framework()?;
 	let test_validators = TestValidator::new_test_set(count, Some(100_000_000));
 	let validators_: Vec<Validator> = test_validators.iter().map(|t| t.data.clone()).collect();
 	let validators = &validators_;
 
 	warn!("Genesis validators: {:?}", validators);
 
-	// This number should not exceed u64::MAX / 1_000_000_000
-	// to avoid overflowing calculations in aptos-vm-genesis.
-	// This will last several centuries.
-	const EPOCH_DURATION_SECS: u64 = 60 * 60 * 24 * 1024 * 128;
-
 	let genesis = encode_genesis_change_set(
-		&public_key,
+		&core_resources_key.public_key(),
 		validators,
-		framework,
-		chain_id,
-		// todo: get this config from somewhere
-		&GenesisConfiguration {
-			allow_new_validators: true,
-			epoch_duration_secs: EPOCH_DURATION_SECS,
-			is_test: true,
-			min_stake: 0,
-			min_voting_threshold: 0,
-			// 1M APTOS coins (with 8 decimals).
-			max_stake: 100_000_000_000_000,
-			recurring_lockup_duration_secs: EPOCH_DURATION_SECS * 2,
-			required_proposer_stake: 0,
-			rewards_apy_percentage: 0,
-			voting_duration_secs: EPOCH_DURATION_SECS,
-			voting_power_increase_limit: 50,
-			employee_vesting_start: 1663456089,
-			employee_vesting_period_duration: 5 * 60, // 5 minutes
-			initial_features_override: None,
-			randomness_config_override: None,
-			jwk_consensus_config_override: None,
-		},
+		&framework,
+		spec.chain_id,
+		&spec.genesis_configuration(),
 		&OnChainConsensusConfig::default_for_genesis(),
 		&OnChainExecutionConfig::default_for_genesis(),
-		&default_gas_schedule(),
+		&spec.gas_schedule(),
 	);
-	(genesis, test_validators)
+	let genesis = fund_genesis_accounts(genesis, spec, core_resources_key)?;
+	Ok((genesis, test_validators))
 }
 
-/// Bootstrap a database with a genesis transaction if it is empty.
-pub fn maybe_bootstrap_empty_db(
-	config: &NodeConfig,
-	db_dir: impl AsRef<Path> + Clone,
-	chain_id: ChainId,
-	public_key: &Ed25519PublicKey,
-) -> Result<(DbReaderWriter, ValidatorSigner), anyhow::Error> {
+/// Funds the accounts of the genesis spec from the core resources account.
+///
+/// The funding transactions are executed on a scratch database bootstrapped with the bare
+/// genesis, and their writes are squashed into the genesis change set, so the balances are
+/// part of the genesis transaction and of its waypoint.
+fn fund_genesis_accounts(
+	genesis: ChangeSet,
+	spec: &GenesisSpec,
+	core_resources_key: &Ed25519PrivateKey,
+) -> Result<ChangeSet, anyhow::Error> {
+	if spec.accounts.is_empty() {
+		return Ok(genesis);
+	}
+
+	let db_dir = TempPath::new();
+	db_dir.create_as_dir()?;
+	let db_rw = open_db(&NodeConfig::default(), db_dir.path())?;
+	let genesis_txn = Transaction::GenesisTransaction(WriteSetPayload::Direct(genesis.clone()));
+	let waypoint = db_bootstrapper::generate_waypoint::<AptosVM>(&db_rw, &genesis_txn)?;
+	db_bootstrapper::maybe_bootstrap::<AptosVM>(&db_rw, &genesis_txn, waypoint)?
+		.ok_or(anyhow::anyhow!("Failed to bootstrap the genesis funding DB"))?;
+	let state_view = db_rw.reader.state_view_at_version(Some(0))?;
+
+	// The chain time is zero at genesis, so the transactions never expire.
+	let mut sequence_number = 0;
+	let mut sign = |payload: TransactionPayload| -> Result<Transaction, anyhow::Error> {
+		let raw_transaction = RawTransaction::new(
+			aptos_test_root_address(),
+			sequence_number,
+			payload,
+			GENESIS_FUNDING_MAX_GAS_AMOUNT,
+			GENESIS_FUNDING_GAS_UNIT_PRICE,
+			u64::MAX,
+			spec.chain_id,
+		);
+		sequence_number += 1;
+		let signed_transaction =
+			raw_transaction.sign(core_resources_key, core_resources_key.public_key())?;
+		Ok(Transaction::UserTransaction(signed_transaction.into_inner()))
+	};
+	let mut transactions = Vec::new();
+	for account in &spec.accounts {
+		transactions.push(sign(aptos_stdlib::aptos_account_create_account(account.address))?);
+		transactions.push(sign(aptos_stdlib::aptos_coin_mint(account.address, account.balance))?);
+	}
+
+	let outputs =
+		AptosVM::execute_block_no_limit(&into_signature_verified_block(transactions), &state_view)
+			.map_err(|e| anyhow::anyhow!("Failed to execute the genesis funding: {:?}", e))?;
+
+	let (write_set, mut events) = genesis.into_inner();
+	let mut write_set = write_set.into_mut();
+	for output in outputs {
+		if !matches!(output.status(), TransactionStatus::Keep(ExecutionStatus::Success)) {
+			anyhow::bail!("Genesis funding transaction failed: {:?}", output.status());
+		}
+		write_set = write_set.squash(output.write_set().clone().into_mut())?;
+		events.extend(output.events().iter().cloned());
+	}
+
+	Ok(ChangeSet::new(write_set.freeze()?, events))
+}
+
+/// Computes the waypoint of the genesis built from the spec, without touching any database.
+pub fn genesis_waypoint(
+	spec: &GenesisSpec,
+	core_resources_key: &Ed25519PrivateKey,
+) -> Result<Waypoint, anyhow::Error> {
+	let db_dir = TempPath::new();
+	db_dir.create_as_dir()?;
+	let db_rw = open_db(&NodeConfig::default(), db_dir.path())?;
+	let (genesis, _validators) =
+		genesis_change_set_and_validators(spec, Some(1), core_resources_key)?;
+	let genesis_txn = Transaction::GenesisTransaction(WriteSetPayload::Direct(genesis));
+	db_bootstrapper::generate_waypoint::<AptosVM>(&db_rw, &genesis_txn)
+}
+
+fn open_db(config: &NodeConfig, db_dir: impl AsRef<Path>) -> Result<DbReaderWriter, anyhow::Error> {
 	let aptos_db = AptosDB::open(
-		StorageDirPaths::from_path(db_dir.clone()),
+		StorageDirPaths::from_path(db_dir),
 		false,
 		config.storage.storage_pruner_config.clone(),
 		config.storage.rocksdb_configs.clone(),
@@ -83,9 +140,22 @@ pub fn maybe_bootstrap_empty_db(
 		config.storage.buffered_state_target_items,
 		config.storage.max_num_nodes_per_lru_cache_shard,
 	)?;
+	Ok(DbReaderWriter::new(aptos_db))
+}
 
-	let db_rw = DbReaderWriter::new(aptos_db);
-	let (genesis, validators) = genesis_change_set_and_validators(chain_id, Some(1), public_key);
+/// Bootstrap a database with a genesis transaction if it is empty.
+///
+/// If an expected waypoint is provided, the genesis of the database is checked against it.
+pub fn maybe_bootstrap_empty_db(
+	config: &NodeConfig,
+	db_dir: impl AsRef<Path>,
+	spec: &GenesisSpec,
+	expected_waypoint: Option<Waypoint>,
+	core_resources_key: &Ed25519PrivateKey,
+) -> Result<(DbReaderWriter, ValidatorSigner), anyhow::Error> {
+	let db_rw = open_db(config, db_dir)?;
+	let (genesis, validators) =
+		genesis_change_set_and_validators(spec, Some(1), core_resources_key)?;
 	let genesis_txn = Transaction::GenesisTransaction(WriteSetPayload::Direct(genesis));
 	let validator_signer =
 		ValidatorSigner::new(validators[0].data.owner_address, validators[0].consensus_key.clone());
@@ -96,12 +166,33 @@ pub fn maybe_bootstrap_empty_db(
 		Some(ledger_info) => {
 			// context exists
 			tracing::warn!("Ledger info found, not bootstrapping DB: {:?}", ledger_info);
+			if let Some(expected_waypoint) = expected_waypoint {
+				let genesis_ledger_info = db_rw.reader.get_epoch_ending_ledger_info(0)?;
+				let waypoint = Waypoint::new_epoch_boundary(genesis_ledger_info.ledger_info())?;
+				if waypoint != expected_waypoint {
+					anyhow::bail!(
+						"Genesis waypoint mismatch: expected {}, found {}",
+						expected_waypoint,
+						waypoint
+					);
+				}
+			}
 		}
 		None => {
 			// context does not exist
 			// simply continue
 			tracing::info!("No ledger info found, bootstrapping DB.");
 			let waypoint = db_bootstrapper::generate_waypoint::<AptosVM>(&db_rw, &genesis_txn)?;
+			if let Some(expected_waypoint) = expected_waypoint {
+				if waypoint != expected_waypoint {
+					anyhow::bail!(
+						"Genesis waypoint mismatch: expected {}, generated {}",
+						expected_waypoint,
+						waypoint
+					);
+				}
+			}
+			tracing::info!("Bootstrapping DB with genesis waypoint {}", waypoint);
 			db_bootstrapper::maybe_bootstrap::<AptosVM>(&db_rw, &genesis_txn, waypoint)?
 				.ok_or(anyhow::anyhow!("Failed to bootstrap DB"))?;
 			assert!(db_rw.reader.get_latest_ledger_info_option()?.is_some());
@@ -110,3 +201,78 @@ pub fn maybe_bootstrap_empty_db(
 
 	Ok((db_rw, validator_signer))
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::genesis::GenesisAccount;
+	use aptos_crypto::Uniform;
+	use aptos_types::account_address::AccountAddress;
+	use aptos_types::chain_id::ChainId;
+
+	#[test]
+	fn test_genesis_waypoint_is_reproducible() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let spec = GenesisSpec::default_for_chain(ChainId::test());
+
+		let waypoint = genesis_waypoint(&spec, &private_key)?;
+		assert_eq!(waypoint, genesis_waypoint(&spec, &private_key)?);
+
+		let tempdir = tempfile::tempdir()?;
+		maybe_bootstrap_empty_db(
+			&NodeConfig::default(),
+			tempdir.path(),
+			&spec,
+			Some(waypoint),
+			&private_key,
+		)?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_bootstrap_rejects_unexpected_waypoint() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let spec = GenesisSpec::default_for_chain(ChainId::test());
+		let other_spec = GenesisSpec::default_for_chain(ChainId::new(126));
+		let other_waypoint = genesis_waypoint(&other_spec, &private_key)?;
+
+		let tempdir = tempfile::tempdir()?;
+		let res = maybe_bootstrap_empty_db(
+			&NodeConfig::default(),
+			tempdir.path(),
+			&spec,
+			Some(other_waypoint),
+			&private_key,
+		);
+		assert!(res.is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_genesis_funds_spec_accounts() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let mut spec = GenesisSpec::default_for_chain(ChainId::test());
+		let waypoint = genesis_waypoint(&spec, &private_key)?;
+		spec.accounts
+			.push(GenesisAccount { address: AccountAddress::random(), balance: 1_000 });
+
+		// The balances are part of the genesis, so they change its waypoint.
+		let funded_waypoint = genesis_waypoint(&spec, &private_key)?;
+		assert_ne!(waypoint, funded_waypoint);
+
+		let tempdir = tempfile::tempdir()?;
+		let (db_rw, _signer) = maybe_bootstrap_empty_db(
+			&NodeConfig::default(),
+			tempdir.path(),
+			&spec,
+			Some(funded_waypoint),
+			&private_key,
+		)?;
+		// No block is committed on top of the genesis.
+		assert_eq!(db_rw.reader.get_latest_ledger_info()?.ledger_info().version(), 0);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,96 @@
+use super::Executor;
+use crate::receipts::{BlockReceipt, TransactionReceipt};
+use aptos_crypto::HashValue;
+use aptos_types::transaction::Version;
+use movement_types::block::BlockCommitment;
+use serde::{Deserialize, Serialize};
+
+/// The state of the ledger after a committed block, to compare its execution across nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockDiagnostics {
+	/// The commitment of the block, with the id of the block
+	pub commitment: BlockCommitment,
+	/// The root hash of the transaction accumulator after the block
+	pub transaction_accumulator_hash: HashValue,
+	/// The root hash of the state after the block
+	pub state_root: HashValue,
+	pub first_version: Version,
+	pub last_version: Version,
+	pub transactions: Vec<TransactionWriteSetSummary>,
+}
+
+/// A summary of the changes a transaction made to the state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionWriteSetSummary {
+	pub hash: HashValue,
+	pub version: Version,
+	pub vm_status: String,
+	pub gas_used: u64,
+	pub events: usize,
+	pub writes: usize,
+	pub deletions: usize,
+	/// The hash of the BCS-encoded changes, equal for transactions with the same effects
+	pub write_set_hash: HashValue,
+}
+
+impl TransactionWriteSetSummary {
+	fn try_from_receipt(receipt: &TransactionReceipt) -> Result<Self, anyhow::Error> {
+		let deletions = receipt.write_set.iter().filter(|change| change.deleted).count();
+		Ok(Self {
+			hash: receipt.hash,
+			version: receipt.version,
+			vm_status: receipt.vm_status.clone(),
+			gas_used: receipt.gas_used,
+			events: receipt.events.len(),
+			writes: receipt.write_set.len() - deletions,
+			deletions,
+			write_set_hash: HashValue::sha3_256_of(&bcs::to_bytes(&receipt.write_set)?),
+		})
+	}
+}
+
+impl Executor {
+	/// Gets the diagnostics of the block committed at a height.
+	pub async fn get_block_diagnostics_at_height(
+		&self,
+		height: u64,
+	) -> Result<BlockDiagnostics, anyhow::Error> {
+		let commitment = self.get_commitment_for_height(height)?;
+		let reader = self.db_reader();
+		let diagnostics = tokio::task::spawn_blocking(move || {
+			let (first_version, last_version, _block_event) =
+				reader.get_block_info_by_height(height)?;
+			let latest_version = reader.get_latest_ledger_info_version()?;
+			let transaction_accumulator_hash = reader.get_accumulator_root_hash(last_version)?;
+			let last_transaction =
+				reader.get_transaction_by_version(last_version, latest_version, false)?;
+			let state_root = last_transaction
+				.proof
+				.transaction_info()
+				.state_checkpoint_hash()
+				.ok_or(anyhow::anyhow!("No state checkpoint at the end of block {}", height))?;
+
+			let receipt = BlockReceipt::try_from_db(
+				reader.as_ref(),
+				HashValue::new(*commitment.block_id().as_bytes()),
+				last_version,
+			)?;
+			let transactions = receipt
+				.transactions
+				.iter()
+				.map(TransactionWriteSetSummary::try_from_receipt)
+				.collect::<Result<Vec<_>, _>>()?;
+
+			Ok::<BlockDiagnostics, anyhow::Error>(BlockDiagnostics {
+				commitment,
+				transaction_accumulator_hash,
+				state_root,
+				first_version,
+				last_version,
+				transactions,
+			})
+		})
+		.await??;
+		Ok(diagnostics)
+	}
+}
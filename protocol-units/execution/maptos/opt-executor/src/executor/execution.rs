@@ -1,8 +1,11 @@
 use super::Executor;
+use crate::receipts::BlockReceipt;
 use aptos_crypto::HashValue;
 use aptos_executor_types::BlockExecutorTrait;
+use aptos_storage_interface::DbReader;
 use aptos_types::transaction::signature_verified_transaction::into_signature_verified_block;
 use aptos_types::{
+	account_config::NewBlockEvent,
 	aggregate_signature::AggregateSignature,
 	block_executor::{
 		config::BlockExecutorConfigFromOnchain,
@@ -16,7 +19,30 @@ use aptos_types::{
 	validator_verifier::{ValidatorConsensusInfo, ValidatorVerifier},
 };
 use movement_types::block::{BlockCommitment, Commitment, Id};
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
+
+/// Gets the height of the last block committed to the ledger.
+pub(crate) fn block_head_height(reader: &dyn DbReader) -> Result<u64, anyhow::Error> {
+	let ledger_info = reader.get_latest_ledger_info()?;
+	let (_, _, new_block_event) =
+		reader.get_block_info_by_version(ledger_info.ledger_info().version())?;
+	Ok(new_block_event.height)
+}
+
+/// Computes the commitment of the ledger at the end of the block at a height.
+pub(crate) fn commitment_for_height(
+	reader: &dyn DbReader,
+	height: u64,
+) -> Result<BlockCommitment, anyhow::Error> {
+	let (_block_start_version, block_end_version, _block_event) =
+		reader.get_block_info_by_height(height)?;
+	let proof = reader.get_state_proof(block_end_version)?;
+
+	let block_id = proof.latest_ledger_info().consensus_block_id();
+
+	let commitment = Commitment::digest_state_proof(&proof);
+	Ok(BlockCommitment::new(height.into(), Id::new(*block_id.clone()), commitment))
+}
 
 impl Executor {
 	pub async fn execute_block(
@@ -51,6 +77,7 @@ impl Executor {
 			(block_metadata, block)
 		};
 
+		let _execution = self.execution_lock.lock().await;
 		let block_id = block.block_id.clone();
 		let parent_block_id = self.block_executor.committed_block_id();
 
@@ -84,6 +111,18 @@ impl Executor {
 		})
 		.await??;
 
+		if let Some(receipts_exporter) = &self.receipts_exporter {
+			// exporting receipts is best effort and must not fail the execution
+			match BlockReceipt::try_from_db(self.db().reader.as_ref(), block_id, version) {
+				Ok(receipt) => {
+					if let Err(e) = receipts_exporter.export(receipt).await {
+						error!("Failed to export receipt of block {}: {:?}", block_id, e);
+					}
+				}
+				Err(e) => error!("Failed to build receipt of block {}: {:?}", block_id, e),
+			}
+		}
+
 		let proof = self.db().reader.get_state_proof(version)?;
 
 		// Context has a reach-around to the db so the block height should
@@ -96,23 +135,11 @@ impl Executor {
 	}
 
 	pub fn get_block_head_height(&self) -> Result<u64, anyhow::Error> {
-		let ledger_info = self.db().reader.get_latest_ledger_info()?;
-		let (_, _, new_block_event) = self
-			.db()
-			.reader
-			.get_block_info_by_version(ledger_info.ledger_info().version())?;
-		Ok(new_block_event.height)
+		block_head_height(self.db().reader.as_ref())
 	}
 
 	pub fn get_commitment_for_height(&self, height: u64) -> Result<BlockCommitment, anyhow::Error> {
-		let (_block_start_version, block_end_version, _block_event) =
-			self.db().reader.get_block_info_by_height(height)?;
-		let proof = self.db().reader.get_state_proof(block_end_version)?;
-
-		let block_id = proof.latest_ledger_info().consensus_block_id();
-
-		let commitment = Commitment::digest_state_proof(&proof);
-		Ok(BlockCommitment::new(height.into(), Id::new(*block_id.clone()), commitment))
+		commitment_for_height(self.db().reader.as_ref(), height)
 	}
 
 	pub fn get_commitment_for_version(
@@ -131,26 +158,40 @@ impl Executor {
 	}
 
 	pub async fn revert_block_head_to(&self, block_height: u64) -> Result<(), anyhow::Error> {
+		let _execution = self.execution_lock.lock().await;
 		let (_start_ver, end_ver, block_event) =
 			self.db().reader.get_block_info_by_height(block_height)?;
+		let ledger_info = self.revert_ledger_info(&block_event, end_ver)?;
+		let db_writer = self.db().writer.clone();
+		let ledger_info_copy = ledger_info.clone();
+		tokio::task::spawn_blocking(move || db_writer.revert_commit(&ledger_info_copy)).await??;
+		// Reset the executor state to the reverted storage
+		self.block_executor.reset()?;
+		// The pool holds the sequence numbers of transactions sent to the DA, which are
+		// ahead of the reverted ledger, so the reverted transactions would be rejected as
+		// already used if they were submitted again.
+		self.clear_used_sequence_numbers();
+		Ok(())
+	}
+
+	/// Builds the ledger info of the block ending at `end_version`, to revert the ledger to it.
+	pub(crate) fn revert_ledger_info(
+		&self,
+		block_event: &NewBlockEvent,
+		end_version: Version,
+	) -> Result<LedgerInfoWithSignatures, anyhow::Error> {
 		let block_info = BlockInfo::new(
 			block_event.epoch(),
 			block_event.round(),
 			block_event.hash()?,
-			self.db().reader.get_accumulator_root_hash(end_ver)?,
-			end_ver,
+			self.db().reader.get_accumulator_root_hash(end_version)?,
+			end_version,
 			block_event.proposed_time(),
 			None,
 		);
 		let ledger_info = LedgerInfo::new(block_info, HashValue::zero());
 		let aggregate_signature = AggregateSignature::empty();
-		let ledger_info = LedgerInfoWithSignatures::new(ledger_info, aggregate_signature);
-		let db_writer = self.db().writer.clone();
-		let ledger_info_copy = ledger_info.clone();
-		tokio::task::spawn_blocking(move || db_writer.revert_commit(&ledger_info_copy)).await??;
-		// Reset the executor state to the reverted storage
-		self.block_executor.reset()?;
-		Ok(())
+		Ok(LedgerInfoWithSignatures::new(ledger_info, aggregate_signature))
 	}
 
 	/// Gets the next epoch and round.
@@ -464,6 +505,55 @@ mod tests {
 
 	#[tokio::test]
 	async fn test_revert_block_head_to() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, _tempdir) = Executor::try_test_default(private_key)?;
+		let (context, _transaction_pipe) = executor.background(tx_sender)?;
+
+		let root_account = LocalAccount::new(
+			aptos_test_root_address(),
+			AccountKey::from_private_key(context.config().chain.maptos_private_key.clone()),
+			0,
+		);
+		let seed = [3u8; 32];
+		let mut rng = ::rand::rngs::StdRng::from_seed(seed);
+		let tx_factory = TransactionFactory::new(context.config().chain.maptos_chain_id.clone());
+
+		// Execute several blocks, recording the created transactions.
+		let mut transaction_hashes = Vec::new();
+		for _ in 0..5 {
+			let (epoch, round) = executor.get_next_epoch_and_round()?;
+			let block_id = HashValue::random();
+			let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+				block_id,
+				epoch,
+				round,
+				executor.signer.author(),
+				vec![],
+				vec![],
+				chrono::Utc::now().timestamp_micros() as u64,
+			));
+			let new_account = LocalAccount::generate(&mut rng);
+			let user_account_creation_tx = root_account.sign_with_transaction_builder(
+				tx_factory.create_user_account(new_account.public_key()),
+			);
+			transaction_hashes.push(user_account_creation_tx.committed_hash());
+			let transactions =
+				ExecutableTransactions::Unsharded(into_signature_verified_block(vec![
+					block_metadata,
+					Transaction::UserTransaction(user_account_creation_tx),
+				]));
+			executor.execute_block(ExecutableBlock::new(block_id, transactions)).await?;
+		}
+		assert_eq!(executor.get_block_head_height()?, 5);
+
+		// Revert several blocks at once.
+		executor.revert_block_head_to(2).await?;
+		assert_eq!(executor.get_block_head_height()?, 2);
+		for (i, hash) in transaction_hashes.into_iter().enumerate() {
+			assert_eq!(executor.has_executed_transaction(hash)?, i < 2);
+		}
+
 		Ok(())
 	}
 }
@@ -1,11 +1,12 @@
 use super::Executor;
 use crate::background::BackgroundTask;
-use crate::{bootstrap, Context};
+use crate::gc_account_sequence_number::UsedSequenceNumberPool;
+use crate::receipts::ReceiptsExporter;
+use crate::{bootstrap, genesis::GenesisSpec, Context};
 
 use aptos_config::config::NodeConfig;
 #[cfg(test)]
 use aptos_crypto::ed25519::Ed25519PrivateKey;
-use aptos_crypto::PrivateKey;
 use aptos_executor::block_executor::BlockExecutor;
 use aptos_mempool::MempoolClientRequest;
 use aptos_types::transaction::SignedTransaction;
@@ -101,11 +102,25 @@ impl Executor {
 		node_config.storage.dir = dot_movement.get_path().join("maptos-storage");
 		node_config.storage.set_data_dir(node_config.storage.dir.clone());
 
+		// load the genesis spec
+		let genesis_spec = match &maptos_config.chain.maptos_genesis_spec_path {
+			Some(path) => GenesisSpec::try_from_path(path)?,
+			None => GenesisSpec::default_for_chain(maptos_config.chain.maptos_chain_id),
+		};
+		if genesis_spec.chain_id != maptos_config.chain.maptos_chain_id {
+			anyhow::bail!(
+				"Genesis spec chain id {} does not match the configured chain id {}",
+				genesis_spec.chain_id,
+				maptos_config.chain.maptos_chain_id
+			);
+		}
+
 		let (db, signer) = bootstrap::maybe_bootstrap_empty_db(
 			&node_config,
 			maptos_config.chain.maptos_db_path.as_ref().context("No db path provided.")?,
-			maptos_config.chain.maptos_chain_id.clone(),
-			&maptos_config.chain.maptos_private_key.public_key(),
+			&genesis_spec,
+			maptos_config.chain.maptos_genesis_waypoint,
+			&maptos_config.chain.maptos_private_key,
 		)?;
 		Ok(Self {
 			block_executor: Arc::new(BlockExecutor::new(db.clone())),
@@ -114,8 +129,14 @@ impl Executor {
 				Duration::try_new(maptos_config.mempool.sequence_number_ttl_ms)?,
 				Duration::try_new(maptos_config.mempool.gc_slot_duration_ms)?,
 			))),
+			used_sequence_number_pool: Arc::new(RwLock::new(UsedSequenceNumberPool::new(
+				maptos_config.mempool.sequence_number_ttl_ms,
+				maptos_config.mempool.gc_slot_duration_ms,
+			))),
 			config: maptos_config.clone(),
 			node_config: node_config.clone(),
+			receipts_exporter: ReceiptsExporter::try_from_config(&maptos_config.receipts),
+			execution_lock: tokio::sync::Mutex::new(()),
 		})
 	}
 
@@ -162,10 +183,10 @@ impl Executor {
 				transaction_sender,
 				self.db().reader.clone(),
 				&node_config,
-				&self.config.mempool,
 				&self.config.access_control,
 				self.transactions_in_flight.clone(),
 				maptos_config.load_shedding.max_transactions_in_flight,
+				self.used_sequence_number_pool.clone(),
 			)?
 		};
 
@@ -1,7 +1,9 @@
 // Implementation is split over multiple files to make the code more manageable.
 // TODO: code smell, refactor the god object.
+pub mod diagnostics;
 pub mod execution;
 pub mod initialization;
+pub mod simulation;
 
 use aptos_config::config::NodeConfig;
 use aptos_crypto::HashValue;
@@ -12,6 +14,8 @@ use aptos_vm::AptosVM;
 
 use tracing::info;
 
+use crate::gc_account_sequence_number::UsedSequenceNumberPool;
+use crate::receipts::{ReceiptsExporter, ReceiptsService};
 use maptos_execution_util::config::Config;
 use movement_collections::garbage::counted::GcCounter;
 use std::sync::{Arc, RwLock};
@@ -25,10 +29,16 @@ pub struct Executor {
 	pub signer: ValidatorSigner,
 	// Shared reference on the counter of transactions in flight.
 	transactions_in_flight: Arc<RwLock<GcCounter>>,
+	// Shared reference on the pool of used sequence numbers.
+	used_sequence_number_pool: Arc<RwLock<UsedSequenceNumberPool>>,
 	// The config for the executor.
 	pub(crate) config: Config,
 	/// The node config derived from the maptos config.
 	pub(crate) node_config: NodeConfig,
+	/// The exporter of block receipts, if enabled.
+	pub(crate) receipts_exporter: Option<ReceiptsExporter>,
+	/// Serializes the uses of the block executor, a simulation resets its speculative blocks.
+	execution_lock: tokio::sync::Mutex<()>,
 }
 
 impl Executor {
@@ -53,6 +63,19 @@ impl Executor {
 		transactions_in_flight.decrement(count);
 	}
 
+	/// Forgets the sequence numbers used by transactions sent to the DA,
+	/// so that the committed sequence numbers are used again for validation.
+	pub fn clear_used_sequence_numbers(&self) {
+		// unwrap because lock is poisoned
+		let mut used_sequence_number_pool = self.used_sequence_number_pool.write().unwrap();
+		used_sequence_number_pool.clear();
+	}
+
+	/// The gRPC service streaming block receipts, if receipts are exported over gRPC.
+	pub fn receipts_service(&self) -> Option<ReceiptsService> {
+		self.receipts_exporter.as_ref().and_then(ReceiptsExporter::service)
+	}
+
 	pub fn config(&self) -> &Config {
 		&self.config
 	}
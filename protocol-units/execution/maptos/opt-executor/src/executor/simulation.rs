@@ -0,0 +1,235 @@
+use super::Executor;
+use crate::receipts::TransactionReceipt;
+use aptos_crypto::hash::CryptoHash;
+use aptos_executor_types::BlockExecutorTrait;
+use aptos_storage_interface::state_view::DbStateViewAtVersion;
+use aptos_types::{
+	block_executor::{
+		config::BlockExecutorConfigFromOnchain,
+		partitioner::{ExecutableBlock, ExecutableTransactions},
+	},
+	epoch_change::EpochChangeProof,
+	proof::StateProof,
+	transaction::{
+		signature_verified_transaction::SignatureVerifiedTransaction, Transaction, Version,
+	},
+};
+use aptos_vm::{AptosVM, VMExecutor};
+use movement_types::block::Commitment;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// The outcome of a block executed against the ledger state without being committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockSimulation {
+	/// The ledger version the block was executed on
+	pub version: Version,
+	/// The receipts of the block transactions, with their write sets and events
+	pub transactions: Vec<TransactionReceipt>,
+	/// The commitment the block would have if it was committed on top of the version, only
+	/// computed on top of the latest version
+	pub commitment: Option<Commitment>,
+}
+
+impl Executor {
+	/// Executes a block against the ledger state at `version`, without committing it.
+	pub async fn simulate_block(
+		&self,
+		block: ExecutableBlock,
+		version: Version,
+	) -> Result<BlockSimulation, anyhow::Error> {
+		let latest_version = self.db().reader.get_latest_ledger_info_version()?;
+		if version > latest_version {
+			anyhow::bail!(
+				"Cannot simulate block at version {} past the latest version {}",
+				version,
+				latest_version
+			);
+		}
+
+		let transactions = block.transactions.clone().into_txns();
+		let db_reader = self.db_reader();
+		let vm_transactions = transactions.clone();
+		let outputs = tokio::task::spawn_blocking(move || {
+			let state_view = db_reader.state_view_at_version(Some(version))?;
+			AptosVM::execute_block_no_limit(&vm_transactions, &state_view)
+				.map_err(|e| anyhow::anyhow!("Failed to execute block: {:?}", e))
+		})
+		.await??;
+
+		let mut receipts = Vec::new();
+		for (offset, (transaction, output)) in transactions.iter().zip(outputs.iter()).enumerate() {
+			receipts.push(TransactionReceipt::try_new(
+				transaction.clone().into_inner().hash(),
+				version + 1 + offset as u64,
+				output,
+			)?);
+		}
+
+		let commitment = self.simulate_block_commitment(block, version).await?;
+
+		info!("Simulated block at version {}", version);
+		Ok(BlockSimulation { version, transactions: receipts, commitment })
+	}
+
+	/// Computes the commitment of a block executed on top of `version` by the live block
+	/// executor. The block executor only extends its committed block, so the commitment is
+	/// computed only if `version` is the latest version of the ledger.
+	///
+	/// The speculative result is never committed: the block executor is reset after it.
+	async fn simulate_block_commitment(
+		&self,
+		block: ExecutableBlock,
+		version: Version,
+	) -> Result<Option<Commitment>, anyhow::Error> {
+		let block_metadata = match block.transactions.clone().into_txns().first() {
+			Some(transaction) => match transaction.clone().into_inner() {
+				Transaction::BlockMetadata(metadata) => metadata,
+				_ => {
+					anyhow::bail!("First transaction in block must be a block metadata transaction")
+				}
+			},
+			None => anyhow::bail!("Block must contain a block metadata transaction"),
+		};
+
+		// no block is executed or committed until the block executor is reset
+		let _execution = self.execution_lock.lock().await;
+		if self.db().reader.get_latest_ledger_info_version()? != version {
+			return Ok(None);
+		}
+		let block_id = block.block_id;
+		let parent_block_id = self.block_executor.committed_block_id();
+		let block_executor = self.block_executor.clone();
+		let state_compute = tokio::task::spawn_blocking(move || {
+			let state_compute = block_executor.execute_block(
+				block,
+				parent_block_id,
+				BlockExecutorConfigFromOnchain::new_no_block_limit(),
+			);
+			block_executor.reset()?;
+			Ok::<_, anyhow::Error>(state_compute?)
+		})
+		.await??;
+
+		let ledger_info_with_sigs = self.ledger_info_with_sigs(
+			block_metadata.epoch(),
+			block_metadata.round(),
+			block_id,
+			block_metadata.timestamp_usecs(),
+			state_compute.root_hash(),
+			state_compute.version(),
+		);
+		let state_proof =
+			StateProof::new(ledger_info_with_sigs, EpochChangeProof::new(vec![], false));
+		Ok(Some(Commitment::digest_state_proof(&state_proof)))
+	}
+
+	/// Gets the block committed at a height as an executable block,
+	/// with the version of the ledger the block was executed on.
+	pub fn get_executable_block_at_height(
+		&self,
+		height: u64,
+	) -> Result<(ExecutableBlock, Version), anyhow::Error> {
+		let reader = self.db_reader();
+		let (start_version, end_version, _block_event) = reader.get_block_info_by_height(height)?;
+		let latest_version = reader.get_latest_ledger_info_version()?;
+		let transactions = reader.get_transactions(
+			start_version,
+			end_version - start_version + 1,
+			latest_version,
+			false,
+		)?;
+
+		let mut block_id = None;
+		let mut block_transactions = Vec::new();
+		for transaction in transactions.transactions {
+			match &transaction {
+				Transaction::BlockMetadata(metadata) => block_id = Some(metadata.id()),
+				Transaction::UserTransaction(_) => {}
+				// the executor appends these itself
+				_ => continue,
+			}
+			block_transactions.push(SignatureVerifiedTransaction::Valid(transaction));
+		}
+		let block_id = block_id
+			.ok_or(anyhow::anyhow!("No block metadata transaction at height {}", height))?;
+
+		let block =
+			ExecutableBlock::new(block_id, ExecutableTransactions::Unsharded(block_transactions));
+		Ok((block, start_version.saturating_sub(1)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, Uniform};
+	use aptos_sdk::{
+		transaction_builder::TransactionFactory,
+		types::{AccountKey, LocalAccount},
+	};
+	use aptos_types::{
+		account_config::aptos_test_root_address, block_metadata::BlockMetadata,
+		transaction::signature_verified_transaction::into_signature_verified_block,
+	};
+	use rand::SeedableRng;
+	use tokio::sync::mpsc;
+
+	#[tokio::test]
+	async fn test_simulate_block_does_not_commit() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, _tempdir) = Executor::try_test_default(private_key)?;
+		let (context, _transaction_pipe) = executor.background(tx_sender)?;
+
+		let root_account = LocalAccount::new(
+			aptos_test_root_address(),
+			AccountKey::from_private_key(context.config().chain.maptos_private_key.clone()),
+			0,
+		);
+		let mut rng = ::rand::rngs::StdRng::from_seed([3u8; 32]);
+		let tx_factory = TransactionFactory::new(context.config().chain.maptos_chain_id.clone());
+
+		let (epoch, round) = executor.get_next_epoch_and_round()?;
+		let block_id = HashValue::random();
+		let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+			block_id,
+			epoch,
+			round,
+			executor.signer.author(),
+			vec![],
+			vec![],
+			chrono::Utc::now().timestamp_micros() as u64,
+		));
+		let new_account = LocalAccount::generate(&mut rng);
+		let transaction = root_account.sign_with_transaction_builder(
+			tx_factory.create_user_account(new_account.public_key()),
+		);
+		let transactions = ExecutableTransactions::Unsharded(into_signature_verified_block(vec![
+			block_metadata,
+			Transaction::UserTransaction(transaction),
+		]));
+		let block = ExecutableBlock::new(block_id, transactions);
+
+		// simulate on top of the head, then execute the same block for real
+		let version = executor.db_reader().get_latest_ledger_info_version()?;
+		let simulation = executor.simulate_block(block.clone(), version).await?;
+		assert_eq!(executor.db_reader().get_latest_ledger_info_version()?, version);
+		assert_eq!(simulation.transactions.len(), 2);
+		assert!(!simulation.transactions[1].write_set.is_empty());
+
+		// the simulated block was dropped by the live block executor
+		let commitment = executor.execute_block(block).await?;
+		assert_eq!(simulation.commitment, Some(commitment.commitment()));
+
+		// re-simulate the committed block against its historical state, the live block
+		// executor does not extend a past version
+		let (block, version) = executor.get_executable_block_at_height(1)?;
+		let historical = executor.simulate_block(block, version).await?;
+		assert_eq!(historical.transactions, simulation.transactions);
+		assert_eq!(historical.commitment, None);
+		assert_eq!(executor.get_block_head_height()?, 1);
+
+		Ok(())
+	}
+}
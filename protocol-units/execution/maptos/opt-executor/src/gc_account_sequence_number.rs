@@ -69,6 +69,11 @@ impl UsedSequenceNumberPool {
 			.insert(*account_address, sequence_number);
 	}
 
+	/// Removes all the sequence numbers.
+	pub(crate) fn clear(&mut self) {
+		self.sequence_number_lifetimes.clear();
+	}
+
 	/// Garbage collects sequence numbers that have expired.
 	/// This should be called periodically.
 	pub(crate) fn gc(&mut self, current_time_ms: u64) {
@@ -156,4 +161,17 @@ pub mod test {
 		assert_eq!(pool.get_sequence_number(&account1), Some(3));
 		assert_eq!(pool.get_sequence_number(&account2), None);
 	}
+
+	#[test]
+	fn test_clear() {
+		let mut pool = UsedSequenceNumberPool::new(1000, 100);
+		let account1 = AccountAddress::random();
+		let account2 = AccountAddress::random();
+
+		pool.set_sequence_number(&account1, 1, 0);
+		pool.set_sequence_number(&account2, 2, 500);
+		pool.clear();
+		assert_eq!(pool.get_sequence_number(&account1), None);
+		assert_eq!(pool.get_sequence_number(&account2), None);
+	}
 }
@@ -0,0 +1,246 @@
+use aptos_framework::ReleaseBundle;
+use aptos_types::{
+	account_address::AccountAddress,
+	chain_id::ChainId,
+	on_chain_config::{FeatureFlag, Features, GasScheduleV2},
+};
+use aptos_vm_genesis::{default_gas_schedule, GenesisConfiguration};
+use serde::{Deserialize, Serialize};
+
+use std::path::{Path, PathBuf};
+
+// This number should not exceed u64::MAX / 1_000_000_000
+// to avoid overflowing calculations in aptos-vm-genesis.
+// This will last several centuries.
+const EPOCH_DURATION_SECS: u64 = 60 * 60 * 24 * 1024 * 128;
+
+/// An account funded at genesis.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisAccount {
+	/// The address of the account
+	pub address: AccountAddress,
+	/// The initial balance in octas
+	pub balance: u64,
+}
+
+/// Overrides applied on top of the default feature flag set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisFeatures {
+	/// Feature flags to enable
+	#[serde(default)]
+	pub enable: Vec<FeatureFlag>,
+	/// Feature flags to disable
+	#[serde(default)]
+	pub disable: Vec<FeatureFlag>,
+}
+
+impl GenesisFeatures {
+	/// Builds the feature set, or `None` if no flag is overridden.
+	pub fn features(&self) -> Option<Features> {
+		if self.enable.is_empty() && self.disable.is_empty() {
+			return None;
+		}
+		let mut features = Features::default();
+		for flag in &self.enable {
+			features.enable(*flag);
+		}
+		for flag in &self.disable {
+			features.disable(*flag);
+		}
+		Some(features)
+	}
+}
+
+/// Parameters of the genesis configuration of the framework.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenesisParameters {
+	pub allow_new_validators: bool,
+	pub epoch_duration_secs: u64,
+	pub is_test: bool,
+	pub min_stake: u64,
+	pub min_voting_threshold: u128,
+	pub max_stake: u64,
+	pub recurring_lockup_duration_secs: u64,
+	pub required_proposer_stake: u64,
+	pub rewards_apy_percentage: u64,
+	pub voting_duration_secs: u64,
+	pub voting_power_increase_limit: u64,
+	pub employee_vesting_start: u64,
+	pub employee_vesting_period_duration: u64,
+}
+
+impl Default for GenesisParameters {
+	fn default() -> Self {
+		Self {
+			allow_new_validators: true,
+			epoch_duration_secs: EPOCH_DURATION_SECS,
+			is_test: true,
+			min_stake: 0,
+			min_voting_threshold: 0,
+			// 1M APTOS coins (with 8 decimals).
+			max_stake: 100_000_000_000_000,
+			recurring_lockup_duration_secs: EPOCH_DURATION_SECS * 2,
+			required_proposer_stake: 0,
+			rewards_apy_percentage: 0,
+			voting_duration_secs: EPOCH_DURATION_SECS,
+			voting_power_increase_limit: 50,
+			employee_vesting_start: 1663456089,
+			employee_vesting_period_duration: 5 * 60, // 5 minutes
+		}
+	}
+}
+
+/// Specification of the genesis of a Movement chain.
+///
+/// The same spec always produces the same genesis transaction, and thus the same waypoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisSpec {
+	/// The chain id the genesis is built for
+	pub chain_id: ChainId,
+	/// Accounts funded at genesis
+	#[serde(default)]
+	pub accounts: Vec<GenesisAccount>,
+	/// Paths to release bundles published at genesis on top of the head framework.
+	/// Relative paths are resolved against the directory of the spec file.
+	#[serde(default)]
+	pub modules: Vec<PathBuf>,
+	/// Feature flag overrides
+	#[serde(default)]
+	pub features: GenesisFeatures,
+	/// The gas schedule. The default schedule is used if not provided.
+	#[serde(default)]
+	pub gas_schedule: Option<GasScheduleV2>,
+	/// Framework genesis parameters
+	#[serde(default)]
+	pub parameters: GenesisParameters,
+}
+
+impl GenesisSpec {
+	/// The default genesis spec for a chain id.
+	pub fn default_for_chain(chain_id: ChainId) -> Self {
+		Self {
+			chain_id,
+			accounts: Vec::new(),
+			modules: Vec::new(),
+			features: GenesisFeatures::default(),
+			gas_schedule: None,
+			parameters: GenesisParameters::default(),
+		}
+	}
+
+	/// Loads a genesis spec from a JSON or YAML file, picked by the file extension.
+	pub fn try_from_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+		let path = path.as_ref();
+		let contents = std::fs::read_to_string(path)
+			.map_err(|e| anyhow::anyhow!("Failed to read genesis spec {:?}: {:?}", path, e))?;
+		let mut spec: Self = match path.extension().and_then(|ext| ext.to_str()) {
+			Some("json") => serde_json::from_str(&contents)?,
+			Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)?,
+			_ => anyhow::bail!("Unsupported genesis spec format: {:?}", path),
+		};
+
+		// resolve module paths relative to the spec
+		if let Some(dir) = path.parent() {
+			spec.modules = spec.modules.into_iter().map(|module| dir.join(module)).collect();
+		}
+
+		Ok(spec)
+	}
+
+	/// The head framework bundle extended with the packages listed in the spec.
+	pub fn framework(&self) -> Result<ReleaseBundle, anyhow::Error> {
+		let mut framework = aptos_cached_packages::head_release_bundle().clone();
+		for path in &self.modules {
+			let bundle = ReleaseBundle::read(path.clone()).map_err(|e| {
+				anyhow::anyhow!("Failed to read release bundle {:?}: {:?}", path, e)
+			})?;
+			framework.packages.extend(bundle.packages);
+		}
+		Ok(framework)
+	}
+
+	/// The gas schedule, defaulting to the Aptos one.
+	pub fn gas_schedule(&self) -> GasScheduleV2 {
+		self.gas_schedule.clone().unwrap_or_else(default_gas_schedule)
+	}
+
+	/// The framework genesis configuration.
+	pub fn genesis_configuration(&self) -> GenesisConfiguration {
+		let parameters = &self.parameters;
+		GenesisConfiguration {
+			allow_new_validators: parameters.allow_new_validators,
+			epoch_duration_secs: parameters.epoch_duration_secs,
+			is_test: parameters.is_test,
+			min_stake: parameters.min_stake,
+			min_voting_threshold: parameters.min_voting_threshold,
+			max_stake: parameters.max_stake,
+			recurring_lockup_duration_secs: parameters.recurring_lockup_duration_secs,
+			required_proposer_stake: parameters.required_proposer_stake,
+			rewards_apy_percentage: parameters.rewards_apy_percentage,
+			voting_duration_secs: parameters.voting_duration_secs,
+			voting_power_increase_limit: parameters.voting_power_increase_limit,
+			employee_vesting_start: parameters.employee_vesting_start,
+			employee_vesting_period_duration: parameters.employee_vesting_period_duration,
+			initial_features_override: self.features.features(),
+			randomness_config_override: None,
+			jwk_consensus_config_override: None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::Write;
+
+	#[test]
+	fn test_json_and_yaml_specs_match() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+
+		let json_path = dir.path().join("genesis.json");
+		let mut json = std::fs::File::create(&json_path)?;
+		json.write_all(
+			br#"{
+				"chain_id": 27,
+				"accounts": [{ "address": "0xa11ce", "balance": 1000 }],
+				"features": { "disable": ["PERIODICAL_REWARD_RATE_DECREASE"] },
+				"parameters": { "allow_new_validators": false, "max_stake": 1 }
+			}"#,
+		)?;
+
+		let yaml_path = dir.path().join("genesis.yaml");
+		let mut yaml = std::fs::File::create(&yaml_path)?;
+		yaml.write_all(
+			br#"
+chain_id: 27
+accounts:
+  - address: "0xa11ce"
+    balance: 1000
+features:
+  disable: [PERIODICAL_REWARD_RATE_DECREASE]
+parameters:
+  allow_new_validators: false
+  max_stake: 1
+"#,
+		)?;
+
+		let json_spec = GenesisSpec::try_from_path(&json_path)?;
+		let yaml_spec = GenesisSpec::try_from_path(&yaml_path)?;
+		assert_eq!(json_spec, yaml_spec);
+		assert_eq!(json_spec.accounts[0].balance, 1000);
+		assert!(json_spec.features.features().is_some());
+		assert_eq!(json_spec.parameters.epoch_duration_secs, EPOCH_DURATION_SECS);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_unknown_spec_format() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("genesis.toml");
+		std::fs::write(&path, "chain_id = 27")?;
+		assert!(GenesisSpec::try_from_path(&path).is_err());
+		Ok(())
+	}
+}
@@ -4,7 +4,9 @@ pub mod context;
 #[warn(unused_imports)]
 pub mod executor;
 pub mod gc_account_sequence_number;
+pub mod genesis;
 pub mod indexer;
+pub mod receipts;
 pub mod service;
 
 pub use context::Context;
@@ -0,0 +1,77 @@
+use super::BlockReceipt;
+use maptos_execution_receipts_proto::{
+	execution_receipts_service_server::{ExecutionReceiptsService, ExecutionReceiptsServiceServer},
+	StreamBlockReceiptsRequest, StreamBlockReceiptsResponse, FILE_DESCRIPTOR_SET,
+};
+
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_stream::Stream;
+use tonic::transport::Server;
+use tracing::{info, warn};
+
+use std::pin::Pin;
+
+/// gRPC service streaming the receipts of the executed blocks to its clients.
+#[derive(Clone)]
+pub struct ReceiptsService {
+	sender: broadcast::Sender<BlockReceipt>,
+	listen_address: String,
+}
+
+impl ReceiptsService {
+	pub(crate) fn new(sender: broadcast::Sender<BlockReceipt>, listen_address: String) -> Self {
+		Self { sender, listen_address }
+	}
+
+	/// Runs the gRPC server.
+	pub async fn run(self) -> Result<(), anyhow::Error> {
+		let reflection = tonic_reflection::server::Builder::configure()
+			.register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+			.build_v1()?;
+
+		let address = self.listen_address.parse()?;
+		info!("Execution receipts service listening on: {}", self.listen_address);
+		Server::builder()
+			.add_service(ExecutionReceiptsServiceServer::new(self))
+			.add_service(reflection)
+			.serve(address)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[tonic::async_trait]
+impl ExecutionReceiptsService for ReceiptsService {
+	type StreamBlockReceiptsStream = Pin<
+		Box<dyn Stream<Item = Result<StreamBlockReceiptsResponse, tonic::Status>> + Send + 'static>,
+	>;
+
+	async fn stream_block_receipts(
+		&self,
+		_request: tonic::Request<StreamBlockReceiptsRequest>,
+	) -> Result<tonic::Response<Self::StreamBlockReceiptsStream>, tonic::Status> {
+		let mut receiver = self.sender.subscribe();
+
+		let output = async_stream::try_stream! {
+			loop {
+				match receiver.recv().await {
+					Ok(receipt) => {
+						yield StreamBlockReceiptsResponse { block_receipt: Some(receipt.into()) };
+					}
+					Err(RecvError::Lagged(skipped)) => {
+						// a client too slow to keep up must resubscribe and reconcile the gap
+						warn!("Receipts stream lagged by {} blocks", skipped);
+						Err(tonic::Status::data_loss(format!(
+							"receipts stream lagged by {} blocks",
+							skipped
+						)))?;
+					}
+					Err(RecvError::Closed) => break,
+				}
+			}
+		};
+
+		Ok(tonic::Response::new(Box::pin(output) as Self::StreamBlockReceiptsStream))
+	}
+}
@@ -0,0 +1,295 @@
+//! Export of the execution receipts of executed blocks.
+
+mod grpc;
+
+pub use grpc::ReceiptsService;
+
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_storage_interface::DbReader;
+use aptos_types::transaction::{TransactionOutput, Version};
+use maptos_execution_receipts_proto as proto;
+use maptos_execution_util::config::receipts::{Config, Sink};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, Mutex};
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// Number of receipts buffered for slow gRPC clients before they start lagging.
+const RECEIPTS_CHANNEL_SIZE: usize = 1024;
+
+/// The receipts of the transactions of an executed block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockReceipt {
+	pub block_id: HashValue,
+	pub height: u64,
+	pub first_version: Version,
+	pub transactions: Vec<TransactionReceipt>,
+}
+
+/// The receipt of an executed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+	pub hash: HashValue,
+	pub version: Version,
+	pub vm_status: String,
+	pub gas_used: u64,
+	pub events: Vec<EventReceipt>,
+	pub write_set: Vec<WriteSetChange>,
+}
+
+/// An event emitted by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventReceipt {
+	pub type_tag: String,
+	#[serde(with = "hex")]
+	pub data: Vec<u8>,
+}
+
+/// A change of the state made by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteSetChange {
+	/// The BCS-encoded state key
+	#[serde(with = "hex")]
+	pub state_key: Vec<u8>,
+	pub deleted: bool,
+	/// The value written, empty if deleted
+	#[serde(with = "hex")]
+	pub value: Vec<u8>,
+}
+
+impl BlockReceipt {
+	/// Reads the receipt of the committed block ending at `end_version` from the ledger.
+	pub fn try_from_db(
+		db_reader: &dyn DbReader,
+		block_id: HashValue,
+		end_version: Version,
+	) -> Result<Self, anyhow::Error> {
+		let (first_version, end_version, block_event) =
+			db_reader.get_block_info_by_version(end_version)?;
+		let outputs = db_reader.get_transaction_outputs(
+			first_version,
+			end_version - first_version + 1,
+			end_version,
+		)?;
+
+		let mut transactions = Vec::new();
+		for (offset, (transaction, output)) in
+			outputs.transactions_and_outputs.into_iter().enumerate()
+		{
+			transactions.push(TransactionReceipt::try_new(
+				transaction.hash(),
+				first_version + offset as u64,
+				&output,
+			)?);
+		}
+
+		Ok(Self { block_id, height: block_event.height, first_version, transactions })
+	}
+}
+
+impl TransactionReceipt {
+	pub(crate) fn try_new(
+		hash: HashValue,
+		version: Version,
+		output: &TransactionOutput,
+	) -> Result<Self, anyhow::Error> {
+		let events = output
+			.events()
+			.iter()
+			.map(|event| EventReceipt {
+				type_tag: event.type_tag().to_canonical_string(),
+				data: event.event_data().to_vec(),
+			})
+			.collect();
+		let mut write_set = Vec::new();
+		for (state_key, write_op) in output.write_set().iter() {
+			let value = write_op.bytes().map(|bytes| bytes.to_vec());
+			write_set.push(WriteSetChange {
+				state_key: bcs::to_bytes(state_key)?,
+				deleted: value.is_none(),
+				value: value.unwrap_or_default(),
+			});
+		}
+
+		Ok(Self {
+			hash,
+			version,
+			vm_status: format!("{:?}", output.status()),
+			gas_used: output.gas_used(),
+			events,
+			write_set,
+		})
+	}
+}
+
+impl From<BlockReceipt> for proto::BlockReceipt {
+	fn from(receipt: BlockReceipt) -> Self {
+		proto::BlockReceipt {
+			block_id: receipt.block_id.to_vec(),
+			height: receipt.height,
+			first_version: receipt.first_version,
+			transactions: receipt.transactions.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+impl From<TransactionReceipt> for proto::TransactionReceipt {
+	fn from(receipt: TransactionReceipt) -> Self {
+		proto::TransactionReceipt {
+			hash: receipt.hash.to_vec(),
+			version: receipt.version,
+			vm_status: receipt.vm_status,
+			gas_used: receipt.gas_used,
+			events: receipt
+				.events
+				.into_iter()
+				.map(|event| proto::Event { type_tag: event.type_tag, data: event.data })
+				.collect(),
+			write_set: receipt
+				.write_set
+				.into_iter()
+				.map(|change| proto::WriteSetChange {
+					state_key: change.state_key,
+					deleted: change.deleted,
+					value: change.value,
+				})
+				.collect(),
+		}
+	}
+}
+
+/// Exports block receipts to the configured sink.
+#[derive(Clone)]
+pub enum ReceiptsExporter {
+	/// Appends JSON lines to a local file.
+	File { path: PathBuf, lock: Arc<Mutex<()>> },
+	/// Broadcasts to the subscribers of the gRPC service.
+	Grpc { sender: broadcast::Sender<BlockReceipt>, listen_address: String },
+}
+
+impl ReceiptsExporter {
+	/// Creates the exporter for the configured sink, if any.
+	pub fn try_from_config(config: &Config) -> Option<Self> {
+		match config.receipts_sink.as_ref()? {
+			Sink::File { path } => {
+				Some(Self::File { path: path.clone(), lock: Arc::new(Mutex::new(())) })
+			}
+			Sink::Grpc { listen_hostname, listen_port } => {
+				let (sender, _) = broadcast::channel(RECEIPTS_CHANNEL_SIZE);
+				Some(Self::Grpc {
+					sender,
+					listen_address: format!("{}:{}", listen_hostname, listen_port),
+				})
+			}
+		}
+	}
+
+	/// Exports the receipt of a block.
+	pub async fn export(&self, receipt: BlockReceipt) -> Result<(), anyhow::Error> {
+		match self {
+			Self::File { path, lock } => {
+				let mut line = serde_json::to_vec(&receipt)?;
+				line.push(b'\n');
+				// the lock keeps the lines of concurrent exports from interleaving
+				let _guard = lock.lock().await;
+				let mut file =
+					tokio::fs::OpenOptions::new().create(true).append(true).open(path).await?;
+				file.write_all(&line).await?;
+				file.flush().await?;
+			}
+			Self::Grpc { sender, .. } => {
+				// an error only means that no client is currently subscribed
+				let _ = sender.send(receipt);
+			}
+		}
+		Ok(())
+	}
+
+	/// The gRPC service streaming the receipts, if the sink is gRPC.
+	pub fn service(&self) -> Option<ReceiptsService> {
+		match self {
+			Self::File { .. } => None,
+			Self::Grpc { sender, listen_address } => {
+				Some(ReceiptsService::new(sender.clone(), listen_address.clone()))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn receipt(height: u64) -> BlockReceipt {
+		BlockReceipt {
+			block_id: HashValue::random(),
+			height,
+			first_version: height * 3,
+			transactions: vec![TransactionReceipt {
+				hash: HashValue::random(),
+				version: height * 3,
+				vm_status: "Keep(Success)".to_string(),
+				gas_used: 7,
+				events: vec![EventReceipt {
+					type_tag: "0x1::coin::DepositEvent".to_string(),
+					data: vec![1, 2, 3],
+				}],
+				write_set: vec![WriteSetChange {
+					state_key: vec![4, 5],
+					deleted: false,
+					value: vec![6],
+				}],
+			}],
+		}
+	}
+
+	#[tokio::test]
+	async fn test_file_sink_appends_json_lines() -> Result<(), anyhow::Error> {
+		let tempdir = tempfile::tempdir()?;
+		let path = tempdir.path().join("receipts.jsonl");
+		let config = Config { receipts_sink: Some(Sink::File { path: path.clone() }) };
+		let exporter = ReceiptsExporter::try_from_config(&config).unwrap();
+		assert!(exporter.service().is_none());
+
+		let receipts = vec![receipt(1), receipt(2)];
+		for receipt in receipts.iter() {
+			exporter.export(receipt.clone()).await?;
+		}
+
+		let contents = std::fs::read_to_string(&path)?;
+		let exported = contents
+			.lines()
+			.map(serde_json::from_str)
+			.collect::<Result<Vec<BlockReceipt>, _>>()?;
+		assert_eq!(exported, receipts);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_grpc_sink_broadcasts() -> Result<(), anyhow::Error> {
+		let config = Config {
+			receipts_sink: Some(Sink::Grpc {
+				listen_hostname: "127.0.0.1".to_string(),
+				listen_port: 0,
+			}),
+		};
+		let exporter = ReceiptsExporter::try_from_config(&config).unwrap();
+		assert!(exporter.service().is_some());
+
+		// exporting without subscribers must not fail
+		exporter.export(receipt(1)).await?;
+
+		let mut receiver = match &exporter {
+			ReceiptsExporter::Grpc { sender, .. } => sender.subscribe(),
+			_ => panic!("expected a gRPC exporter"),
+		};
+		let receipt = receipt(2);
+		exporter.export(receipt.clone()).await?;
+		assert_eq!(receiver.recv().await?, receipt);
+
+		Ok(())
+	}
+}
@@ -1,3 +1,4 @@
+use crate::executor::execution::{block_head_height, commitment_for_height};
 use crate::Context;
 
 use aptos_api::{
@@ -8,7 +9,14 @@ use aptos_api::{
 use aptos_storage_interface::DbReaderWriter;
 
 use futures::prelude::*;
-use poem::{http::Method, listener::TcpListener, middleware::Cors, EndpointExt, Route, Server};
+use poem::{
+	handler,
+	http::{Method, StatusCode},
+	listener::TcpListener,
+	middleware::Cors,
+	web::{Data, Json, Path},
+	EndpointExt, IntoResponse, Response, Route, Server,
+};
 use tracing::info;
 
 use std::future::Future;
@@ -78,6 +86,10 @@ impl Service {
 				"/set_failpoint",
 				poem::get(set_failpoints::set_failpoint_poem).data(self.api_context()),
 			)
+			.at(
+				"/movement/v1/block-commitment/:height",
+				poem::get(block_commitment).data(self.api_context()),
+			)
 			.with(cors);
 
 		Server::new(listener)
@@ -86,19 +98,43 @@ impl Service {
 	}
 }
 
+/// Serves the commitment of the local ledger at a block height,
+/// or `404 Not Found` if the ledger has not reached the height yet.
+#[handler]
+async fn block_commitment(
+	Path(height): Path<u64>,
+	context: Data<&Arc<aptos_api::Context>>,
+) -> Result<Response, anyhow::Error> {
+	if height > block_head_height(context.db.as_ref())? {
+		return Ok(StatusCode::NOT_FOUND.into_response());
+	}
+	let commitment = commitment_for_height(context.db.as_ref(), height)?;
+	Ok(Json(commitment).into_response())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::Executor;
+	use aptos_crypto::HashValue;
 	use aptos_mempool::MempoolClientRequest;
 	use aptos_types::{
-		account_config, mempool_status::MempoolStatusCode, test_helpers::transaction_test_helpers,
-		transaction::SignedTransaction,
+		account_config,
+		block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
+		block_metadata::BlockMetadata,
+		mempool_status::MempoolStatusCode,
+		test_helpers::transaction_test_helpers,
+		transaction::{
+			signature_verified_transaction::into_signature_verified_block, SignedTransaction,
+			Transaction,
+		},
 	};
 	use aptos_vm_genesis::GENESIS_KEYPAIR;
 	use futures::channel::oneshot;
 	use futures::SinkExt;
 	use maptos_execution_util::config::chain::Config;
+	use movement_types::block::BlockCommitment;
+	use poem::test::TestClient;
 	use tokio::sync::mpsc;
 
 	fn create_signed_transaction(sequence_number: u64, chain_config: &Config) -> SignedTransaction {
@@ -147,4 +183,43 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn test_block_commitment_endpoint() -> Result<(), anyhow::Error> {
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, _tempdir) = Executor::try_test_default(GENESIS_KEYPAIR.0.clone())?;
+		let (context, _background) = executor.background(tx_sender)?;
+		let service = Service::new(&context);
+
+		let (epoch, round) = executor.get_next_epoch_and_round()?;
+		let block_id = HashValue::random();
+		let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+			block_id,
+			epoch,
+			round,
+			executor.signer.author(),
+			vec![],
+			vec![],
+			chrono::Utc::now().timestamp_micros() as u64,
+		));
+		let transactions =
+			ExecutableTransactions::Unsharded(into_signature_verified_block(vec![block_metadata]));
+		let commitment =
+			executor.execute_block(ExecutableBlock::new(block_id, transactions)).await?;
+
+		let client = TestClient::new(Route::new().at(
+			"/movement/v1/block-commitment/:height",
+			poem::get(block_commitment).data(service.api_context()),
+		));
+		let response = client.get("/movement/v1/block-commitment/1").send().await;
+		response.assert_status_is_ok();
+		let served: BlockCommitment = response.json().await.value().deserialize();
+		assert_eq!(served, commitment);
+
+		// the ledger has not reached the next height yet
+		let response = client.get("/movement/v1/block-commitment/2").send().await;
+		response.assert_status(StatusCode::NOT_FOUND);
+
+		Ok(())
+	}
 }
@@ -0,0 +1 @@
+buildtime::proto_build_main!("movementlabs/protocol_units/execution/maptos/receipts/v1beta1.proto");
@@ -0,0 +1,8 @@
+pub mod v1beta1 {
+	tonic::include_proto!("movementlabs.protocol_units.execution.maptos.receipts.v1beta1");
+	pub const FILE_DESCRIPTOR_SET: &[u8] =
+		tonic::include_file_descriptor_set!("maptos-execution-receipts-proto-descriptor");
+}
+
+// Re-export the latest version at the crate root
+pub use v1beta1::*;
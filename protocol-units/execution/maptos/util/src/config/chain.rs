@@ -6,7 +6,7 @@ use super::common::{
 	default_maptos_state_merkle_prune_window,
 };
 use aptos_crypto::ed25519::Ed25519PrivateKey;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{chain_id::ChainId, waypoint::Waypoint};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -57,6 +57,13 @@ pub struct Config {
 	/// The genesis block hash
 	#[serde(default = "default_genesis_block_hash_hex")]
 	pub genesis_block_hash_hex: String,
+
+	/// The path to a JSON or YAML genesis spec.
+	/// If not provided, the default devnet genesis is used.
+	pub maptos_genesis_spec_path: Option<PathBuf>,
+
+	/// The expected genesis waypoint, verified against the database at startup
+	pub maptos_genesis_waypoint: Option<Waypoint>,
 }
 
 impl Default for Config {
@@ -74,6 +81,8 @@ impl Default for Config {
 			genesis_timestamp_microseconds: default_genesis_timestamp_microseconds(),
 			genesis_block_hash_hex: default_genesis_block_hash_hex(),
 			maptos_db_path: None,
+			maptos_genesis_spec_path: None,
+			maptos_genesis_waypoint: None,
 		}
 	}
 }
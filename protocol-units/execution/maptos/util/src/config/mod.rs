@@ -7,6 +7,7 @@ pub mod indexer;
 pub mod indexer_processor;
 pub mod load_shedding;
 pub mod mempool;
+pub mod receipts;
 
 use serde::{Deserialize, Serialize};
 
@@ -47,6 +48,10 @@ pub struct Config {
 	/// Access control
 	#[serde(default)]
 	pub access_control: aptos_account_whitelist::config::Config,
+
+	/// The execution receipts export configuration
+	#[serde(default)]
+	pub receipts: receipts::Config,
 }
 
 impl Default for Config {
@@ -61,6 +66,7 @@ impl Default for Config {
 			load_shedding: load_shedding::Config::default(),
 			mempool: mempool::Config::default(),
 			access_control: aptos_account_whitelist::config::Config::default(),
+			receipts: receipts::Config::default(),
 		}
 	}
 }
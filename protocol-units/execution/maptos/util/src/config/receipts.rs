@@ -0,0 +1,22 @@
+//! Configuration for the export of block execution receipts.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The sink the execution receipts are streamed to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Sink {
+	/// Appends the receipts as JSON lines to a local file.
+	File { path: PathBuf },
+	/// Streams the receipts to the clients of a gRPC service.
+	Grpc { listen_hostname: String, listen_port: u16 },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Config {
+	/// The sink to export the receipts of executed blocks to.
+	/// Receipts are not exported if not set.
+	#[serde(default)]
+	pub receipts_sink: Option<Sink>,
+}
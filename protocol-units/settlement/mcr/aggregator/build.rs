@@ -0,0 +1,3 @@
+buildtime::proto_build_main!(
+	"movementlabs/protocol_units/settlement/mcr/aggregation/v1beta1.proto"
+);
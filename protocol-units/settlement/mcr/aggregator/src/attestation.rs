@@ -0,0 +1,106 @@
+use crate::v1beta1;
+use movement_types::block::{BlockCommitment, Commitment, Id};
+
+use alloy::signers::local::PrivateKeySigner;
+use alloy_primitives::{keccak256, Address, Signature, B256, U256};
+use alloy_signer::Signer;
+use alloy_sol_types::SolValue;
+
+/// The domain attestations are signed for: an MCR contract on a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttestationDomain {
+	pub contract_address: Address,
+	pub chain_id: u64,
+}
+
+impl AttestationDomain {
+	/// The digest an attester signs to attest to a block commitment.
+	///
+	/// This is the message the `attestationDigest` function of the MCR contract
+	/// prefixes as an EIP-191 signed message.
+	pub fn digest(&self, block_commitment: &BlockCommitment) -> B256 {
+		keccak256(
+			(
+				self.contract_address,
+				U256::from(self.chain_id),
+				U256::from(block_commitment.height()),
+				B256::from(*block_commitment.commitment().as_bytes()),
+				B256::from(*block_commitment.block_id().as_bytes()),
+			)
+				.abi_encode_params(),
+		)
+	}
+}
+
+/// An attester's signature of a block commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attestation {
+	pub block_commitment: BlockCommitment,
+	pub signature: Signature,
+}
+
+impl Attestation {
+	/// Signs a block commitment.
+	pub async fn sign(
+		signer: &PrivateKeySigner,
+		domain: &AttestationDomain,
+		block_commitment: BlockCommitment,
+	) -> Result<Self, anyhow::Error> {
+		let digest = domain.digest(&block_commitment);
+		let signature = signer
+			.sign_message(digest.as_slice())
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to sign the attestation: {:?}", e))?;
+		Ok(Self { block_commitment, signature })
+	}
+
+	/// Recovers the address of the attester who signed the attestation.
+	pub fn recover_attester(&self, domain: &AttestationDomain) -> Result<Address, anyhow::Error> {
+		let digest = domain.digest(&self.block_commitment);
+		self.signature
+			.recover_address_from_msg(digest.as_slice())
+			.map_err(|e| anyhow::anyhow!("Failed to recover the attester: {:?}", e))
+	}
+}
+
+impl From<Attestation> for v1beta1::Attestation {
+	fn from(attestation: Attestation) -> Self {
+		let block_commitment = &attestation.block_commitment;
+		v1beta1::Attestation {
+			block_commitment: Some(v1beta1::BlockCommitment {
+				height: block_commitment.height(),
+				block_id: block_commitment.block_id().as_bytes().to_vec(),
+				commitment: block_commitment.commitment().as_bytes().to_vec(),
+			}),
+			signature: attestation.signature.as_bytes().to_vec(),
+		}
+	}
+}
+
+impl TryFrom<v1beta1::Attestation> for Attestation {
+	type Error = anyhow::Error;
+
+	fn try_from(attestation: v1beta1::Attestation) -> Result<Self, Self::Error> {
+		let block_commitment = attestation
+			.block_commitment
+			.ok_or(anyhow::anyhow!("Attestation without a block commitment"))?;
+		let block_id = block_commitment
+			.block_id
+			.try_into()
+			.map_err(|e| anyhow::anyhow!("Failed to decode the block id: {:?}", e))?;
+		let commitment = block_commitment
+			.commitment
+			.try_into()
+			.map_err(|e| anyhow::anyhow!("Failed to decode the commitment: {:?}", e))?;
+		let signature = Signature::try_from(attestation.signature.as_slice())
+			.map_err(|e| anyhow::anyhow!("Failed to decode the signature: {:?}", e))?;
+		Ok(Self {
+			block_commitment: BlockCommitment::new(
+				block_commitment.height,
+				Id::new(block_id),
+				Commitment::new(commitment),
+			),
+			signature,
+		})
+	}
+}
@@ -0,0 +1,188 @@
+//! Off-chain aggregation of the attesters' MCR commitments.
+//!
+//! Attesters sign their block commitments with their ETH keys and send the attestations
+//! to each other over gRPC. A designated submitter posts the commitments attested to
+//! by a quorum in one transaction.
+pub mod attestation;
+pub mod pool;
+pub mod service;
+
+pub mod v1beta1 {
+	tonic::include_proto!("movementlabs.protocol_units.settlement.mcr.aggregation.v1beta1");
+	pub const FILE_DESCRIPTOR_SET: &[u8] =
+		tonic::include_file_descriptor_set!("mcr-settlement-aggregator-descriptor");
+}
+
+pub use attestation::{Attestation, AttestationDomain};
+pub use pool::AttestationPool;
+pub use service::AttestationService;
+
+use mcr_settlement_config::Config;
+use mcr_settlement_manager::CommitmentAggregation;
+use movement_types::block::BlockCommitment;
+use v1beta1::{attestation_service_client::AttestationServiceClient, SubmitAttestationRequest};
+
+use alloy::signers::local::PrivateKeySigner;
+use alloy_primitives::Address;
+use anyhow::Context;
+use async_trait::async_trait;
+use futures::future;
+use tokio::sync::Notify;
+use tokio::time::{self, Instant};
+use tracing::warn;
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Aggregation of this attester's commitments with the other attesters.
+#[derive(Clone)]
+pub struct Aggregation {
+	signer: PrivateKeySigner,
+	domain: AttestationDomain,
+	peers: Vec<String>,
+	is_submitter: bool,
+	quorum: usize,
+	timeout: Duration,
+	pool: Arc<Mutex<AttestationPool>>,
+	attested: Arc<Notify>,
+}
+
+impl Aggregation {
+	/// Creates the aggregation from the settlement config.
+	///
+	/// Returns the aggregation to give to the settlement manager,
+	/// and the gRPC service receiving the attestations of the other attesters, to be run.
+	pub fn try_from_config(config: &Config) -> Result<(Self, AttestationService), anyhow::Error> {
+		let aggregation_config =
+			config.aggregation.as_ref().context("No MCR aggregation config")?;
+		let signer = config
+			.settle
+			.signer_private_key
+			.parse::<PrivateKeySigner>()
+			.context("Failed to parse the private key of the attester")?;
+		let domain = AttestationDomain {
+			contract_address: config
+				.settle
+				.mcr_contract_address
+				.parse()
+				.context("Failed to parse the MCR contract address")?,
+			chain_id: config.eth_connection.eth_chain_id,
+		};
+		let submitter: Address = aggregation_config
+			.aggregation_submitter
+			.parse()
+			.context("Failed to parse the address of the aggregation submitter")?;
+		let mut attesters = aggregation_config
+			.aggregation_attesters
+			.iter()
+			.map(|attester| {
+				attester
+					.parse()
+					.map_err(|e| anyhow::anyhow!("Failed to parse attester address: {:?}", e))
+			})
+			.collect::<Result<HashSet<Address>, _>>()?;
+		attesters.insert(signer.address());
+
+		let pool = Arc::new(Mutex::new(AttestationPool::new(domain, attesters)));
+		let attested = Arc::new(Notify::new());
+		let service = AttestationService::new(
+			pool.clone(),
+			attested.clone(),
+			aggregation_config.aggregation_listen_address.clone(),
+		);
+		let aggregation = Aggregation {
+			is_submitter: signer.address() == submitter,
+			signer,
+			domain,
+			peers: aggregation_config.aggregation_peers.clone(),
+			quorum: aggregation_config.aggregation_quorum,
+			timeout: Duration::from_millis(aggregation_config.aggregation_timeout),
+			pool,
+			attested,
+		};
+		Ok((aggregation, service))
+	}
+
+	async fn send_to_peer(peer: String, attestation: Attestation) -> Result<(), anyhow::Error> {
+		let mut client = AttestationServiceClient::connect(peer.clone())
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to connect to {}: {:?}", peer, e))?;
+		client
+			.submit_attestation(SubmitAttestationRequest { attestation: Some(attestation.into()) })
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to send attestation to {}: {:?}", peer, e))?;
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl CommitmentAggregation for Aggregation {
+	async fn attest(&self, block_commitment: &BlockCommitment) -> Result<(), anyhow::Error> {
+		let attestation =
+			Attestation::sign(&self.signer, &self.domain, block_commitment.clone()).await?;
+		self.pool.lock().unwrap().add(attestation.clone())?;
+		self.attested.notify_waiters();
+
+		// An unreachable peer falls back to posting its commitment directly.
+		let sent = self
+			.peers
+			.iter()
+			.map(|peer| Self::send_to_peer(peer.clone(), attestation.clone()));
+		for result in future::join_all(sent).await {
+			if let Err(e) = result {
+				warn!("{:?}", e);
+			}
+		}
+		Ok(())
+	}
+
+	fn is_submitter(&self) -> bool {
+		self.is_submitter
+	}
+
+	async fn wait_for_aggregate(
+		&self,
+		block_commitment: &BlockCommitment,
+		deadline: Instant,
+	) -> Result<Option<Vec<Vec<u8>>>, anyhow::Error> {
+		loop {
+			// Registered before checking the pool, so that no attestation is missed.
+			let attested = self.attested.notified();
+			{
+				let mut pool = self.pool.lock().unwrap();
+				let signatures = pool.signatures(block_commitment);
+				if signatures.len() >= self.quorum {
+					pool.remove(block_commitment.height());
+					return Ok(Some(
+						signatures.iter().map(|signature| signature.as_bytes().to_vec()).collect(),
+					));
+				}
+			}
+			if time::timeout_at(deadline, attested).await.is_err() {
+				return Ok(None);
+			}
+		}
+	}
+
+	fn timeout(&self) -> Duration {
+		self.timeout
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_default_aggregation_config() -> Result<(), anyhow::Error> {
+		let mut config = Config::default();
+		config.aggregation = Some(Default::default());
+
+		// no attester is the submitter until one is configured
+		let (aggregation, _service) = Aggregation::try_from_config(&config)?;
+		assert!(!aggregation.is_submitter());
+
+		Ok(())
+	}
+}
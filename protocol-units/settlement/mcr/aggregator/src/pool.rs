@@ -0,0 +1,119 @@
+use crate::attestation::{Attestation, AttestationDomain};
+use movement_types::block::BlockCommitment;
+
+use alloy_primitives::{Address, Signature};
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The number of heights attestations are kept for.
+/// Attestations at lower heights are dropped when a new height is attested to.
+const MAX_POOLED_HEIGHTS: usize = 1024;
+
+/// The attestations received from the attesters, by block commitment.
+pub struct AttestationPool {
+	domain: AttestationDomain,
+	attesters: HashSet<Address>,
+	attestations: BTreeMap<u64, HashMap<BlockCommitment, BTreeMap<Address, Signature>>>,
+}
+
+impl AttestationPool {
+	pub fn new(domain: AttestationDomain, attesters: HashSet<Address>) -> Self {
+		Self { domain, attesters, attestations: BTreeMap::new() }
+	}
+
+	/// Adds an attestation, returning the attester who signed it.
+	pub fn add(&mut self, attestation: Attestation) -> Result<Address, anyhow::Error> {
+		let attester = attestation.recover_attester(&self.domain)?;
+		if !self.attesters.contains(&attester) {
+			anyhow::bail!("Attestation from unknown attester {}", attester);
+		}
+
+		let height = attestation.block_commitment.height();
+		self.attestations
+			.entry(height)
+			.or_default()
+			.entry(attestation.block_commitment)
+			.or_default()
+			.insert(attester, attestation.signature);
+		while self.attestations.len() > MAX_POOLED_HEIGHTS {
+			self.attestations.pop_first();
+		}
+		Ok(attester)
+	}
+
+	/// The signatures of the attestations to a block commitment, ordered by attester.
+	pub fn signatures(&self, block_commitment: &BlockCommitment) -> Vec<Signature> {
+		self.attestations
+			.get(&block_commitment.height())
+			.and_then(|commitments| commitments.get(block_commitment))
+			.map(|signatures| signatures.values().copied().collect())
+			.unwrap_or_default()
+	}
+
+	/// Drops the attestations at a height.
+	pub fn remove(&mut self, height: u64) {
+		self.attestations.remove(&height);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy::signers::local::PrivateKeySigner;
+	use movement_types::block::{Commitment, Id};
+
+	fn domain() -> AttestationDomain {
+		AttestationDomain { contract_address: Address::repeat_byte(1), chain_id: 31337 }
+	}
+
+	fn commitment(height: u64, value: u8) -> BlockCommitment {
+		BlockCommitment::new(height, Id::new([height as u8; 32]), Commitment::new([value; 32]))
+	}
+
+	#[tokio::test]
+	async fn test_pools_attestations_by_commitment() -> Result<(), anyhow::Error> {
+		let alice = PrivateKeySigner::random();
+		let bob = PrivateKeySigner::random();
+		let mut pool =
+			AttestationPool::new(domain(), HashSet::from([alice.address(), bob.address()]));
+
+		let attestation = Attestation::sign(&alice, &domain(), commitment(1, 1)).await?;
+		assert_eq!(pool.add(attestation.clone())?, alice.address());
+		// the same attestation is only counted once
+		pool.add(attestation)?;
+		// bob disagrees on the commitment
+		pool.add(Attestation::sign(&bob, &domain(), commitment(1, 2)).await?)?;
+
+		assert_eq!(pool.signatures(&commitment(1, 1)).len(), 1);
+		pool.add(Attestation::sign(&bob, &domain(), commitment(1, 1)).await?)?;
+		assert_eq!(pool.signatures(&commitment(1, 1)).len(), 2);
+
+		pool.remove(1);
+		assert!(pool.signatures(&commitment(1, 1)).is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_rejects_unknown_attesters() -> Result<(), anyhow::Error> {
+		let alice = PrivateKeySigner::random();
+		let mallory = PrivateKeySigner::random();
+		let mut pool = AttestationPool::new(domain(), HashSet::from([alice.address()]));
+
+		assert!(pool
+			.add(Attestation::sign(&mallory, &domain(), commitment(1, 1)).await?)
+			.is_err());
+
+		// an attestation signed for another contract recovers another attester
+		let other_domain =
+			AttestationDomain { contract_address: Address::repeat_byte(2), chain_id: 31337 };
+		let mut attestation = Attestation::sign(&alice, &other_domain, commitment(1, 1)).await?;
+		assert!(pool.add(attestation.clone()).is_err());
+
+		attestation.signature =
+			Attestation::sign(&alice, &domain(), commitment(1, 1)).await?.signature;
+		assert_eq!(pool.add(attestation)?, alice.address());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,74 @@
+use crate::attestation::Attestation;
+use crate::pool::AttestationPool;
+use crate::v1beta1::{
+	attestation_service_server::{self, AttestationServiceServer},
+	SubmitAttestationRequest, SubmitAttestationResponse, FILE_DESCRIPTOR_SET,
+};
+
+use tokio::sync::Notify;
+use tonic::transport::Server;
+use tracing::{debug, info};
+
+use std::sync::{Arc, Mutex};
+
+/// gRPC service receiving the attestations of the other attesters.
+#[derive(Clone)]
+pub struct AttestationService {
+	pool: Arc<Mutex<AttestationPool>>,
+	attested: Arc<Notify>,
+	listen_address: String,
+}
+
+impl AttestationService {
+	pub(crate) fn new(
+		pool: Arc<Mutex<AttestationPool>>,
+		attested: Arc<Notify>,
+		listen_address: String,
+	) -> Self {
+		Self { pool, attested, listen_address }
+	}
+
+	/// Runs the gRPC server.
+	pub async fn run(self) -> Result<(), anyhow::Error> {
+		let reflection = tonic_reflection::server::Builder::configure()
+			.register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+			.build_v1()?;
+
+		let address = self.listen_address.parse()?;
+		info!("MCR attestation service listening on: {}", self.listen_address);
+		Server::builder()
+			.add_service(AttestationServiceServer::new(self))
+			.add_service(reflection)
+			.serve(address)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[tonic::async_trait]
+impl attestation_service_server::AttestationService for AttestationService {
+	async fn submit_attestation(
+		&self,
+		request: tonic::Request<SubmitAttestationRequest>,
+	) -> Result<tonic::Response<SubmitAttestationResponse>, tonic::Status> {
+		let attestation = request
+			.into_inner()
+			.attestation
+			.ok_or(tonic::Status::invalid_argument("No attestation in the request"))?;
+		let attestation = Attestation::try_from(attestation)
+			.map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+		let height = attestation.block_commitment.height();
+
+		let attester = self
+			.pool
+			.lock()
+			.unwrap()
+			.add(attestation)
+			.map_err(|e| tonic::Status::permission_denied(e.to_string()))?;
+		debug!("Received attestation from {} at height {}", attester, height);
+		self.attested.notify_waiters();
+
+		Ok(tonic::Response::new(SubmitAttestationResponse {}))
+	}
+}
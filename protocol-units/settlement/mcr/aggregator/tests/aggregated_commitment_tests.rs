@@ -0,0 +1,63 @@
+use anyhow::Context;
+use mcr_settlement_aggregator::{Attestation, AttestationDomain};
+use mcr_settlement_client::{McrSettlementClient, McrSettlementClientOperations};
+use mcr_settlement_test_evm::{TestEvm, ATTESTER_PRIVATE_KEYS, CHAIN_ID};
+use movement_types::block::{BlockCommitment, Commitment, Id};
+
+use alloy::signers::local::PrivateKeySigner;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+
+// The event filters of a local node are polled every 250ms.
+const EVENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn sign_all(
+	domain: &AttestationDomain,
+	block_commitment: &BlockCommitment,
+	private_keys: &[&str],
+) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+	let mut signatures = Vec::new();
+	for key in private_keys {
+		let signer = key.parse::<PrivateKeySigner>()?;
+		let attestation = Attestation::sign(&signer, domain, block_commitment.clone()).await?;
+		signatures.push(attestation.signature.as_bytes().to_vec());
+	}
+	Ok(signatures)
+}
+
+#[tokio::test]
+async fn test_post_aggregated_block_commitment() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let evm = TestEvm::start().await?;
+	let domain = AttestationDomain { contract_address: evm.deployment().mcr, chain_id: CHAIN_ID };
+	let alice =
+		McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[0])).await?;
+	let mut stream = alice.stream_block_commitments().await?;
+
+	// half of the stake is not a supermajority
+	let first = BlockCommitment::new(1, Id::new([1; 32]), Commitment::new([101; 32]));
+	let signatures = sign_all(&domain, &first, &ATTESTER_PRIVATE_KEYS[..1]).await?;
+	alice.post_aggregated_block_commitment(first.clone(), signatures).await?;
+	assert_eq!(alice.get_posted_commitment_at_height(1).await?, Some(first.clone()));
+	assert!(tokio::time::timeout(Duration::from_secs(1), stream.next()).await.is_err());
+	assert_eq!(alice.get_commitment_at_height(1).await?, None);
+
+	// resubmitting with every attestation skips the recorded one and accepts the commitment
+	let signatures = sign_all(&domain, &first, &ATTESTER_PRIVATE_KEYS).await?;
+	alice.post_aggregated_block_commitment(first.clone(), signatures).await?;
+	let accepted = tokio::time::timeout(EVENT_TIMEOUT, stream.next())
+		.await
+		.context("No accepted commitment received")?
+		.context("Accepted commitment stream closed")??;
+	assert_eq!(accepted, first);
+	assert_eq!(alice.get_commitment_at_height(1).await?, Some(first));
+
+	// attestations signed for another contract are rejected
+	let other_domain = AttestationDomain { chain_id: CHAIN_ID + 1, ..domain };
+	let second = BlockCommitment::new(2, Id::new([2; 32]), Commitment::new([102; 32]));
+	let signatures = sign_all(&other_domain, &second, &ATTESTER_PRIVATE_KEYS).await?;
+	assert!(alice.post_aggregated_block_commitment(second, signatures).await.is_err());
+	assert_eq!(alice.get_commitment_at_height(2).await?, None);
+
+	Ok(())
+}
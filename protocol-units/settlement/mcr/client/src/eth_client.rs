@@ -1,8 +1,13 @@
+use crate::send_eth_transaction::CappedEip1559FeeStrategy;
 use crate::send_eth_transaction::InsufficentFunds;
 use crate::send_eth_transaction::SendTransactionErrorRule;
+use crate::send_eth_transaction::TransactionSender;
 use crate::send_eth_transaction::UnderPriced;
 use crate::send_eth_transaction::VerifyRule;
-use crate::{CommitmentStream, McrSettlementClientOperations};
+use crate::{
+	CommitmentStream, EpochStake, McrSettlementClientOperations, McrStakingClientOperations,
+	ValidatorStatus,
+};
 use alloy::providers::fillers::ChainIdFiller;
 use alloy::providers::fillers::FillProvider;
 use alloy::providers::fillers::GasFiller;
@@ -16,19 +21,26 @@ use alloy_network::Ethereum;
 use alloy_network::EthereumWallet;
 use alloy_primitives::Address;
 use alloy_primitives::U256;
+use alloy_rpc_types::Log;
 use alloy_sol_types::sol;
 use alloy_transport::BoxTransport;
 use alloy_transport_ws::WsConnect;
 use anyhow::Context;
+use async_stream::stream;
+use mcr_settlement_config::common::eth_connection::Config as EthConnectionConfig;
+use mcr_settlement_config::common::transactions::Config as TransactionsConfig;
 use mcr_settlement_config::Config;
 use movement_types::block::{BlockCommitment, Commitment, Id};
 use serde_json::Value as JsonValue;
 use std::array::TryFromSliceError;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
+use std::pin::Pin;
+use std::time::Duration;
 use thiserror::Error;
-use tokio_stream::StreamExt;
-use tracing::info;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{info, warn};
 
 #[derive(Error, Debug)]
 pub enum McrEthConnectorError {
@@ -36,6 +48,10 @@ pub enum McrEthConnectorError {
 		"MCR Settlement Transaction fails because gas estimation is too high. Estimated gas:{0} gas limit:{1}"
 	)]
 	GasLimitExceed(u128, u128),
+	#[error("MCR Settlement Transaction gas estimation failed: {0}")]
+	GasEstimation(String),
+	#[error("MCR Settlement Transaction fees can't be raised above the cap. Max fee per gas:{0} cap:{1}")]
+	FeeCapReached(u128, u128),
 	#[error("MCR Settlement Transaction fails because account funds are insufficient. error:{0}")]
 	InsufficientFunds(String),
 	#[error("MCR Settlement Transaction send failed because :{0}")]
@@ -76,11 +92,14 @@ pub struct McrSettlementClient<P> {
 	run_commitment_admin_mode: bool,
 	rpc_provider: P,
 	ws_provider: RootProvider<PubSubFrontend>,
+	ws_url: String,
 	pub signer_address: Address,
 	contract_address: Address,
-	send_transaction_error_rules: Vec<Box<dyn VerifyRule>>,
-	gas_limit: u64,
-	send_transaction_retries: u32,
+	/// The L1 block the MCR contract was deployed at
+	deployment_block: u64,
+	transaction_sender: TransactionSender,
+	reconnect_backoff: Duration,
+	max_reconnect_backoff: Duration,
 }
 
 impl
@@ -135,8 +154,9 @@ impl
 			ws_url,
 			signer_address,
 			contract_address,
-			config.transactions.gas_limit,
-			config.transactions.transaction_send_retries,
+			config.settle.mcr_deployment_block,
+			&config.eth_connection,
+			&config.transactions,
 		)
 		.await
 		.context(
@@ -153,14 +173,16 @@ impl<P> McrSettlementClient<P> {
 		ws_url: S,
 		signer_address: Address,
 		contract_address: Address,
-		gas_limit: u64,
-		send_transaction_retries: u32,
+		deployment_block: u64,
+		eth_connection_config: &EthConnectionConfig,
+		transactions_config: &TransactionsConfig,
 	) -> Result<Self, anyhow::Error>
 	where
 		P: Provider + Clone,
 		S: Into<String>,
 	{
-		let ws = WsConnect::new(ws_url);
+		let ws_url = ws_url.into();
+		let ws = WsConnect::new(ws_url.clone());
 
 		let ws_provider = ProviderBuilder::new()
 			.on_ws(ws)
@@ -171,18 +193,37 @@ impl<P> McrSettlementClient<P> {
 		let rule2: Box<dyn VerifyRule> =
 			Box::new(SendTransactionErrorRule::<InsufficentFunds>::new());
 		let send_transaction_error_rules = vec![rule1, rule2];
+		let transaction_sender = TransactionSender::new(
+			signer_address,
+			send_transaction_error_rules,
+			Box::new(CappedEip1559FeeStrategy::from_config(transactions_config)),
+			transactions_config,
+		);
 
 		Ok(McrSettlementClient {
 			run_commitment_admin_mode,
 			rpc_provider,
 			ws_provider,
+			ws_url,
 			signer_address,
 			contract_address,
-			send_transaction_error_rules,
-			gas_limit,
-			send_transaction_retries,
+			deployment_block,
+			transaction_sender,
+			reconnect_backoff: Duration::from_millis(
+				eth_connection_config.eth_ws_reconnect_backoff,
+			),
+			max_reconnect_backoff: Duration::from_millis(
+				eth_connection_config.eth_ws_reconnect_max_backoff,
+			),
 		})
 	}
+
+	/// Splits the L1 blocks from the MCR deployment to the latest block into the ranges to
+	/// query the events in.
+	async fn event_ranges(&self) -> Result<Vec<(u64, u64)>, anyhow::Error> {
+		let latest_block = self.ws_provider.get_block_number().await?;
+		Ok(backfill_ranges(self.deployment_block, latest_block))
+	}
 }
 
 #[async_trait::async_trait]
@@ -207,22 +248,10 @@ where
 
 		if self.run_commitment_admin_mode {
 			let call_builder = contract.forceLatestCommitment(eth_block_commitment);
-			crate::send_eth_transaction::send_transaction(
-				call_builder,
-				&self.send_transaction_error_rules,
-				self.send_transaction_retries,
-				self.gas_limit as u128,
-			)
-			.await
+			self.transaction_sender.send_transaction(call_builder).await
 		} else {
 			let call_builder = contract.submitBlockCommitment(eth_block_commitment);
-			crate::send_eth_transaction::send_transaction(
-				call_builder,
-				&self.send_transaction_error_rules,
-				self.send_transaction_retries,
-				self.gas_limit as u128,
-			)
-			.await
+			self.transaction_sender.send_transaction(call_builder).await
 		}
 	}
 
@@ -250,13 +279,29 @@ where
 
 		let call_builder = contract.submitBatchBlockCommitment(eth_block_commitment);
 
-		crate::send_eth_transaction::send_transaction(
-			call_builder,
-			&self.send_transaction_error_rules,
-			self.send_transaction_retries,
-			self.gas_limit as u128,
-		)
-		.await
+		self.transaction_sender.send_transaction(call_builder).await
+	}
+
+	async fn post_aggregated_block_commitment(
+		&self,
+		block_commitment: BlockCommitment,
+		signatures: Vec<Vec<u8>>,
+	) -> Result<(), anyhow::Error> {
+		let contract = MCR::new(self.contract_address, &self.rpc_provider);
+
+		let eth_block_commitment = MCR::BlockCommitment {
+			// Currently, to simplify the API, we'll say 0 is uncommitted all other numbers are legitimate heights
+			height: U256::from(block_commitment.height()),
+			commitment: alloy_primitives::FixedBytes(
+				block_commitment.commitment().as_bytes().clone(),
+			),
+			blockId: alloy_primitives::FixedBytes(block_commitment.block_id().as_bytes().clone()),
+		};
+		let signatures = signatures.into_iter().map(Into::into).collect();
+
+		let call_builder =
+			contract.submitAggregatedBlockCommitment(eth_block_commitment, signatures);
+		self.transaction_sender.send_transaction(call_builder).await
 	}
 
 	async fn force_block_commitment(
@@ -275,37 +320,77 @@ where
 		};
 
 		let call_builder = contract.forceLatestCommitment(eth_block_commitment);
-		crate::send_eth_transaction::send_transaction(
-			call_builder,
-			&self.send_transaction_error_rules,
-			self.send_transaction_retries,
-			self.gas_limit as u128,
-		)
-		.await
+		self.transaction_sender.send_transaction(call_builder).await
 	}
 
 	async fn stream_block_commitments(&self) -> Result<CommitmentStream, anyhow::Error> {
 		// Register to contract BlockCommitmentSubmitted event
-
-		let contract = MCR::new(self.contract_address, &self.ws_provider);
-		let event_filter = contract.BlockAccepted_filter().watch().await?;
-
-		let stream = event_filter.into_stream().map(|event| {
-			event
-				.and_then(|(commitment, _)| {
-					let height = commitment.height.try_into().map_err(
-						|err: alloy::primitives::ruint::FromUintError<u64>| {
-							alloy_sol_types::Error::Other(err.to_string().into())
-						},
-					)?;
-					Ok(BlockCommitment::new(
-						height,
-						Id::new(commitment.blockHash.0),
-						Commitment::new(commitment.stateCommitment.0),
-					))
-				})
-				.map_err(|err| McrEthConnectorError::EventNotificationError(err).into())
-		});
+		// The subscription is made over its own WebSocket, reconnected when it drops.
+		let ws_url = self.ws_url.clone();
+		let contract_address = self.contract_address;
+		let reconnect_backoff = self.reconnect_backoff;
+		let max_reconnect_backoff = self.max_reconnect_backoff;
+
+		// The first subscription is made right away, so that no event is missed before polling.
+		let first_subscription =
+			BlockAcceptedSubscription::connect(&ws_url, contract_address, None).await?;
+
+		let stream = stream! {
+			// The position of the last event seen, to skip those recovered twice
+			let mut last_seen: Option<(u64, u64)> = None;
+			// The L1 block to recover the events missed while disconnected from
+			let mut resume_from: Option<u64> = None;
+			let mut backoff = reconnect_backoff;
+			let mut next_subscription = Some(first_subscription);
+			loop {
+				let subscription = match next_subscription.take() {
+					Some(subscription) => subscription,
+					None => match BlockAcceptedSubscription::connect(
+						&ws_url,
+						contract_address,
+						resume_from,
+					)
+					.await
+					{
+						Ok(subscription) => subscription,
+						Err(e) => {
+							warn!(
+								"Failed to subscribe to MCR BlockAccepted events, retrying in {:?}: {:?}",
+								backoff, e
+							);
+							tokio::time::sleep(backoff).await;
+							backoff = (backoff * 2).min(max_reconnect_backoff);
+							continue;
+						}
+					},
+				};
+				backoff = reconnect_backoff;
+				// the events up to the block of the subscription are recovered by it,
+				// so the next one only has to recover those mined after
+				resume_from = Some(subscription.block_number + 1);
+
+				let mut events = subscription.events;
+				while let Some(event) = events.next().await {
+					let (event, log) = match event {
+						Ok(event) => event,
+						Err(err) => {
+							yield Err(McrEthConnectorError::EventNotificationError(err).into());
+							continue;
+						}
+					};
+					if let (Some(block_number), Some(log_index)) = (log.block_number, log.log_index) {
+						if last_seen.is_some_and(|seen| (block_number, log_index) <= seen) {
+							continue;
+						}
+						last_seen = Some((block_number, log_index));
+						// the recovered events are behind the block of the subscription
+						resume_from = resume_from.max(Some(block_number));
+					}
+					yield block_commitment_from_event(event);
+				}
+				warn!("MCR BlockAccepted event stream closed, reconnecting");
+			}
+		};
 		Ok(Box::pin(stream) as CommitmentStream)
 	}
 
@@ -335,10 +420,18 @@ where
 	async fn get_posted_commitment_at_height(
 		&self,
 		height: u64,
+	) -> Result<Option<BlockCommitment>, anyhow::Error> {
+		self.get_attester_commitment_at_height(height, self.signer_address).await
+	}
+
+	async fn get_attester_commitment_at_height(
+		&self,
+		height: u64,
+		attester: Address,
 	) -> Result<Option<BlockCommitment>, anyhow::Error> {
 		let contract = MCR::new(self.contract_address, &self.ws_provider);
 		let MCR::getValidatorCommitmentAtBlockHeightReturn { _0: commitment } = contract
-			.getValidatorCommitmentAtBlockHeight(U256::from(height), self.signer_address)
+			.getValidatorCommitmentAtBlockHeight(U256::from(height), attester)
 			.call()
 			.await?;
 
@@ -357,6 +450,12 @@ where
 		)))
 	}
 
+	async fn get_attesters(&self) -> Result<Vec<Address>, anyhow::Error> {
+		let contract = MCR::new(self.contract_address, &self.ws_provider);
+		let MCR::getAttestersReturn { _0: attesters } = contract.getAttesters().call().await?;
+		Ok(attesters)
+	}
+
 	async fn get_max_tolerable_block_height(&self) -> Result<u64, anyhow::Error> {
 		let contract = MCR::new(self.contract_address, &self.ws_provider);
 		let MCR::getMaxTolerableBlockHeightReturn { _0: block_height } =
@@ -365,6 +464,272 @@ where
 			.try_into()
 			.context("Failed to convert the max tolerable block height from U256 to u64")?)
 	}
+
+	async fn get_l1_block_number(&self) -> Result<u64, anyhow::Error> {
+		Ok(self.rpc_provider.get_block_number().await?)
+	}
+}
+
+impl<P> McrSettlementClient<P>
+where
+	P: Provider + Clone,
+{
+	/// Gets the staking contract of the settlement contract and the custodian stake is held by.
+	async fn staking_custodian(&self) -> Result<(Address, Address), anyhow::Error> {
+		let contract = MCR::new(self.contract_address, &self.ws_provider);
+		let MCR::stakingContractReturn { _0: staking_address } =
+			contract.stakingContract().call().await?;
+		let staking = MovementStaking::new(staking_address, &self.ws_provider);
+		let MovementStaking::getCustodiansByDomainReturn { _0: custodians } =
+			staking.getCustodiansByDomain(self.contract_address).call().await?;
+		let custodian = custodians
+			.first()
+			.copied()
+			.context("No custodian registered for the MCR settlement contract")?;
+		Ok((staking_address, custodian))
+	}
+}
+
+#[async_trait::async_trait]
+impl<P> McrStakingClientOperations for McrSettlementClient<P>
+where
+	P: Provider + Clone,
+{
+	async fn stake(&self, amount: U256) -> Result<(), anyhow::Error> {
+		let (staking_address, custodian) = self.staking_custodian().await?;
+
+		let token = MOVEToken::new(custodian, &self.rpc_provider);
+		let call_builder = token.approve(staking_address, amount);
+		self.transaction_sender.send_transaction(call_builder).await?;
+
+		let staking = MovementStaking::new(staking_address, &self.rpc_provider);
+		let call_builder = staking.stake(self.contract_address, custodian, amount);
+		self.transaction_sender.send_transaction(call_builder).await
+	}
+
+	async fn unstake(&self, amount: U256) -> Result<(), anyhow::Error> {
+		let (staking_address, custodian) = self.staking_custodian().await?;
+
+		let staking = MovementStaking::new(staking_address, &self.rpc_provider);
+		let call_builder = staking.unstake(self.contract_address, custodian, amount);
+		self.transaction_sender.send_transaction(call_builder).await
+	}
+
+	async fn get_current_epoch(&self) -> Result<u64, anyhow::Error> {
+		let contract = MCR::new(self.contract_address, &self.ws_provider);
+		let MCR::getCurrentEpochReturn { _0: epoch } = contract.getCurrentEpoch().call().await?;
+		Ok(epoch.try_into().context("Failed to convert the epoch from U256 to u64")?)
+	}
+
+	async fn get_validator_set(&self) -> Result<Vec<(Address, U256)>, anyhow::Error> {
+		let contract = MCR::new(self.contract_address, &self.ws_provider);
+		let mut validators = Vec::new();
+		for attester in self.get_attesters().await? {
+			let MCR::computeAllCurrentEpochStakeReturn { _0: stake } =
+				contract.computeAllCurrentEpochStake(attester).call().await?;
+			validators.push((attester, stake));
+		}
+		Ok(validators)
+	}
+
+	async fn get_validator_status(
+		&self,
+		attester: Address,
+	) -> Result<ValidatorStatus, anyhow::Error> {
+		let (staking_address, custodian) = self.staking_custodian().await?;
+		let staking = MovementStaking::new(staking_address, &self.ws_provider);
+		let domain = self.contract_address;
+
+		let MovementStaking::WHITELIST_ROLEReturn { _0: whitelist_role } =
+			staking.WHITELIST_ROLE().call().await?;
+		let MovementStaking::hasRoleReturn { _0: whitelisted } =
+			staking.hasRole(whitelist_role, attester).call().await?;
+		let MovementStaking::getCurrentEpochReturn { _0: current_epoch } =
+			staking.getCurrentEpoch(domain).call().await?;
+		let MovementStaking::getNextEpochReturn { _0: next_epoch } =
+			staking.getNextEpoch(domain).call().await?;
+		let MovementStaking::getStakeAtEpochReturn { _0: current_stake } = staking
+			.getStakeAtEpoch(domain, current_epoch, custodian, attester)
+			.call()
+			.await?;
+		let MovementStaking::getStakeAtEpochReturn { _0: next_epoch_stake } =
+			staking.getStakeAtEpoch(domain, next_epoch, custodian, attester).call().await?;
+		let MovementStaking::getUnstakeAtEpochReturn { _0: pending_unstake } = staking
+			.getUnstakeAtEpoch(domain, next_epoch, custodian, attester)
+			.call()
+			.await?;
+
+		Ok(ValidatorStatus {
+			attester,
+			whitelisted,
+			current_epoch: current_epoch
+				.try_into()
+				.context("Failed to convert the epoch from U256 to u64")?,
+			current_stake,
+			next_epoch_stake,
+			pending_unstake,
+		})
+	}
+
+	async fn get_rewards(&self, attester: Address) -> Result<U256, anyhow::Error> {
+		let (staking_address, _custodian) = self.staking_custodian().await?;
+		let staking = MovementStaking::new(staking_address, &self.ws_provider);
+		let MovementStaking::tokenReturn { _0: token_address } = staking.token().call().await?;
+
+		// the settlement contract pays the rewards from its own balance
+		let token = MOVEToken::new(token_address, &self.ws_provider);
+		let mut transfers = Vec::new();
+		for (from, to) in self.event_ranges().await? {
+			let events = token
+				.Transfer_filter()
+				.topic1(self.contract_address.into_word())
+				.topic2(attester.into_word())
+				.from_block(from)
+				.to_block(to)
+				.query()
+				.await?;
+			transfers.extend(events);
+		}
+		Ok(transfers
+			.into_iter()
+			.fold(U256::ZERO, |total, (transfer, _log)| total + transfer.value))
+	}
+
+	async fn get_epoch_stakes(&self, attester: Address) -> Result<Vec<EpochStake>, anyhow::Error> {
+		let (staking_address, custodian) = self.staking_custodian().await?;
+		let staking = MovementStaking::new(staking_address, &self.ws_provider);
+
+		let event_ranges = self.event_ranges().await?;
+		// the stake for an epoch is added before it starts, the genesis stake for epoch 0
+		let mut staked = BTreeMap::<u64, U256>::new();
+		let mut staked_events = Vec::new();
+		for (from, to) in event_ranges.iter().copied() {
+			let events = staking
+				.AttesterStaked_filter()
+				.topic1(self.contract_address.into_word())
+				.topic3(custodian.into_word())
+				.from_block(from)
+				.to_block(to)
+				.query()
+				.await?;
+			staked_events.extend(events);
+		}
+		for (event, _log) in staked_events {
+			if event.attester != attester {
+				continue;
+			}
+			let epoch: u64 =
+				event.epoch.try_into().context("Failed to convert the epoch from U256 to u64")?;
+			*staked.entry(epoch).or_default() += event.stake;
+		}
+
+		let mut rolled_over_events = Vec::new();
+		for (from, to) in event_ranges {
+			let events = staking
+				.AttesterEpochRolledOver_filter()
+				.topic1(attester.into_word())
+				.topic3(custodian.into_word())
+				.from_block(from)
+				.to_block(to)
+				.query()
+				.await?;
+			rolled_over_events.extend(events);
+		}
+		let mut carried_over = staked.get(&0).copied().unwrap_or_default();
+		let mut epoch_stakes = Vec::new();
+		for (event, _log) in rolled_over_events {
+			let epoch: u64 =
+				event.epoch.try_into().context("Failed to convert the epoch from U256 to u64")?;
+			let staked_for_epoch = if epoch == 0 {
+				U256::ZERO
+			} else {
+				staked.get(&epoch).copied().unwrap_or_default()
+			};
+			epoch_stakes.push(EpochStake {
+				epoch,
+				starting_stake: carried_over + staked_for_epoch,
+				stake: event.stake,
+				unstake: event.unstake,
+			});
+			// the roll over never pays out more than the stake
+			carried_over = event.stake.saturating_sub(event.unstake);
+		}
+		Ok(epoch_stakes)
+	}
+}
+
+type BlockAcceptedStream =
+	Pin<Box<dyn Stream<Item = Result<(MCR::BlockAccepted, Log), alloy_sol_types::Error>> + Send>>;
+
+/// A subscription to the BlockAccepted events over a dedicated WebSocket.
+struct BlockAcceptedSubscription {
+	// The provider is kept alive for as long as its events are streamed
+	_ws_provider: RootProvider<PubSubFrontend>,
+	/// The L1 block number when the subscription was made
+	block_number: u64,
+	/// The events recovered from the resumed block, followed by the new events
+	events: BlockAcceptedStream,
+}
+
+impl BlockAcceptedSubscription {
+	async fn connect(
+		ws_url: &str,
+		contract_address: Address,
+		resume_from: Option<u64>,
+	) -> Result<Self, anyhow::Error> {
+		let ws_provider = ProviderBuilder::new()
+			.on_ws(WsConnect::new(ws_url))
+			.await
+			.context("Failed to create the WebSocket provider for the MCR event stream")?;
+		// Subscribe before recovering the missed events, so that none is lost in between.
+		let contract = MCR::new(contract_address, &ws_provider);
+		let new_events = contract.BlockAccepted_filter().watch().await?.into_stream();
+		// the events up to this block are recovered, those after it are watched
+		let block_number = ws_provider.get_block_number().await?;
+		let mut missed_events = Vec::new();
+		if let Some(from_block) = resume_from {
+			for (from, to) in backfill_ranges(from_block, block_number) {
+				let events =
+					contract.BlockAccepted_filter().from_block(from).to_block(to).query().await?;
+				missed_events.extend(events);
+			}
+			info!(
+				"Recovered {} MCR BlockAccepted events from L1 block {} to {}",
+				missed_events.len(),
+				from_block,
+				block_number
+			);
+		}
+		let events = tokio_stream::iter(missed_events.into_iter().map(Ok)).chain(new_events);
+
+		Ok(Self { _ws_provider: ws_provider, block_number, events: Box::pin(events) })
+	}
+}
+
+/// The most L1 blocks the events are queried for at once,
+/// within the range limits of the RPC providers.
+const BACKFILL_BLOCK_RANGE: u64 = 1000;
+
+/// Splits the L1 blocks from `from_block` to `to_block`, inclusive, into the ranges to query.
+fn backfill_ranges(from_block: u64, to_block: u64) -> Vec<(u64, u64)> {
+	(from_block..=to_block)
+		.step_by(BACKFILL_BLOCK_RANGE as usize)
+		.map(|from| (from, (from + BACKFILL_BLOCK_RANGE - 1).min(to_block)))
+		.collect()
+}
+
+fn block_commitment_from_event(
+	event: MCR::BlockAccepted,
+) -> Result<BlockCommitment, anyhow::Error> {
+	let height = event
+		.height
+		.try_into()
+		.context("Failed to convert the commitment height from U256 to u64")?;
+	Ok(BlockCommitment::new(
+		height,
+		Id::new(event.blockHash.0),
+		Commitment::new(event.stateCommitment.0),
+	))
 }
 
 pub struct AnvilAddressEntry {
@@ -405,3 +770,16 @@ pub fn read_anvil_json_file_addresses<P: AsRef<Path>>(
 		.collect::<Vec<_>>();
 	Ok(res)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_backfill_ranges() {
+		assert_eq!(backfill_ranges(5, 5), vec![(5, 5)]);
+		assert_eq!(backfill_ranges(6, 5), vec![]);
+		assert_eq!(backfill_ranges(1, 1000), vec![(1, 1000)]);
+		assert_eq!(backfill_ranges(1, 2500), vec![(1, 1000), (1001, 2000), (2001, 2500)]);
+	}
+}
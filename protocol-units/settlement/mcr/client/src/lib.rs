@@ -1,3 +1,4 @@
+use alloy_primitives::{Address, U256};
 use movement_types::block::BlockCommitment;
 use tokio_stream::Stream;
 pub mod mock;
@@ -30,6 +31,13 @@ pub trait McrSettlementClientOperations {
 		block_commitment: Vec<BlockCommitment>,
 	) -> Result<(), anyhow::Error>;
 
+	/// Posts a block commitment for all the attesters whose signatures were aggregated off-chain.
+	async fn post_aggregated_block_commitment(
+		&self,
+		block_commitment: BlockCommitment,
+		signatures: Vec<Vec<u8>>,
+	) -> Result<(), anyhow::Error>;
+
 	/// Forces a block commitment
 	/// This will only work in admin mode
 	async fn force_block_commitment(
@@ -52,6 +60,86 @@ pub trait McrSettlementClientOperations {
 		height: u64,
 	) -> Result<Option<BlockCommitment>, anyhow::Error>;
 
+	/// Gets the commitment an attester has made at a given height
+	async fn get_attester_commitment_at_height(
+		&self,
+		height: u64,
+		attester: Address,
+	) -> Result<Option<BlockCommitment>, anyhow::Error>;
+
+	/// Gets the attesters of the settlement contract.
+	async fn get_attesters(&self) -> Result<Vec<Address>, anyhow::Error>;
+
 	/// Gets the max tolerable block height.
 	async fn get_max_tolerable_block_height(&self) -> Result<u64, anyhow::Error>;
+
+	/// Gets the number of the latest block of the settlement chain.
+	async fn get_l1_block_number(&self) -> Result<u64, anyhow::Error>;
+}
+
+/// The staking status of a validator of the settlement contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorStatus {
+	pub attester: Address,
+	/// Whether the validator is allowed to stake
+	pub whitelisted: bool,
+	pub current_epoch: u64,
+	/// The stake counted in the current epoch, after any slashing
+	pub current_stake: U256,
+	/// The stake added for the next epoch
+	pub next_epoch_stake: U256,
+	/// The stake to be paid out at the next epoch roll over
+	pub pending_unstake: U256,
+}
+
+/// The stake of a validator over an epoch that has been rolled over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochStake {
+	pub epoch: u64,
+	/// The stake the epoch started with, carried over from the previous epoch or staked for it
+	pub starting_stake: U256,
+	/// The stake at the roll over, after any slashing
+	pub stake: U256,
+	/// The stake paid out at the roll over
+	pub unstake: U256,
+}
+
+impl EpochStake {
+	/// The stake slashed during the epoch.
+	pub fn slashed(&self) -> U256 {
+		self.starting_stake.saturating_sub(self.stake)
+	}
+}
+
+/// Staking of the validator with the staking contract of the settlement contract.
+///
+/// Stake is held by the first custodian registered for the settlement contract.
+#[async_trait::async_trait]
+pub trait McrStakingClientOperations {
+	/// Stakes an amount for the next epoch, approving its transfer to the staking contract.
+	async fn stake(&self, amount: U256) -> Result<(), anyhow::Error>;
+
+	/// Unstakes an amount at the next epoch.
+	async fn unstake(&self, amount: U256) -> Result<(), anyhow::Error>;
+
+	/// Gets the current epoch of the settlement contract.
+	async fn get_current_epoch(&self) -> Result<u64, anyhow::Error>;
+
+	/// Gets the attesters with their stake in the current epoch.
+	async fn get_validator_set(&self) -> Result<Vec<(Address, U256)>, anyhow::Error>;
+
+	/// Gets the staking status of a validator.
+	async fn get_validator_status(
+		&self,
+		attester: Address,
+	) -> Result<ValidatorStatus, anyhow::Error>;
+
+	/// Gets the total rewards paid to a validator by the settlement contract.
+	///
+	/// The staking contract pays rewards out as they are issued, so none is held pending.
+	async fn get_rewards(&self, attester: Address) -> Result<U256, anyhow::Error>;
+
+	/// Gets the stake of a validator over each epoch rolled over, in epoch order,
+	/// from which the slashing of each epoch is derived.
+	async fn get_epoch_stakes(&self, attester: Address) -> Result<Vec<EpochStake>, anyhow::Error>;
 }
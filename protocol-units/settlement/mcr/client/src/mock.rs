@@ -1,4 +1,5 @@
 use crate::{CommitmentStream, McrSettlementClientOperations};
+use alloy_primitives::Address;
 use mcr_settlement_config::Config;
 use movement_types::block::BlockCommitment;
 use std::collections::BTreeMap;
@@ -10,10 +11,12 @@ use tracing::info;
 #[derive(Clone)]
 pub struct McrSettlementClient {
 	commitments: Arc<RwLock<BTreeMap<u64, BlockCommitment>>>,
+	attester_commitments: Arc<RwLock<BTreeMap<Address, BTreeMap<u64, BlockCommitment>>>>,
 	stream_sender: mpsc::Sender<Result<BlockCommitment, anyhow::Error>>,
 	stream_receiver: Arc<Mutex<Option<mpsc::Receiver<Result<BlockCommitment, anyhow::Error>>>>>,
 	pub current_height: Arc<RwLock<u64>>,
 	pub block_lead_tolerance: u64,
+	pub l1_block_number: Arc<RwLock<u64>>,
 	paused_at_height: Arc<RwLock<Option<u64>>>,
 }
 
@@ -22,10 +25,12 @@ impl McrSettlementClient {
 		let (stream_sender, receiver) = mpsc::channel(10);
 		McrSettlementClient {
 			commitments: Arc::new(RwLock::new(BTreeMap::new())),
+			attester_commitments: Arc::new(RwLock::new(BTreeMap::new())),
 			stream_sender,
 			stream_receiver: Arc::new(Mutex::new(Some(receiver))),
 			current_height: Arc::new(RwLock::new(0)),
 			block_lead_tolerance: 16,
+			l1_block_number: Arc::new(RwLock::new(0)),
 			paused_at_height: Arc::new(RwLock::new(None)),
 		}
 	}
@@ -44,6 +49,15 @@ impl McrSettlementClient {
 		commitments.insert(commitment.height(), commitment);
 	}
 
+	/// Records a commitment posted by another attester.
+	pub async fn post_attester_commitment(&self, attester: Address, commitment: BlockCommitment) {
+		let mut attester_commitments = self.attester_commitments.write().await;
+		attester_commitments
+			.entry(attester)
+			.or_default()
+			.insert(commitment.height(), commitment);
+	}
+
 	/// Stop streaming commitments after the given height.
 	///
 	/// Any posted commitments will be accumulated.
@@ -57,12 +71,24 @@ impl McrSettlementClient {
 	/// commitments
 	pub async fn resume(&self) {
 		let resume_height = {
-			let mut paused_at_height = self.paused_at_height.write().await;
-			paused_at_height.take().expect("not paused")
+			let paused_at_height = self.paused_at_height.read().await;
+			paused_at_height.expect("not paused")
 		};
+		self.resume_from(resume_height + 1).await;
+	}
+
+	/// Resume streaming from the given height, skipping the commitments
+	/// posted between the pause and that height.
+	///
+	/// This simulates settlement moving past heights without streaming them.
+	pub async fn resume_from(&self, height: u64) {
+		{
+			let mut paused_at_height = self.paused_at_height.write().await;
+			paused_at_height.take().expect("not paused");
+		}
 		{
 			let commitments = self.commitments.read().await;
-			for (_, commitment) in commitments.range(resume_height + 1..) {
+			for (_, commitment) in commitments.range(height..) {
 				println!("resume sends commitment for height {}", commitment.height());
 				self.stream_sender.send(Ok(commitment.clone())).await.unwrap();
 			}
@@ -112,6 +138,15 @@ impl McrSettlementClientOperations for McrSettlementClient {
 		Ok(())
 	}
 
+	async fn post_aggregated_block_commitment(
+		&self,
+		block_commitment: BlockCommitment,
+		_signatures: Vec<Vec<u8>>,
+	) -> Result<(), anyhow::Error> {
+		// The mock does not track attesters, the aggregate settles like a single commitment.
+		self.post_block_commitment(block_commitment).await
+	}
+
 	async fn force_block_commitment(
 		&self,
 		_block_commitment: BlockCommitment,
@@ -123,7 +158,26 @@ impl McrSettlementClientOperations for McrSettlementClient {
 		&self,
 		height: u64,
 	) -> Result<Option<BlockCommitment>, anyhow::Error> {
-		unimplemented!();
+		// The mock settles every posted commitment, so the posted and accepted commitments match.
+		let guard = self.commitments.read().await;
+		Ok(guard.get(&height).cloned())
+	}
+
+	async fn get_attester_commitment_at_height(
+		&self,
+		height: u64,
+		attester: Address,
+	) -> Result<Option<BlockCommitment>, anyhow::Error> {
+		let attester_commitments = self.attester_commitments.read().await;
+		Ok(attester_commitments
+			.get(&attester)
+			.and_then(|commitments| commitments.get(&height))
+			.cloned())
+	}
+
+	async fn get_attesters(&self) -> Result<Vec<Address>, anyhow::Error> {
+		let attester_commitments = self.attester_commitments.read().await;
+		Ok(attester_commitments.keys().copied().collect())
 	}
 
 	async fn stream_block_commitments(&self) -> Result<CommitmentStream, anyhow::Error> {
@@ -147,6 +201,10 @@ impl McrSettlementClientOperations for McrSettlementClient {
 	async fn get_max_tolerable_block_height(&self) -> Result<u64, anyhow::Error> {
 		Ok(*self.current_height.read().await + self.block_lead_tolerance)
 	}
+
+	async fn get_l1_block_number(&self) -> Result<u64, anyhow::Error> {
+		Ok(*self.l1_block_number.read().await)
+	}
 }
 
 #[cfg(test)]
@@ -3,9 +3,15 @@ use alloy::providers::Provider;
 use alloy_contract::CallBuilder;
 use alloy_contract::CallDecoder;
 use alloy_network::Ethereum;
+use alloy_primitives::{Address, TxHash};
+use alloy_rpc_types::TransactionReceipt;
 use alloy_transport::{Transport, TransportError};
+use mcr_settlement_config::common::transactions::Config as TransactionsConfig;
+use std::future::Future;
 use std::marker::PhantomData;
-use tracing::info;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
 
 // Define a rule to verify the error generated when a transaction is send to determine if:
 // * the Transaction must me resend with more gas: return Ok(true)
@@ -59,90 +65,430 @@ impl VerifyRule for SendTransactionErrorRule<InsufficentFunds> {
 	}
 }
 
-pub async fn send_transaction<
-	P: Provider<T, Ethereum> + Clone,
-	T: Transport + Clone,
-	D: CallDecoder + Clone,
->(
-	base_call_builder: CallBuilder<T, &&P, D, Ethereum>,
-	send_transaction_error_rules: &[Box<dyn VerifyRule>],
+/// Returns true if the error reports that the nonce of the transaction was already used.
+fn is_nonce_too_low(error: &alloy_contract::Error) -> bool {
+	let alloy_contract::Error::TransportError(TransportError::ErrorResp(payload)) = error else {
+		return false;
+	};
+	payload.message.contains("nonce too low")
+}
+
+/// The EIP-1559 fees of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip1559Fees {
+	pub max_fee_per_gas: u128,
+	pub max_priority_fee_per_gas: u128,
+}
+
+// Minimal fee increase accepted by nodes to replace a pending transaction.
+const MIN_REPLACEMENT_BUMP_PERCENT: u128 = 10;
+
+/// Decides the fees paid by the sent transactions.
+pub trait FeeStrategy: Sync + Send {
+	/// The fees of a new transaction, given the fees estimated by the network.
+	fn initial_fees(&self, estimated: Eip1559Fees) -> Eip1559Fees;
+
+	/// The fees to replace an underpriced or stuck transaction.
+	///
+	/// Returns an error if the fees can't be raised anymore.
+	fn bump(&self, fees: Eip1559Fees) -> Result<Eip1559Fees, McrEthConnectorError>;
+}
+
+/// Follows the network estimation, capped by the configured fees.
+#[derive(Debug, Clone)]
+pub struct CappedEip1559FeeStrategy {
+	pub max_fee_per_gas: u128,
+	pub max_priority_fee_per_gas: u128,
+	pub bump_percent: u128,
+}
+
+impl CappedEip1559FeeStrategy {
+	pub fn from_config(config: &TransactionsConfig) -> Self {
+		Self {
+			max_fee_per_gas: config.max_fee_per_gas,
+			max_priority_fee_per_gas: config.max_priority_fee_per_gas,
+			bump_percent: (config.fee_bump_percent as u128).max(MIN_REPLACEMENT_BUMP_PERCENT),
+		}
+	}
+
+	fn cap(&self, fees: Eip1559Fees) -> Eip1559Fees {
+		let max_fee_per_gas = fees.max_fee_per_gas.min(self.max_fee_per_gas);
+		// the priority fee can't exceed the max fee
+		let max_priority_fee_per_gas = fees
+			.max_priority_fee_per_gas
+			.min(self.max_priority_fee_per_gas)
+			.min(max_fee_per_gas);
+		Eip1559Fees { max_fee_per_gas, max_priority_fee_per_gas }
+	}
+}
+
+impl FeeStrategy for CappedEip1559FeeStrategy {
+	fn initial_fees(&self, estimated: Eip1559Fees) -> Eip1559Fees {
+		self.cap(estimated)
+	}
+
+	fn bump(&self, fees: Eip1559Fees) -> Result<Eip1559Fees, McrEthConnectorError> {
+		let bump = |fee: u128| fee + (fee * self.bump_percent / 100).max(1);
+		let bumped = self.cap(Eip1559Fees {
+			max_fee_per_gas: bump(fees.max_fee_per_gas),
+			max_priority_fee_per_gas: bump(fees.max_priority_fee_per_gas),
+		});
+		// A replacement must raise both fees by the minimal bump to be accepted.
+		let min_bump = |fee: u128| fee + fee * MIN_REPLACEMENT_BUMP_PERCENT / 100;
+		if bumped.max_fee_per_gas < min_bump(fees.max_fee_per_gas)
+			|| bumped.max_priority_fee_per_gas < min_bump(fees.max_priority_fee_per_gas)
+		{
+			return Err(McrEthConnectorError::FeeCapReached(
+				fees.max_fee_per_gas,
+				self.max_fee_per_gas,
+			));
+		}
+		Ok(bumped)
+	}
+}
+
+/// Hands out the nonces of the signer locally,
+/// so that several transactions can be in flight at the same time.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+	next_nonce: Mutex<Option<u64>>,
+}
+
+impl NonceManager {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Reserves the next nonce. The nonce is read with `fetch` from the chain on first use,
+	/// or after a reset.
+	pub async fn next_nonce<F, Fut>(&self, fetch: F) -> Result<u64, anyhow::Error>
+	where
+		F: FnOnce() -> Fut,
+		Fut: Future<Output = Result<u64, anyhow::Error>>,
+	{
+		let mut next_nonce = self.next_nonce.lock().await;
+		let nonce = match *next_nonce {
+			Some(nonce) => nonce,
+			None => fetch().await?,
+		};
+		*next_nonce = Some(nonce + 1);
+		Ok(nonce)
+	}
+
+	/// Forgets the local nonce, so that the next one is read from the chain.
+	///
+	/// Used when a reserved nonce was not consumed or the local state diverged from the chain.
+	pub async fn reset(&self) {
+		*self.next_nonce.lock().await = None;
+	}
+}
+
+/// Sends the transactions of the settlement client.
+pub struct TransactionSender {
+	signer_address: Address,
+	send_transaction_error_rules: Vec<Box<dyn VerifyRule>>,
+	fee_strategy: Box<dyn FeeStrategy>,
+	nonce_manager: NonceManager,
 	number_retry: u32,
 	gas_limit: u128,
-) -> Result<(), anyhow::Error> {
-	info!("Sending transaction with gas limit: {}", gas_limit);
-	//validate gas price.
-	let mut estimate_gas = base_call_builder.estimate_gas().await.expect("Failed to estimate gas");
-	// Add 20% because initial gas estimate are too low.
-	estimate_gas += (estimate_gas * 20) / 100;
-
-	println!("estimated_gas: {}", estimate_gas);
-
-	// Sending Transaction automatically can lead to errors that depend on the state for Eth.
-	// It's convenient to manage some of them automatically to avoid to fail commitment Transaction.
-	// I define a first one but other should be added depending on the test with mainnet.
-	for _ in 0..number_retry {
-		let call_builder = base_call_builder.clone().gas(estimate_gas);
-
-		//detect if the gas price doesn't execeed the limit.
-		let gas_price = call_builder.provider.get_gas_price().await?;
-		let transaction_fee_wei = estimate_gas * gas_price;
-		if transaction_fee_wei > gas_limit {
-			return Err(McrEthConnectorError::GasLimitExceed(transaction_fee_wei, gas_limit).into());
+	stuck_transaction_timeout: Duration,
+}
+
+impl TransactionSender {
+	pub fn new(
+		signer_address: Address,
+		send_transaction_error_rules: Vec<Box<dyn VerifyRule>>,
+		fee_strategy: Box<dyn FeeStrategy>,
+		config: &TransactionsConfig,
+	) -> Self {
+		Self {
+			signer_address,
+			send_transaction_error_rules,
+			fee_strategy,
+			nonce_manager: NonceManager::new(),
+			number_retry: config.transaction_send_retries,
+			gas_limit: config.gas_limit as u128,
+			stuck_transaction_timeout: Duration::from_secs(config.stuck_transaction_timeout),
 		}
+	}
+
+	pub async fn send_transaction<
+		P: Provider<T, Ethereum> + Clone,
+		T: Transport + Clone,
+		D: CallDecoder + Clone,
+	>(
+		&self,
+		base_call_builder: CallBuilder<T, &&P, D, Ethereum>,
+	) -> Result<(), anyhow::Error> {
+		info!("Sending transaction with gas limit: {}", self.gas_limit);
+		let provider = *base_call_builder.provider;
+		let base_call_builder = base_call_builder.from(self.signer_address);
+
+		let mut estimate_gas = base_call_builder
+			.estimate_gas()
+			.await
+			.map_err(|e| McrEthConnectorError::GasEstimation(e.to_string()))?;
+		// Add 20% because initial gas estimate are too low.
+		estimate_gas += (estimate_gas * 20) / 100;
+		info!("estimated_gas: {}", estimate_gas);
+
+		let estimated_fees = provider
+			.estimate_eip1559_fees(None)
+			.await
+			.map_err(|e| McrEthConnectorError::GasEstimation(e.to_string()))?;
+		let mut fees = self.fee_strategy.initial_fees(Eip1559Fees {
+			max_fee_per_gas: estimated_fees.max_fee_per_gas,
+			max_priority_fee_per_gas: estimated_fees.max_priority_fee_per_gas,
+		});
+
+		let mut nonce = self.reserve_nonce(provider).await?;
+		// Hashes of the transactions sent with the current nonce, replaced ones included.
+		let mut sent_transactions: Vec<TxHash> = Vec::new();
+
+		// Sending Transaction automatically can lead to errors that depend on the state for Eth.
+		// It's convenient to manage some of them automatically to avoid to fail commitment Transaction.
+		// I define a first one but other should be added depending on the test with mainnet.
+		for _ in 0..self.number_retry {
+			//detect if the fees don't exceed the limit.
+			let transaction_fee_wei = estimate_gas * fees.max_fee_per_gas;
+			if transaction_fee_wei > self.gas_limit {
+				self.release_nonce(&sent_transactions).await;
+				return Err(McrEthConnectorError::GasLimitExceed(
+					transaction_fee_wei,
+					self.gas_limit,
+				)
+				.into());
+			}
 
-		info!("Sending transaction with gas: {}", estimate_gas);
-
-		//send the Transaction and detect send error.
-		let pending_transaction = match call_builder.send().await {
-			Ok(pending_transaction) => pending_transaction,
-			Err(err) => {
-				//apply defined rules.
-				for rule in send_transaction_error_rules {
-					// Verify all rules. If one rule return true or an error stop verification.
-					// If true retry with more gas else return the error.
-					if rule.verify(&err)? {
-						//increase gas of 10% and retry
-						estimate_gas += (estimate_gas * 10) / 100;
+			let call_builder = base_call_builder
+				.clone()
+				.gas(estimate_gas)
+				.max_fee_per_gas(fees.max_fee_per_gas)
+				.max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+				.nonce(nonce);
+			info!(
+				"Sending transaction with gas: {}, fees: {:?}, nonce: {}",
+				estimate_gas, fees, nonce
+			);
+
+			//send the Transaction and detect send error.
+			let pending_transaction = match call_builder.send().await {
+				Ok(pending_transaction) => pending_transaction,
+				Err(err) if is_nonce_too_low(&err) && !sent_transactions.is_empty() => {
+					// A transaction replaced by this one has been included in the meantime.
+					return self.check_sent_transactions(provider, &sent_transactions).await;
+				}
+				Err(err) if is_nonce_too_low(&err) => {
+					// The local nonce is behind the chain, resync it.
+					warn!("Nonce {} already used, resyncing the nonce", nonce);
+					self.nonce_manager.reset().await;
+					nonce = self.reserve_nonce(provider).await?;
+					continue;
+				}
+				Err(err) => {
+					//apply defined rules.
+					let mut retry = false;
+					for rule in &self.send_transaction_error_rules {
+						// Verify all rules. If one rule return true or an error stop verification.
+						// If true retry with higher fees else return the error.
+						match rule.verify(&err) {
+							Ok(true) => {
+								retry = true;
+								break;
+							}
+							Ok(false) => {}
+							Err(e) => {
+								self.release_nonce(&sent_transactions).await;
+								return Err(e.into());
+							}
+						}
+					}
+					if retry {
+						fees = match self.fee_strategy.bump(fees) {
+							Ok(fees) => fees,
+							Err(e) => {
+								self.release_nonce(&sent_transactions).await;
+								return Err(e.into());
+							}
+						};
 						tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 						continue;
 					}
-				}
 
-				return Err(McrEthConnectorError::from(err).into());
-			}
-		};
+					self.release_nonce(&sent_transactions).await;
+					return Err(McrEthConnectorError::from(err).into());
+				}
+			};
+			sent_transactions.push(*pending_transaction.tx_hash());
 
-		match pending_transaction.get_receipt().await {
-			// Transaction execution fail
-			Ok(transaction_receipt) if !transaction_receipt.status() => {
-				tracing::debug!(
-					"transaction_receipt.gas_used: {} / estimate_gas: {estimate_gas}",
-					transaction_receipt.gas_used
-				);
-				// Some valid Tx can abort cause of insufficient gas without consuming all its gas.
-				// Define a threshold a little less than estimated gas to detect them.
-				let tx_gas_consumption_threshold = estimate_gas - (estimate_gas * 10) / 100;
-				if transaction_receipt.gas_used >= tx_gas_consumption_threshold {
-					tracing::info!("Send commitment Transaction  fail because of insufficient gas, receipt:{transaction_receipt:?} ");
-					estimate_gas += (estimate_gas * 30) / 100;
+			let receipt = match tokio::time::timeout(
+				self.stuck_transaction_timeout,
+				pending_transaction.get_receipt(),
+			)
+			.await
+			{
+				Ok(receipt) => receipt,
+				Err(_) => {
+					// Speed the stuck transaction up by replacing it with higher fees.
+					warn!(
+						"Transaction {:?} with nonce {} is stuck, replacing it with higher fees",
+						sent_transactions.last(),
+						nonce
+					);
+					fees = self.fee_strategy.bump(fees)?;
 					continue;
-				} else {
+				}
+			};
+
+			match receipt {
+				// Transaction execution fail
+				Ok(transaction_receipt) if !transaction_receipt.status() => {
+					tracing::debug!(
+						"transaction_receipt.gas_used: {} / estimate_gas: {estimate_gas}",
+						transaction_receipt.gas_used
+					);
+					// Some valid Tx can abort cause of insufficient gas without consuming all its gas.
+					// Define a threshold a little less than estimated gas to detect them.
+					let tx_gas_consumption_threshold = estimate_gas - (estimate_gas * 10) / 100;
+					if transaction_receipt.gas_used >= tx_gas_consumption_threshold {
+						tracing::info!("Send commitment Transaction  fail because of insufficient gas, receipt:{transaction_receipt:?} ");
+						estimate_gas += (estimate_gas * 30) / 100;
+						// the failed transaction consumed its nonce
+						sent_transactions.clear();
+						nonce = self.reserve_nonce(provider).await?;
+						continue;
+					} else {
+						return Err(McrEthConnectorError::RpcTransactionExecution(format!(
+							"Send commitment Transaction fail, abort Transaction, receipt:{transaction_receipt:?}"
+						))
+						.into());
+					}
+				}
+				Ok(_) => return Ok(()),
+				Err(err) => {
+					return Err(
+						McrEthConnectorError::RpcTransactionExecution(err.to_string()).into()
+					)
+				}
+			};
+		}
+
+		self.release_nonce(&sent_transactions).await;
+		//Max retry exceed
+		Err(McrEthConnectorError::RpcTransactionExecution(
+			"Send commitment Transaction fail because of exceed max retry".to_string(),
+		)
+		.into())
+	}
+
+	async fn reserve_nonce<P: Provider<T, Ethereum>, T: Transport + Clone>(
+		&self,
+		provider: &P,
+	) -> Result<u64, anyhow::Error> {
+		self.nonce_manager
+			.next_nonce(|| async {
+				provider
+					.get_transaction_count(self.signer_address)
+					.pending()
+					.await
+					.map_err(|e| anyhow::anyhow!("Failed to get the signer nonce: {:?}", e))
+			})
+			.await
+	}
+
+	/// Gives up on the current nonce.
+	///
+	/// If no transaction was sent with it, the nonce is unused and the following
+	/// transactions would be stuck behind it, so the nonce is read from the chain again.
+	async fn release_nonce(&self, sent_transactions: &[TxHash]) {
+		if sent_transactions.is_empty() {
+			self.nonce_manager.reset().await;
+		}
+	}
+
+	/// Looks up which of the transactions sent with a nonce has been included.
+	async fn check_sent_transactions<P: Provider<T, Ethereum>, T: Transport + Clone>(
+		&self,
+		provider: &P,
+		sent_transactions: &[TxHash],
+	) -> Result<(), anyhow::Error> {
+		for hash in sent_transactions {
+			let receipt: Option<TransactionReceipt> = provider
+				.get_transaction_receipt(*hash)
+				.await
+				.map_err(|e| McrEthConnectorError::RpcTransactionExecution(e.to_string()))?;
+			match receipt {
+				Some(receipt) if receipt.status() => return Ok(()),
+				Some(receipt) => {
 					return Err(McrEthConnectorError::RpcTransactionExecution(format!(
-						"Send commitment Transaction fail, abort Transaction, receipt:{transaction_receipt:?}"
+						"Send commitment Transaction fail, abort Transaction, receipt:{receipt:?}"
 					))
-					.into());
+					.into())
 				}
+				None => {}
 			}
-			Ok(_) => return Ok(()),
-			Err(err) => {
-				return Err(McrEthConnectorError::RpcTransactionExecution(err.to_string()).into())
-			}
-		};
+		}
+		Err(McrEthConnectorError::RpcTransactionExecution(
+			"Transaction nonce was used by another transaction".to_string(),
+		)
+		.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn strategy() -> CappedEip1559FeeStrategy {
+		CappedEip1559FeeStrategy {
+			max_fee_per_gas: 1_000,
+			max_priority_fee_per_gas: 100,
+			bump_percent: 20,
+		}
 	}
 
-	//Max retry exceed
-	Err(McrEthConnectorError::RpcTransactionExecution(
-		"Send commitment Transaction fail because of exceed max retry".to_string(),
-	)
-	.into())
+	#[test]
+	fn test_initial_fees_are_capped() {
+		let fees = strategy()
+			.initial_fees(Eip1559Fees { max_fee_per_gas: 2_000, max_priority_fee_per_gas: 500 });
+		assert_eq!(fees, Eip1559Fees { max_fee_per_gas: 1_000, max_priority_fee_per_gas: 100 });
+
+		let fees = strategy()
+			.initial_fees(Eip1559Fees { max_fee_per_gas: 50, max_priority_fee_per_gas: 80 });
+		assert_eq!(fees, Eip1559Fees { max_fee_per_gas: 50, max_priority_fee_per_gas: 50 });
+	}
+
+	#[test]
+	fn test_bump_until_cap() -> Result<(), anyhow::Error> {
+		let strategy = strategy();
+		let fees =
+			strategy.bump(Eip1559Fees { max_fee_per_gas: 500, max_priority_fee_per_gas: 50 })?;
+		assert_eq!(fees, Eip1559Fees { max_fee_per_gas: 600, max_priority_fee_per_gas: 60 });
+
+		// the bump is limited by the caps, and can't go on once they are reached
+		let fees =
+			strategy.bump(Eip1559Fees { max_fee_per_gas: 900, max_priority_fee_per_gas: 90 })?;
+		assert_eq!(fees, Eip1559Fees { max_fee_per_gas: 1_000, max_priority_fee_per_gas: 100 });
+		assert!(strategy.bump(fees).is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_nonce_manager() -> Result<(), anyhow::Error> {
+		let nonce_manager = NonceManager::new();
+		assert_eq!(nonce_manager.next_nonce(|| async { Ok(5) }).await?, 5);
+		// the chain is only read once
+		assert_eq!(nonce_manager.next_nonce(|| async { Ok(0) }).await?, 6);
+		assert_eq!(nonce_manager.next_nonce(|| async { Ok(0) }).await?, 7);
+
+		nonce_manager.reset().await;
+		assert_eq!(nonce_manager.next_nonce(|| async { Ok(9) }).await?, 9);
+		assert!(NonceManager::new()
+			.next_nonce(|| async { Err(anyhow::anyhow!("no connection")) })
+			.await
+			.is_err());
+
+		Ok(())
+	}
 }
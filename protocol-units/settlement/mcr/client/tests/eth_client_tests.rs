@@ -0,0 +1,188 @@
+use alloy_primitives::U256;
+use anyhow::Context;
+use mcr_settlement_client::{
+	McrSettlementClient, McrSettlementClientOperations, McrStakingClientOperations,
+};
+use mcr_settlement_test_evm::{GenesisConfig, TestEvm, ATTESTER_PRIVATE_KEYS};
+use movement_types::block::{BlockCommitment, Commitment, Id};
+use std::time::Duration;
+use tokio_stream::{Stream, StreamExt};
+
+// The event filters of a local node are polled every 250ms.
+const EVENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn next_accepted<S>(stream: &mut S) -> Result<BlockCommitment, anyhow::Error>
+where
+	S: Stream<Item = Result<BlockCommitment, anyhow::Error>> + Unpin,
+{
+	tokio::time::timeout(EVENT_TIMEOUT, stream.next())
+		.await
+		.context("No accepted commitment received")?
+		.context("Accepted commitment stream closed")?
+}
+
+fn commitment(height: u64) -> BlockCommitment {
+	BlockCommitment::new(
+		height,
+		Id::new([height as u8; 32]),
+		Commitment::new([height as u8 + 100; 32]),
+	)
+}
+
+#[tokio::test]
+async fn test_post_block_commitment() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let evm = TestEvm::start().await?;
+	let alice =
+		McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[0])).await?;
+	let bob = McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[1])).await?;
+	let mut stream = alice.stream_block_commitments().await?;
+
+	// half of the stake is not a supermajority
+	alice.post_block_commitment(commitment(1)).await?;
+	assert_eq!(alice.get_posted_commitment_at_height(1).await?, Some(commitment(1)));
+	assert_eq!(bob.get_posted_commitment_at_height(1).await?, None);
+	assert!(tokio::time::timeout(Duration::from_secs(1), stream.next()).await.is_err());
+	assert_eq!(alice.get_commitment_at_height(1).await?, None);
+
+	bob.post_block_commitment(commitment(1)).await?;
+	assert_eq!(next_accepted(&mut stream).await?, commitment(1));
+	assert_eq!(alice.get_commitment_at_height(1).await?, Some(commitment(1)));
+	assert_eq!(alice.get_max_tolerable_block_height().await?, 11);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_post_block_commitment_batch() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let evm = TestEvm::start().await?;
+	let alice =
+		McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[0])).await?;
+	let bob = McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[1])).await?;
+	let mut stream = bob.stream_block_commitments().await?;
+
+	let batch: Vec<_> = (1..=3).map(commitment).collect();
+	alice.post_block_commitment_batch(batch.clone()).await?;
+	bob.post_block_commitment_batch(batch.clone()).await?;
+
+	// the last commitment of the batch accepts all of them, in order
+	for expected in batch {
+		assert_eq!(next_accepted(&mut stream).await?, expected);
+	}
+	assert_eq!(bob.get_commitment_at_height(3).await?, Some(commitment(3)));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_stream_recovers_from_disconnection() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let mut evm = TestEvm::start().await?;
+	let alice =
+		McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[0])).await?;
+	let bob = McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[1])).await?;
+	let mut stream = alice.stream_block_commitments().await?;
+
+	alice.post_block_commitment(commitment(1)).await?;
+	bob.post_block_commitment(commitment(1)).await?;
+	assert_eq!(next_accepted(&mut stream).await?, commitment(1));
+
+	// the commitments accepted while the stream reconnects are recovered, once
+	evm.restart().await?;
+	let batch: Vec<_> = (2..=3).map(commitment).collect();
+	alice.post_block_commitment_batch(batch.clone()).await?;
+	bob.post_block_commitment_batch(batch.clone()).await?;
+	for expected in batch {
+		assert_eq!(next_accepted(&mut stream).await?, expected);
+	}
+	assert!(tokio::time::timeout(Duration::from_secs(1), stream.next()).await.is_err());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_force_block_commitment() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let evm = TestEvm::start().await?;
+	let alice =
+		McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[0])).await?;
+
+	alice.force_block_commitment(commitment(5)).await?;
+	assert_eq!(alice.get_commitment_at_height(5).await?, Some(commitment(5)));
+	assert_eq!(alice.get_max_tolerable_block_height().await?, 15);
+
+	// forcing a commitment discards the previously accepted ones
+	alice.force_block_commitment(commitment(7)).await?;
+	assert_eq!(alice.get_commitment_at_height(5).await?, None);
+	assert_eq!(alice.get_commitment_at_height(7).await?, Some(commitment(7)));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_validator_staking() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let evm = TestEvm::start().await?;
+	let alice =
+		McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[0])).await?;
+	let bob = McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[1])).await?;
+
+	let validators = alice.get_validator_set().await?;
+	assert_eq!(
+		validators,
+		vec![(alice.signer_address, U256::from(100)), (bob.signer_address, U256::from(100))]
+	);
+
+	alice.unstake(U256::from(40)).await?;
+	let status = bob.get_validator_status(alice.signer_address).await?;
+	assert!(status.whitelisted);
+	assert_eq!(status.current_epoch, bob.get_current_epoch().await?);
+	assert_eq!(status.current_stake, U256::from(100));
+	assert_eq!(status.pending_unstake, U256::from(40));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_validator_rewards_and_slashing() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	// a short epoch, rolled over by the commitments of the test
+	let genesis = GenesisConfig { epoch_duration: 10, ..GenesisConfig::default() };
+	let evm = TestEvm::start_with_genesis(genesis).await?;
+	let alice =
+		McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[0])).await?;
+	let bob = McrSettlementClient::build_with_config(&evm.config(ATTESTER_PRIVATE_KEYS[1])).await?;
+	assert_eq!(alice.get_epoch_stakes(alice.signer_address).await?, vec![]);
+
+	// each transaction is mined one second later, so the commitments move the epochs on
+	alice.unstake(U256::from(40)).await?;
+	let mut unstaked_epoch = None;
+	for height in 1..=40 {
+		alice.post_block_commitment(commitment(height)).await?;
+		bob.post_block_commitment(commitment(height)).await?;
+		let epoch_stakes = bob.get_epoch_stakes(alice.signer_address).await?;
+		unstaked_epoch =
+			epoch_stakes.into_iter().find(|epoch_stake| !epoch_stake.unstake.is_zero());
+		if unstaked_epoch.is_some() {
+			break;
+		}
+	}
+	let unstaked_epoch = unstaked_epoch.context("The unstake was never paid out")?;
+	assert_eq!(unstaked_epoch.starting_stake, U256::from(100));
+	assert_eq!(unstaked_epoch.stake, U256::from(100));
+	assert_eq!(unstaked_epoch.unstake, U256::from(40));
+	assert_eq!(unstaked_epoch.slashed(), U256::ZERO);
+
+	// the stake paid out is not carried over, and nothing is slashed or rewarded
+	for epoch_stake in bob.get_epoch_stakes(bob.signer_address).await? {
+		assert_eq!(epoch_stake.starting_stake, U256::from(100));
+		assert_eq!(epoch_stake.slashed(), U256::ZERO);
+	}
+	let status = bob.get_validator_status(alice.signer_address).await?;
+	assert_eq!(status.current_epoch, unstaked_epoch.epoch + 1);
+	assert_eq!(status.current_stake, U256::from(60));
+	assert_eq!(alice.get_rewards(alice.signer_address).await?, U256::ZERO);
+
+	Ok(())
+}
@@ -0,0 +1,73 @@
+use godfig::env_short_default;
+use serde::{Deserialize, Serialize};
+
+/// Off-chain aggregation of the attesters' commitments,
+/// posted to the MCR contract by a single submitter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+	/// Address the attestation gRPC service listens on
+	#[serde(default = "default_aggregation_listen_address")]
+	pub aggregation_listen_address: String,
+
+	/// gRPC URLs of the attestation services of the other attesters
+	#[serde(default = "Vec::new")]
+	pub aggregation_peers: Vec<String>,
+
+	/// ETH addresses of the attesters whose attestations are aggregated
+	#[serde(default = "Vec::new")]
+	pub aggregation_attesters: Vec<String>,
+
+	/// ETH address of the attester posting the aggregates
+	#[serde(default = "default_aggregation_submitter")]
+	pub aggregation_submitter: String,
+
+	/// Number of attestations, including the submitter's own, needed to post an aggregate
+	#[serde(default = "default_aggregation_quorum")]
+	pub aggregation_quorum: usize,
+
+	/// Time to wait for an aggregate before posting the commitment directly, in milliseconds
+	#[serde(default = "default_aggregation_timeout")]
+	pub aggregation_timeout: u64,
+}
+
+env_short_default!(default_aggregation_listen_address, String, "0.0.0.0:30740");
+
+// The zero address: no attester submits the aggregates until the submitter is configured.
+env_short_default!(
+	default_aggregation_submitter,
+	String,
+	"0x0000000000000000000000000000000000000000"
+);
+
+env_short_default!(default_aggregation_quorum, usize, 2 as usize);
+
+env_short_default!(default_aggregation_timeout, u64, 4000 as u64);
+
+pub fn maybe_aggregation() -> Option<Config> {
+	match std::env::var("MAYBE_AGGREGATE_MCR") {
+		Ok(str_value) => {
+			// if it parses as true then we want to aggregate under the default config
+			let bool_value = str_value.parse::<bool>().unwrap_or(false);
+
+			if bool_value {
+				Some(Config::default())
+			} else {
+				None
+			}
+		}
+		Err(_) => None,
+	}
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Config {
+			aggregation_listen_address: default_aggregation_listen_address(),
+			aggregation_peers: Vec::new(),
+			aggregation_attesters: Vec::new(),
+			aggregation_submitter: default_aggregation_submitter(),
+			aggregation_quorum: default_aggregation_quorum(),
+			aggregation_timeout: default_aggregation_timeout(),
+		}
+	}
+}
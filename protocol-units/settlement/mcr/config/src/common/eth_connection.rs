@@ -24,6 +24,13 @@ pub struct Config {
 
 	#[serde(default)]
 	pub eth_chain_id: u64,
+
+	/// Delay before the first attempt to reconnect the WebSocket, in milliseconds
+	#[serde(default = "default_eth_ws_reconnect_backoff")]
+	pub eth_ws_reconnect_backoff: u64,
+	/// Cap on the delay between attempts to reconnect the WebSocket, in milliseconds
+	#[serde(default = "default_eth_ws_reconnect_max_backoff")]
+	pub eth_ws_reconnect_max_backoff: u64,
 }
 
 env_default!(
@@ -70,6 +77,10 @@ env_default!(
 
 env_default!(default_eth_chain_id, "ETH_CHAIN_ID", u64, 0);
 
+env_default!(default_eth_ws_reconnect_backoff, "ETH_WS_RECONNECT_BACKOFF", u64, 500);
+
+env_default!(default_eth_ws_reconnect_max_backoff, "ETH_WS_RECONNECT_MAX_BACKOFF", u64, 30_000);
+
 impl Default for Config {
 	fn default() -> Self {
 		Config {
@@ -81,6 +92,8 @@ impl Default for Config {
 			eth_ws_connection_hostname: default_eth_ws_connection_hostname(),
 			eth_ws_connection_port: default_eth_ws_connection_port(),
 			eth_chain_id: default_eth_chain_id(),
+			eth_ws_reconnect_backoff: default_eth_ws_reconnect_backoff(),
+			eth_ws_reconnect_max_backoff: default_eth_ws_reconnect_max_backoff(),
 		}
 	}
 }
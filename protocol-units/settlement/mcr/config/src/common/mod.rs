@@ -1,3 +1,4 @@
+pub mod aggregation;
 pub mod deploy;
 pub mod eth_connection;
 pub mod settlement;
@@ -2,6 +2,7 @@ use alloy::signers::local::PrivateKeySigner;
 use godfig::env_default;
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::str::FromStr;
 
 const DEFAULT_MCR_CONTRACT_ADDRESS: &str = "0x5fc8d32690cc91d4c39d9d3abcbd16989f875707";
 
@@ -13,12 +14,52 @@ pub struct Config {
 	pub signer_private_key: String,
 	#[serde(default = "default_mcr_contract_address")]
 	pub mcr_contract_address: String,
+	/// The L1 block the MCR contract was deployed at, its events are queried from it.
+	#[serde(default = "default_mcr_deployment_block")]
+	pub mcr_deployment_block: u64,
 	#[serde(default = "default_settlement_super_block_size")]
 	pub settlement_super_block_size: u64,
+	/// What decides when executed blocks are settled.
+	#[serde(default = "default_settlement_slots")]
+	pub settlement_slots: SettlementSlots,
+	/// The length of a settlement slot: in seconds for wall-clock slots,
+	/// in L1 blocks for L1-block slots.
+	#[serde(default = "default_settlement_slot_length")]
+	pub settlement_slot_length: u64,
 	#[serde(default = "default_settlement_admin_mode")]
 	pub settlement_admin_mode: bool,
 }
 
+/// What decides when executed blocks are settled.
+///
+/// With slots, the latest executed block is settled at each slot boundary:
+/// - a slot in which no new block was executed is skipped, nothing is posted for it;
+/// - slot boundaries missed while the node was busy are coalesced,
+///   only the latest executed block is settled once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SettlementSlots {
+	/// Settle the blocks read at DA heights multiple of the super block size.
+	DaHeight,
+	/// Settle at the boundaries of wall-clock slots.
+	WallClock,
+	/// Settle at the boundaries of slots of L1 blocks.
+	L1Block,
+}
+
+impl FromStr for SettlementSlots {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"da-height" => Ok(SettlementSlots::DaHeight),
+			"wall-clock" => Ok(SettlementSlots::WallClock),
+			"l1-block" => Ok(SettlementSlots::L1Block),
+			_ => Err(anyhow::anyhow!("Unknown settlement slots: {}", s)),
+		}
+	}
+}
+
 pub fn default_signer_private_key() -> String {
 	let random_wallet = PrivateKeySigner::random();
 	let random_wallet_string = random_wallet.to_bytes().to_string();
@@ -32,10 +73,21 @@ env_default!(
 	DEFAULT_MCR_CONTRACT_ADDRESS.to_string()
 );
 
+env_default!(default_mcr_deployment_block, "MCR_DEPLOYMENT_BLOCK", u64, 0);
+
 env_default!(default_settlement_admin_mode, "MCR_SETTLEMENT_ADMIN_MODE", bool, false);
 
 env_default!(default_settlement_super_block_size, "MCR_SETTLEMENT_SUPER_BLOCK_SIZE", u64, 1);
 
+env_default!(
+	default_settlement_slots,
+	"MCR_SETTLEMENT_SLOTS",
+	SettlementSlots,
+	SettlementSlots::DaHeight
+);
+
+env_default!(default_settlement_slot_length, "MCR_SETTLEMENT_SLOT_LENGTH", u64, 12);
+
 pub fn default_should_settle() -> bool {
 	env::var("ETH_SIGNER_PRIVATE_KEY").is_ok()
 }
@@ -46,8 +98,11 @@ impl Default for Config {
 			should_settle: default_should_settle(),
 			signer_private_key: default_signer_private_key(),
 			mcr_contract_address: default_mcr_contract_address(),
+			mcr_deployment_block: default_mcr_deployment_block(),
 			settlement_admin_mode: default_settlement_admin_mode(),
 			settlement_super_block_size: default_settlement_super_block_size(),
+			settlement_slots: default_settlement_slots(),
+			settlement_slot_length: default_settlement_slot_length(),
 		}
 	}
 }
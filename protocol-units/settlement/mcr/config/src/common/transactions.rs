@@ -10,6 +10,18 @@ pub struct Config {
 	pub batch_timeout: u64,
 	#[serde(default = "default_transaction_send_retries")]
 	pub transaction_send_retries: u32,
+	/// Cap on the EIP-1559 max fee per gas, in wei
+	#[serde(default = "default_max_fee_per_gas")]
+	pub max_fee_per_gas: u128,
+	/// Cap on the EIP-1559 max priority fee per gas, in wei
+	#[serde(default = "default_max_priority_fee_per_gas")]
+	pub max_priority_fee_per_gas: u128,
+	/// Percentage by which fees are raised to replace an underpriced or stuck transaction
+	#[serde(default = "default_fee_bump_percent")]
+	pub fee_bump_percent: u64,
+	/// Time after which a pending transaction is considered stuck and sped up, in seconds
+	#[serde(default = "default_stuck_transaction_timeout")]
+	pub stuck_transaction_timeout: u64,
 }
 
 env_short_default!(default_gas_limit, u64, 10_000_000_000_000_000 as u64);
@@ -18,12 +30,26 @@ env_short_default!(default_batch_timeout, u64, 2000 as u64);
 
 env_short_default!(default_transaction_send_retries, u32, 10 as u32);
 
+// 500 gwei
+env_short_default!(default_max_fee_per_gas, u128, 500_000_000_000 as u128);
+
+// 10 gwei
+env_short_default!(default_max_priority_fee_per_gas, u128, 10_000_000_000 as u128);
+
+env_short_default!(default_fee_bump_percent, u64, 20 as u64);
+
+env_short_default!(default_stuck_transaction_timeout, u64, 60 as u64);
+
 impl Default for Config {
 	fn default() -> Self {
 		Config {
 			gas_limit: default_gas_limit(),
 			batch_timeout: default_batch_timeout(),
 			transaction_send_retries: default_transaction_send_retries(),
+			max_fee_per_gas: default_max_fee_per_gas(),
+			max_priority_fee_per_gas: default_max_priority_fee_per_gas(),
+			fee_bump_percent: default_fee_bump_percent(),
+			stuck_transaction_timeout: default_stuck_transaction_timeout(),
 		}
 	}
 }
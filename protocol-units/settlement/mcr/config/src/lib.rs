@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 pub mod common;
 
+use common::aggregation::maybe_aggregation;
 use common::deploy::maybe_deploy;
 use common::testing::maybe_testing;
 use godfig::env_short_default;
@@ -31,6 +32,10 @@ pub struct Config {
 	/// Optional testing config
 	#[serde(default = "maybe_testing")]
 	pub testing: Option<common::testing::Config>,
+
+	/// Optional off-chain aggregation of commitments config
+	#[serde(default = "maybe_aggregation")]
+	pub aggregation: Option<common::aggregation::Config>,
 }
 
 env_short_default!(maybe_run_local, bool, false);
@@ -62,6 +67,7 @@ impl Default for Config {
 			maybe_run_local: maybe_run_local(),
 			deploy: maybe_deploy(),
 			testing: maybe_testing(),
+			aggregation: maybe_aggregation(),
 		}
 	}
 }
@@ -9,6 +9,8 @@ import {MCRStorage} from "./MCRStorage.sol";
 import {BaseSettlement} from "./settlement/BaseSettlement.sol";
 import {IMCR} from "./interfaces/IMCR.sol";
 import "@openzeppelin/contracts/utils/ReentrancyGuard.sol";
+import {ECDSA} from "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
+import {MessageHashUtils} from "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
 
 contract MCR is Initializable, BaseSettlement, MCRStorage, IMCR {
 
@@ -353,6 +355,48 @@ contract MCR is Initializable, BaseSettlement, MCRStorage, IMCR {
         }
     }
 
+    /**
+     * @dev The digest an attester signs to attest to a block commitment off-chain.
+     * @dev It is bound to this contract and chain, so that attestations cannot be replayed elsewhere.
+     */
+    function attestationDigest(BlockCommitment memory blockCommitment) public view returns (bytes32) {
+        return MessageHashUtils.toEthSignedMessageHash(
+            keccak256(
+                abi.encode(
+                    address(this),
+                    block.chainid,
+                    blockCommitment.height,
+                    blockCommitment.commitment,
+                    blockCommitment.blockId
+                )
+            )
+        );
+    }
+
+    /**
+     * @dev Submits a block commitment for all the attesters whose signatures were aggregated off-chain.
+     * @dev Attesters that already committed at this height are skipped, so that an attester falling back to submitting directly does not fail the aggregate.
+     */
+    function submitAggregatedBlockCommitment(
+        BlockCommitment memory blockCommitment,
+        bytes[] memory signatures
+    ) public {
+        require(
+            openAttestationEnabled || hasRole(TRUSTED_ATTESTER, msg.sender),
+            "UNAUTHORIZED_BLOCK_COMMITMENT"
+        );
+        bytes32 digest = attestationDigest(blockCommitment);
+        for (uint256 i = 0; i < signatures.length; i++) {
+            address attester = ECDSA.recover(digest, signatures[i]);
+            require(
+                openAttestationEnabled || hasRole(TRUSTED_ATTESTER, attester),
+                "UNAUTHORIZED_BLOCK_COMMITMENT"
+            );
+            if (commitments[blockCommitment.height][attester].height != 0) continue;
+            submitBlockCommitmentForAttester(attester, blockCommitment);
+        }
+    }
+
     /**
      * @dev Accepts a block commitment.
      * @dev Under the current implementation this shares in recursion with the tickOnBlockHeight, so it should be reentrant.
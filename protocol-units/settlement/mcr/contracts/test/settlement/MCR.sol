@@ -405,4 +405,76 @@ contract MCRTest is Test, IMCR {
         mcr.forceLatestCommitment(badForcedCommitment);
 
     }
+    function signAttestation(
+        uint256 privateKey,
+        MCRStorage.BlockCommitment memory blockCommitment
+    ) internal view returns (bytes memory) {
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(privateKey, mcr.attestationDigest(blockCommitment));
+        return abi.encodePacked(r, s, v);
+    }
+
+    function testAggregatedAttestation() public {
+        // three well-funded signers
+        address payable alice = payable(vm.addr(1));
+        staking.whitelistAddress(alice);
+        moveToken.mint(alice, 100);
+        address payable bob = payable(vm.addr(2));
+        staking.whitelistAddress(bob);
+        moveToken.mint(bob, 100);
+        address payable carol = payable(vm.addr(3));
+        staking.whitelistAddress(carol);
+        moveToken.mint(carol, 100);
+
+        // have them participate in the genesis ceremony
+        vm.prank(alice);
+        moveToken.approve(address(staking), 100);
+        vm.prank(alice);
+        staking.stake(address(mcr), moveToken, 34);
+        vm.prank(bob);
+        moveToken.approve(address(staking), 100);
+        vm.prank(bob);
+        staking.stake(address(mcr), moveToken, 33);
+        vm.prank(carol);
+        moveToken.approve(address(staking), 100);
+        vm.prank(carol);
+        staking.stake(address(mcr), moveToken, 33);
+
+        // end the genesis ceremony
+        mcr.acceptGenesisCeremony();
+
+        MCRStorage.BlockCommitment memory bc1 = MCRStorage.BlockCommitment({
+            height: 1,
+            commitment: keccak256(abi.encodePacked(uint256(1), uint256(2), uint256(3))),
+            blockId: keccak256(abi.encodePacked(uint256(1), uint256(2), uint256(3)))
+        });
+
+        // bob already committed directly, his signature in the aggregate is skipped
+        vm.prank(bob);
+        mcr.submitBlockCommitment(bc1);
+
+        // alice submits the attestations of alice and bob in one transaction
+        bytes[] memory signatures = new bytes[](2);
+        signatures[0] = signAttestation(1, bc1);
+        signatures[1] = signAttestation(2, bc1);
+        vm.prank(alice);
+        mcr.submitAggregatedBlockCommitment(bc1, signatures);
+
+        MCRStorage.BlockCommitment memory retrievedCommitment = mcr.getAcceptedCommitmentAtBlockHeight(1);
+        assertEq(retrievedCommitment.commitment, bc1.commitment);
+        assertEq(retrievedCommitment.blockId, bc1.blockId);
+        assertEq(retrievedCommitment.height, 1);
+        assertEq(mcr.getValidatorCommitmentAtBlockHeight(1, alice).commitment, bc1.commitment);
+
+        // a signature for another commitment does not attest to this one
+        MCRStorage.BlockCommitment memory bc2 = MCRStorage.BlockCommitment({
+            height: 2,
+            commitment: keccak256(abi.encodePacked(uint256(2), uint256(3), uint256(4))),
+            blockId: keccak256(abi.encodePacked(uint256(2), uint256(3), uint256(4)))
+        });
+        signatures = new bytes[](1);
+        signatures[0] = signAttestation(3, bc1);
+        vm.prank(alice);
+        mcr.submitAggregatedBlockCommitment(bc2, signatures);
+        assertEq(mcr.getValidatorCommitmentAtBlockHeight(2, carol).height, 0);
+    }
 }
\ No newline at end of file
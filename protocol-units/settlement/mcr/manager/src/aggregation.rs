@@ -0,0 +1,31 @@
+use movement_types::block::BlockCommitment;
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+
+use std::time::Duration;
+
+/// Off-chain aggregation of the attesters' commitments.
+///
+/// Attesters exchange signed attestations to their commitments,
+/// and a single submitter posts them to the settlement contract in one transaction.
+#[async_trait]
+pub trait CommitmentAggregation {
+	/// Signs a commitment and sends the attestation to the other attesters.
+	async fn attest(&self, block_commitment: &BlockCommitment) -> Result<(), anyhow::Error>;
+
+	/// Whether this attester posts the aggregated commitments.
+	fn is_submitter(&self) -> bool;
+
+	/// Waits for a quorum of attestations to a commitment, returning their signatures.
+	///
+	/// Returns `None` if the quorum is not reached by the deadline.
+	async fn wait_for_aggregate(
+		&self,
+		block_commitment: &BlockCommitment,
+		deadline: Instant,
+	) -> Result<Option<Vec<Vec<u8>>>, anyhow::Error>;
+
+	/// How long to wait for a commitment to be aggregated before posting it directly.
+	fn timeout(&self) -> Duration;
+}
@@ -1,9 +1,13 @@
 use movement_types::block::{BlockCommitment, BlockCommitmentEvent};
 use tokio_stream::Stream;
 
+pub mod aggregation;
 mod manager;
+pub mod store;
 
+pub use aggregation::CommitmentAggregation;
 pub use manager::Manager as McrSettlementManager;
+pub use store::{MemorySettlementStore, PendingCommitment, SettlementStore};
 
 pub type CommitmentEventStream =
 	std::pin::Pin<Box<dyn Stream<Item = Result<BlockCommitmentEvent, anyhow::Error>> + Send>>;
@@ -1,8 +1,10 @@
+use crate::aggregation::CommitmentAggregation;
+use crate::store::{MemorySettlementStore, PendingCommitment, SettlementStore};
 use crate::{BlockCommitmentEvent, CommitmentEventStream, McrSettlementManagerOperations};
 
 use mcr_settlement_client::McrSettlementClientOperations;
 use mcr_settlement_config::Config;
-use movement_types::block::{BlockCommitment, BlockCommitmentRejectionReason};
+use movement_types::block::{BlockCommitment, BlockCommitmentRejectionReason, Commitment};
 
 use async_stream::stream;
 use async_trait::async_trait;
@@ -10,11 +12,17 @@ use futures::future::{self, Either};
 use tokio::sync::mpsc;
 use tokio::time;
 use tokio_stream::StreamExt;
+use tracing::{debug, info};
 
 use std::collections::BTreeMap;
 use std::mem;
 use std::time::Duration;
 
+/// How often the settlement contract is checked for an attestation posted by the submitter.
+const AGGREGATE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+type BoxedAggregation = Box<dyn CommitmentAggregation + Send + Sync>;
+
 /// Public handle for the MCR settlement manager.
 pub struct Manager {
 	sender: mpsc::Sender<BlockCommitment>,
@@ -26,13 +34,58 @@ impl Manager {
 	/// Returns the handle with the public API and the stream to receive commitment events.
 	/// The stream needs to be polled to drive the MCR settlement client and
 	/// process the commitments.
+	/// Pending commitments are only kept in memory.
 	pub fn new<C: McrSettlementClientOperations + Send + 'static>(
 		client: C,
 		config: &Config,
 	) -> (Self, CommitmentEventStream) {
+		Self::new_with_store(client, config, MemorySettlementStore::new())
+	}
+
+	/// Creates a new MCR settlement manager keeping its pending commitments in a store.
+	///
+	/// The pending commitments found in the store are restored when the event stream
+	/// is first polled, and those unknown to the settlement contract are posted again.
+	pub fn new_with_store<C, S>(
+		client: C,
+		config: &Config,
+		store: S,
+	) -> (Self, CommitmentEventStream)
+	where
+		C: McrSettlementClientOperations + Send + 'static,
+		S: SettlementStore + Send + Sync + 'static,
+	{
+		let batch_timeout = Duration::from_millis(config.transactions.batch_timeout);
+		let (sender, receiver) = mpsc::channel(16);
+		let event_stream = process_commitments(receiver, client, store, None, batch_timeout);
+		(Self { sender }, event_stream)
+	}
+
+	/// Creates a new MCR settlement manager aggregating the commitments off-chain.
+	///
+	/// Each batch of commitments is attested to the other attesters,
+	/// and posted in one transaction by the submitter.
+	/// The commitments not posted when the aggregation times out are posted directly.
+	pub fn new_with_aggregation<C, S, A>(
+		client: C,
+		config: &Config,
+		store: S,
+		aggregation: A,
+	) -> (Self, CommitmentEventStream)
+	where
+		C: McrSettlementClientOperations + Send + 'static,
+		S: SettlementStore + Send + Sync + 'static,
+		A: CommitmentAggregation + Send + Sync + 'static,
+	{
 		let batch_timeout = Duration::from_millis(config.transactions.batch_timeout);
 		let (sender, receiver) = mpsc::channel(16);
-		let event_stream = process_commitments(receiver, client, batch_timeout);
+		let event_stream = process_commitments(
+			receiver,
+			client,
+			store,
+			Some(Box::new(aggregation)),
+			batch_timeout,
+		);
 		(Self { sender }, event_stream)
 	}
 }
@@ -48,22 +101,51 @@ impl McrSettlementManagerOperations for Manager {
 	}
 }
 
-fn process_commitments<C: McrSettlementClientOperations + Send + 'static>(
+fn process_commitments<C, S>(
 	mut receiver: mpsc::Receiver<BlockCommitment>,
 	client: C,
+	store: S,
+	aggregation: Option<BoxedAggregation>,
 	batch_timeout: Duration,
-) -> CommitmentEventStream {
+) -> CommitmentEventStream
+where
+	C: McrSettlementClientOperations + Send + 'static,
+	S: SettlementStore + Send + Sync + 'static,
+{
 	// Can't mix try_stream! and select!, see https://github.com/tokio-rs/async-stream/issues/63
 	Box::pin(stream! {
 		let mut settlement_stream = client.stream_block_commitments().await?;
 		let mut max_height = client.get_max_tolerable_block_height().await?;
 		let mut ahead_of_settlement = false;
-		let mut commitments_to_settle = BTreeMap::new();
-		let mut batch_acc = Vec::new();
 		let mut batch_ready = Either::Left(future::pending::<()>());
+
+		// Restore the commitments left pending by a previous run.
+		let (settled_events, mut commitments_to_settle, mut batch_acc) =
+			match restore_pending_commitments(&client, &store).await {
+				Ok(restored) => restored,
+				Err(e) => {
+					yield Err(e);
+					return;
+				}
+			};
+		for event in settled_events {
+			yield Ok(event);
+		}
+		if let Some(last) = batch_acc.last() {
+			if last.height() > max_height {
+				ahead_of_settlement = true;
+			}
+			batch_ready = Either::Right(Box::pin(time::sleep(batch_timeout)));
+		}
+
 		loop {
 			tokio::select! {
 				Some(block_commitment) = receiver.recv(), if !ahead_of_settlement => {
+					let pending = PendingCommitment { commitment: block_commitment.clone(), posted: false };
+					if let Err(e) = store.put_pending_commitments(vec![pending]).await {
+						yield Err(e);
+						break;
+					}
 					commitments_to_settle.insert(
 						block_commitment.height(),
 						block_commitment.commitment().clone(),
@@ -74,7 +156,7 @@ fn process_commitments<C: McrSettlementClientOperations + Send + 'static>(
 						// and pause reading from input.
 						ahead_of_settlement = true;
 						let batch = mem::replace(&mut batch_acc, Vec::new());
-						if let Err(e) = client.post_block_commitment_batch(batch).await {
+						if let Err(e) = post_batch(&client, &store, aggregation.as_deref(), batch).await {
 							yield Err(e);
 							break;
 						}
@@ -88,7 +170,7 @@ fn process_commitments<C: McrSettlementClientOperations + Send + 'static>(
 				_ = &mut batch_ready => {
 					// Batch timeout has expired, post the commitments we have now
 					let batch = mem::replace(&mut batch_acc, Vec::new());
-					if let Err(e) = client.post_block_commitment_batch(batch).await {
+					if let Err(e) = post_batch(&client, &store, aggregation.as_deref(), batch).await {
 						yield Err(e);
 						break;
 					}
@@ -105,25 +187,55 @@ fn process_commitments<C: McrSettlementClientOperations + Send + 'static>(
 					};
 
 					let height = settled_commitment.height();
+
+					// Settlement has moved past the pending commitments below this height,
+					// catch up on them by querying what was accepted at these heights.
+					let mut skipped = commitments_to_settle.split_off(&height);
+					mem::swap(&mut skipped, &mut commitments_to_settle);
+					let fell_behind = !skipped.is_empty();
+					if fell_behind {
+						let skipped_heights = skipped.keys().copied().collect();
+						match catch_up_on_settlement(&client, skipped).await {
+							Ok(events) => {
+								for event in events {
+									yield Ok(event);
+								}
+							}
+							Err(e) => {
+								yield Err(e);
+								break;
+							}
+						}
+						if let Err(e) = store.remove_pending_commitments(skipped_heights).await {
+							yield Err(e);
+							break;
+						}
+						// Don't post commitments for heights that are already settled.
+						batch_acc.retain(|commitment| commitment.height() > height);
+						if batch_acc.is_empty() {
+							batch_ready = Either::Left(future::pending::<()>());
+						}
+					}
+
 					if let Some(commitment) = commitments_to_settle.remove(&height) {
+						if let Err(e) = store.remove_pending_commitments(vec![height]).await {
+							yield Err(e);
+							break;
+						}
 						let event = if commitment == settled_commitment.commitment() {
 							BlockCommitmentEvent::Accepted(settled_commitment)
 						} else {
 							BlockCommitmentEvent::Rejected {
 								height,
 								reason: BlockCommitmentRejectionReason::InvalidCommitment,
+								accepted: Some(settled_commitment),
 							}
 						};
 						yield Ok(event);
-					} else if let Some((&lh, _)) = commitments_to_settle.last_key_value() {
-						if lh < height {
-							// Settlement has left some commitments behind, but the client could
-							// deliver them of order?
-							todo!("Handle falling behind on settlement")
-						}
 					}
 					// Remove back-pressure if we can proceed settling new blocks.
-					if ahead_of_settlement {
+					// After falling behind, the max tolerable height is resynced with the client.
+					if ahead_of_settlement || fell_behind {
 						let new_max_height = match client.get_max_tolerable_block_height().await {
 							Ok(h) => h,
 							Err(e) => {
@@ -132,9 +244,11 @@ fn process_commitments<C: McrSettlementClientOperations + Send + 'static>(
 							}
 						};
 						if new_max_height > max_height {
-							max_height = new_max_height;
 							ahead_of_settlement = false;
 						}
+						if new_max_height > max_height || fell_behind {
+							max_height = new_max_height;
+						}
 					}
 				}
 				else => break
@@ -143,11 +257,211 @@ fn process_commitments<C: McrSettlementClientOperations + Send + 'static>(
 	})
 }
 
+/// Posts a batch of commitments and records them as posted in the store.
+async fn post_batch<C, S>(
+	client: &C,
+	store: &S,
+	aggregation: Option<&(dyn CommitmentAggregation + Send + Sync)>,
+	batch: Vec<BlockCommitment>,
+) -> Result<(), anyhow::Error>
+where
+	C: McrSettlementClientOperations,
+	S: SettlementStore,
+{
+	if batch.is_empty() {
+		return Ok(());
+	}
+	let posted = batch
+		.iter()
+		.map(|commitment| PendingCommitment { commitment: commitment.clone(), posted: true })
+		.collect();
+	match aggregation {
+		Some(aggregation) => post_aggregated_batch(client, aggregation, batch).await?,
+		None => client.post_block_commitment_batch(batch).await?,
+	}
+	store.put_pending_commitments(posted).await
+}
+
+/// Posts a batch of commitments through off-chain aggregation.
+///
+/// The submitter posts the aggregate of each commitment reaching a quorum,
+/// the other attesters wait for their attestation to be posted by the submitter.
+/// The commitments not posted when the aggregation times out are posted directly.
+async fn post_aggregated_batch<C>(
+	client: &C,
+	aggregation: &(dyn CommitmentAggregation + Send + Sync),
+	batch: Vec<BlockCommitment>,
+) -> Result<(), anyhow::Error>
+where
+	C: McrSettlementClientOperations,
+{
+	let deadline = time::Instant::now() + aggregation.timeout();
+	for commitment in batch.iter() {
+		aggregation.attest(commitment).await?;
+	}
+
+	let mut direct = Vec::new();
+	for commitment in batch {
+		let posted = if aggregation.is_submitter() {
+			match aggregation.wait_for_aggregate(&commitment, deadline).await? {
+				Some(signatures) => {
+					client.post_aggregated_block_commitment(commitment.clone(), signatures).await?;
+					true
+				}
+				None => false,
+			}
+		} else {
+			wait_for_posted_commitment(client, &commitment, deadline).await?
+		};
+		if !posted {
+			info!("Aggregation timed out at height {}, posting directly", commitment.height());
+			direct.push(commitment);
+		}
+	}
+	if !direct.is_empty() {
+		client.post_block_commitment_batch(direct).await?;
+	}
+	Ok(())
+}
+
+/// Waits for the commitment of this attester to be posted by the submitter.
+///
+/// Returns whether it was posted by the deadline.
+async fn wait_for_posted_commitment<C: McrSettlementClientOperations>(
+	client: &C,
+	commitment: &BlockCommitment,
+	deadline: time::Instant,
+) -> Result<bool, anyhow::Error> {
+	loop {
+		if client.get_posted_commitment_at_height(commitment.height()).await?.is_some() {
+			return Ok(true);
+		}
+		if time::Instant::now() >= deadline {
+			return Ok(false);
+		}
+		time::sleep(AGGREGATE_POLL_INTERVAL).await;
+	}
+}
+
+/// Restores the pending commitments from the store.
+///
+/// Returns the events for the commitments settled while the manager was not running,
+/// the commitments still to settle, and the commitments to post again because
+/// the settlement contract does not know about them.
+async fn restore_pending_commitments<C, S>(
+	client: &C,
+	store: &S,
+) -> Result<
+	(Vec<BlockCommitmentEvent>, BTreeMap<u64, Commitment>, Vec<BlockCommitment>),
+	anyhow::Error,
+>
+where
+	C: McrSettlementClientOperations,
+	S: SettlementStore,
+{
+	let mut settled_events = Vec::new();
+	let mut settled_heights = Vec::new();
+	let mut commitments_to_settle = BTreeMap::new();
+	let mut to_post = Vec::new();
+	for pending in store.load_pending_commitments().await? {
+		let height = pending.commitment.height();
+		if let Some(accepted) = client.get_commitment_at_height(height).await? {
+			let event = if accepted.commitment() == pending.commitment.commitment() {
+				BlockCommitmentEvent::Accepted(accepted)
+			} else {
+				BlockCommitmentEvent::Rejected {
+					height,
+					reason: BlockCommitmentRejectionReason::InvalidCommitment,
+					accepted: Some(accepted),
+				}
+			};
+			settled_events.push(event);
+			settled_heights.push(height);
+			continue;
+		}
+		if !pending.posted || client.get_posted_commitment_at_height(height).await?.is_none() {
+			to_post.push(pending.commitment.clone());
+		}
+		commitments_to_settle.insert(height, pending.commitment.commitment());
+	}
+	if !settled_heights.is_empty() {
+		store.remove_pending_commitments(settled_heights).await?;
+	}
+	Ok((settled_events, commitments_to_settle, to_post))
+}
+
+/// Resolves the pending commitments at heights that settlement has moved past.
+///
+/// The commitment accepted at each of these heights is fetched from the client.
+/// No event is emitted for a height without an accepted commitment: settlement skipped it,
+/// so there is nothing the local commitment could diverge from.
+async fn catch_up_on_settlement<C: McrSettlementClientOperations>(
+	client: &C,
+	skipped: BTreeMap<u64, Commitment>,
+) -> Result<Vec<BlockCommitmentEvent>, anyhow::Error> {
+	let mut events = Vec::with_capacity(skipped.len());
+	for (height, commitment) in skipped {
+		let event = match client.get_commitment_at_height(height).await? {
+			Some(accepted) if accepted.commitment() == commitment => {
+				BlockCommitmentEvent::Accepted(accepted)
+			}
+			Some(accepted) => BlockCommitmentEvent::Rejected {
+				height,
+				reason: BlockCommitmentRejectionReason::InvalidCommitment,
+				accepted: Some(accepted),
+			},
+			None => {
+				debug!("No commitment accepted at skipped height {}", height);
+				continue;
+			}
+		};
+		events.push(event);
+	}
+	Ok(events)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use mcr_settlement_client::mock::McrSettlementClient;
-	use movement_types::block::{BlockCommitment, Commitment};
+	use std::sync::{Arc, Mutex};
+
+	/// Aggregation between attesters that all attest to the same commitments.
+	#[derive(Clone, Default)]
+	struct MockAggregation {
+		submitter: bool,
+		quorum_reached: bool,
+		attested: Arc<Mutex<Vec<u64>>>,
+	}
+
+	#[async_trait]
+	impl CommitmentAggregation for MockAggregation {
+		async fn attest(&self, block_commitment: &BlockCommitment) -> Result<(), anyhow::Error> {
+			self.attested.lock().unwrap().push(block_commitment.height());
+			Ok(())
+		}
+
+		fn is_submitter(&self) -> bool {
+			self.submitter
+		}
+
+		async fn wait_for_aggregate(
+			&self,
+			_block_commitment: &BlockCommitment,
+			deadline: time::Instant,
+		) -> Result<Option<Vec<Vec<u8>>>, anyhow::Error> {
+			if self.quorum_reached {
+				Ok(Some(vec![vec![0; 65]]))
+			} else {
+				time::sleep_until(deadline).await;
+				Ok(None)
+			}
+		}
+
+		fn timeout(&self) -> Duration {
+			Duration::from_millis(100)
+		}
+	}
 
 	#[tokio::test]
 	async fn test_block_commitment_accepted() -> Result<(), anyhow::Error> {
@@ -191,6 +505,11 @@ mod tests {
 			BlockCommitmentEvent::Rejected {
 				height: 1,
 				reason: BlockCommitmentRejectionReason::InvalidCommitment,
+				accepted: Some(BlockCommitment::new(
+					1,
+					Default::default(),
+					Commitment::new([3; 32]),
+				)),
 			}
 		);
 		Ok(())
@@ -267,4 +586,211 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn test_falling_behind_on_settlement() -> Result<(), anyhow::Error> {
+		let mut config = Config::default();
+		config.transactions.batch_timeout = 100;
+		let client = McrSettlementClient::new();
+		client.pause_after(1).await;
+		client
+			.override_block_commitment(BlockCommitment::new(
+				3,
+				Default::default(),
+				Commitment::new([0; 32]),
+			))
+			.await;
+		let (manager, mut event_stream) = Manager::new(client.clone(), &config);
+
+		let commitments: Vec<_> = (1..=4)
+			.map(|height| {
+				BlockCommitment::new(
+					height,
+					Default::default(),
+					Commitment::new([height as u8; 32]),
+				)
+			})
+			.collect();
+		for commitment in commitments.iter() {
+			manager.post_block_commitment(commitment.clone()).await?;
+		}
+
+		let event = event_stream.next().await.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitments[0].clone()));
+
+		// Settlement skips heights 2 and 3, the manager catches up on them.
+		client.resume_from(4).await;
+		let event = event_stream.next().await.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitments[1].clone()));
+		let event = event_stream.next().await.expect("stream has ended")?;
+		assert_eq!(
+			event,
+			BlockCommitmentEvent::Rejected {
+				height: 3,
+				reason: BlockCommitmentRejectionReason::InvalidCommitment,
+				accepted: Some(BlockCommitment::new(
+					3,
+					Default::default(),
+					Commitment::new([0; 32]),
+				)),
+			}
+		);
+		let event = event_stream.next().await.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitments[3].clone()));
+
+		// The manager keeps settling new commitments.
+		let commitment5 = BlockCommitment::new(5, Default::default(), Commitment::new([5; 32]));
+		manager.post_block_commitment(commitment5.clone()).await?;
+		let event = time::timeout(Duration::from_secs(2), event_stream.next())
+			.await
+			.expect("no timeout")
+			.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitment5));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_catch_up_on_missing_settlement() -> Result<(), anyhow::Error> {
+		let client = McrSettlementClient::new();
+		let commitment = BlockCommitment::new(1, Default::default(), Commitment::new([1; 32]));
+		client.post_block_commitment(commitment.clone()).await?;
+
+		let skipped = BTreeMap::from([(1, commitment.commitment()), (2, Commitment::new([2; 32]))]);
+		let events = catch_up_on_settlement(&client, skipped).await?;
+		// no commitment was accepted at height 2, so nothing is reported for it
+		assert_eq!(events, vec![BlockCommitmentEvent::Accepted(commitment)]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_restore_pending_commitments() -> Result<(), anyhow::Error> {
+		let mut config = Config::default();
+		config.transactions.batch_timeout = 100;
+		let client = McrSettlementClient::new();
+		let store = MemorySettlementStore::new();
+
+		let commitments: Vec<_> = (1..=3)
+			.map(|height| {
+				BlockCommitment::new(
+					height,
+					Default::default(),
+					Commitment::new([height as u8; 32]),
+				)
+			})
+			.collect();
+		// Left pending by a previous run: 1 was posted but the L1 does not know about it,
+		// 2 was never posted, and 3 was accepted while the node was down.
+		store
+			.put_pending_commitments(vec![
+				PendingCommitment { commitment: commitments[0].clone(), posted: true },
+				PendingCommitment { commitment: commitments[1].clone(), posted: false },
+				PendingCommitment { commitment: commitments[2].clone(), posted: true },
+			])
+			.await?;
+		client.override_block_commitment(commitments[2].clone()).await;
+
+		let (_manager, mut event_stream) =
+			Manager::new_with_store(client.clone(), &config, store.clone());
+
+		let event = event_stream.next().await.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitments[2].clone()));
+		let event = time::timeout(Duration::from_secs(2), event_stream.next())
+			.await
+			.expect("no timeout")
+			.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitments[0].clone()));
+		let event = event_stream.next().await.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitments[1].clone()));
+
+		assert!(store.load_pending_commitments().await?.is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_pending_commitments_are_stored() -> Result<(), anyhow::Error> {
+		let config = Config::default();
+		let mut client = McrSettlementClient::new();
+		client.block_lead_tolerance = 1;
+		client.pause_after(1).await;
+		let store = MemorySettlementStore::new();
+		let (manager, mut event_stream) =
+			Manager::new_with_store(client.clone(), &config, store.clone());
+
+		let commitment1 = BlockCommitment::new(1, Default::default(), Commitment::new([1; 32]));
+		manager.post_block_commitment(commitment1.clone()).await?;
+		let commitment2 = BlockCommitment::new(2, Default::default(), Commitment::new([2; 32]));
+		manager.post_block_commitment(commitment2.clone()).await?;
+
+		let event = event_stream.next().await.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitment1));
+
+		// The second commitment is held back and waits in the store.
+		assert_eq!(
+			store.load_pending_commitments().await?,
+			vec![PendingCommitment { commitment: commitment2, posted: false }]
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_aggregated_commitments_posted() -> Result<(), anyhow::Error> {
+		let mut config = Config::default();
+		config.transactions.batch_timeout = 100;
+		let client = McrSettlementClient::new();
+		let aggregation =
+			MockAggregation { submitter: true, quorum_reached: true, ..Default::default() };
+		let (manager, mut event_stream) = Manager::new_with_aggregation(
+			client.clone(),
+			&config,
+			MemorySettlementStore::new(),
+			aggregation.clone(),
+		);
+
+		let commitment1 = BlockCommitment::new(1, Default::default(), Commitment::new([1; 32]));
+		manager.post_block_commitment(commitment1.clone()).await?;
+		let commitment2 = BlockCommitment::new(2, Default::default(), Commitment::new([2; 32]));
+		manager.post_block_commitment(commitment2.clone()).await?;
+
+		let event = time::timeout(Duration::from_secs(2), event_stream.next())
+			.await
+			.expect("no timeout")
+			.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitment1));
+		let event = event_stream.next().await.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitment2));
+		assert_eq!(*aggregation.attested.lock().unwrap(), vec![1, 2]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_aggregation_timeout_posts_directly() -> Result<(), anyhow::Error> {
+		let mut config = Config::default();
+		config.transactions.batch_timeout = 100;
+		let client = McrSettlementClient::new();
+		// the submitter never posts the attestation of this attester
+		let aggregation = MockAggregation::default();
+		let (manager, mut event_stream) = Manager::new_with_aggregation(
+			client.clone(),
+			&config,
+			MemorySettlementStore::new(),
+			aggregation.clone(),
+		);
+
+		let commitment = BlockCommitment::new(1, Default::default(), Commitment::new([1; 32]));
+		manager.post_block_commitment(commitment.clone()).await?;
+
+		let event = time::timeout(Duration::from_secs(2), event_stream.next())
+			.await
+			.expect("no timeout")
+			.expect("stream has ended")?;
+		assert_eq!(event, BlockCommitmentEvent::Accepted(commitment));
+		assert_eq!(*aggregation.attested.lock().unwrap(), vec![1]);
+
+		Ok(())
+	}
 }
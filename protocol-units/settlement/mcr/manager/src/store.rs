@@ -0,0 +1,75 @@
+use movement_types::block::BlockCommitment;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// A commitment the manager has not yet seen settled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingCommitment {
+	pub commitment: BlockCommitment,
+	/// Whether the commitment has been posted to the settlement contract
+	pub posted: bool,
+}
+
+/// Storage of the pending commitments of the manager.
+///
+/// Pending commitments are restored when the manager is created,
+/// so that a restarted node does not lose track of them.
+#[async_trait]
+pub trait SettlementStore {
+	/// Loads all pending commitments, in height order.
+	async fn load_pending_commitments(&self) -> Result<Vec<PendingCommitment>, anyhow::Error>;
+
+	/// Inserts or replaces pending commitments by height.
+	async fn put_pending_commitments(
+		&self,
+		commitments: Vec<PendingCommitment>,
+	) -> Result<(), anyhow::Error>;
+
+	/// Removes the pending commitments at the given heights.
+	async fn remove_pending_commitments(&self, heights: Vec<u64>) -> Result<(), anyhow::Error>;
+}
+
+/// A store keeping the pending commitments in memory.
+///
+/// Pending commitments are lost when the process exits.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySettlementStore {
+	commitments: Arc<Mutex<BTreeMap<u64, PendingCommitment>>>,
+}
+
+impl MemorySettlementStore {
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[async_trait]
+impl SettlementStore for MemorySettlementStore {
+	async fn load_pending_commitments(&self) -> Result<Vec<PendingCommitment>, anyhow::Error> {
+		Ok(self.commitments.lock().await.values().cloned().collect())
+	}
+
+	async fn put_pending_commitments(
+		&self,
+		commitments: Vec<PendingCommitment>,
+	) -> Result<(), anyhow::Error> {
+		let mut stored = self.commitments.lock().await;
+		for commitment in commitments {
+			stored.insert(commitment.commitment.height(), commitment);
+		}
+		Ok(())
+	}
+
+	async fn remove_pending_commitments(&self, heights: Vec<u64>) -> Result<(), anyhow::Error> {
+		let mut stored = self.commitments.lock().await;
+		for height in heights {
+			stored.remove(&height);
+		}
+		Ok(())
+	}
+}
@@ -138,6 +138,20 @@ impl Deploy {
 				s.to_owned()
 			})?;
 
+		// The events of the contracts are queried from the block of the first deployment
+		let deployment_block = json_value["receipts"]
+			.as_array()
+			.and_then(|receipts| {
+				receipts
+					.iter()
+					.filter_map(|receipt| receipt["blockNumber"].as_str())
+					.filter_map(|block| {
+						u64::from_str_radix(block.trim_start_matches("0x"), 16).ok()
+					})
+					.min()
+			})
+			.ok_or(anyhow!("No block number in forge script exec deployment result file."))?;
+
 		// generate random well-known accounts and addresses
 		// let mut well_known_account_private_keys =
 		// 	if let Some(existing_testing_config) = config.testing.clone() {
@@ -165,6 +179,7 @@ impl Deploy {
 		}
 
 		config.settle.mcr_contract_address = mcr_address;
+		config.settle.mcr_deployment_block = deployment_block;
 
 		Ok(config)
 	}
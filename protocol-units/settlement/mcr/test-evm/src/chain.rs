@@ -0,0 +1,485 @@
+use alloy_consensus::TxEnvelope;
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{keccak256, Address, Bytes, Log, SignatureError, TxKind, B256, U256};
+use alloy_rpc_types::{BlockNumberOrTag, Filter, FilterBlockOption};
+use revm::db::{CacheDB, EmptyDB};
+use revm::primitives::{
+	AccountInfo, Bytecode, EVMError, ExecutionResult, InvalidTransaction, Output, ResultAndState,
+};
+use revm::{DatabaseRef, Evm};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use thiserror::Error;
+
+/// The base fee of every block, in wei.
+pub(crate) const BASE_FEE: u128 = 1_000_000_000;
+/// The gas limit of every block.
+pub(crate) const BLOCK_GAS_LIMIT: u64 = 30_000_000;
+/// The timestamp of the genesis block. Each mined block is one second later than its parent.
+const GENESIS_TIMESTAMP: u64 = 1_700_000_000;
+
+#[derive(Error, Debug)]
+pub(crate) enum ChainError {
+	#[error("execution reverted")]
+	Reverted(Bytes),
+	#[error("{0}")]
+	Rejected(String),
+}
+
+impl From<EVMError<Infallible>> for ChainError {
+	fn from(error: EVMError<Infallible>) -> Self {
+		// Use the messages of the Ethereum clients, the settlement client matches on some of them.
+		let message = match error {
+			EVMError::Transaction(InvalidTransaction::NonceTooLow { tx, state }) => {
+				format!("nonce too low: next nonce {}, tx nonce {}", state, tx)
+			}
+			EVMError::Transaction(InvalidTransaction::NonceTooHigh { tx, state }) => {
+				format!("nonce too high: next nonce {}, tx nonce {}", state, tx)
+			}
+			EVMError::Transaction(InvalidTransaction::LackOfFundForMaxFee { fee, balance }) => {
+				format!("insufficient funds for gas * price + value: have {} want {}", balance, fee)
+			}
+			EVMError::Transaction(InvalidTransaction::GasPriceLessThanBasefee) => {
+				"transaction underpriced: max fee per gas less than block base fee".to_string()
+			}
+			error => format!("invalid transaction: {:?}", error),
+		};
+		ChainError::Rejected(message)
+	}
+}
+
+/// A transaction to execute on the chain.
+#[derive(Debug, Clone)]
+pub(crate) struct Transaction {
+	pub hash: B256,
+	pub from: Address,
+	pub to: TxKind,
+	/// The nonce of the transaction, not checked when `None`.
+	pub nonce: Option<u64>,
+	pub chain_id: Option<u64>,
+	pub gas_limit: u64,
+	pub max_fee_per_gas: u128,
+	/// The priority fee of an EIP-1559 transaction, `None` for legacy transactions.
+	pub max_priority_fee_per_gas: Option<u128>,
+	pub value: U256,
+	pub input: Bytes,
+	pub transaction_type: u8,
+}
+
+impl Transaction {
+	/// Decodes and recovers the signer of an EIP-2718 encoded signed transaction.
+	pub(crate) fn decode_signed(raw: &[u8]) -> Result<Self, ChainError> {
+		let envelope = TxEnvelope::decode_2718(&mut &raw[..])
+			.map_err(|e| ChainError::Rejected(format!("failed to decode transaction: {}", e)))?;
+		let invalid_signature = |e: SignatureError| {
+			ChainError::Rejected(format!("invalid transaction signature: {}", e))
+		};
+		match envelope {
+			TxEnvelope::Legacy(signed) => {
+				let from = signed.recover_signer().map_err(invalid_signature)?;
+				let transaction = signed.tx();
+				Ok(Self {
+					hash: *signed.hash(),
+					from,
+					to: transaction.to,
+					nonce: Some(transaction.nonce),
+					chain_id: transaction.chain_id,
+					gas_limit: transaction.gas_limit as u64,
+					max_fee_per_gas: transaction.gas_price,
+					max_priority_fee_per_gas: None,
+					value: transaction.value,
+					input: transaction.input.clone(),
+					transaction_type: 0,
+				})
+			}
+			TxEnvelope::Eip1559(signed) => {
+				let from = signed.recover_signer().map_err(invalid_signature)?;
+				let transaction = signed.tx();
+				Ok(Self {
+					hash: *signed.hash(),
+					from,
+					to: transaction.to,
+					nonce: Some(transaction.nonce),
+					chain_id: Some(transaction.chain_id),
+					gas_limit: transaction.gas_limit as u64,
+					max_fee_per_gas: transaction.max_fee_per_gas,
+					max_priority_fee_per_gas: Some(transaction.max_priority_fee_per_gas),
+					value: transaction.value,
+					input: transaction.input.clone(),
+					transaction_type: 2,
+				})
+			}
+			_ => Err(ChainError::Rejected("transaction type not supported".to_string())),
+		}
+	}
+
+	/// The gas price paid by the transaction in a block with the chain base fee.
+	pub(crate) fn effective_gas_price(&self) -> u128 {
+		match self.max_priority_fee_per_gas {
+			Some(priority_fee) => self.max_fee_per_gas.min(BASE_FEE + priority_fee),
+			None => self.max_fee_per_gas,
+		}
+	}
+}
+
+/// A log emitted by a mined transaction.
+#[derive(Debug, Clone)]
+pub(crate) struct MinedLog {
+	pub log: Log,
+	pub block_number: u64,
+	pub block_hash: B256,
+	pub transaction_hash: B256,
+	pub log_index: u64,
+}
+
+/// A transaction included in a block, with the outcome of its execution.
+#[derive(Debug, Clone)]
+pub(crate) struct MinedTransaction {
+	pub transaction: Transaction,
+	pub block_number: u64,
+	pub block_hash: B256,
+	pub success: bool,
+	pub gas_used: u64,
+	pub contract_address: Option<Address>,
+	pub logs: Vec<MinedLog>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Block {
+	pub number: u64,
+	pub hash: B256,
+	pub parent_hash: B256,
+	pub timestamp: u64,
+	pub transactions: Vec<B256>,
+	pub gas_used: u64,
+}
+
+enum InstalledFilter {
+	Logs { filter: Filter, last_polled_block: u64 },
+	Blocks { last_polled_block: u64 },
+}
+
+pub(crate) enum FilterChanges {
+	Logs(Vec<MinedLog>),
+	Blocks(Vec<B256>),
+}
+
+/// An automining chain: each transaction is executed in its own block as soon as it is received.
+pub(crate) struct Chain {
+	chain_id: u64,
+	db: CacheDB<EmptyDB>,
+	blocks: Vec<Block>,
+	transactions: HashMap<B256, MinedTransaction>,
+	filters: HashMap<u64, InstalledFilter>,
+	next_filter_id: u64,
+}
+
+impl Chain {
+	pub(crate) fn new(chain_id: u64) -> Self {
+		let genesis = Block {
+			number: 0,
+			hash: keccak256(chain_id.to_be_bytes()),
+			parent_hash: B256::ZERO,
+			timestamp: GENESIS_TIMESTAMP,
+			transactions: Vec::new(),
+			gas_used: 0,
+		};
+		Self {
+			chain_id,
+			db: CacheDB::new(EmptyDB::default()),
+			blocks: vec![genesis],
+			transactions: HashMap::new(),
+			filters: HashMap::new(),
+			next_filter_id: 1,
+		}
+	}
+
+	pub(crate) fn chain_id(&self) -> u64 {
+		self.chain_id
+	}
+
+	pub(crate) fn block_number(&self) -> u64 {
+		self.head().number
+	}
+
+	fn head(&self) -> &Block {
+		self.blocks.last().expect("the chain always has a genesis block")
+	}
+
+	pub(crate) fn set_balance(&mut self, address: Address, balance: U256) {
+		let mut info = self.account(address);
+		info.balance = balance;
+		self.db.insert_account_info(address, info);
+	}
+
+	pub(crate) fn set_code(&mut self, address: Address, code: Bytes) {
+		let mut info = self.account(address);
+		let bytecode = Bytecode::new_raw(code);
+		info.code_hash = bytecode.hash_slow();
+		info.code = Some(bytecode);
+		self.db.insert_account_info(address, info);
+	}
+
+	fn account(&self, address: Address) -> AccountInfo {
+		match self.db.basic_ref(address) {
+			Ok(Some(info)) => info,
+			Ok(None) => AccountInfo::default(),
+			Err(e) => match e {},
+		}
+	}
+
+	pub(crate) fn balance(&self, address: Address) -> U256 {
+		self.account(address).balance
+	}
+
+	pub(crate) fn nonce(&self, address: Address) -> u64 {
+		self.account(address).nonce
+	}
+
+	pub(crate) fn code(&self, address: Address) -> Bytes {
+		let info = self.account(address);
+		let code = match info.code {
+			Some(code) => code,
+			None => match self.db.code_by_hash_ref(info.code_hash) {
+				Ok(code) => code,
+				Err(e) => match e {},
+			},
+		};
+		code.original_bytes()
+	}
+
+	pub(crate) fn block_by_number(&self, number: BlockNumberOrTag) -> Option<&Block> {
+		self.blocks.get(self.resolve_block_number(number) as usize)
+	}
+
+	pub(crate) fn block_by_hash(&self, hash: B256) -> Option<&Block> {
+		self.blocks.iter().find(|block| block.hash == hash)
+	}
+
+	pub(crate) fn transaction(&self, hash: B256) -> Option<&MinedTransaction> {
+		self.transactions.get(&hash)
+	}
+
+	fn resolve_block_number(&self, number: BlockNumberOrTag) -> u64 {
+		match number {
+			BlockNumberOrTag::Number(number) => number,
+			BlockNumberOrTag::Earliest => 0,
+			// every transaction is mined immediately, all other tags are the head
+			_ => self.block_number(),
+		}
+	}
+
+	/// Executes a call against the head state, without committing it.
+	/// Returns the output of the call and the gas it needs.
+	pub(crate) fn call(
+		&self,
+		from: Address,
+		to: TxKind,
+		input: Bytes,
+		value: U256,
+		gas_limit: Option<u64>,
+	) -> Result<(Bytes, u64), ChainError> {
+		let chain_id = self.chain_id;
+		let head = self.head();
+		let (number, timestamp) = (head.number + 1, head.timestamp + 1);
+		let mut evm = Evm::builder()
+			.with_ref_db(&self.db)
+			.modify_cfg_env(|cfg| cfg.chain_id = chain_id)
+			.modify_block_env(|block| {
+				block.number = U256::from(number);
+				block.timestamp = U256::from(timestamp);
+				block.gas_limit = U256::from(BLOCK_GAS_LIMIT);
+				// calls are free, so that any account can make them
+				block.basefee = U256::ZERO;
+			})
+			.modify_tx_env(|tx| {
+				tx.caller = from;
+				tx.transact_to = to;
+				tx.data = input;
+				tx.value = value;
+				tx.gas_limit = gas_limit.unwrap_or(BLOCK_GAS_LIMIT);
+				tx.gas_price = U256::ZERO;
+				tx.nonce = None;
+			})
+			.build();
+		let ResultAndState { result, .. } = evm.transact()?;
+		match result {
+			ExecutionResult::Success { output, gas_used, gas_refunded, .. } => {
+				// the refund is only paid back at the end of the execution
+				Ok((output.into_data(), gas_used + gas_refunded))
+			}
+			ExecutionResult::Revert { output, .. } => Err(ChainError::Reverted(output)),
+			ExecutionResult::Halt { reason, .. } => {
+				Err(ChainError::Rejected(format!("execution halted: {:?}", reason)))
+			}
+		}
+	}
+
+	/// Executes a transaction in a new block.
+	/// Returns the hash of the transaction, which is mined even if its execution failed.
+	pub(crate) fn execute(&mut self, transaction: Transaction) -> Result<B256, ChainError> {
+		let chain_id = self.chain_id;
+		let head = self.head();
+		let (number, parent_hash, timestamp) = (head.number + 1, head.hash, head.timestamp + 1);
+
+		let db = std::mem::replace(&mut self.db, CacheDB::new(EmptyDB::default()));
+		let tx_env = transaction.clone();
+		let mut evm = Evm::builder()
+			.with_db(db)
+			.modify_cfg_env(|cfg| cfg.chain_id = chain_id)
+			.modify_block_env(|block| {
+				block.number = U256::from(number);
+				block.timestamp = U256::from(timestamp);
+				block.gas_limit = U256::from(BLOCK_GAS_LIMIT);
+				block.basefee = U256::from(BASE_FEE);
+			})
+			.modify_tx_env(|tx| {
+				tx.caller = tx_env.from;
+				tx.transact_to = tx_env.to;
+				tx.data = tx_env.input;
+				tx.value = tx_env.value;
+				tx.gas_limit = tx_env.gas_limit;
+				tx.gas_price = U256::from(tx_env.max_fee_per_gas);
+				tx.gas_priority_fee = tx_env.max_priority_fee_per_gas.map(U256::from);
+				tx.nonce = tx_env.nonce;
+				tx.chain_id = tx_env.chain_id;
+			})
+			.build();
+		let result = evm.transact_commit();
+		let (db, _env) = evm.into_db_and_env_with_handler_cfg();
+		self.db = db;
+		let result = result?;
+
+		let (success, gas_used, logs, contract_address) = match result {
+			ExecutionResult::Success { gas_used, logs, output, .. } => {
+				let contract_address = match output {
+					Output::Create(_, address) => address,
+					Output::Call(_) => None,
+				};
+				(true, gas_used, logs, contract_address)
+			}
+			ExecutionResult::Revert { gas_used, .. } => (false, gas_used, Vec::new(), None),
+			ExecutionResult::Halt { gas_used, .. } => (false, gas_used, Vec::new(), None),
+		};
+
+		let mut preimage = Vec::new();
+		preimage.extend_from_slice(&number.to_be_bytes());
+		preimage.extend_from_slice(parent_hash.as_slice());
+		preimage.extend_from_slice(transaction.hash.as_slice());
+		let block_hash = keccak256(preimage);
+
+		let logs = logs
+			.into_iter()
+			.enumerate()
+			.map(|(index, log)| MinedLog {
+				log,
+				block_number: number,
+				block_hash,
+				transaction_hash: transaction.hash,
+				log_index: index as u64,
+			})
+			.collect();
+		let hash = transaction.hash;
+		self.blocks.push(Block {
+			number,
+			hash: block_hash,
+			parent_hash,
+			timestamp,
+			transactions: vec![hash],
+			gas_used,
+		});
+		self.transactions.insert(
+			hash,
+			MinedTransaction {
+				transaction,
+				block_number: number,
+				block_hash,
+				success,
+				gas_used,
+				contract_address,
+				logs,
+			},
+		);
+		Ok(hash)
+	}
+
+	/// Gets the logs matching a filter.
+	pub(crate) fn logs(&self, filter: &Filter) -> Vec<MinedLog> {
+		let (from, to) = match filter.block_option {
+			FilterBlockOption::AtBlockHash(hash) => match self.block_by_hash(hash) {
+				Some(block) => (block.number, block.number),
+				None => return Vec::new(),
+			},
+			FilterBlockOption::Range { from_block, to_block } => (
+				from_block.map_or(self.block_number(), |number| self.resolve_block_number(number)),
+				to_block.map_or(self.block_number(), |number| self.resolve_block_number(number)),
+			),
+		};
+		self.logs_in_range(filter, from, to)
+	}
+
+	fn logs_in_range(&self, filter: &Filter, from: u64, to: u64) -> Vec<MinedLog> {
+		let to = to.min(self.block_number());
+		if from > to {
+			return Vec::new();
+		}
+		self.blocks[from as usize..=to as usize]
+			.iter()
+			.flat_map(|block| block.transactions.iter())
+			.filter_map(|hash| self.transactions.get(hash))
+			.flat_map(|transaction| transaction.logs.iter())
+			.filter(|mined| Self::log_matches(filter, &mined.log))
+			.cloned()
+			.collect()
+	}
+
+	fn log_matches(filter: &Filter, log: &Log) -> bool {
+		filter.address.matches(&log.address)
+			&& filter.topics.iter().enumerate().all(|(position, topic)| {
+				topic.is_empty()
+					|| log.data.topics().get(position).map_or(false, |value| topic.matches(value))
+			})
+	}
+
+	pub(crate) fn new_log_filter(&mut self, filter: Filter) -> u64 {
+		let last_polled_block = self.block_number();
+		self.install_filter(InstalledFilter::Logs { filter, last_polled_block })
+	}
+
+	pub(crate) fn new_block_filter(&mut self) -> u64 {
+		let last_polled_block = self.block_number();
+		self.install_filter(InstalledFilter::Blocks { last_polled_block })
+	}
+
+	fn install_filter(&mut self, filter: InstalledFilter) -> u64 {
+		let id = self.next_filter_id;
+		self.next_filter_id += 1;
+		self.filters.insert(id, filter);
+		id
+	}
+
+	pub(crate) fn uninstall_filter(&mut self, id: u64) -> bool {
+		self.filters.remove(&id).is_some()
+	}
+
+	/// Gets what changed since the last poll of a filter.
+	pub(crate) fn filter_changes(&mut self, id: u64) -> Option<FilterChanges> {
+		let head = self.block_number();
+		let changes = match self.filters.get(&id)? {
+			InstalledFilter::Logs { filter, last_polled_block } => {
+				FilterChanges::Logs(self.logs_in_range(filter, *last_polled_block + 1, head))
+			}
+			InstalledFilter::Blocks { last_polled_block } => FilterChanges::Blocks(
+				self.blocks[*last_polled_block as usize + 1..]
+					.iter()
+					.map(|block| block.hash)
+					.collect(),
+			),
+		};
+		match self.filters.get_mut(&id)? {
+			InstalledFilter::Logs { last_polled_block, .. }
+			| InstalledFilter::Blocks { last_polled_block } => *last_polled_block = head,
+		}
+		Some(changes)
+	}
+}
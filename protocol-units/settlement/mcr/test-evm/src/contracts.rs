@@ -0,0 +1,211 @@
+use crate::chain::{Chain, Transaction, BASE_FEE, BLOCK_GAS_LIMIT};
+use alloy_primitives::{address, keccak256, Address, Bytes, TxKind, U256};
+use alloy_sol_types::{sol, SolCall};
+use anyhow::Context;
+
+// The compiled contracts the settlement client is generated from.
+const MCR_ARTIFACT: &str = include_str!("../../client/abis/MCR.json");
+const MOVEMENT_STAKING_ARTIFACT: &str = include_str!("../../client/abis/MovementStaking.json");
+const MOVE_TOKEN_ARTIFACT: &str = include_str!("../../client/abis/MOVEToken.json");
+
+/// The address of the MOVE token proxy.
+/// The token implementation disables its initializers, so it can only be used behind a proxy.
+const MOVE_TOKEN_ADDRESS: Address = address!("00000000000000000000000000000000000a11ce");
+
+// Only the calls needed to set the contracts up.
+sol! {
+	interface IMCR {
+		function initialize(
+			address stakingContract,
+			uint256 lastAcceptedBlockHeight,
+			uint256 leadingBlockTolerance,
+			uint256 epochDuration,
+			address[] custodians
+		);
+		function grantTrustedAttester(address attester);
+		function acceptGenesisCeremony();
+	}
+
+	interface IMovementStaking {
+		function initialize(address token);
+		function whitelistAddress(address addr);
+		function stake(address domain, address custodian, uint256 amount);
+	}
+
+	interface IMOVEToken {
+		function initialize(address owner, address custody);
+		function transfer(address to, uint256 amount) returns (bool);
+		function approve(address spender, uint256 amount) returns (bool);
+	}
+}
+
+/// The parameters of the settlement contracts genesis.
+#[derive(Debug, Clone)]
+pub struct GenesisConfig {
+	/// The number of blocks attesters can commit to past the last accepted block
+	pub leading_block_tolerance: u64,
+	/// The duration of a staking epoch, in seconds of block time.
+	/// Blocks are mined one second apart.
+	pub epoch_duration: u64,
+	/// The MOVE stake of each attester
+	pub attester_stake: u64,
+}
+
+impl Default for GenesisConfig {
+	fn default() -> Self {
+		// A long epoch, so that all the commitments of a test are made in the genesis epoch.
+		GenesisConfig { leading_block_tolerance: 10, epoch_duration: 3600, attester_stake: 100 }
+	}
+}
+
+/// The addresses of the deployed settlement contracts.
+#[derive(Debug, Clone, Copy)]
+pub struct Deployment {
+	pub mcr: Address,
+	pub movement_staking: Address,
+	pub move_token: Address,
+}
+
+fn creation_code(artifact: &str) -> Result<Bytes, anyhow::Error> {
+	let artifact: serde_json::Value =
+		serde_json::from_str(artifact).context("Failed to parse the contract artifact")?;
+	let bytecode = artifact["bytecode"]["object"]
+		.as_str()
+		.ok_or(anyhow::anyhow!("No bytecode in the contract artifact"))?;
+	bytecode
+		.parse()
+		.map_err(|e| anyhow::anyhow!("Failed to decode the contract bytecode: {:?}", e))
+}
+
+/// The runtime code of an EIP-1167 minimal proxy delegating to `implementation`.
+fn minimal_proxy_code(implementation: Address) -> Bytes {
+	let mut code = Vec::with_capacity(45);
+	code.extend_from_slice(&[0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73]);
+	code.extend_from_slice(implementation.as_slice());
+	code.extend_from_slice(&[
+		0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+	]);
+	code.into()
+}
+
+/// Executes a setup transaction, failing if it reverts.
+/// Returns the address of the created contract, if any.
+fn send(
+	chain: &mut Chain,
+	from: Address,
+	to: TxKind,
+	input: Vec<u8>,
+) -> Result<Option<Address>, anyhow::Error> {
+	let nonce = chain.nonce(from);
+	let mut preimage = from.to_vec();
+	preimage.extend_from_slice(&nonce.to_be_bytes());
+	let transaction = Transaction {
+		hash: keccak256(preimage),
+		from,
+		to,
+		nonce: Some(nonce),
+		chain_id: None,
+		gas_limit: BLOCK_GAS_LIMIT,
+		max_fee_per_gas: BASE_FEE,
+		max_priority_fee_per_gas: Some(0),
+		value: U256::ZERO,
+		input: input.into(),
+		transaction_type: 2,
+	};
+	let hash = chain
+		.execute(transaction)
+		.map_err(|e| anyhow::anyhow!("Failed to execute setup transaction: {:?}", e))?;
+	let mined = chain.transaction(hash).context("Setup transaction not mined")?;
+	if !mined.success {
+		anyhow::bail!("Setup transaction {} from {} reverted", hash, from);
+	}
+	Ok(mined.contract_address)
+}
+
+fn create(chain: &mut Chain, from: Address, artifact: &str) -> Result<Address, anyhow::Error> {
+	send(chain, from, TxKind::Create, creation_code(artifact)?.to_vec())?
+		.context("No contract created by the deployment")
+}
+
+fn call<C: SolCall>(
+	chain: &mut Chain,
+	from: Address,
+	to: Address,
+	call: C,
+) -> Result<(), anyhow::Error> {
+	send(chain, from, TxKind::Call(to), call.abi_encode())
+		.with_context(|| format!("Failed to call {}", C::SIGNATURE))?;
+	Ok(())
+}
+
+/// Deploys the settlement contracts and runs the genesis ceremony, with the given attesters staked.
+pub(crate) fn deploy(
+	chain: &mut Chain,
+	governor: Address,
+	attesters: &[Address],
+	genesis: &GenesisConfig,
+) -> Result<Deployment, anyhow::Error> {
+	let move_token_implementation = create(chain, governor, MOVE_TOKEN_ARTIFACT)?;
+	chain.set_code(MOVE_TOKEN_ADDRESS, minimal_proxy_code(move_token_implementation));
+	let move_token = MOVE_TOKEN_ADDRESS;
+	call(
+		chain,
+		governor,
+		move_token,
+		IMOVEToken::initializeCall { owner: governor, custody: governor },
+	)?;
+
+	let movement_staking = create(chain, governor, MOVEMENT_STAKING_ARTIFACT)?;
+	call(
+		chain,
+		governor,
+		movement_staking,
+		IMovementStaking::initializeCall { token: move_token },
+	)?;
+
+	let mcr = create(chain, governor, MCR_ARTIFACT)?;
+	call(
+		chain,
+		governor,
+		mcr,
+		IMCR::initializeCall {
+			stakingContract: movement_staking,
+			lastAcceptedBlockHeight: U256::ZERO,
+			leadingBlockTolerance: U256::from(genesis.leading_block_tolerance),
+			epochDuration: U256::from(genesis.epoch_duration),
+			custodians: vec![move_token],
+		},
+	)?;
+
+	let stake = U256::from(genesis.attester_stake);
+	for attester in attesters {
+		call(
+			chain,
+			governor,
+			movement_staking,
+			IMovementStaking::whitelistAddressCall { addr: *attester },
+		)?;
+		call(chain, governor, mcr, IMCR::grantTrustedAttesterCall { attester: *attester })?;
+		call(
+			chain,
+			governor,
+			move_token,
+			IMOVEToken::transferCall { to: *attester, amount: stake },
+		)?;
+		call(
+			chain,
+			*attester,
+			move_token,
+			IMOVEToken::approveCall { spender: movement_staking, amount: stake },
+		)?;
+		call(
+			chain,
+			*attester,
+			movement_staking,
+			IMovementStaking::stakeCall { domain: mcr, custodian: move_token, amount: stake },
+		)?;
+	}
+	call(chain, governor, mcr, IMCR::acceptGenesisCeremonyCall {})?;
+
+	Ok(Deployment { mcr, movement_staking, move_token })
+}
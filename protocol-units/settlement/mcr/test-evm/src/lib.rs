@@ -0,0 +1,145 @@
+//! An in-process EVM chain with the MCR settlement contracts deployed,
+//! to test the settlement client without running an Ethereum node.
+//!
+//! The chain mines a block for each transaction it receives and serves the JSON-RPC methods
+//! the settlement client uses, over HTTP and WebSocket.
+mod chain;
+mod contracts;
+mod rpc;
+
+pub use contracts::{Deployment, GenesisConfig};
+
+use alloy::signers::local::PrivateKeySigner;
+use alloy_primitives::U256;
+use chain::Chain;
+use jsonrpsee::server::ServerHandle;
+use mcr_settlement_config::common;
+use mcr_settlement_config::Config;
+use rpc::SharedChain;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+/// The chain id of the test chain, the same as the local Anvil chain.
+pub const CHAIN_ID: u64 = 31337;
+
+/// The private key of the account deploying and governing the contracts.
+pub const GOVERNOR_PRIVATE_KEY: &str =
+	"0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// The private keys of the staked attesters, the well known Anvil accounts following the governor.
+pub const ATTESTER_PRIVATE_KEYS: [&str; 2] = [
+	"0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+	"0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fa9da804843ffba",
+];
+
+/// The ETH balance of each well known account, in wei.
+const ACCOUNT_BALANCE: u128 = 10_000 * 1_000_000_000_000_000_000;
+
+/// A running test chain. The JSON-RPC server stops when it is dropped.
+pub struct TestEvm {
+	chain: SharedChain,
+	address: SocketAddr,
+	handle: ServerHandle,
+	deployment: Deployment,
+}
+
+impl TestEvm {
+	/// Starts a chain with the settlement contracts deployed with the default genesis.
+	pub async fn start() -> Result<Self, anyhow::Error> {
+		Self::start_with_genesis(GenesisConfig::default()).await
+	}
+
+	pub async fn start_with_genesis(genesis: GenesisConfig) -> Result<Self, anyhow::Error> {
+		let governor = GOVERNOR_PRIVATE_KEY.parse::<PrivateKeySigner>()?.address();
+		let attesters = ATTESTER_PRIVATE_KEYS
+			.iter()
+			.map(|key| Ok(key.parse::<PrivateKeySigner>()?.address()))
+			.collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+		let mut chain = Chain::new(CHAIN_ID);
+		for account in std::iter::once(&governor).chain(attesters.iter()) {
+			chain.set_balance(*account, U256::from(ACCOUNT_BALANCE));
+		}
+		let deployment = contracts::deploy(&mut chain, governor, &attesters, &genesis)?;
+		info!("Deployed the settlement contracts {:?}", deployment);
+
+		let chain = Arc::new(Mutex::new(chain));
+		let (address, handle) = rpc::serve(chain.clone(), "127.0.0.1:0".parse()?).await?;
+		info!("Test EVM listening on {}", address);
+		Ok(Self { chain, address, handle, deployment })
+	}
+
+	/// Restarts the JSON-RPC server on the same address, closing the open connections.
+	/// The state of the chain is kept.
+	pub async fn restart(&mut self) -> Result<(), anyhow::Error> {
+		self.handle.stop()?;
+		self.handle.clone().stopped().await;
+		let (_, handle) = rpc::serve(self.chain.clone(), self.address).await?;
+		self.handle = handle;
+		info!("Test EVM restarted on {}", self.address);
+		Ok(())
+	}
+
+	pub fn deployment(&self) -> Deployment {
+		self.deployment
+	}
+
+	pub fn rpc_url(&self) -> String {
+		format!("http://{}", self.address)
+	}
+
+	pub fn ws_url(&self) -> String {
+		format!("ws://{}", self.address)
+	}
+
+	/// The settlement config connecting to this chain, signing with the given private key.
+	pub fn config(&self, signer_private_key: &str) -> Config {
+		let eth_connection = common::eth_connection::Config {
+			eth_rpc_connection_protocol: "http".to_string(),
+			eth_rpc_connection_hostname: self.address.ip().to_string(),
+			eth_rpc_connection_port: self.address.port(),
+			eth_ws_connection_protocol: "ws".to_string(),
+			eth_ws_connection_hostname: self.address.ip().to_string(),
+			eth_ws_connection_port: self.address.port(),
+			eth_chain_id: CHAIN_ID,
+			eth_ws_reconnect_backoff: 100,
+			eth_ws_reconnect_max_backoff: 1000,
+		};
+		let settle = common::settlement::Config {
+			should_settle: true,
+			signer_private_key: signer_private_key.to_string(),
+			mcr_contract_address: self.deployment.mcr.to_string(),
+			mcr_deployment_block: 0,
+			settlement_super_block_size: 1,
+			settlement_slots: common::settlement::SettlementSlots::DaHeight,
+			settlement_slot_length: 1,
+			settlement_admin_mode: false,
+		};
+		let testing = common::testing::Config {
+			well_known_account_private_keys: std::iter::once(GOVERNOR_PRIVATE_KEY)
+				.chain(ATTESTER_PRIVATE_KEYS)
+				.map(str::to_string)
+				.collect(),
+			mcr_testing_admin_account_private_key: GOVERNOR_PRIVATE_KEY.to_string(),
+			move_token_contract_address: self.deployment.move_token.to_string(),
+			movement_staking_contract_address: self.deployment.movement_staking.to_string(),
+		};
+		Config {
+			eth_connection,
+			settle,
+			transactions: common::transactions::Config::default(),
+			maybe_run_local: false,
+			deploy: None,
+			testing: Some(testing),
+			aggregation: None,
+		}
+	}
+}
+
+impl Drop for TestEvm {
+	fn drop(&mut self) {
+		// the server may already be stopped
+		let _ = self.handle.stop();
+	}
+}
@@ -0,0 +1,315 @@
+use crate::chain::{
+	Block, Chain, ChainError, FilterChanges, MinedLog, MinedTransaction, Transaction, BASE_FEE,
+	BLOCK_GAS_LIMIT,
+};
+use alloy_primitives::{Address, Bloom, Bytes, TxKind, B256, U256, U64};
+use alloy_rpc_types::{BlockNumberOrTag, Filter, TransactionRequest};
+use anyhow::Context;
+use jsonrpsee::server::{Server, ServerHandle};
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
+use jsonrpsee::RpcModule;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The priority fee suggested to the clients, in wei.
+const PRIORITY_FEE: u128 = 1_000_000_000;
+
+pub(crate) type SharedChain = Arc<Mutex<Chain>>;
+
+fn lock(chain: &SharedChain) -> MutexGuard<'_, Chain> {
+	// a panicking handler leaves the chain in a consistent state, every method is atomic
+	chain.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl From<ChainError> for ErrorObjectOwned {
+	fn from(error: ChainError) -> Self {
+		match error {
+			ChainError::Reverted(output) => {
+				ErrorObject::owned(3, "execution reverted", Some(output))
+			}
+			ChainError::Rejected(message) => ErrorObject::owned(-32000, message, None::<()>),
+		}
+	}
+}
+
+fn quantity<T: std::fmt::LowerHex>(value: T) -> String {
+	format!("{:#x}", value)
+}
+
+fn block_json(chain: &Chain, block: &Block, full_transactions: bool) -> Value {
+	let transactions: Vec<Value> = if full_transactions {
+		block
+			.transactions
+			.iter()
+			.filter_map(|hash| chain.transaction(*hash))
+			.map(transaction_json)
+			.collect()
+	} else {
+		block.transactions.iter().map(|hash| json!(hash)).collect()
+	};
+	json!({
+		"number": quantity(block.number),
+		"hash": block.hash,
+		"parentHash": block.parent_hash,
+		"sha3Uncles": B256::ZERO,
+		"miner": Address::ZERO,
+		"stateRoot": B256::ZERO,
+		"transactionsRoot": B256::ZERO,
+		"receiptsRoot": B256::ZERO,
+		"logsBloom": Bloom::ZERO,
+		"difficulty": "0x0",
+		"totalDifficulty": "0x0",
+		"gasLimit": quantity(BLOCK_GAS_LIMIT),
+		"gasUsed": quantity(block.gas_used),
+		"timestamp": quantity(block.timestamp),
+		"extraData": "0x",
+		"mixHash": B256::ZERO,
+		"nonce": "0x0000000000000000",
+		"baseFeePerGas": quantity(BASE_FEE),
+		"size": "0x0",
+		"uncles": [],
+		"transactions": transactions,
+	})
+}
+
+fn to_address(transaction: &Transaction) -> Option<Address> {
+	match transaction.to {
+		TxKind::Call(address) => Some(address),
+		TxKind::Create => None,
+	}
+}
+
+fn transaction_json(mined: &MinedTransaction) -> Value {
+	let transaction = &mined.transaction;
+	let mut value = json!({
+		"hash": transaction.hash,
+		"nonce": quantity(transaction.nonce.unwrap_or_default()),
+		"blockHash": mined.block_hash,
+		"blockNumber": quantity(mined.block_number),
+		"transactionIndex": "0x0",
+		"from": transaction.from,
+		"to": to_address(transaction),
+		"value": quantity(transaction.value),
+		"gas": quantity(transaction.gas_limit),
+		"gasPrice": quantity(transaction.effective_gas_price()),
+		"input": transaction.input,
+		"type": quantity(transaction.transaction_type),
+		// the signature is not kept once the sender is recovered
+		"v": "0x0",
+		"r": "0x0",
+		"s": "0x0",
+	});
+	if let Some(priority_fee) = transaction.max_priority_fee_per_gas {
+		value["maxFeePerGas"] = json!(quantity(transaction.max_fee_per_gas));
+		value["maxPriorityFeePerGas"] = json!(quantity(priority_fee));
+		value["yParity"] = json!("0x0");
+		value["accessList"] = json!([]);
+	}
+	if let Some(chain_id) = transaction.chain_id {
+		value["chainId"] = json!(quantity(chain_id));
+	}
+	value
+}
+
+fn log_json(mined: &MinedLog) -> Value {
+	json!({
+		"address": mined.log.address,
+		"topics": mined.log.data.topics(),
+		"data": mined.log.data.data,
+		"blockHash": mined.block_hash,
+		"blockNumber": quantity(mined.block_number),
+		"transactionHash": mined.transaction_hash,
+		"transactionIndex": "0x0",
+		"logIndex": quantity(mined.log_index),
+		"removed": false,
+	})
+}
+
+fn receipt_json(mined: &MinedTransaction) -> Value {
+	let transaction = &mined.transaction;
+	let mut logs_bloom = Bloom::ZERO;
+	for mined_log in &mined.logs {
+		logs_bloom.accrue_log(&mined_log.log);
+	}
+	json!({
+		"transactionHash": transaction.hash,
+		"transactionIndex": "0x0",
+		"blockHash": mined.block_hash,
+		"blockNumber": quantity(mined.block_number),
+		"from": transaction.from,
+		"to": to_address(transaction),
+		"cumulativeGasUsed": quantity(mined.gas_used),
+		"gasUsed": quantity(mined.gas_used),
+		"effectiveGasPrice": quantity(transaction.effective_gas_price()),
+		"contractAddress": mined.contract_address,
+		"logs": mined.logs.iter().map(log_json).collect::<Vec<_>>(),
+		"logsBloom": logs_bloom,
+		"status": if mined.success { "0x1" } else { "0x0" },
+		"type": quantity(transaction.transaction_type),
+	})
+}
+
+/// Splits a call request in the parameters of [Chain::call].
+fn call_parameters(request: TransactionRequest) -> (Address, TxKind, Bytes, U256, Option<u64>) {
+	(
+		request.from.unwrap_or_default(),
+		request.to.unwrap_or(TxKind::Create),
+		request.input.input().cloned().unwrap_or_default(),
+		request.value.unwrap_or_default(),
+		request.gas.map(|gas| gas as u64),
+	)
+}
+
+fn filter_not_found() -> ErrorObjectOwned {
+	ErrorObject::owned(-32000, "filter not found", None::<()>)
+}
+
+fn module(chain: SharedChain) -> Result<RpcModule<SharedChain>, anyhow::Error> {
+	let mut module = RpcModule::new(chain);
+
+	module.register_method("web3_clientVersion", |_, _| {
+		Ok::<_, ErrorObjectOwned>(json!("mcr-settlement-test-evm"))
+	})?;
+	module.register_method("eth_chainId", |_, chain| {
+		Ok::<_, ErrorObjectOwned>(json!(quantity(lock(chain).chain_id())))
+	})?;
+	module.register_method("net_version", |_, chain| {
+		Ok::<_, ErrorObjectOwned>(json!(lock(chain).chain_id().to_string()))
+	})?;
+	module.register_method("eth_blockNumber", |_, chain| {
+		Ok::<_, ErrorObjectOwned>(json!(quantity(lock(chain).block_number())))
+	})?;
+	module.register_method("eth_gasPrice", |_, _| {
+		Ok::<_, ErrorObjectOwned>(json!(quantity(BASE_FEE + PRIORITY_FEE)))
+	})?;
+	module.register_method("eth_maxPriorityFeePerGas", |_, _| {
+		Ok::<_, ErrorObjectOwned>(json!(quantity(PRIORITY_FEE)))
+	})?;
+	module.register_method("eth_feeHistory", |params, chain| {
+		let mut params = params.sequence();
+		let block_count: U64 = params.next()?;
+		let _newest_block: BlockNumberOrTag = params.next()?;
+		let reward_percentiles: Option<Vec<f64>> = params.optional_next()?;
+		let head = lock(chain).block_number();
+		let block_count = block_count.to::<u64>().min(head + 1);
+		// the base fee is constant, and all the blocks are as empty as each other
+		let mut history = json!({
+			"oldestBlock": quantity(head + 1 - block_count),
+			"baseFeePerGas": vec![quantity(BASE_FEE); block_count as usize + 1],
+			"gasUsedRatio": vec![0.0; block_count as usize],
+		});
+		if let Some(reward_percentiles) = reward_percentiles {
+			history["reward"] = json!(vec![
+				vec![quantity(PRIORITY_FEE); reward_percentiles.len()];
+				block_count as usize
+			]);
+		}
+		Ok::<_, ErrorObjectOwned>(history)
+	})?;
+
+	module.register_method("eth_getBalance", |params, chain| {
+		let address: Address = params.sequence().next()?;
+		Ok::<_, ErrorObjectOwned>(json!(quantity(lock(chain).balance(address))))
+	})?;
+	module.register_method("eth_getTransactionCount", |params, chain| {
+		let address: Address = params.sequence().next()?;
+		Ok::<_, ErrorObjectOwned>(json!(quantity(lock(chain).nonce(address))))
+	})?;
+	module.register_method("eth_getCode", |params, chain| {
+		let address: Address = params.sequence().next()?;
+		Ok::<_, ErrorObjectOwned>(json!(lock(chain).code(address)))
+	})?;
+
+	module.register_method("eth_getBlockByNumber", |params, chain| {
+		let mut params = params.sequence();
+		let number: BlockNumberOrTag = params.next()?;
+		let full_transactions: Option<bool> = params.optional_next()?;
+		let chain = lock(chain);
+		let block = chain
+			.block_by_number(number)
+			.map(|block| block_json(&chain, block, full_transactions.unwrap_or_default()));
+		Ok::<_, ErrorObjectOwned>(json!(block))
+	})?;
+	module.register_method("eth_getBlockByHash", |params, chain| {
+		let mut params = params.sequence();
+		let hash: B256 = params.next()?;
+		let full_transactions: Option<bool> = params.optional_next()?;
+		let chain = lock(chain);
+		let block = chain
+			.block_by_hash(hash)
+			.map(|block| block_json(&chain, block, full_transactions.unwrap_or_default()));
+		Ok::<_, ErrorObjectOwned>(json!(block))
+	})?;
+
+	module.register_method("eth_call", |params, chain| {
+		let request: TransactionRequest = params.sequence().next()?;
+		let (from, to, input, value, gas) = call_parameters(request);
+		let (output, _gas_used) = lock(chain).call(from, to, input, value, gas)?;
+		Ok::<_, ErrorObjectOwned>(json!(output))
+	})?;
+	module.register_method("eth_estimateGas", |params, chain| {
+		let request: TransactionRequest = params.sequence().next()?;
+		let (from, to, input, value, gas) = call_parameters(request);
+		let (_output, gas_used) = lock(chain).call(from, to, input, value, gas)?;
+		Ok::<_, ErrorObjectOwned>(json!(quantity(gas_used)))
+	})?;
+
+	module.register_method("eth_sendRawTransaction", |params, chain| {
+		let raw: Bytes = params.sequence().next()?;
+		let transaction = Transaction::decode_signed(&raw)?;
+		let hash = lock(chain).execute(transaction)?;
+		Ok::<_, ErrorObjectOwned>(json!(hash))
+	})?;
+	module.register_method("eth_getTransactionByHash", |params, chain| {
+		let hash: B256 = params.sequence().next()?;
+		Ok::<_, ErrorObjectOwned>(json!(lock(chain).transaction(hash).map(transaction_json)))
+	})?;
+	module.register_method("eth_getTransactionReceipt", |params, chain| {
+		let hash: B256 = params.sequence().next()?;
+		Ok::<_, ErrorObjectOwned>(json!(lock(chain).transaction(hash).map(receipt_json)))
+	})?;
+
+	module.register_method("eth_getLogs", |params, chain| {
+		let filter: Filter = params.sequence().next()?;
+		let logs: Vec<Value> = lock(chain).logs(&filter).iter().map(log_json).collect();
+		Ok::<_, ErrorObjectOwned>(json!(logs))
+	})?;
+	module.register_method("eth_newFilter", |params, chain| {
+		let filter: Filter = params.sequence().next()?;
+		Ok::<_, ErrorObjectOwned>(json!(quantity(lock(chain).new_log_filter(filter))))
+	})?;
+	module.register_method("eth_newBlockFilter", |_, chain| {
+		Ok::<_, ErrorObjectOwned>(json!(quantity(lock(chain).new_block_filter())))
+	})?;
+	module.register_method("eth_getFilterChanges", |params, chain| {
+		let id: U256 = params.sequence().next()?;
+		let id = u64::try_from(id).map_err(|_| filter_not_found())?;
+		let changes = match lock(chain).filter_changes(id).ok_or_else(filter_not_found)? {
+			FilterChanges::Logs(logs) => json!(logs.iter().map(log_json).collect::<Vec<_>>()),
+			FilterChanges::Blocks(hashes) => json!(hashes),
+		};
+		Ok::<_, ErrorObjectOwned>(changes)
+	})?;
+	module.register_method("eth_uninstallFilter", |params, chain| {
+		let id: U256 = params.sequence().next()?;
+		let removed = u64::try_from(id).map_or(false, |id| lock(chain).uninstall_filter(id));
+		Ok::<_, ErrorObjectOwned>(json!(removed))
+	})?;
+
+	Ok(module)
+}
+
+/// Serves the JSON-RPC API of the chain over HTTP and WebSocket, on the same local port.
+pub(crate) async fn serve(
+	chain: SharedChain,
+	address: SocketAddr,
+) -> Result<(SocketAddr, ServerHandle), anyhow::Error> {
+	let server = Server::builder()
+		.build(address)
+		.await
+		.context("Failed to bind the JSON-RPC server")?;
+	let address = server.local_addr().context("Failed to get the JSON-RPC server address")?;
+	let handle = server.start(module(chain)?);
+	Ok((address, handle))
+}
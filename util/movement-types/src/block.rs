@@ -205,5 +205,10 @@ pub enum BlockCommitmentRejectionReason {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum BlockCommitmentEvent {
 	Accepted(BlockCommitment),
-	Rejected { height: u64, reason: BlockCommitmentRejectionReason },
+	Rejected {
+		height: u64,
+		reason: BlockCommitmentRejectionReason,
+		/// The commitment accepted at the height instead, if any
+		accepted: Option<BlockCommitment>,
+	},
 }